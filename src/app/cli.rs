@@ -10,10 +10,81 @@ use crate::logging::{PROJECT_NAME, get_data_dir};
 
 #[derive(Parser)]
 #[clap(author, version = version(), about, long_about = None, styles = get_styles())]
+#[clap(subcommand_negates_reqs = true)]
 pub struct Cli {
     /// Top-level CLI arguments controlling repository selection and runtime behavior.
     #[clap(flatten)]
     pub args: Args,
+    /// Runs a command instead of opening the interface.
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Clone)]
+pub enum Command {
+    /// Copy labels between repositories.
+    #[clap(subcommand)]
+    Labels(LabelsCommand),
+}
+
+#[derive(clap::Subcommand, Clone)]
+pub enum LabelsCommand {
+    /// Prints the labels of a repository as YAML.
+    ///
+    /// For example: `gitv labels export owner/repo > labels.yaml`.
+    Export {
+        /// Repository to read the labels of, as `owner/repo`.
+        repo: RepoSlug,
+    },
+    /// Makes the labels of a repository match a YAML file written by `export`.
+    ///
+    /// Prints the labels to create, update and delete, then asks before
+    /// changing anything.
+    Import {
+        /// Repository to change the labels of, as `owner/repo`.
+        repo: RepoSlug,
+        /// Label file to read, or `-` for standard input.
+        #[clap(default_value = "-")]
+        file: String,
+        /// Print the changes without making them.
+        #[clap(long, short = 'n')]
+        dry_run: bool,
+        /// Keep labels that are not in the file instead of deleting them.
+        #[clap(long)]
+        no_delete: bool,
+        /// Apply the changes without asking for confirmation.
+        #[clap(long, short)]
+        yes: bool,
+    },
+}
+
+/// A repository given as `owner/repo`.
+#[derive(Clone, Debug)]
+pub struct RepoSlug {
+    pub owner: String,
+    pub repo: String,
+}
+
+impl FromStr for RepoSlug {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+                Ok(Self {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                })
+            }
+            _ => Err(format!("expected owner/repo, got {s:?}")),
+        }
+    }
+}
+
+impl Display for RepoSlug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.owner, self.repo)
+    }
 }
 
 #[derive(clap::Args, Clone)]
@@ -46,8 +117,20 @@ pub struct Args {
     pub generate_man: bool,
 
     /// When provided, this command will read the GitHub token from the environment variable
-    #[clap(short, long)]
+    #[clap(short, long, global = true)]
     pub env: bool,
+
+    /// GitHub host to connect to, for GitHub Enterprise Server (for example: `github.example.com`).
+    ///
+    /// Defaults to the `GH_HOST` environment variable, then `github.com`.
+    #[clap(long, global = true)]
+    pub host: Option<String>,
+
+    /// Browse the local cache of previously loaded issues without contacting GitHub.
+    ///
+    /// Offline mode is read-only; commenting, closing and other edits are disabled.
+    #[clap(long)]
+    pub offline: bool,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
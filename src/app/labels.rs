@@ -0,0 +1,433 @@
+//! The `gitv labels` commands, which copy a repository's labels to a YAML file
+//! and make another repository's labels match such a file.
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io::{self, Read},
+};
+
+use anyhow::anyhow;
+use inquire::Confirm;
+use octocrab::models::Label;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::cli::{LabelsCommand, RepoSlug},
+    backend::{IssueTracker, LabelEdit, github::GithubBackend},
+    errors::AppError,
+    github::GithubClient,
+    ui::components::label_list::{DEFAULT_COLOR, LabelList},
+};
+
+/// A label as written to and read from a label file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LabelSpec {
+    pub name: String,
+    /// A label file may leave the color out, to keep the current one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl LabelSpec {
+    fn color(&self) -> &str {
+        self.color.as_deref().unwrap_or(DEFAULT_COLOR)
+    }
+}
+
+impl From<&Label> for LabelSpec {
+    fn from(label: &Label) -> Self {
+        Self {
+            name: label.name.clone(),
+            color: Some(label.color.to_lowercase()),
+            description: label.description.clone().filter(|desc| !desc.is_empty()),
+        }
+    }
+}
+
+/// A label that exists on both sides but differs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelUpdate {
+    /// Current name of the label in the repository.
+    pub name: String,
+    pub from: LabelSpec,
+    pub to: LabelSpec,
+}
+
+impl LabelUpdate {
+    fn edit(&self) -> LabelEdit {
+        LabelEdit {
+            new_name: (self.from.name != self.to.name).then(|| self.to.name.clone()),
+            color: (self.from.color != self.to.color).then(|| self.to.color().to_string()),
+            description: (self.from.description != self.to.description)
+                .then(|| self.to.description.clone().unwrap_or_default()),
+        }
+    }
+}
+
+/// The changes that make a repository's labels match a label file.
+#[derive(Debug, Default, PartialEq)]
+pub struct LabelPlan {
+    pub creates: Vec<LabelSpec>,
+    pub updates: Vec<LabelUpdate>,
+    pub deletes: Vec<LabelSpec>,
+    pub unchanged: usize,
+}
+
+impl LabelPlan {
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty() && self.updates.is_empty() && self.deletes.is_empty()
+    }
+
+    /// Renders the plan like a diff: `+` creates, `~` updates and `-` deletes.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for spec in &self.creates {
+            let _ = writeln!(out, "+ {}", describe(spec));
+        }
+        for update in &self.updates {
+            let _ = writeln!(out, "~ {}", update.name);
+            if update.from.name != update.to.name {
+                let _ = writeln!(out, "    name: {} -> {}", update.from.name, update.to.name);
+            }
+            if update.from.color != update.to.color {
+                let _ = writeln!(
+                    out,
+                    "    color: #{} -> #{}",
+                    update.from.color(),
+                    update.to.color()
+                );
+            }
+            if update.from.description != update.to.description {
+                let _ = writeln!(
+                    out,
+                    "    description: {:?} -> {:?}",
+                    update.from.description.as_deref().unwrap_or_default(),
+                    update.to.description.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        for spec in &self.deletes {
+            let _ = writeln!(out, "- {}", describe(spec));
+        }
+        let _ = writeln!(
+            out,
+            "{} to create, {} to update, {} to delete, {} unchanged.",
+            self.creates.len(),
+            self.updates.len(),
+            self.deletes.len(),
+            self.unchanged
+        );
+        out
+    }
+}
+
+fn describe(spec: &LabelSpec) -> String {
+    match spec.description.as_deref() {
+        Some(desc) => format!("{} (#{}) {desc:?}", spec.name, spec.color()),
+        None => format!("{} (#{})", spec.name, spec.color()),
+    }
+}
+
+/// Normalizes the labels of a label file with the same rules as the label
+/// list, returning every problem found rather than just the first.
+pub fn normalize_specs(specs: Vec<LabelSpec>) -> Result<Vec<LabelSpec>, Vec<String>> {
+    let mut conflicts = Vec::new();
+    let mut normalized: Vec<LabelSpec> = Vec::with_capacity(specs.len());
+    for (idx, spec) in specs.into_iter().enumerate() {
+        let Some(name) = LabelList::normalize_label_name(&spec.name) else {
+            conflicts.push(format!("label #{}: name is empty", idx + 1));
+            continue;
+        };
+        let color = match spec.color.filter(|color| !color.trim().is_empty()) {
+            None => None,
+            Some(color) => match LabelList::normalize_color(&color) {
+                Ok(color) => Some(color),
+                Err(message) => {
+                    conflicts.push(format!("{name}: {message} (got {color:?})"));
+                    continue;
+                }
+            },
+        };
+        // GitHub compares label names without regard to case.
+        if let Some(other) = normalized
+            .iter()
+            .find(|other| other.name.eq_ignore_ascii_case(&name))
+        {
+            conflicts.push(format!(
+                "{name}: listed more than once (also as {}); label names are case-insensitive",
+                other.name
+            ));
+            continue;
+        }
+        let description = spec
+            .description
+            .map(|desc| desc.trim().to_string())
+            .filter(|desc| !desc.is_empty());
+        normalized.push(LabelSpec {
+            name,
+            color,
+            description,
+        });
+    }
+    if conflicts.is_empty() {
+        Ok(normalized)
+    } else {
+        Err(conflicts)
+    }
+}
+
+/// Works out how to turn `current` into `wanted`. Labels are matched by name
+/// without regard to case, so a change of case is an update. A wanted label
+/// without a color keeps its current color, or gets the default one when it
+/// is created. Labels missing from `wanted` are only deleted when `delete` is
+/// set.
+pub fn plan(current: &[LabelSpec], wanted: &[LabelSpec], delete: bool) -> LabelPlan {
+    let mut plan = LabelPlan::default();
+    let by_name: HashMap<String, &LabelSpec> = current
+        .iter()
+        .map(|spec| (spec.name.to_lowercase(), spec))
+        .collect();
+    for spec in wanted {
+        let existing = by_name.get(&spec.name.to_lowercase());
+        let color = spec
+            .color
+            .clone()
+            .or_else(|| existing.and_then(|existing| existing.color.clone()))
+            .unwrap_or_else(|| DEFAULT_COLOR.to_string());
+        let spec = LabelSpec {
+            color: Some(color),
+            ..spec.clone()
+        };
+        match existing {
+            None => plan.creates.push(spec),
+            Some(existing) if **existing == spec => plan.unchanged += 1,
+            Some(existing) => plan.updates.push(LabelUpdate {
+                name: existing.name.clone(),
+                from: (*existing).clone(),
+                to: spec,
+            }),
+        }
+    }
+    if delete {
+        plan.deletes = current
+            .iter()
+            .filter(|spec| {
+                !wanted
+                    .iter()
+                    .any(|wanted| wanted.name.eq_ignore_ascii_case(&spec.name))
+            })
+            .cloned()
+            .collect();
+    }
+    plan
+}
+
+async fn fetch_labels(backend: &GithubBackend) -> Result<Vec<LabelSpec>, AppError> {
+    let mut specs = Vec::new();
+    let mut page_number = 1;
+    loop {
+        let page = backend.list_labels(page_number, 100).await?;
+        specs.extend(page.items.iter().map(LabelSpec::from));
+        if page.next.is_none() {
+            return Ok(specs);
+        }
+        page_number += 1;
+    }
+}
+
+fn read_label_file(path: &str) -> Result<Vec<LabelSpec>, AppError> {
+    let contents = if path == "-" {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)?;
+        contents
+    } else {
+        std::fs::read_to_string(path)?
+    };
+    serde_norway::from_str(&contents)
+        .map_err(|err| AppError::Other(anyhow!("{path} is not a valid label file: {err}")))
+}
+
+/// Runs a `gitv labels` subcommand against GitHub.
+pub async fn run(command: LabelsCommand, client: GithubClient) -> Result<(), AppError> {
+    match command {
+        LabelsCommand::Export { repo } => {
+            let backend = backend(client, &repo);
+            let labels = fetch_labels(&backend).await?;
+            let yaml = serde_norway::to_string(&labels)
+                .map_err(|err| AppError::Other(anyhow!("failed to write labels: {err}")))?;
+            print!("{yaml}");
+            Ok(())
+        }
+        LabelsCommand::Import {
+            repo,
+            file,
+            dry_run,
+            no_delete,
+            yes,
+        } => {
+            let wanted = normalize_specs(read_label_file(&file)?).map_err(|conflicts| {
+                AppError::Other(anyhow!(
+                    "{file} has conflicting labels:\n  {}",
+                    conflicts.join("\n  ")
+                ))
+            })?;
+            let backend = backend(client, &repo);
+            let current = fetch_labels(&backend).await?;
+            let plan = plan(&current, &wanted, !no_delete);
+            print!("{}", plan.render());
+            if plan.is_empty() {
+                println!("{repo} already matches {file}.");
+                return Ok(());
+            }
+            if dry_run {
+                println!("Dry run: no changes made to {repo}.");
+                return Ok(());
+            }
+            if !yes
+                && !Confirm::new(&format!("Apply these changes to {repo}?"))
+                    .with_default(false)
+                    .prompt()?
+            {
+                println!("No changes made.");
+                return Ok(());
+            }
+            apply(&backend, &plan).await
+        }
+    }
+}
+
+fn backend(client: GithubClient, repo: &RepoSlug) -> GithubBackend {
+    GithubBackend::new(client, repo.owner.clone(), repo.repo.clone())
+}
+
+/// Applies every change of the plan, carrying on past failures so that one
+/// bad label does not leave the rest untouched.
+async fn apply(backend: &GithubBackend, plan: &LabelPlan) -> Result<(), AppError> {
+    let mut failures = Vec::new();
+    for spec in &plan.creates {
+        let description = spec.description.as_deref().unwrap_or_default();
+        match backend
+            .create_label(&spec.name, spec.color(), description)
+            .await
+        {
+            Ok(_) => println!("created {}", spec.name),
+            Err(err) => failures.push(format!("create {}: {err}", spec.name)),
+        }
+    }
+    for update in &plan.updates {
+        match backend.update_label(&update.name, update.edit()).await {
+            Ok(_) => println!("updated {}", update.name),
+            Err(err) => failures.push(format!("update {}: {err}", update.name)),
+        }
+    }
+    for spec in &plan.deletes {
+        match backend.delete_label(&spec.name).await {
+            Ok(()) => println!("deleted {}", spec.name),
+            Err(err) => failures.push(format!("delete {}: {err}", spec.name)),
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Other(anyhow!(
+            "{} of the changes failed:\n  {}",
+            failures.len(),
+            failures.join("\n  ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str, color: &str, description: Option<&str>) -> LabelSpec {
+        LabelSpec {
+            name: name.to_string(),
+            color: (!color.is_empty()).then(|| color.to_string()),
+            description: description.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn label_files_are_normalized_like_the_label_list() {
+        let specs = normalize_specs(vec![
+            spec(" bug ", "#D73A4A", Some("  ")),
+            spec("docs", "", None),
+        ])
+        .unwrap();
+        assert_eq!(
+            specs,
+            vec![spec("bug", "d73a4a", None), spec("docs", "", None)]
+        );
+    }
+
+    #[test]
+    fn every_conflict_is_reported() {
+        let conflicts = normalize_specs(vec![
+            spec("", "ffffff", None),
+            spec("bug", "red", None),
+            spec("docs", "ffffff", None),
+            spec("Docs", "000000", None),
+        ])
+        .unwrap_err();
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts[0].contains("name is empty"));
+        assert!(conflicts[1].starts_with("bug: Invalid color"));
+        assert!(conflicts[2].contains("listed more than once (also as docs)"));
+    }
+
+    #[test]
+    fn plan_creates_updates_and_deletes() {
+        let current = vec![
+            spec("bug", "d73a4a", None),
+            spec("Docs", "0075ca", Some("Documentation")),
+            spec("stale", "ffffff", None),
+        ];
+        let wanted = vec![
+            spec("bug", "d73a4a", None),
+            spec("docs", "0075ca", Some("Documentation")),
+            spec("feature", "a2eeef", None),
+        ];
+        let plan = plan(&current, &wanted, true);
+        assert_eq!(plan.creates, vec![spec("feature", "a2eeef", None)]);
+        assert_eq!(plan.updates.len(), 1);
+        let edit = plan.updates[0].edit();
+        assert_eq!(edit.new_name.as_deref(), Some("docs"));
+        assert_eq!(edit.color, None);
+        assert_eq!(edit.description, None);
+        assert_eq!(plan.deletes, vec![spec("stale", "ffffff", None)]);
+        assert_eq!(plan.unchanged, 1);
+    }
+
+    #[test]
+    fn removed_descriptions_are_cleared() {
+        let current = vec![spec("bug", "d73a4a", Some("Broken"))];
+        let wanted = vec![spec("bug", "d73a4a", None)];
+        let plan = plan(&current, &wanted, false);
+        assert_eq!(plan.updates[0].edit().description.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn labels_without_a_color_keep_their_color() {
+        let current = vec![spec("bug", "d73a4a", None)];
+        let wanted = normalize_specs(vec![
+            spec("bug", "", Some("Broken")),
+            spec("docs", "", None),
+        ])
+        .unwrap();
+        let plan = plan(&current, &wanted, false);
+        assert_eq!(plan.updates.len(), 1);
+        let edit = plan.updates[0].edit();
+        assert_eq!(edit.color, None);
+        assert_eq!(edit.description.as_deref(), Some("Broken"));
+        assert_eq!(plan.creates, vec![spec("docs", "ededed", None)]);
+        assert_eq!(
+            super::plan(&current, &[spec("bug", "", None)], false).unchanged,
+            1
+        );
+    }
+}
@@ -1,54 +1,105 @@
-use anyhow::anyhow;
 use inquire::Password;
+use std::{sync::Arc, time::Duration};
+use tracing::error;
 
-use crate::app::cli::Cli;
+use crate::app::cli::{Args, Cli};
 use crate::auth::AuthProvider;
+use crate::backend::{
+    Backend,
+    cache::{CachedBackend, RepoCache},
+    github::GithubBackend,
+    offline::OfflineBackend,
+};
 use crate::errors::AppError;
-use crate::github::GithubClient;
+use crate::github::{GithubClient, GithubHost};
 use crate::logging::LoggingConfig;
+use crate::{drafts::Drafts, outbox::Outbox, searches::SavedSearches};
 use crate::{logging, ui};
-use std::sync::OnceLock;
+
+/// How often the issue cache is written while the app runs, so a crash does
+/// not lose everything fetched in the session.
+const CACHE_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
 
 pub struct App {
+    pub host: GithubHost,
     pub owner: String,
     pub repo: String,
+    pub backend: Backend,
 }
 
-pub static GITHUB_CLIENT: OnceLock<GithubClient> = OnceLock::new();
-
 impl App {
     pub async fn new(cli: Cli) -> Result<Self, AppError> {
-        logging::init(LoggingConfig::new(cli.args.log_level))?;
-        let auth = if cli.args.env {
-            Box::new(crate::auth::env::EnvAuth) as Box<dyn AuthProvider>
-        } else {
-            Box::new(crate::auth::keyring::KeyringAuth::new("gitv")?) as Box<dyn AuthProvider>
-        };
-        let token = match auth.get_token().ok() {
-            Some(token) => token,
-            None => Self::handle_no_token(&auth)?,
-        };
-        let github = GithubClient::new(Some(token))?;
-        let _ = GITHUB_CLIENT.set(github);
+        logging::init(LoggingConfig::new(cli.args.log_level.clone()))?;
+        let owner = cli.args.owner.clone().unwrap_or_default();
+        let repo = cli.args.repo.clone().unwrap_or_default();
+        let host = GithubHost::resolve(cli.args.host.as_deref());
+        let cache_path = RepoCache::path(&host, &owner, &repo);
+        if cli.args.offline {
+            let backend: Backend = Arc::new(OfflineBackend::new(&cache_path));
+            return Ok(Self {
+                host,
+                owner,
+                repo,
+                backend,
+            });
+        }
+        let github = Self::github_client(&cli.args, &host)?;
+        let github: Backend = Arc::new(GithubBackend::new(github, owner.clone(), repo.clone()));
+        let backend: Backend = Arc::new(CachedBackend::new(github, cache_path));
         Ok(Self {
-            owner: cli.args.owner.unwrap_or_default(),
-            repo: cli.args.repo.unwrap_or_default(),
+            host,
+            owner,
+            repo,
+            backend,
         })
     }
 
     pub async fn run(&mut self) -> Result<(), AppError> {
         use crate::ui::AppState;
-        let current_user = GITHUB_CLIENT
-            .get()
-            .ok_or_else(|| AppError::Other(anyhow!("github client is not initialized")))?
-            .inner()
-            .current()
-            .user()
-            .await?
-            .login;
+        let current_user = self.backend.current_user().await?;
 
-        let ap = AppState::new(self.repo.clone(), self.owner.clone(), current_user);
-        ui::run(ap).await
+        let ap = AppState::new(
+            self.host.clone(),
+            self.repo.clone(),
+            self.owner.clone(),
+            current_user,
+            self.backend.clone(),
+            Outbox::open(&self.host, &self.owner, &self.repo),
+            Drafts::open(&self.host, &self.owner, &self.repo),
+            SavedSearches::open(&self.host, &self.owner, &self.repo),
+        );
+        let backend = self.backend.clone();
+        let flusher = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CACHE_FLUSH_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(err) = backend.flush().await {
+                    error!(error = %err, "failed to write issue cache");
+                }
+            }
+        });
+        let result = ui::run(ap).await;
+        flusher.abort();
+        if let Err(err) = self.backend.flush().await {
+            error!(error = %err, "failed to write issue cache on shutdown");
+        }
+        result
+    }
+
+    /// Authenticates with the token from the environment or the credential
+    /// store, asking for one if none is saved.
+    pub fn github_client(args: &Args, host: &GithubHost) -> Result<GithubClient, AppError> {
+        let auth = if args.env {
+            Box::new(crate::auth::env::EnvAuth) as Box<dyn AuthProvider>
+        } else {
+            Box::new(crate::auth::keyring::KeyringAuth::new("gitv", host)?) as Box<dyn AuthProvider>
+        };
+        let token = match auth.get_token().ok() {
+            Some(token) => token,
+            None => Self::handle_no_token(&auth)?,
+        };
+        GithubClient::new(Some(token), host)
     }
 
     pub fn handle_no_token(auth: &impl AuthProvider) -> Result<String, AppError> {
@@ -63,3 +114,4 @@ impl App {
 }
 
 pub mod cli;
+pub mod labels;
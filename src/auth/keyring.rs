@@ -1,26 +1,36 @@
 use crate::auth::AuthProvider;
 use crate::errors::AppError;
+use crate::github::GithubHost;
 
 pub struct KeyringAuth {
     service: String,
+    user: String,
 }
 
 impl KeyringAuth {
-    pub fn new(service: &str) -> Result<Self, AppError> {
+    /// Tokens are stored per host. github.com keeps the original `github`
+    /// entry so existing tokens keep working.
+    pub fn new(service: &str, host: &GithubHost) -> Result<Self, AppError> {
+        let user = if host.is_default() {
+            "github".to_string()
+        } else {
+            format!("github@{host}")
+        };
         Ok(Self {
             service: service.to_string(),
+            user,
         })
     }
 }
 
 impl AuthProvider for KeyringAuth {
     fn get_token(&self) -> Result<String, AppError> {
-        let entry = keyring::Entry::new(&self.service, "github")?;
+        let entry = keyring::Entry::new(&self.service, &self.user)?;
         let token = entry.get_password()?;
         Ok(token)
     }
     fn set_token(&self, token: &str) -> Result<(), AppError> {
-        let entry = keyring::Entry::new(&self.service, "github")?;
+        let entry = keyring::Entry::new(&self.service, &self.user)?;
         entry.set_password(token)?;
 
         Ok(())
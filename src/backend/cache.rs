@@ -0,0 +1,659 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use octocrab::{
+    Page,
+    models::{
+        Author, IssueState, Label, Milestone,
+        issues::{Comment, Issue, IssueStateReason},
+        reactions::{Reaction, ReactionContent},
+        repos::DiffEntry,
+        timelines::TimelineEvent,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::{
+        Backend, Conversation, IssueEdit, IssueTracker, LabelEdit, MergeRequest, MergeStatus,
+        MilestoneEdit, NewIssue, NewReview, PageCursor, PullRequestChecks, PullRequestSummary,
+        RerunTarget, ReviewThread, SearchParams, templates::IssueTemplate,
+    },
+    errors::AppError,
+    github::GithubHost,
+    store,
+};
+
+/// Everything gitv has seen for a single repository, keyed by issue number.
+///
+/// Stored as JSON under `<data dir>/cache/<owner>/<repo>.json`, or
+/// `<data dir>/cache/<host>/<owner>/<repo>.json` for GitHub Enterprise hosts.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct RepoCache {
+    pub current_user: Option<String>,
+    pub issues: BTreeMap<u64, Issue>,
+    pub comments: HashMap<u64, Vec<Comment>>,
+    /// Reactions keyed by comment id.
+    pub reactions: HashMap<u64, Vec<Reaction>>,
+    pub timeline: HashMap<u64, Vec<TimelineEvent>>,
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub conversations: HashMap<u64, Conversation>,
+    #[serde(default)]
+    pub pull_requests: HashMap<u64, PullRequestSummary>,
+    #[serde(default)]
+    pub pull_request_files: HashMap<u64, Vec<DiffEntry>>,
+    #[serde(default)]
+    pub review_threads: HashMap<u64, Vec<ReviewThread>>,
+    #[serde(default)]
+    pub pull_request_checks: HashMap<u64, PullRequestChecks>,
+    #[serde(default)]
+    pub milestones: Vec<Milestone>,
+    #[serde(default)]
+    pub issue_templates: Vec<IssueTemplate>,
+    #[serde(default)]
+    pub assignees: Vec<Author>,
+    #[serde(default)]
+    pub label_open_issue_counts: HashMap<String, u64>,
+    #[serde(default)]
+    pub assignee_open_issue_counts: HashMap<String, u64>,
+}
+
+impl RepoCache {
+    pub fn path(host: &GithubHost, owner: &str, repo: &str) -> PathBuf {
+        store::repo_file("cache", host, owner, repo)
+    }
+
+    /// Reads the cache at `path`, falling back to an empty cache if the file
+    /// is missing or unreadable.
+    pub fn read(path: &Path) -> Self {
+        if let Ok(contents) = std::fs::read(path) {
+            serde_json::from_slice(&contents).unwrap_or_default()
+        } else {
+            Self::default()
+        }
+    }
+
+    fn store_issue(&mut self, issue: &Issue) {
+        self.issues.insert(issue.number, issue.clone());
+    }
+
+    fn store_conversation(
+        &mut self,
+        number: u64,
+        comments: &PageCursor,
+        timeline: &PageCursor,
+        page: &Conversation,
+    ) {
+        let entry = self.conversations.entry(number).or_default();
+        if *comments == PageCursor::Start {
+            entry.comments.clear();
+        }
+        if *timeline == PageCursor::Start {
+            entry.timeline.clear();
+        }
+        entry.extend(page.clone());
+    }
+
+    fn store_page<T: Clone>(entry: &mut Vec<T>, page: u32, items: &[T]) {
+        if page <= 1 {
+            entry.clear();
+        }
+        entry.extend_from_slice(items);
+    }
+
+    /// Forgets reaction `reaction_id` of the current user. Conversations
+    /// loaded over GraphQL never list reaction ids, so their counts go by
+    /// `content`.
+    fn remove_reaction(&mut self, comment_id: u64, reaction_id: u64, content: ReactionContent) {
+        if let Some(reactions) = self.reactions.get_mut(&comment_id) {
+            reactions.retain(|r| r.id.0 != reaction_id);
+        }
+        self.count_my_reaction(comment_id, content, false);
+    }
+
+    /// Counts a reaction the current user added (`added`) or removed on the
+    /// comment in the cached conversations.
+    fn count_my_reaction(&mut self, comment_id: u64, content: ReactionContent, added: bool) {
+        let Some(comment) = self
+            .conversations
+            .values_mut()
+            .flat_map(|conversation| conversation.comments.iter_mut())
+            .find(|comment| comment.id == comment_id)
+        else {
+            return;
+        };
+        let mine = comment.my_reactions.contains(&content);
+        if mine == added {
+            return;
+        }
+        if added {
+            comment.my_reactions.push(content.clone());
+            match comment.reactions.iter_mut().find(|(c, _)| *c == content) {
+                Some((_, count)) => *count += 1,
+                None => comment.reactions.push((content, 1)),
+            }
+        } else {
+            comment.my_reactions.retain(|c| *c != content);
+            if let Some((_, count)) = comment.reactions.iter_mut().find(|(c, _)| *c == content) {
+                *count = count.saturating_sub(1);
+            }
+            comment.reactions.retain(|(_, count)| *count > 0);
+        }
+    }
+}
+
+/// Writes through a temporary file, so a crash mid-write keeps the old cache.
+fn write_contents(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(tmp, path)
+}
+
+/// Wraps another tracker and records every successful response in a
+/// [`RepoCache`], so the repository can later be browsed with `--offline`.
+///
+/// The cache is written on [`flush`](IssueTracker::flush), which the app
+/// calls periodically and on shutdown, and only if something changed.
+pub struct CachedBackend {
+    inner: Backend,
+    path: PathBuf,
+    cache: Mutex<RepoCache>,
+    /// Set whenever the cache changes, cleared once it is written.
+    dirty: AtomicBool,
+    /// Held while writing, so an older cache never replaces a newer one.
+    flushing: tokio::sync::Mutex<()>,
+}
+
+impl CachedBackend {
+    /// `path` is usually [`RepoCache::path`].
+    pub fn new(inner: Backend, path: PathBuf) -> Self {
+        let cache = Mutex::new(RepoCache::read(&path));
+        Self {
+            inner,
+            path,
+            cache,
+            dirty: AtomicBool::new(false),
+            flushing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// The cache, for storing a response in it.
+    fn cache(&self) -> MutexGuard<'_, RepoCache> {
+        self.dirty.store(true, Ordering::Relaxed);
+        self.cache.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[async_trait]
+impl IssueTracker for CachedBackend {
+    async fn current_user(&self) -> Result<String, AppError> {
+        let login = self.inner.current_user().await?;
+        self.cache().current_user = Some(login.clone());
+        Ok(login)
+    }
+
+    async fn flush(&self) -> Result<(), AppError> {
+        self.inner.flush().await?;
+        let _flushing = self.flushing.lock().await;
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let contents = {
+            let cache = self.cache.lock().unwrap_or_else(|err| err.into_inner());
+            serde_json::to_vec(&*cache)
+        };
+        let path = self.path.clone();
+        let written = match contents {
+            Ok(contents) => {
+                tokio::task::spawn_blocking(move || write_contents(&path, &contents)).await?
+            }
+            Err(err) => Err(err.into()),
+        };
+        if written.is_err() {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        Ok(written?)
+    }
+
+    async fn search_issues(&self, params: SearchParams) -> Result<Page<Issue>, AppError> {
+        let page = self.inner.search_issues(params).await?;
+        let mut cache = self.cache();
+        page.items.iter().for_each(|issue| cache.store_issue(issue));
+        Ok(page)
+    }
+
+    async fn next_issue_page(&self, page: &Page<Issue>) -> Result<Option<Page<Issue>>, AppError> {
+        let next = self.inner.next_issue_page(page).await?;
+        if let Some(next) = &next {
+            let mut cache = self.cache();
+            next.items.iter().for_each(|issue| cache.store_issue(issue));
+        }
+        Ok(next)
+    }
+
+    async fn get_issue(&self, number: u64) -> Result<Issue, AppError> {
+        let issue = self.inner.get_issue(number).await?;
+        self.cache().store_issue(&issue);
+        Ok(issue)
+    }
+
+    async fn issue_templates(&self) -> Result<Vec<IssueTemplate>, AppError> {
+        let templates = self.inner.issue_templates().await?;
+        self.cache().issue_templates = templates.clone();
+        Ok(templates)
+    }
+
+    async fn create_issue(&self, issue: NewIssue) -> Result<Issue, AppError> {
+        let issue = self.inner.create_issue(issue).await?;
+        self.cache().store_issue(&issue);
+        Ok(issue)
+    }
+
+    async fn set_issue_state(
+        &self,
+        number: u64,
+        state: IssueState,
+        reason: Option<IssueStateReason>,
+    ) -> Result<Issue, AppError> {
+        let issue = self.inner.set_issue_state(number, state, reason).await?;
+        self.cache().store_issue(&issue);
+        Ok(issue)
+    }
+
+    async fn close_as_duplicate(&self, number: u64, original: u64) -> Result<Issue, AppError> {
+        let issue = self.inner.close_as_duplicate(number, original).await?;
+        self.cache().store_issue(&issue);
+        Ok(issue)
+    }
+
+    async fn update_issue(&self, number: u64, edit: IssueEdit) -> Result<Issue, AppError> {
+        let issue = self.inner.update_issue(number, edit).await?;
+        self.cache().store_issue(&issue);
+        Ok(issue)
+    }
+
+    async fn add_assignees(&self, number: u64, assignees: &[&str]) -> Result<Issue, AppError> {
+        let issue = self.inner.add_assignees(number, assignees).await?;
+        self.cache().store_issue(&issue);
+        Ok(issue)
+    }
+
+    async fn remove_assignees(&self, number: u64, assignees: &[&str]) -> Result<Issue, AppError> {
+        let issue = self.inner.remove_assignees(number, assignees).await?;
+        self.cache().store_issue(&issue);
+        Ok(issue)
+    }
+
+    async fn list_assignees(&self) -> Result<Vec<Author>, AppError> {
+        let assignees = self.inner.list_assignees().await?;
+        self.cache().assignees = assignees.clone();
+        Ok(assignees)
+    }
+
+    async fn list_comments(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Page<Comment>, AppError> {
+        let comments = self.inner.list_comments(number, page, per_page).await?;
+        let mut cache = self.cache();
+        let entry = cache.comments.entry(number).or_default();
+        RepoCache::store_page(entry, page, &comments.items);
+        Ok(comments)
+    }
+
+    async fn create_comment(&self, number: u64, body: String) -> Result<Comment, AppError> {
+        let comment = self.inner.create_comment(number, body).await?;
+        self.cache()
+            .comments
+            .entry(number)
+            .or_default()
+            .push(comment.clone());
+        Ok(comment)
+    }
+
+    async fn update_comment(&self, comment_id: u64, body: String) -> Result<Comment, AppError> {
+        let comment = self.inner.update_comment(comment_id, body).await?;
+        let mut cache = self.cache();
+        if let Some(existing) = cache
+            .comments
+            .values_mut()
+            .flatten()
+            .find(|c| c.id == comment.id)
+        {
+            *existing = comment.clone();
+        }
+        Ok(comment)
+    }
+
+    async fn list_comment_reactions(&self, comment_id: u64) -> Result<Vec<Reaction>, AppError> {
+        let reactions = self.inner.list_comment_reactions(comment_id).await?;
+        self.cache().reactions.insert(comment_id, reactions.clone());
+        Ok(reactions)
+    }
+
+    async fn add_comment_reaction(
+        &self,
+        comment_id: u64,
+        content: ReactionContent,
+    ) -> Result<Reaction, AppError> {
+        let reaction = self
+            .inner
+            .add_comment_reaction(comment_id, content.clone())
+            .await?;
+        let mut cache = self.cache();
+        if let Some(reactions) = cache.reactions.get_mut(&comment_id)
+            && !reactions.iter().any(|r| r.id == reaction.id)
+        {
+            reactions.push(reaction.clone());
+        }
+        cache.count_my_reaction(comment_id, content, true);
+        Ok(reaction)
+    }
+
+    async fn delete_comment_reaction(
+        &self,
+        comment_id: u64,
+        reaction_id: u64,
+        content: ReactionContent,
+    ) -> Result<(), AppError> {
+        self.inner
+            .delete_comment_reaction(comment_id, reaction_id, content.clone())
+            .await?;
+        self.cache()
+            .remove_reaction(comment_id, reaction_id, content);
+        Ok(())
+    }
+
+    async fn list_timeline(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Page<TimelineEvent>, AppError> {
+        let events = self.inner.list_timeline(number, page, per_page).await?;
+        let mut cache = self.cache();
+        let entry = cache.timeline.entry(number).or_default();
+        RepoCache::store_page(entry, page, &events.items);
+        Ok(events)
+    }
+
+    async fn conversation(
+        &self,
+        number: u64,
+        comments: &PageCursor,
+        timeline: &PageCursor,
+    ) -> Result<Option<Conversation>, AppError> {
+        let page = self.inner.conversation(number, comments, timeline).await?;
+        if let Some(page) = &page {
+            self.cache()
+                .store_conversation(number, comments, timeline, page);
+        }
+        Ok(page)
+    }
+
+    async fn pull_request_summaries(
+        &self,
+        numbers: &[u64],
+    ) -> Result<Vec<PullRequestSummary>, AppError> {
+        let summaries = self.inner.pull_request_summaries(numbers).await?;
+        let mut cache = self.cache();
+        for summary in &summaries {
+            cache.pull_requests.insert(summary.number, summary.clone());
+        }
+        Ok(summaries)
+    }
+
+    async fn list_pull_request_files(&self, number: u64) -> Result<Vec<DiffEntry>, AppError> {
+        let files = self.inner.list_pull_request_files(number).await?;
+        self.cache()
+            .pull_request_files
+            .insert(number, files.clone());
+        Ok(files)
+    }
+
+    async fn pull_request_checks(&self, number: u64) -> Result<PullRequestChecks, AppError> {
+        let checks = self.inner.pull_request_checks(number).await?;
+        self.cache()
+            .pull_request_checks
+            .insert(number, checks.clone());
+        Ok(checks)
+    }
+
+    async fn rerun_check(&self, target: RerunTarget) -> Result<(), AppError> {
+        self.inner.rerun_check(target).await
+    }
+
+    async fn review_threads(&self, number: u64) -> Result<Vec<ReviewThread>, AppError> {
+        let threads = self.inner.review_threads(number).await?;
+        self.cache().review_threads.insert(number, threads.clone());
+        Ok(threads)
+    }
+
+    async fn set_review_thread_resolved(
+        &self,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<(), AppError> {
+        self.inner
+            .set_review_thread_resolved(thread_id, resolved)
+            .await?;
+        if let Some(thread) = self
+            .cache()
+            .review_threads
+            .values_mut()
+            .flatten()
+            .find(|thread| thread.id == thread_id)
+        {
+            thread.resolved = resolved;
+        }
+        Ok(())
+    }
+
+    async fn submit_review(&self, number: u64, review: NewReview) -> Result<(), AppError> {
+        self.inner.submit_review(number, review).await
+    }
+
+    async fn merge_status(&self, number: u64) -> Result<MergeStatus, AppError> {
+        self.inner.merge_status(number).await
+    }
+
+    async fn merge_pull_request(&self, number: u64, request: MergeRequest) -> Result<(), AppError> {
+        self.inner.merge_pull_request(number, request).await
+    }
+
+    async fn enable_auto_merge(
+        &self,
+        pull_request_id: &str,
+        request: MergeRequest,
+    ) -> Result<(), AppError> {
+        self.inner.enable_auto_merge(pull_request_id, request).await
+    }
+
+    async fn disable_auto_merge(&self, pull_request_id: &str) -> Result<(), AppError> {
+        self.inner.disable_auto_merge(pull_request_id).await
+    }
+
+    async fn delete_branch(&self, branch: &str) -> Result<(), AppError> {
+        self.inner.delete_branch(branch).await
+    }
+
+    async fn list_milestones(&self) -> Result<Vec<Milestone>, AppError> {
+        let milestones = self.inner.list_milestones().await?;
+        self.cache().milestones = milestones.clone();
+        Ok(milestones)
+    }
+
+    async fn create_milestone(&self, milestone: MilestoneEdit) -> Result<Milestone, AppError> {
+        let milestone = self.inner.create_milestone(milestone).await?;
+        self.cache().milestones.push(milestone.clone());
+        Ok(milestone)
+    }
+
+    async fn update_milestone(
+        &self,
+        number: u64,
+        edit: MilestoneEdit,
+    ) -> Result<Milestone, AppError> {
+        let milestone = self.inner.update_milestone(number, edit).await?;
+        if let Some(existing) = self
+            .cache()
+            .milestones
+            .iter_mut()
+            .find(|m| m.number == milestone.number)
+        {
+            *existing = milestone.clone();
+        }
+        Ok(milestone)
+    }
+
+    async fn list_labels(&self, page: u32, per_page: u8) -> Result<Page<Label>, AppError> {
+        let labels = self.inner.list_labels(page, per_page).await?;
+        let mut cache = self.cache();
+        RepoCache::store_page(&mut cache.labels, page, &labels.items);
+        Ok(labels)
+    }
+
+    async fn get_label(&self, name: &str) -> Result<Label, AppError> {
+        self.inner.get_label(name).await
+    }
+
+    async fn create_label(
+        &self,
+        name: &str,
+        color: &str,
+        description: &str,
+    ) -> Result<Label, AppError> {
+        let label = self.inner.create_label(name, color, description).await?;
+        self.cache().labels.push(label.clone());
+        Ok(label)
+    }
+
+    async fn add_labels(&self, number: u64, labels: &[String]) -> Result<Vec<Label>, AppError> {
+        let labels = self.inner.add_labels(number, labels).await?;
+        if let Some(issue) = self.cache().issues.get_mut(&number) {
+            issue.labels = labels.clone();
+        }
+        Ok(labels)
+    }
+
+    async fn remove_label(&self, number: u64, name: &str) -> Result<Vec<Label>, AppError> {
+        let labels = self.inner.remove_label(number, name).await?;
+        if let Some(issue) = self.cache().issues.get_mut(&number) {
+            issue.labels = labels.clone();
+        }
+        Ok(labels)
+    }
+
+    async fn update_label(&self, name: &str, edit: LabelEdit) -> Result<Label, AppError> {
+        let label = self.inner.update_label(name, edit).await?;
+        let mut cache = self.cache();
+        if let Some(existing) = cache.labels.iter_mut().find(|l| l.name == name) {
+            *existing = label.clone();
+        }
+        if let Some(count) = cache.label_open_issue_counts.remove(name) {
+            cache
+                .label_open_issue_counts
+                .insert(label.name.clone(), count);
+        }
+        Ok(label)
+    }
+
+    async fn delete_label(&self, name: &str) -> Result<(), AppError> {
+        self.inner.delete_label(name).await?;
+        let mut cache = self.cache();
+        cache.labels.retain(|label| label.name != name);
+        cache.label_open_issue_counts.remove(name);
+        Ok(())
+    }
+
+    async fn label_open_issue_counts(&self) -> Result<HashMap<String, u64>, AppError> {
+        let counts = self.inner.label_open_issue_counts().await?;
+        self.cache().label_open_issue_counts = counts.clone();
+        Ok(counts)
+    }
+
+    async fn assignee_open_issue_counts(
+        &self,
+        logins: &[String],
+    ) -> Result<HashMap<String, u64>, AppError> {
+        let counts = self.inner.assignee_open_issue_counts(logins).await?;
+        self.cache()
+            .assignee_open_issue_counts
+            .extend(counts.clone());
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ConversationComment;
+
+    #[test]
+    fn my_reactions_are_counted_in_cached_conversations() {
+        let mut cache = RepoCache::default();
+        cache.conversations.insert(
+            1,
+            Conversation {
+                comments: vec![ConversationComment {
+                    id: 7,
+                    author: "jane".to_string(),
+                    created_at: chrono::Utc::now(),
+                    body: String::new(),
+                    reactions: vec![(ReactionContent::Heart, 2)],
+                    my_reactions: Vec::new(),
+                }],
+                ..Default::default()
+            },
+        );
+        cache.count_my_reaction(7, ReactionContent::Heart, true);
+        cache.count_my_reaction(7, ReactionContent::Heart, true);
+        cache.count_my_reaction(7, ReactionContent::Rocket, true);
+        let comment = &cache.conversations[&1].comments[0];
+        assert_eq!(
+            comment.reactions,
+            vec![(ReactionContent::Heart, 3), (ReactionContent::Rocket, 1)]
+        );
+
+        cache.count_my_reaction(7, ReactionContent::Rocket, false);
+        let comment = &cache.conversations[&1].comments[0];
+        assert_eq!(comment.reactions, vec![(ReactionContent::Heart, 3)]);
+        assert_eq!(comment.my_reactions, vec![ReactionContent::Heart]);
+    }
+
+    #[test]
+    fn removed_reactions_are_uncounted_in_graphql_conversations() {
+        let mut cache = RepoCache::default();
+        let page = Conversation {
+            comments: vec![ConversationComment {
+                id: 7,
+                author: "jane".to_string(),
+                created_at: chrono::Utc::now(),
+                body: String::new(),
+                reactions: vec![(ReactionContent::Heart, 2)],
+                my_reactions: vec![ReactionContent::Heart],
+            }],
+            ..Default::default()
+        };
+        cache.store_conversation(1, &PageCursor::Start, &PageCursor::Start, &page);
+        assert!(cache.reactions.is_empty());
+
+        cache.remove_reaction(7, 42, ReactionContent::Heart);
+        let comment = &cache.conversations[&1].comments[0];
+        assert_eq!(comment.reactions, vec![(ReactionContent::Heart, 1)]);
+        assert!(comment.my_reactions.is_empty());
+    }
+}
@@ -0,0 +1,495 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use octocrab::{
+    Page,
+    models::{
+        Author, CommentId, IssueState, Label, Milestone,
+        issues::{Comment, Issue, IssueStateReason},
+        reactions::{Reaction, ReactionContent},
+        repos::DiffEntry,
+        timelines::TimelineEvent,
+    },
+};
+use tracing::warn;
+
+use crate::{
+    backend::{
+        Conversation, IssueEdit, IssueTracker, LabelEdit, MergeRequest, MergeStatus, MilestoneEdit,
+        NewIssue, NewReview, PageCursor, PullRequestChecks, PullRequestSummary, RerunTarget,
+        ReviewThread, SearchParams,
+        templates::{self, IssueTemplate},
+    },
+    errors::AppError,
+    github::GithubClient,
+};
+
+mod assignees;
+mod checks;
+mod conversation;
+mod duplicate;
+mod labels;
+mod merge;
+mod pulls;
+
+/// [`IssueTracker`] backed by the GitHub REST API, with GraphQL where it saves
+/// round trips.
+pub struct GithubBackend {
+    client: GithubClient,
+    owner: String,
+    repo: String,
+}
+
+impl GithubBackend {
+    pub fn new(client: GithubClient, owner: String, repo: String) -> Self {
+        Self {
+            client,
+            owner,
+            repo,
+        }
+    }
+
+    pub fn client(&self) -> &GithubClient {
+        &self.client
+    }
+
+    fn issues(&self) -> octocrab::issues::IssueHandler<'_> {
+        self.client.inner().issues(&self.owner, &self.repo)
+    }
+}
+
+#[async_trait]
+impl IssueTracker for GithubBackend {
+    async fn current_user(&self) -> Result<String, AppError> {
+        Ok(self
+            .client
+            .send(self.client.inner().current().user())
+            .await?
+            .login)
+    }
+
+    async fn search_issues(&self, params: SearchParams) -> Result<Page<Issue>, AppError> {
+        let search = self.client.inner().search();
+        let mut request = search
+            .issues_and_pull_requests(&params.query)
+            .page(params.page)
+            .per_page(params.per_page);
+        if let Some(sort) = params.sort.as_deref() {
+            request = request.sort(sort);
+        }
+        if let Some(order) = params.order.as_deref() {
+            request = request.order(order);
+        }
+        self.client.send_search(request.send()).await
+    }
+
+    async fn next_issue_page(&self, page: &Page<Issue>) -> Result<Option<Page<Issue>>, AppError> {
+        self.client.next_page(page).await
+    }
+
+    async fn get_issue(&self, number: u64) -> Result<Issue, AppError> {
+        self.client.send(self.issues().get(number)).await
+    }
+
+    async fn create_issue(&self, issue: NewIssue) -> Result<Issue, AppError> {
+        let issues = self.issues();
+        let mut create = issues.create(issue.title);
+        if let Some(body) = issue.body {
+            create = create.body(body);
+        }
+        if let Some(labels) = issue.labels {
+            create = create.labels(labels);
+        }
+        if let Some(assignees) = issue.assignees {
+            create = create.assignees(assignees);
+        }
+        self.client.send(create.send()).await
+    }
+
+    async fn issue_templates(&self) -> Result<Vec<IssueTemplate>, AppError> {
+        let repos = self.client.inner().repos(&self.owner, &self.repo);
+        let listing = match self
+            .client
+            .send(repos.get_content().path(templates::TEMPLATE_DIR).send())
+            .await
+        {
+            Ok(listing) => listing,
+            Err(err) if err.is_not_found() => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut found = Vec::new();
+        for entry in listing
+            .items
+            .into_iter()
+            .filter(|item| item.r#type == "file")
+        {
+            let mut file = self
+                .client
+                .send(repos.get_content().path(&entry.path).send())
+                .await?;
+            let Some(contents) = file.items.pop().and_then(|file| file.decoded_content()) else {
+                continue;
+            };
+            match templates::parse_template(&entry.name, &contents) {
+                Ok(Some(template)) => found.push(template),
+                Ok(None) => {}
+                Err(err) => warn!(file = %entry.path, error = %err, "skipping issue template"),
+            }
+        }
+        found.sort_by_key(|template| template.name.to_lowercase());
+        Ok(found)
+    }
+
+    async fn set_issue_state(
+        &self,
+        number: u64,
+        state: IssueState,
+        reason: Option<IssueStateReason>,
+    ) -> Result<Issue, AppError> {
+        let issues = self.issues();
+        let mut update = issues.update(number).state(state);
+        if let Some(reason) = reason {
+            update = update.state_reason(reason);
+        }
+        self.client.send(update.send()).await
+    }
+
+    async fn close_as_duplicate(&self, number: u64, original: u64) -> Result<Issue, AppError> {
+        let original = self.get_issue(original).await?;
+        let issue = self.get_issue(number).await?;
+        // Closing comes first, so a failed close leaves no comment behind.
+        // Hosts without `duplicateIssueId` (older GHES) reject the mutation;
+        // there the issue is closed as not planned, which is how GitHub
+        // closed duplicates before.
+        match duplicate::close_as_duplicate(&self.client, &issue.node_id, &original.node_id).await {
+            Ok(()) => {}
+            Err(AppError::GraphQl(message)) if duplicate::is_unsupported(&message) => {
+                warn!(%message, "host cannot close as duplicate, closing as not planned");
+                self.set_issue_state(
+                    number,
+                    IssueState::Closed,
+                    Some(IssueStateReason::NotPlanned),
+                )
+                .await?;
+            }
+            Err(err) => return Err(err),
+        }
+        self.create_comment(number, format!("Duplicate of #{}", original.number))
+            .await?;
+        self.get_issue(number).await
+    }
+
+    async fn update_issue(&self, number: u64, edit: IssueEdit) -> Result<Issue, AppError> {
+        // The update builder has no way to send `"milestone": null`.
+        let route = format!("/repos/{}/{}/issues/{number}", self.owner, self.repo);
+        self.client
+            .send(self.client.inner().patch(route, Some(&edit)))
+            .await
+    }
+
+    async fn add_assignees(&self, number: u64, assignees: &[&str]) -> Result<Issue, AppError> {
+        self.client
+            .send(self.issues().add_assignees(number, assignees))
+            .await
+    }
+
+    async fn remove_assignees(&self, number: u64, assignees: &[&str]) -> Result<Issue, AppError> {
+        self.client
+            .send(self.issues().remove_assignees(number, assignees))
+            .await
+    }
+
+    async fn list_assignees(&self) -> Result<Vec<Author>, AppError> {
+        let route = format!("/repos/{}/{}/assignees", self.owner, self.repo);
+        let inner = self.client.inner();
+        let params = serde_json::json!({ "per_page": 100 });
+        let mut page: Page<Author> = self.client.send(inner.get(route, Some(&params))).await?;
+        let mut assignees = std::mem::take(&mut page.items);
+        while let Some(mut next) = self.client.next_page(&page).await? {
+            assignees.append(&mut next.items);
+            page = next;
+        }
+        Ok(assignees)
+    }
+
+    async fn list_comments(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Page<Comment>, AppError> {
+        self.client
+            .send(
+                self.issues()
+                    .list_comments(number)
+                    .per_page(per_page)
+                    .page(page)
+                    .send(),
+            )
+            .await
+    }
+
+    async fn create_comment(&self, number: u64, body: String) -> Result<Comment, AppError> {
+        self.client
+            .send(self.issues().create_comment(number, body))
+            .await
+    }
+
+    async fn update_comment(&self, comment_id: u64, body: String) -> Result<Comment, AppError> {
+        self.client
+            .send(self.issues().update_comment(CommentId(comment_id), body))
+            .await
+    }
+
+    async fn list_comment_reactions(&self, comment_id: u64) -> Result<Vec<Reaction>, AppError> {
+        let issues = self.issues();
+        let request = issues.list_comment_reactions(comment_id).per_page(100);
+        Ok(self.client.send(request.send()).await?.items)
+    }
+
+    async fn add_comment_reaction(
+        &self,
+        comment_id: u64,
+        content: ReactionContent,
+    ) -> Result<Reaction, AppError> {
+        self.client
+            .send(self.issues().create_comment_reaction(comment_id, content))
+            .await
+    }
+
+    async fn delete_comment_reaction(
+        &self,
+        comment_id: u64,
+        reaction_id: u64,
+        _content: ReactionContent,
+    ) -> Result<(), AppError> {
+        self.client
+            .send(
+                self.issues()
+                    .delete_comment_reaction(comment_id, reaction_id),
+            )
+            .await
+    }
+
+    async fn list_timeline(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Page<TimelineEvent>, AppError> {
+        self.client
+            .send(
+                self.issues()
+                    .list_timeline_events(number)
+                    .per_page(per_page)
+                    .page(page)
+                    .send(),
+            )
+            .await
+    }
+
+    async fn conversation(
+        &self,
+        number: u64,
+        comments: &PageCursor,
+        timeline: &PageCursor,
+    ) -> Result<Option<Conversation>, AppError> {
+        let conversation = conversation::fetch(
+            &self.client,
+            &self.owner,
+            &self.repo,
+            number,
+            comments,
+            timeline,
+        )
+        .await?;
+        Ok(Some(conversation))
+    }
+
+    async fn pull_request_summaries(
+        &self,
+        numbers: &[u64],
+    ) -> Result<Vec<PullRequestSummary>, AppError> {
+        pulls::summaries(&self.client, &self.owner, &self.repo, numbers).await
+    }
+
+    async fn list_pull_request_files(&self, number: u64) -> Result<Vec<DiffEntry>, AppError> {
+        let route = format!("/repos/{}/{}/pulls/{number}/files", self.owner, self.repo);
+        let inner = self.client.inner();
+        let mut page: Page<DiffEntry> = self
+            .client
+            .send(inner.get(route, Some(&[("per_page", 100)])))
+            .await?;
+        let mut files = std::mem::take(&mut page.items);
+        while let Some(mut next) = self.client.next_page(&page).await? {
+            files.append(&mut next.items);
+            page = next;
+        }
+        Ok(files)
+    }
+
+    async fn pull_request_checks(&self, number: u64) -> Result<PullRequestChecks, AppError> {
+        checks::fetch(&self.client, &self.owner, &self.repo, number).await
+    }
+
+    async fn rerun_check(&self, target: RerunTarget) -> Result<(), AppError> {
+        checks::rerun(&self.client, &self.owner, &self.repo, target).await
+    }
+
+    async fn review_threads(&self, number: u64) -> Result<Vec<ReviewThread>, AppError> {
+        pulls::review_threads(&self.client, &self.owner, &self.repo, number).await
+    }
+
+    async fn set_review_thread_resolved(
+        &self,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<(), AppError> {
+        pulls::set_thread_resolved(&self.client, thread_id, resolved).await
+    }
+
+    async fn submit_review(&self, number: u64, review: NewReview) -> Result<(), AppError> {
+        let route = format!("/repos/{}/{}/pulls/{number}/reviews", self.owner, self.repo);
+        let body = serde_json::json!({
+            "event": review.event,
+            "body": review.body,
+            "comments": review.comments,
+        });
+        let _: serde_json::Value = self
+            .client
+            .send(self.client.inner().post(route, Some(&body)))
+            .await?;
+        Ok(())
+    }
+
+    async fn merge_status(&self, number: u64) -> Result<MergeStatus, AppError> {
+        merge::status(&self.client, &self.owner, &self.repo, number).await
+    }
+
+    async fn merge_pull_request(&self, number: u64, request: MergeRequest) -> Result<(), AppError> {
+        merge::merge(&self.client, &self.owner, &self.repo, number, request).await
+    }
+
+    async fn enable_auto_merge(
+        &self,
+        pull_request_id: &str,
+        request: MergeRequest,
+    ) -> Result<(), AppError> {
+        merge::enable_auto_merge(&self.client, pull_request_id, request).await
+    }
+
+    async fn disable_auto_merge(&self, pull_request_id: &str) -> Result<(), AppError> {
+        merge::disable_auto_merge(&self.client, pull_request_id).await
+    }
+
+    async fn delete_branch(&self, branch: &str) -> Result<(), AppError> {
+        let reference = octocrab::params::repos::Reference::Branch(branch.to_string());
+        self.client
+            .send(
+                self.client
+                    .inner()
+                    .repos(&self.owner, &self.repo)
+                    .delete_ref(&reference),
+            )
+            .await
+    }
+
+    async fn list_milestones(&self) -> Result<Vec<Milestone>, AppError> {
+        let route = format!("/repos/{}/{}/milestones", self.owner, self.repo);
+        let inner = self.client.inner();
+        let params = serde_json::json!({ "state": "all", "per_page": 100 });
+        let mut page: Page<Milestone> = self.client.send(inner.get(route, Some(&params))).await?;
+        let mut milestones = std::mem::take(&mut page.items);
+        while let Some(mut next) = self.client.next_page(&page).await? {
+            milestones.append(&mut next.items);
+            page = next;
+        }
+        Ok(milestones)
+    }
+
+    async fn create_milestone(&self, milestone: MilestoneEdit) -> Result<Milestone, AppError> {
+        let route = format!("/repos/{}/{}/milestones", self.owner, self.repo);
+        self.client
+            .send(self.client.inner().post(route, Some(&milestone)))
+            .await
+    }
+
+    async fn update_milestone(
+        &self,
+        number: u64,
+        edit: MilestoneEdit,
+    ) -> Result<Milestone, AppError> {
+        let route = format!("/repos/{}/{}/milestones/{number}", self.owner, self.repo);
+        self.client
+            .send(self.client.inner().patch(route, Some(&edit)))
+            .await
+    }
+
+    async fn list_labels(&self, page: u32, per_page: u8) -> Result<Page<Label>, AppError> {
+        self.client
+            .send(
+                self.issues()
+                    .list_labels_for_repo()
+                    .per_page(per_page)
+                    .page(page)
+                    .send(),
+            )
+            .await
+    }
+
+    async fn get_label(&self, name: &str) -> Result<Label, AppError> {
+        self.client.send(self.issues().get_label(name)).await
+    }
+
+    async fn create_label(
+        &self,
+        name: &str,
+        color: &str,
+        description: &str,
+    ) -> Result<Label, AppError> {
+        self.client
+            .send(self.issues().create_label(name, color, description))
+            .await
+    }
+
+    async fn add_labels(&self, number: u64, labels: &[String]) -> Result<Vec<Label>, AppError> {
+        self.client
+            .send(self.issues().add_labels(number, labels))
+            .await
+    }
+
+    async fn remove_label(&self, number: u64, name: &str) -> Result<Vec<Label>, AppError> {
+        self.client
+            .send(self.issues().remove_label(number, name))
+            .await
+    }
+
+    async fn update_label(&self, name: &str, edit: LabelEdit) -> Result<Label, AppError> {
+        let route = format!(
+            "/repos/{}/{}/labels/{}",
+            self.owner,
+            self.repo,
+            labels::encode_name(name)
+        );
+        self.client
+            .send(self.client.inner().patch(route, Some(&edit)))
+            .await
+    }
+
+    async fn delete_label(&self, name: &str) -> Result<(), AppError> {
+        self.client
+            .send(self.issues().delete_label(labels::encode_name(name)))
+            .await
+    }
+
+    async fn label_open_issue_counts(&self) -> Result<HashMap<String, u64>, AppError> {
+        labels::open_issue_counts(&self.client, &self.owner, &self.repo).await
+    }
+
+    async fn assignee_open_issue_counts(
+        &self,
+        logins: &[String],
+    ) -> Result<HashMap<String, u64>, AppError> {
+        assignees::open_issue_counts(&self.client, &self.owner, &self.repo, logins).await
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{errors::AppError, github::GithubClient};
+
+/// Users counted per request; each one is a search in the same query.
+const BATCH: usize = 50;
+
+/// Number of open issues assigned to each of `logins`.
+pub(super) async fn open_issue_counts(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    logins: &[String],
+) -> Result<HashMap<String, u64>, AppError> {
+    let mut counts = HashMap::new();
+    for batch in logins.chunks(BATCH) {
+        let (query, variables) = counts_query(owner, repo, batch);
+        let data: HashMap<String, SearchCount> = client.graphql_query(&query, variables).await?;
+        for (idx, login) in batch.iter().enumerate() {
+            if let Some(count) = data.get(&format!("u{idx}")) {
+                counts.insert(login.clone(), count.issue_count);
+            }
+        }
+    }
+    Ok(counts)
+}
+
+/// Builds one aliased issue search per login, as GraphQL has no way to group
+/// open issues by assignee.
+fn counts_query(owner: &str, repo: &str, logins: &[String]) -> (String, Value) {
+    let mut params = Vec::with_capacity(logins.len());
+    let mut searches = String::new();
+    let mut variables = Map::new();
+    for (idx, login) in logins.iter().enumerate() {
+        params.push(format!("$q{idx}: String!"));
+        searches.push_str(&format!(
+            "  u{idx}: search(query: $q{idx}, type: ISSUE, first: 0) {{ issueCount }}\n"
+        ));
+        variables.insert(
+            format!("q{idx}"),
+            Value::String(format!(
+                "repo:{owner}/{repo} is:issue is:open assignee:{login}"
+            )),
+        );
+    }
+    let query = format!("query({}) {{\n{searches}}}", params.join(", "));
+    (query, Value::Object(variables))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchCount {
+    issue_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_search_per_login() {
+        let logins = vec!["octocat".to_string(), "hubot".to_string()];
+        let (query, variables) = counts_query("o", "r", &logins);
+        assert!(query.starts_with("query($q0: String!, $q1: String!) {"));
+        assert!(query.contains("u1: search(query: $q1, type: ISSUE, first: 0) { issueCount }"));
+        assert_eq!(
+            variables["q0"],
+            "repo:o/r is:issue is:open assignee:octocat"
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    backend::{CheckAnnotation, CheckEntry, CheckOutcome, PullRequestChecks, RerunTarget},
+    errors::AppError,
+    github::GithubClient,
+};
+
+const CHECKS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      headRefOid
+      commits(last: 1) {
+        nodes {
+          commit {
+            statusCheckRollup {
+              contexts(first: 100) {
+                nodes {
+                  __typename
+                  ... on CheckRun {
+                    databaseId
+                    name
+                    status
+                    conclusion
+                    startedAt
+                    completedAt
+                    detailsUrl
+                    title
+                    checkSuite {
+                      app { name }
+                      workflowRun { databaseId workflow { name } }
+                    }
+                    annotations(first: 50) {
+                      nodes {
+                        path
+                        location { start { line } }
+                        annotationLevel
+                        title
+                        message
+                      }
+                    }
+                  }
+                  ... on StatusContext {
+                    context
+                    state
+                    description
+                    targetUrl
+                    createdAt
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+pub(super) async fn fetch(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> Result<PullRequestChecks, AppError> {
+    let variables = json!({ "owner": owner, "repo": repo, "number": number });
+    let data: Data = client.graphql_query(CHECKS_QUERY, variables).await?;
+    let pull_request = data
+        .repository
+        .and_then(|repo| repo.pull_request)
+        .ok_or_else(|| AppError::GraphQl(format!("pull request #{number} not found")))?;
+    let checks = pull_request
+        .commits
+        .nodes
+        .into_iter()
+        .flatten()
+        .next_back()
+        .and_then(|node| node.commit.status_check_rollup)
+        .map(|rollup| {
+            rollup
+                .contexts
+                .nodes
+                .into_iter()
+                .flatten()
+                .filter_map(Context::into_entry)
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(PullRequestChecks {
+        head_sha: pull_request.head_ref_oid,
+        checks,
+    })
+}
+
+fn check_run_outcome(status: &str, conclusion: Option<&str>) -> CheckOutcome {
+    if status != "COMPLETED" {
+        return CheckOutcome::Pending;
+    }
+    match conclusion {
+        Some("SUCCESS") => CheckOutcome::Success,
+        Some("NEUTRAL") => CheckOutcome::Neutral,
+        Some("SKIPPED") => CheckOutcome::Skipped,
+        Some("CANCELLED" | "STALE") => CheckOutcome::Cancelled,
+        _ => CheckOutcome::Failure,
+    }
+}
+
+fn status_outcome(state: &str) -> CheckOutcome {
+    match state {
+        "SUCCESS" => CheckOutcome::Success,
+        "PENDING" | "EXPECTED" => CheckOutcome::Pending,
+        _ => CheckOutcome::Failure,
+    }
+}
+
+#[derive(Deserialize)]
+struct Data {
+    repository: Option<Repository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Repository {
+    pull_request: Option<PullRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequest {
+    head_ref_oid: String,
+    commits: Nodes<CommitNode>,
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<Option<T>>,
+}
+
+#[derive(Deserialize)]
+struct CommitNode {
+    commit: Commit,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Commit {
+    status_check_rollup: Option<Rollup>,
+}
+
+#[derive(Deserialize)]
+struct Rollup {
+    contexts: Nodes<Context>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "__typename")]
+enum Context {
+    CheckRun(CheckRun),
+    StatusContext(StatusContext),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckRun {
+    database_id: Option<u64>,
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    details_url: Option<String>,
+    title: Option<String>,
+    check_suite: Option<CheckSuite>,
+    annotations: Option<Nodes<Annotation>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckSuite {
+    app: Option<Named>,
+    workflow_run: Option<WorkflowRun>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkflowRun {
+    database_id: Option<u64>,
+    workflow: Option<Named>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Annotation {
+    path: String,
+    location: Option<Location>,
+    annotation_level: Option<String>,
+    title: Option<String>,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct Location {
+    start: Position,
+}
+
+#[derive(Deserialize)]
+struct Position {
+    line: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusContext {
+    context: String,
+    state: String,
+    description: Option<String>,
+    target_url: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl Context {
+    fn into_entry(self) -> Option<CheckEntry> {
+        match self {
+            Context::CheckRun(run) => Some(run.into_entry()),
+            Context::StatusContext(status) => Some(CheckEntry {
+                outcome: status_outcome(&status.state),
+                name: status.context,
+                source: None,
+                started_at: status.created_at,
+                completed_at: None,
+                url: status.target_url,
+                description: status.description,
+                annotations: Vec::new(),
+                rerun: None,
+            }),
+            Context::Other => None,
+        }
+    }
+}
+
+impl CheckRun {
+    fn into_entry(self) -> CheckEntry {
+        let suite = self.check_suite;
+        let workflow_run = suite.as_ref().and_then(|suite| suite.workflow_run.as_ref());
+        let rerun = match (
+            workflow_run.and_then(|run| run.database_id),
+            self.database_id,
+        ) {
+            (Some(run), _) => Some(RerunTarget::WorkflowRun(run)),
+            (None, Some(id)) => Some(RerunTarget::CheckRun(id)),
+            (None, None) => None,
+        };
+        let source = workflow_run
+            .and_then(|run| run.workflow.as_ref())
+            .or_else(|| suite.as_ref().and_then(|suite| suite.app.as_ref()))
+            .map(|named| named.name.clone());
+        let annotations = self
+            .annotations
+            .map(|annotations| annotations.nodes.into_iter().flatten().collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|annotation| CheckAnnotation {
+                path: annotation.path,
+                start_line: annotation.location.and_then(|loc| loc.start.line),
+                level: annotation
+                    .annotation_level
+                    .unwrap_or_else(|| "NOTICE".to_string()),
+                title: annotation.title,
+                message: annotation.message,
+            })
+            .collect();
+        CheckEntry {
+            outcome: check_run_outcome(&self.status, self.conclusion.as_deref()),
+            name: self.name,
+            source,
+            started_at: self.started_at,
+            completed_at: self.completed_at,
+            url: self.details_url,
+            description: self.title,
+            annotations,
+            rerun,
+        }
+    }
+}
+
+/// Re-runs the failed jobs of a workflow run, or re-requests a check run.
+pub(super) async fn rerun(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    target: RerunTarget,
+) -> Result<(), AppError> {
+    match target {
+        RerunTarget::WorkflowRun(id) => {
+            let route = format!("/repos/{owner}/{repo}/actions/runs/{id}/rerun-failed-jobs");
+            client
+                .send(async {
+                    let response = client.inner()._post(route, None::<&()>).await?;
+                    octocrab::map_github_error(response).await.map(drop)
+                })
+                .await
+        }
+        RerunTarget::CheckRun(id) => {
+            client
+                .send(
+                    client
+                        .inner()
+                        .checks(owner, repo)
+                        .rerequest_check_run(id.into())
+                        .send(),
+                )
+                .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contexts_become_check_entries() {
+        let rollup: Rollup = serde_json::from_value(json!({
+            "contexts": { "nodes": [
+                {
+                    "__typename": "CheckRun",
+                    "databaseId": 7,
+                    "name": "test",
+                    "status": "COMPLETED",
+                    "conclusion": "TIMED_OUT",
+                    "startedAt": "2024-01-01T00:00:00Z",
+                    "completedAt": "2024-01-01T00:01:30Z",
+                    "detailsUrl": "https://example.com/7",
+                    "title": null,
+                    "checkSuite": {
+                        "app": { "name": "GitHub Actions" },
+                        "workflowRun": { "databaseId": 99, "workflow": { "name": "CI" } }
+                    },
+                    "annotations": { "nodes": [{
+                        "path": "src/lib.rs",
+                        "location": { "start": { "line": 3 } },
+                        "annotationLevel": "FAILURE",
+                        "title": null,
+                        "message": "boom"
+                    }] }
+                },
+                {
+                    "__typename": "StatusContext",
+                    "context": "ci/legacy",
+                    "state": "PENDING",
+                    "description": "Waiting",
+                    "targetUrl": null,
+                    "createdAt": "2024-01-01T00:00:00Z"
+                },
+                { "__typename": "SomethingNew" }
+            ] }
+        }))
+        .unwrap();
+        let entries = rollup
+            .contexts
+            .nodes
+            .into_iter()
+            .flatten()
+            .filter_map(Context::into_entry)
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].outcome, CheckOutcome::Failure);
+        assert_eq!(entries[0].source.as_deref(), Some("CI"));
+        assert_eq!(entries[0].rerun, Some(RerunTarget::WorkflowRun(99)));
+        assert_eq!(entries[0].annotations[0].start_line, Some(3));
+        assert_eq!(entries[1].outcome, CheckOutcome::Pending);
+        assert_eq!(entries[1].rerun, None);
+    }
+}
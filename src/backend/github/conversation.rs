@@ -0,0 +1,549 @@
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use octocrab::models::{Event as IssueEvent, reactions::ReactionContent};
+use serde::{Deserialize, Deserializer};
+use serde_json::json;
+
+use crate::{
+    backend::{Conversation, ConversationComment, ConversationEvent, PageCursor},
+    errors::AppError,
+    github::GithubClient,
+};
+
+const PAGE_SIZE: u8 = 100;
+
+/// Timeline item types shared by issues and pull requests, with the fields
+/// needed to describe them.
+const EVENTS: &[(&str, IssueEvent, &str)] = &[
+    ("LabeledEvent", IssueEvent::Labeled, "label { name }"),
+    ("UnlabeledEvent", IssueEvent::Unlabeled, "label { name }"),
+    ("ClosedEvent", IssueEvent::Closed, "closer { ...Subject }"),
+    ("ReopenedEvent", IssueEvent::Reopened, ""),
+    (
+        "AssignedEvent",
+        IssueEvent::Assigned,
+        "assignee { ...Login }",
+    ),
+    (
+        "UnassignedEvent",
+        IssueEvent::Unassigned,
+        "assignee { ...Login }",
+    ),
+    ("MilestonedEvent", IssueEvent::Milestoned, "milestoneTitle"),
+    (
+        "DemilestonedEvent",
+        IssueEvent::Demilestoned,
+        "milestoneTitle",
+    ),
+    (
+        "RenamedTitleEvent",
+        IssueEvent::Renamed,
+        "previousTitle currentTitle",
+    ),
+    ("LockedEvent", IssueEvent::Locked, ""),
+    ("UnlockedEvent", IssueEvent::Unlocked, ""),
+    (
+        "CrossReferencedEvent",
+        IssueEvent::CrossReferenced,
+        "source { ...Subject }",
+    ),
+    ("ReferencedEvent", IssueEvent::Referenced, "commit { oid }"),
+    (
+        "ConnectedEvent",
+        IssueEvent::Connected,
+        "subject { ...Subject }",
+    ),
+    (
+        "DisconnectedEvent",
+        IssueEvent::Disconnected,
+        "subject { ...Subject }",
+    ),
+    (
+        "MarkedAsDuplicateEvent",
+        IssueEvent::MarkedAsDuplicate,
+        "canonical { ...Subject }",
+    ),
+];
+
+/// Timeline item types that only exist on pull requests.
+const PULL_REQUEST_EVENTS: &[(&str, IssueEvent, &str)] = &[
+    ("MergedEvent", IssueEvent::Merged, "commit { oid }"),
+    (
+        "ReviewRequestedEvent",
+        IssueEvent::ReviewRequested,
+        "requestedReviewer { ...Login }",
+    ),
+    (
+        "ReviewRequestRemovedEvent",
+        IssueEvent::ReviewRequestRemoved,
+        "requestedReviewer { ...Login }",
+    ),
+    (
+        "HeadRefForcePushedEvent",
+        IssueEvent::HeadRefForcePushed,
+        "afterCommit { oid }",
+    ),
+    ("ReadyForReviewEvent", IssueEvent::ReadyForReview, ""),
+    ("ConvertToDraftEvent", IssueEvent::ConvertToDraft, ""),
+];
+
+static QUERY: LazyLock<String> = LazyLock::new(|| {
+    let issue = conversation_fields(EVENTS.iter());
+    let pull_request = conversation_fields(EVENTS.iter().chain(PULL_REQUEST_EVENTS));
+    format!(
+        r#"
+query($owner: String!, $repo: String!, $number: Int!,
+      $withComments: Boolean!, $commentsAfter: String,
+      $withTimeline: Boolean!, $timelineAfter: String) {{
+  repository(owner: $owner, name: $repo) {{
+    issueOrPullRequest(number: $number) {{
+      ... on Issue {{ {issue} }}
+      ... on PullRequest {{ {pull_request} }}
+    }}
+  }}
+}}
+fragment Login on Node {{
+  ... on Actor {{ login }}
+  ... on Team {{ teamSlug: slug }}
+}}
+fragment Subject on Node {{
+  __typename
+  ... on Issue {{ number }}
+  ... on PullRequest {{ number }}
+  ... on Commit {{ oid }}
+}}
+"#
+    )
+});
+
+fn conversation_fields<'a>(
+    events: impl Iterator<Item = &'a (&'a str, IssueEvent, &'a str)>,
+) -> String {
+    let (item_types, selections): (Vec<_>, Vec<_>) = events
+        .map(|(typename, _, fields)| {
+            (
+                item_type(typename),
+                format!("... on {typename} {{ createdAt actor {{ login }} {fields} }}"),
+            )
+        })
+        .unzip();
+    format!(
+        r#"
+      body
+      comments(first: {PAGE_SIZE}, after: $commentsAfter) @include(if: $withComments) {{
+        pageInfo {{ hasNextPage endCursor }}
+        nodes {{
+          fullDatabaseId
+          author {{ login }}
+          createdAt
+          body
+          reactionGroups {{ content viewerHasReacted reactors {{ totalCount }} }}
+        }}
+      }}
+      timelineItems(first: {PAGE_SIZE}, after: $timelineAfter, itemTypes: [{}]) @include(if: $withTimeline) {{
+        pageInfo {{ hasNextPage endCursor }}
+        nodes {{ __typename {} }}
+      }}"#,
+        item_types.join(", "),
+        selections.join(" ")
+    )
+}
+
+/// `LabeledEvent` -> `LABELED_EVENT`
+fn item_type(typename: &str) -> String {
+    let mut out = String::with_capacity(typename.len() + 4);
+    for (idx, ch) in typename.chars().enumerate() {
+        if ch.is_ascii_uppercase() && idx > 0 {
+            out.push('_');
+        }
+        out.push(ch.to_ascii_uppercase());
+    }
+    out
+}
+
+pub(super) async fn fetch(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    comments: &PageCursor,
+    timeline: &PageCursor,
+) -> Result<Conversation, AppError> {
+    let variables = json!({
+        "owner": owner,
+        "repo": repo,
+        "number": number,
+        "withComments": !comments.is_done(),
+        "commentsAfter": comments.after(),
+        "withTimeline": !timeline.is_done(),
+        "timelineAfter": timeline.after(),
+    });
+    let data: Data = client.graphql_query(&QUERY, variables).await?;
+    let node = data
+        .repository
+        .and_then(|repo| repo.issue_or_pull_request)
+        .ok_or_else(|| AppError::GraphQl(format!("issue #{number} not found")))?;
+    Ok(node.into_conversation())
+}
+
+#[derive(Deserialize)]
+struct Data {
+    repository: Option<Repository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Repository {
+    issue_or_pull_request: Option<IssueNode>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueNode {
+    body: Option<String>,
+    comments: Option<Connection<CommentNode>>,
+    timeline_items: Option<Connection<TimelineNode>>,
+}
+
+#[derive(Deserialize)]
+struct Connection<T> {
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+    nodes: Vec<Option<T>>,
+}
+
+impl<T> Connection<T> {
+    /// Splits the connection into its items and the cursor of the next page.
+    fn into_parts(connection: Option<Self>) -> (Vec<T>, PageCursor) {
+        let Some(connection) = connection else {
+            return (Vec::new(), PageCursor::Done);
+        };
+        let next = match connection.page_info {
+            PageInfo {
+                has_next_page: true,
+                end_cursor: Some(cursor),
+            } => PageCursor::After(cursor),
+            _ => PageCursor::Done,
+        };
+        (connection.nodes.into_iter().flatten().collect(), next)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+/// The login of a user, bot or app, or the slug of a team.
+#[derive(Deserialize)]
+struct Login {
+    #[serde(alias = "teamSlug")]
+    login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentNode {
+    #[serde(deserialize_with = "big_int")]
+    full_database_id: Option<u64>,
+    author: Option<Login>,
+    created_at: DateTime<Utc>,
+    body: String,
+    reaction_groups: Option<Vec<ReactionGroup>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReactionGroup {
+    content: String,
+    viewer_has_reacted: bool,
+    reactors: TotalCount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TotalCount {
+    total_count: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimelineNode {
+    #[serde(rename = "__typename")]
+    typename: String,
+    created_at: Option<DateTime<Utc>>,
+    actor: Option<Login>,
+    label: Option<Name>,
+    assignee: Option<Login>,
+    requested_reviewer: Option<Login>,
+    milestone_title: Option<String>,
+    previous_title: Option<String>,
+    current_title: Option<String>,
+    closer: Option<Subject>,
+    source: Option<Subject>,
+    subject: Option<Subject>,
+    canonical: Option<Subject>,
+    commit: Option<Subject>,
+    after_commit: Option<Subject>,
+}
+
+#[derive(Deserialize)]
+struct Name {
+    name: String,
+}
+
+/// An issue, pull request or commit referenced from the timeline.
+#[derive(Deserialize, Default)]
+struct Subject {
+    #[serde(rename = "__typename", default)]
+    typename: String,
+    number: Option<u64>,
+    oid: Option<String>,
+}
+
+/// `BigInt` scalars are serialised as strings.
+fn big_int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BigInt {
+        Number(u64),
+        String(String),
+    }
+    Ok(match Option::<BigInt>::deserialize(deserializer)? {
+        Some(BigInt::Number(value)) => Some(value),
+        Some(BigInt::String(value)) => value.parse().ok(),
+        None => None,
+    })
+}
+
+fn reaction_content(content: &str) -> Option<ReactionContent> {
+    Some(match content {
+        "THUMBS_UP" => ReactionContent::PlusOne,
+        "THUMBS_DOWN" => ReactionContent::MinusOne,
+        "LAUGH" => ReactionContent::Laugh,
+        "HOORAY" => ReactionContent::Hooray,
+        "CONFUSED" => ReactionContent::Confused,
+        "HEART" => ReactionContent::Heart,
+        "ROCKET" => ReactionContent::Rocket,
+        "EYES" => ReactionContent::Eyes,
+        _ => return None,
+    })
+}
+
+impl IssueNode {
+    fn into_conversation(self) -> Conversation {
+        let (comments, next_comments) = Connection::into_parts(self.comments);
+        let (timeline, next_timeline) = Connection::into_parts(self.timeline_items);
+        Conversation {
+            body: self.body,
+            comments: comments
+                .into_iter()
+                .filter_map(CommentNode::into_comment)
+                .collect(),
+            timeline: timeline
+                .into_iter()
+                .filter_map(TimelineNode::into_event)
+                .collect(),
+            next_comments,
+            next_timeline,
+        }
+    }
+}
+
+impl CommentNode {
+    fn into_comment(self) -> Option<ConversationComment> {
+        let mut reactions = Vec::new();
+        let mut my_reactions = Vec::new();
+        for group in self.reaction_groups.unwrap_or_default() {
+            let Some(content) = reaction_content(&group.content) else {
+                continue;
+            };
+            if group.viewer_has_reacted {
+                my_reactions.push(content.clone());
+            }
+            if group.reactors.total_count > 0 {
+                reactions.push((content, group.reactors.total_count));
+            }
+        }
+        Some(ConversationComment {
+            id: self.full_database_id?,
+            author: self
+                .author
+                .map_or_else(|| "ghost".to_string(), |author| author.login),
+            created_at: self.created_at,
+            body: self.body,
+            reactions,
+            my_reactions,
+        })
+    }
+}
+
+impl TimelineNode {
+    fn into_event(self) -> Option<ConversationEvent> {
+        let event = EVENTS
+            .iter()
+            .chain(PULL_REQUEST_EVENTS)
+            .find(|(typename, _, _)| *typename == self.typename)
+            .map(|(_, event, _)| event.clone())?;
+        let details = self.details();
+        Some(ConversationEvent {
+            created_at: self.created_at?,
+            actor: self.actor.map(|actor| actor.login),
+            event,
+            details,
+        })
+    }
+
+    fn details(&self) -> String {
+        if let Some(label) = &self.label {
+            return format!("label: {}", label.name);
+        }
+        if let Some(title) = &self.milestone_title {
+            return format!("milestone: {title}");
+        }
+        if let (Some(from), Some(to)) = (&self.previous_title, &self.current_title) {
+            return format!("title: '{from}' -> '{to}'");
+        }
+        if let Some(assignee) = &self.assignee {
+            return format!("assignee: @{}", assignee.login);
+        }
+        if let Some(reviewer) = &self.requested_reviewer {
+            return format!("reviewer: @{}", reviewer.login);
+        }
+        [
+            &self.source,
+            &self.subject,
+            &self.canonical,
+            &self.closer,
+            &self.commit,
+            &self.after_commit,
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(Subject::describe)
+        .unwrap_or_default()
+    }
+}
+
+impl Subject {
+    fn describe(&self) -> Option<String> {
+        match (self.typename.as_str(), self.number, self.oid.as_deref()) {
+            ("PullRequest", Some(number), _) => Some(format!("pull request #{number}")),
+            (_, Some(number), _) => Some(format!("issue #{number}")),
+            (_, None, Some(oid)) => Some(format!("commit {}", &oid[..oid.len().min(8)])),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use async_graphql_parser::types::{ExecutableDocument, Selection, SelectionSet};
+
+    use super::*;
+
+    /// Fails when a response key selects two different fields, which GraphQL
+    /// rejects even if the fields sit on different types.
+    fn check_fields(document: &ExecutableDocument, sets: &[&SelectionSet]) {
+        let mut fields: HashMap<&str, (&str, Vec<&SelectionSet>)> = HashMap::new();
+        let mut pending = sets.to_vec();
+        while let Some(set) = pending.pop() {
+            for selection in &set.items {
+                match &selection.node {
+                    Selection::Field(field) => {
+                        let key = field.node.response_key().node.as_str();
+                        let name = field.node.name.node.as_str();
+                        let entry = fields.entry(key).or_insert((name, Vec::new()));
+                        assert_eq!(entry.0, name, "`{key}` selects `{}` and `{name}`", entry.0);
+                        entry.1.push(&field.node.selection_set.node);
+                    }
+                    Selection::InlineFragment(fragment) => {
+                        pending.push(&fragment.node.selection_set.node);
+                    }
+                    Selection::FragmentSpread(spread) => {
+                        let name = spread.node.fragment_name.node.as_str();
+                        let (_, fragment) = document
+                            .fragments
+                            .iter()
+                            .find(|(fragment, _)| fragment.as_str() == name)
+                            .unwrap_or_else(|| panic!("fragment `{name}` is not defined"));
+                        pending.push(&fragment.node.selection_set.node);
+                    }
+                }
+            }
+        }
+        for (_, sets) in fields.values() {
+            check_fields(document, sets);
+        }
+    }
+
+    #[test]
+    fn query_is_valid() {
+        let document = async_graphql_parser::parse_query(QUERY.as_str()).unwrap();
+        for (_, operation) in document.operations.iter() {
+            check_fields(&document, &[&operation.node.selection_set.node]);
+        }
+    }
+
+    #[test]
+    fn item_type_is_screaming_snake_case() {
+        assert_eq!(item_type("LabeledEvent"), "LABELED_EVENT");
+        assert_eq!(
+            item_type("ReviewRequestRemovedEvent"),
+            "REVIEW_REQUEST_REMOVED_EVENT"
+        );
+    }
+
+    #[test]
+    fn parses_a_conversation_page() {
+        let node: IssueNode = serde_json::from_value(json!({
+            "body": "body",
+            "comments": {
+                "pageInfo": { "hasNextPage": true, "endCursor": "abc" },
+                "nodes": [{
+                    "fullDatabaseId": "3000000000",
+                    "author": null,
+                    "createdAt": "2024-01-02T03:04:05Z",
+                    "body": "hi",
+                    "reactionGroups": [
+                        { "content": "THUMBS_UP", "viewerHasReacted": true, "reactors": { "totalCount": 2 } },
+                        { "content": "EYES", "viewerHasReacted": false, "reactors": { "totalCount": 0 } }
+                    ]
+                }]
+            },
+            "timelineItems": {
+                "pageInfo": { "hasNextPage": false, "endCursor": "def" },
+                "nodes": [{
+                    "__typename": "CrossReferencedEvent",
+                    "createdAt": "2024-01-02T03:04:05Z",
+                    "actor": { "login": "octocat" },
+                    "source": { "__typename": "PullRequest", "number": 7 }
+                }, {
+                    "__typename": "ReviewRequestedEvent",
+                    "createdAt": "2024-01-02T03:04:05Z",
+                    "actor": { "login": "octocat" },
+                    "requestedReviewer": { "teamSlug": "core" }
+                }]
+            }
+        }))
+        .unwrap();
+        let conversation = node.into_conversation();
+
+        assert_eq!(conversation.next_comments, PageCursor::After("abc".into()));
+        assert_eq!(conversation.next_timeline, PageCursor::Done);
+        let comment = &conversation.comments[0];
+        assert_eq!(comment.id, 3_000_000_000);
+        assert_eq!(comment.author, "ghost");
+        assert_eq!(comment.reactions, vec![(ReactionContent::PlusOne, 2)]);
+        assert_eq!(comment.my_reactions, vec![ReactionContent::PlusOne]);
+        let event = &conversation.timeline[0];
+        assert_eq!(event.event, IssueEvent::CrossReferenced);
+        assert_eq!(event.details, "pull request #7");
+        assert_eq!(conversation.timeline[1].details, "reviewer: @core");
+    }
+}
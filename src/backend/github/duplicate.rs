@@ -0,0 +1,54 @@
+use serde::de::IgnoredAny;
+use serde_json::json;
+
+use crate::{errors::AppError, github::GithubClient};
+
+/// Closes the issue with node id `issue_id` as a duplicate of `original_id`,
+/// which links the two in both timelines.
+pub(super) async fn close_as_duplicate(
+    client: &GithubClient,
+    issue_id: &str,
+    original_id: &str,
+) -> Result<(), AppError> {
+    let query = r#"
+mutation($id: ID!, $original: ID!) {
+  closeIssue(input: { issueId: $id, stateReason: DUPLICATE, duplicateIssueId: $original }) {
+    clientMutationId
+  }
+}"#;
+    let _: IgnoredAny = client
+        .graphql_query(query, json!({ "id": issue_id, "original": original_id }))
+        .await?;
+    Ok(())
+}
+
+/// Whether `message`, from a failed [`close_as_duplicate`], says the host's
+/// schema has no `duplicateIssueId` argument or `DUPLICATE` reason, as on
+/// older GHES. Other failures, such as missing permissions or an original
+/// that is a pull request, are real errors.
+pub(super) fn is_unsupported(message: &str) -> bool {
+    let schema_error = ["doesn't accept argument", "isn't defined", "invalid value"]
+        .iter()
+        .any(|wording| message.contains(wording));
+    schema_error && (message.contains("duplicateIssueId") || message.contains("DUPLICATE"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_schema_errors_count_as_unsupported() {
+        assert!(is_unsupported(
+            "InputObject 'CloseIssueInput' doesn't accept argument 'duplicateIssueId'"
+        ));
+        assert!(is_unsupported(
+            "Argument 'stateReason' on InputObject 'CloseIssueInput' has an invalid value \
+             (DUPLICATE). Expected type 'IssueClosedStateReason'."
+        ));
+        assert!(!is_unsupported("Resource not accessible by integration"));
+        assert!(!is_unsupported(
+            "Could not resolve to a node with the global id of 'PR_123' (duplicateIssueId)"
+        ));
+    }
+}
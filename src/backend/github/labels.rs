@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{errors::AppError, github::GithubClient};
+
+const OPEN_ISSUE_COUNTS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $after: String) {
+  repository(owner: $owner, name: $repo) {
+    labels(first: 100, after: $after) {
+      pageInfo { hasNextPage endCursor }
+      nodes { name issues(states: OPEN) { totalCount } }
+    }
+  }
+}
+"#;
+
+/// Number of open issues per label name, for every label of the repository.
+pub(super) async fn open_issue_counts(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+) -> Result<HashMap<String, u64>, AppError> {
+    let mut counts = HashMap::new();
+    let mut after: Option<String> = None;
+    loop {
+        let variables = json!({ "owner": owner, "repo": repo, "after": after });
+        let data: Data = client
+            .graphql_query(OPEN_ISSUE_COUNTS_QUERY, variables)
+            .await?;
+        let Some(connection) = data.repository.map(|repo| repo.labels) else {
+            return Err(AppError::GraphQl(format!(
+                "repository {owner}/{repo} not found"
+            )));
+        };
+        counts.extend(
+            connection
+                .nodes
+                .into_iter()
+                .flatten()
+                .map(|node| (node.name, node.issues.total_count)),
+        );
+        match connection.page_info.end_cursor {
+            Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+            _ => return Ok(counts),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Data {
+    repository: Option<Repository>,
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    labels: Connection,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection {
+    page_info: PageInfo,
+    nodes: Vec<Option<LabelNode>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LabelNode {
+    name: String,
+    issues: IssueCount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueCount {
+    total_count: u64,
+}
+
+/// Percent-encodes a label name for use in a REST route, as names may contain
+/// spaces, slashes and other reserved characters.
+pub(super) fn encode_name(name: &str) -> String {
+    name.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_names_are_percent_encoded() {
+        assert_eq!(encode_name("bug"), "bug");
+        assert_eq!(encode_name("good first issue"), "good%20first%20issue");
+        assert_eq!(encode_name("area/ui"), "area%2Fui");
+        assert_eq!(encode_name("ü"), "%C3%BC");
+    }
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, de::IgnoredAny};
+use serde_json::json;
+
+use crate::{
+    backend::{
+        CheckState, CommitMessage, MergeMethod, MergeRequest, MergeStateStatus, MergeStatus,
+        Mergeable, PullRequestState, ReviewDecision,
+    },
+    errors::AppError,
+    github::GithubClient,
+};
+
+const MERGE_STATUS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    mergeCommitAllowed
+    squashMergeAllowed
+    rebaseMergeAllowed
+    autoMergeAllowed
+    deleteBranchOnMerge
+    pullRequest(number: $number) {
+      id
+      number
+      state
+      isDraft
+      mergeable
+      mergeStateStatus
+      reviewDecision
+      baseRefName
+      headRefName
+      headRefOid
+      isCrossRepository
+      autoMergeRequest { mergeMethod }
+      commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+      mergeTitle: viewerMergeHeadlineText(mergeType: MERGE)
+      mergeBody: viewerMergeBodyText(mergeType: MERGE)
+      squashTitle: viewerMergeHeadlineText(mergeType: SQUASH)
+      squashBody: viewerMergeBodyText(mergeType: SQUASH)
+    }
+  }
+}
+"#;
+
+pub(super) async fn status(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> Result<MergeStatus, AppError> {
+    let variables = json!({ "owner": owner, "repo": repo, "number": number });
+    let data: Data = client.graphql_query(MERGE_STATUS_QUERY, variables).await?;
+    data.repository
+        .and_then(Repository::into_status)
+        .ok_or_else(|| AppError::GraphQl(format!("pull request #{number} not found")))
+}
+
+pub(super) async fn merge(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    request: MergeRequest,
+) -> Result<(), AppError> {
+    use octocrab::params::pulls::MergeMethod as Method;
+
+    let pulls = client.inner().pulls(owner, repo);
+    let mut builder = pulls.merge(number).method(match request.method {
+        MergeMethod::Merge => Method::Merge,
+        MergeMethod::Squash => Method::Squash,
+        MergeMethod::Rebase => Method::Rebase,
+    });
+    if request.method != MergeMethod::Rebase {
+        builder = builder.title(request.title).message(request.message);
+    }
+    if let Some(sha) = request.sha {
+        builder = builder.sha(sha);
+    }
+    client.send(builder.send()).await.map(drop)
+}
+
+pub(super) async fn enable_auto_merge(
+    client: &GithubClient,
+    pull_request_id: &str,
+    request: MergeRequest,
+) -> Result<(), AppError> {
+    let query = r#"
+mutation($id: ID!, $method: PullRequestMergeMethod!, $title: String, $body: String) {
+  enablePullRequestAutoMerge(input: {
+    pullRequestId: $id, mergeMethod: $method, commitHeadline: $title, commitBody: $body
+  }) { clientMutationId }
+}"#;
+    let (title, body) = match request.method {
+        MergeMethod::Rebase => (None, None),
+        _ => (Some(request.title), Some(request.message)),
+    };
+    let variables = json!({
+        "id": pull_request_id,
+        "method": request.method,
+        "title": title,
+        "body": body,
+    });
+    let _: IgnoredAny = client.graphql_query(query, variables).await?;
+    Ok(())
+}
+
+pub(super) async fn disable_auto_merge(
+    client: &GithubClient,
+    pull_request_id: &str,
+) -> Result<(), AppError> {
+    let query = r#"
+mutation($id: ID!) {
+  disablePullRequestAutoMerge(input: { pullRequestId: $id }) { clientMutationId }
+}"#;
+    let _: IgnoredAny = client
+        .graphql_query(query, json!({ "id": pull_request_id }))
+        .await?;
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct Data {
+    repository: Option<Repository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Repository {
+    merge_commit_allowed: bool,
+    squash_merge_allowed: bool,
+    rebase_merge_allowed: bool,
+    auto_merge_allowed: bool,
+    delete_branch_on_merge: bool,
+    pull_request: Option<PullRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequest {
+    id: String,
+    number: u64,
+    state: PullRequestState,
+    is_draft: bool,
+    mergeable: Mergeable,
+    merge_state_status: MergeStateStatus,
+    review_decision: Option<ReviewDecision>,
+    base_ref_name: String,
+    head_ref_name: String,
+    head_ref_oid: String,
+    is_cross_repository: bool,
+    auto_merge_request: Option<AutoMergeRequest>,
+    commits: Commits,
+    merge_title: String,
+    merge_body: String,
+    squash_title: String,
+    squash_body: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AutoMergeRequest {
+    merge_method: MergeMethod,
+}
+
+#[derive(Deserialize)]
+struct Commits {
+    nodes: Vec<Option<CommitNode>>,
+}
+
+#[derive(Deserialize)]
+struct CommitNode {
+    commit: Commit,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Commit {
+    status_check_rollup: Option<Rollup>,
+}
+
+#[derive(Deserialize)]
+struct Rollup {
+    state: CheckState,
+}
+
+impl Repository {
+    fn into_status(self) -> Option<MergeStatus> {
+        let pr = self.pull_request?;
+        let allowed_methods = MergeMethod::ALL
+            .into_iter()
+            .zip([
+                self.merge_commit_allowed,
+                self.squash_merge_allowed,
+                self.rebase_merge_allowed,
+            ])
+            .filter_map(|(method, allowed)| allowed.then_some(method))
+            .collect();
+        let checks = pr
+            .commits
+            .nodes
+            .into_iter()
+            .flatten()
+            .last()
+            .and_then(|node| node.commit.status_check_rollup)
+            .map(|rollup| rollup.state);
+        Some(MergeStatus {
+            id: pr.id,
+            number: pr.number,
+            state: pr.state,
+            draft: pr.is_draft,
+            mergeable: pr.mergeable,
+            merge_state: pr.merge_state_status,
+            review_decision: pr.review_decision,
+            checks,
+            base: pr.base_ref_name,
+            head: pr.head_ref_name,
+            head_sha: pr.head_ref_oid,
+            cross_repository: pr.is_cross_repository,
+            allowed_methods,
+            auto_merge_allowed: self.auto_merge_allowed,
+            auto_merge: pr.auto_merge_request.map(|request| request.merge_method),
+            delete_branch_on_merge: self.delete_branch_on_merge,
+            merge_message: CommitMessage {
+                title: pr.merge_title,
+                body: pr.merge_body,
+            },
+            squash_message: CommitMessage {
+                title: pr.squash_title,
+                body: pr.squash_body,
+            },
+        })
+    }
+}
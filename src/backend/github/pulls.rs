@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, de::IgnoredAny};
+use serde_json::json;
+
+use crate::{
+    backend::{CheckState, PullRequestSummary, ReviewDecision, ReviewThread, ReviewThreadComment},
+    errors::AppError,
+    github::GithubClient,
+};
+
+const SUMMARY_FIELDS: &str = r#"
+  ... on PullRequest {
+    number
+    isDraft
+    reviewDecision
+    baseRefName
+    headRefName
+    commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+  }"#;
+
+/// Builds one aliased `issueOrPullRequest` lookup per number, so that issue
+/// numbers resolve to an empty object instead of failing the whole query.
+fn summaries_query(numbers: &[u64]) -> String {
+    let lookups = numbers
+        .iter()
+        .map(|number| format!("n{number}: issueOrPullRequest(number: {number}) {{ ...Summary }}"))
+        .collect::<Vec<_>>()
+        .join("\n    ");
+    format!(
+        r#"
+query($owner: String!, $repo: String!) {{
+  repository(owner: $owner, name: $repo) {{
+    {lookups}
+  }}
+}}
+fragment Summary on IssueOrPullRequest {{{SUMMARY_FIELDS}
+}}
+"#
+    )
+}
+
+pub(super) async fn summaries(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    numbers: &[u64],
+) -> Result<Vec<PullRequestSummary>, AppError> {
+    if numbers.is_empty() {
+        return Ok(Vec::new());
+    }
+    let variables = json!({ "owner": owner, "repo": repo });
+    let data: Data = client
+        .graphql_query(&summaries_query(numbers), variables)
+        .await?;
+    Ok(data
+        .repository
+        .unwrap_or_default()
+        .into_values()
+        .flatten()
+        .filter_map(PullRequestNode::into_summary)
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct Data {
+    repository: Option<HashMap<String, Option<PullRequestNode>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestNode {
+    number: Option<u64>,
+    #[serde(default)]
+    is_draft: bool,
+    review_decision: Option<ReviewDecision>,
+    base_ref_name: Option<String>,
+    head_ref_name: Option<String>,
+    commits: Option<Commits>,
+}
+
+#[derive(Deserialize)]
+struct Commits {
+    nodes: Vec<Option<CommitNode>>,
+}
+
+#[derive(Deserialize)]
+struct CommitNode {
+    commit: Commit,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Commit {
+    status_check_rollup: Option<Rollup>,
+}
+
+#[derive(Deserialize)]
+struct Rollup {
+    state: CheckState,
+}
+
+impl PullRequestNode {
+    fn into_summary(self) -> Option<PullRequestSummary> {
+        let checks = self
+            .commits
+            .and_then(|commits| commits.nodes.into_iter().flatten().last())
+            .and_then(|node| node.commit.status_check_rollup)
+            .map(|rollup| rollup.state);
+        Some(PullRequestSummary {
+            number: self.number?,
+            draft: self.is_draft,
+            review_decision: self.review_decision,
+            base: self.base_ref_name.unwrap_or_default(),
+            head: self.head_ref_name.unwrap_or_default(),
+            checks,
+        })
+    }
+}
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes {
+          id
+          isResolved
+          isOutdated
+          path
+          line
+          originalLine
+          comments(first: 100) {
+            nodes { author { login } body createdAt diffHunk }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+pub(super) async fn review_threads(
+    client: &GithubClient,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> Result<Vec<ReviewThread>, AppError> {
+    let mut threads = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let variables = json!({
+            "owner": owner,
+            "repo": repo,
+            "number": number,
+            "after": after,
+        });
+        let data: ThreadsData = client
+            .graphql_query(REVIEW_THREADS_QUERY, variables)
+            .await?;
+        let Some(connection) = data
+            .repository
+            .and_then(|repo| repo.pull_request)
+            .map(|pr| pr.review_threads)
+        else {
+            return Err(AppError::GraphQl(format!(
+                "pull request #{number} not found"
+            )));
+        };
+        threads.extend(
+            connection
+                .nodes
+                .into_iter()
+                .flatten()
+                .map(ThreadNode::into_thread),
+        );
+        match connection.page_info.end_cursor {
+            Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+            _ => return Ok(threads),
+        }
+    }
+}
+
+pub(super) async fn set_thread_resolved(
+    client: &GithubClient,
+    thread_id: &str,
+    resolved: bool,
+) -> Result<(), AppError> {
+    let mutation = if resolved {
+        "resolveReviewThread"
+    } else {
+        "unresolveReviewThread"
+    };
+    let query = format!(
+        "mutation($id: ID!) {{ {mutation}(input: {{ threadId: $id }}) {{ thread {{ isResolved }} }} }}"
+    );
+    let _: IgnoredAny = client
+        .graphql_query(&query, json!({ "id": thread_id }))
+        .await?;
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct ThreadsData {
+    repository: Option<ThreadsRepository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadsRepository {
+    pull_request: Option<ThreadsPullRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadsPullRequest {
+    review_threads: ThreadConnection,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadConnection {
+    page_info: PageInfo,
+    nodes: Vec<Option<ThreadNode>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadNode {
+    id: String,
+    is_resolved: bool,
+    is_outdated: bool,
+    path: String,
+    line: Option<u32>,
+    original_line: Option<u32>,
+    comments: ThreadComments,
+}
+
+#[derive(Deserialize)]
+struct ThreadComments {
+    nodes: Vec<Option<ThreadCommentNode>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadCommentNode {
+    author: Option<Author>,
+    body: String,
+    created_at: DateTime<Utc>,
+    diff_hunk: String,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    login: String,
+}
+
+impl ThreadNode {
+    fn into_thread(self) -> ReviewThread {
+        let comments = self
+            .comments
+            .nodes
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let diff_hunk = comments
+            .first()
+            .map(|comment| comment.diff_hunk.clone())
+            .unwrap_or_default();
+        ReviewThread {
+            id: self.id,
+            path: self.path,
+            line: self.line.or(self.original_line),
+            resolved: self.is_resolved,
+            outdated: self.is_outdated,
+            diff_hunk,
+            comments: comments
+                .into_iter()
+                .map(|comment| ReviewThreadComment {
+                    author: comment
+                        .author
+                        .map_or_else(|| "ghost".to_string(), |author| author.login),
+                    created_at: comment.created_at,
+                    body: comment.body,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issues_are_skipped_in_summaries() {
+        let data: Data = serde_json::from_value(json!({
+            "repository": {
+                "n1": {},
+                "n2": {
+                    "number": 2,
+                    "isDraft": true,
+                    "reviewDecision": "CHANGES_REQUESTED",
+                    "baseRefName": "main",
+                    "headRefName": "fix",
+                    "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "state": "FAILURE" } } }] }
+                },
+                "n3": null
+            }
+        }))
+        .unwrap();
+        let summaries = data
+            .repository
+            .unwrap()
+            .into_values()
+            .flatten()
+            .filter_map(PullRequestNode::into_summary)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summaries,
+            vec![PullRequestSummary {
+                number: 2,
+                draft: true,
+                review_decision: Some(ReviewDecision::ChangesRequested),
+                base: "main".to_string(),
+                head: "fix".to_string(),
+                checks: Some(CheckState::Failure),
+            }]
+        );
+    }
+
+    #[test]
+    fn outdated_threads_fall_back_to_original_line() {
+        let node: ThreadNode = serde_json::from_value(json!({
+            "id": "T1",
+            "isResolved": false,
+            "isOutdated": true,
+            "path": "src/lib.rs",
+            "line": null,
+            "originalLine": 12,
+            "comments": { "nodes": [
+                { "author": null, "body": "nit", "createdAt": "2024-01-01T00:00:00Z", "diffHunk": "@@ -1 +1 @@\n-a\n+b" },
+                { "author": { "login": "octocat" }, "body": "done", "createdAt": "2024-01-02T00:00:00Z", "diffHunk": "" }
+            ] }
+        }))
+        .unwrap();
+        let thread = node.into_thread();
+        assert_eq!(thread.line, Some(12));
+        assert!(thread.outdated);
+        assert_eq!(thread.diff_hunk, "@@ -1 +1 @@\n-a\n+b");
+        let authors = thread
+            .comments
+            .iter()
+            .map(|c| c.author.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(authors, vec!["ghost", "octocat"]);
+    }
+}
@@ -0,0 +1,633 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use octocrab::{
+    Page,
+    models::{
+        Author, Event as IssueEvent, IssueState, Label, Milestone,
+        issues::{Comment, Issue, IssueStateReason},
+        reactions::{Reaction, ReactionContent},
+        repos::DiffEntry,
+        timelines::TimelineEvent,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{backend::templates::IssueTemplate, errors::AppError};
+
+pub mod cache;
+pub mod github;
+pub mod offline;
+pub mod query;
+pub mod templates;
+
+/// Shared handle to the issue tracker the UI talks to.
+pub type Backend = Arc<dyn IssueTracker>;
+
+/// Parameters for an issue search. `query` is passed to the tracker verbatim.
+#[derive(Debug, Clone)]
+pub struct SearchParams {
+    pub query: String,
+    pub page: u32,
+    pub per_page: u8,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+}
+
+impl SearchParams {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            page: 1,
+            per_page: 15,
+            sort: None,
+            order: None,
+        }
+    }
+
+    pub fn per_page(self, per_page: u8) -> Self {
+        Self { per_page, ..self }
+    }
+
+    pub fn sort(self, sort: impl Into<String>, order: impl Into<String>) -> Self {
+        Self {
+            sort: Some(sort.into()),
+            order: Some(order.into()),
+            ..self
+        }
+    }
+}
+
+/// Fields for a new issue. Empty optional fields are left unset.
+#[derive(Debug, Clone, Default)]
+pub struct NewIssue {
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub assignees: Option<Vec<String>>,
+}
+
+/// Changes to an existing issue. Fields left `None` are not touched.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct IssueEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Number of the milestone to set; `Some(None)` removes the milestone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<Option<u64>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MilestoneState {
+    Open,
+    Closed,
+}
+
+/// Fields of a milestone to create or change. Fields left `None` are not
+/// touched; a new milestone needs a title.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct MilestoneEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `Some(None)` removes the due date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<MilestoneState>,
+}
+
+/// Fields of a label to change. Fields left `None` are not touched.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct LabelEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    /// Six hex digits, without the `#`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Splits a search query on whitespace, keeping quoted values such as
+/// `milestone:"Next release"` in one piece.
+pub fn query_tokens(query: &str) -> Vec<&str> {
+    query::token_ranges(query)
+        .into_iter()
+        .map(|range| &query[range])
+        .collect()
+}
+
+/// Position in one of the paginated lists of a [`Conversation`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub enum PageCursor {
+    /// Nothing fetched yet.
+    #[default]
+    Start,
+    /// More items follow the given opaque cursor.
+    After(String),
+    /// Every item has been fetched.
+    Done,
+}
+
+impl PageCursor {
+    pub fn is_done(&self) -> bool {
+        matches!(self, PageCursor::Done)
+    }
+
+    pub fn after(&self) -> Option<&str> {
+        match self {
+            PageCursor::After(cursor) => Some(cursor),
+            PageCursor::Start | PageCursor::Done => None,
+        }
+    }
+}
+
+/// A comment with its reactions already summarised for the viewer.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConversationComment {
+    pub id: u64,
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub body: String,
+    pub reactions: Vec<(ReactionContent, u64)>,
+    pub my_reactions: Vec<ReactionContent>,
+}
+
+/// A timeline entry, with its details pre-rendered by the tracker.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConversationEvent {
+    pub created_at: DateTime<Utc>,
+    pub actor: Option<String>,
+    pub event: IssueEvent,
+    pub details: String,
+}
+
+/// One page of an issue conversation: body, comments and timeline fetched together.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Conversation {
+    pub body: Option<String>,
+    pub comments: Vec<ConversationComment>,
+    pub timeline: Vec<ConversationEvent>,
+    pub next_comments: PageCursor,
+    pub next_timeline: PageCursor,
+}
+
+impl Conversation {
+    /// Whether both lists have been fetched completely.
+    pub fn is_complete(&self) -> bool {
+        self.next_comments.is_done() && self.next_timeline.is_done()
+    }
+
+    /// Appends a following page and takes over its cursors.
+    pub fn extend(&mut self, page: Conversation) {
+        if page.body.is_some() {
+            self.body = page.body;
+        }
+        self.comments.extend(page.comments);
+        self.timeline.extend(page.timeline);
+        self.next_comments = page.next_comments;
+        self.next_timeline = page.next_timeline;
+    }
+}
+
+/// Overall review verdict on a pull request.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+/// Combined state of the checks and statuses on a commit.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CheckState {
+    Success,
+    Pending,
+    Expected,
+    Failure,
+    Error,
+}
+
+/// Pull request details the issue search API leaves out.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestSummary {
+    pub number: u64,
+    pub draft: bool,
+    pub review_decision: Option<ReviewDecision>,
+    pub base: String,
+    pub head: String,
+    /// State of the checks on the head commit, `None` if it has none.
+    pub checks: Option<CheckState>,
+}
+
+/// Outcome of a check run or commit status, in a vocabulary shared by both.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// Queued or still running.
+    Pending,
+    Success,
+    Failure,
+    Cancelled,
+    Skipped,
+    Neutral,
+}
+
+/// How a failed check can be started again.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RerunTarget {
+    /// A GitHub Actions workflow run; only its failed jobs are re-run.
+    WorkflowRun(u64),
+    /// A check run created by some other app.
+    CheckRun(u64),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: Option<u32>,
+    /// `NOTICE`, `WARNING` or `FAILURE`.
+    pub level: String,
+    pub title: Option<String>,
+    pub message: String,
+}
+
+/// A check run or commit status on the head commit of a pull request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheckEntry {
+    pub name: String,
+    /// Workflow or app that reported the check.
+    pub source: Option<String>,
+    pub outcome: CheckOutcome,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub url: Option<String>,
+    /// Check run title or status description.
+    pub description: Option<String>,
+    pub annotations: Vec<CheckAnnotation>,
+    /// `None` for commit statuses, which cannot be re-run from here.
+    pub rerun: Option<RerunTarget>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PullRequestChecks {
+    pub head_sha: String,
+    pub checks: Vec<CheckEntry>,
+}
+
+/// Verdict a pull request review is submitted with.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewEvent {
+    #[default]
+    Comment,
+    Approve,
+    RequestChanges,
+}
+
+/// Side of a diff a review comment is attached to: `Left` for the base
+/// version, `Right` for the head.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DiffSide {
+    Left,
+    Right,
+}
+
+/// A comment on a diff line, held back until its review is submitted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DraftReviewComment {
+    pub path: String,
+    pub line: u32,
+    pub side: DiffSide,
+    pub body: String,
+}
+
+/// A review to submit on a pull request.
+#[derive(Debug, Clone, Default)]
+pub struct NewReview {
+    pub event: ReviewEvent,
+    pub body: String,
+    pub comments: Vec<DraftReviewComment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReviewThreadComment {
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub body: String,
+}
+
+/// Review comments attached to one line of a pull request diff.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReviewThread {
+    /// Opaque id used to resolve the thread.
+    pub id: String,
+    pub path: String,
+    /// Line in the current diff, or in the diff the thread was started on
+    /// if the code has changed since.
+    pub line: Option<u32>,
+    pub resolved: bool,
+    pub outdated: bool,
+    /// The lines of the diff the thread refers to, ending with its line.
+    pub diff_hunk: String,
+    pub comments: Vec<ReviewThreadComment>,
+}
+
+/// How the commits of a pull request land on its base branch.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MergeMethod {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeMethod {
+    pub const ALL: [MergeMethod; 3] =
+        [MergeMethod::Merge, MergeMethod::Squash, MergeMethod::Rebase];
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PullRequestState {
+    Open,
+    Closed,
+    Merged,
+}
+
+/// Whether the head branch merges cleanly into the base branch.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Mergeable {
+    Mergeable,
+    Conflicting,
+    /// GitHub has not finished computing it yet.
+    Unknown,
+}
+
+/// GitHub's summary of what stands between a pull request and a merge.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MergeStateStatus {
+    /// The head branch is out of date with the base branch.
+    Behind,
+    /// Required reviews or checks are missing.
+    Blocked,
+    Clean,
+    /// There are merge conflicts.
+    Dirty,
+    Draft,
+    /// Mergeable, with passing commit status and pre-receive hooks.
+    HasHooks,
+    Unknown,
+    /// Mergeable, but some non-required checks fail.
+    Unstable,
+}
+
+/// A suggested commit title and message for a merge method.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitMessage {
+    pub title: String,
+    pub body: String,
+}
+
+/// Everything needed to decide whether, and how, a pull request can be merged.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MergeStatus {
+    /// GraphQL node id, used to enable auto-merge.
+    pub id: String,
+    pub number: u64,
+    pub state: PullRequestState,
+    pub draft: bool,
+    pub mergeable: Mergeable,
+    pub merge_state: MergeStateStatus,
+    pub review_decision: Option<ReviewDecision>,
+    pub checks: Option<CheckState>,
+    pub base: String,
+    pub head: String,
+    pub head_sha: String,
+    /// Whether the head branch lives in a fork, where it cannot be deleted.
+    pub cross_repository: bool,
+    /// Merge methods the repository allows, in [`MergeMethod::ALL`] order.
+    pub allowed_methods: Vec<MergeMethod>,
+    pub auto_merge_allowed: bool,
+    /// Method of the auto-merge already enabled on the pull request.
+    pub auto_merge: Option<MergeMethod>,
+    /// Whether the repository deletes head branches after merging anyway.
+    pub delete_branch_on_merge: bool,
+    pub merge_message: CommitMessage,
+    pub squash_message: CommitMessage,
+}
+
+/// A merge, or an auto-merge to enable, with its commit message. The title and
+/// message are ignored when rebasing.
+#[derive(Debug, Clone, Default)]
+pub struct MergeRequest {
+    pub method: MergeMethod,
+    pub title: String,
+    pub message: String,
+    /// Head commit the merge was reviewed at; the merge fails if it moved.
+    pub sha: Option<String>,
+}
+
+/// Everything the UI needs from a forge, scoped to a single repository.
+///
+/// Implementations must be cheap to share across tasks; components clone the
+/// [`Backend`] handle into every spawned request.
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Login of the authenticated user.
+    async fn current_user(&self) -> Result<String, AppError>;
+
+    /// Whether this tracker only serves previously cached data.
+    fn is_offline(&self) -> bool {
+        false
+    }
+    /// Persists any state the tracker keeps locally. Called periodically and
+    /// on shutdown.
+    async fn flush(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn search_issues(&self, params: SearchParams) -> Result<Page<Issue>, AppError>;
+    /// Fetches the page following `page`, if there is one.
+    async fn next_issue_page(&self, page: &Page<Issue>) -> Result<Option<Page<Issue>>, AppError>;
+
+    async fn get_issue(&self, number: u64) -> Result<Issue, AppError>;
+    async fn create_issue(&self, issue: NewIssue) -> Result<Issue, AppError>;
+    /// Lists the issue templates in [`templates::TEMPLATE_DIR`], sorted by name.
+    async fn issue_templates(&self) -> Result<Vec<IssueTemplate>, AppError>;
+    async fn set_issue_state(
+        &self,
+        number: u64,
+        state: IssueState,
+        reason: Option<IssueStateReason>,
+    ) -> Result<Issue, AppError>;
+    /// Closes an issue as a duplicate of `original`, posting the usual
+    /// "Duplicate of #N" comment.
+    async fn close_as_duplicate(&self, number: u64, original: u64) -> Result<Issue, AppError>;
+    async fn update_issue(&self, number: u64, edit: IssueEdit) -> Result<Issue, AppError>;
+    async fn add_assignees(&self, number: u64, assignees: &[&str]) -> Result<Issue, AppError>;
+    async fn remove_assignees(&self, number: u64, assignees: &[&str]) -> Result<Issue, AppError>;
+    /// Users that issues in the repository can be assigned to.
+    async fn list_assignees(&self) -> Result<Vec<Author>, AppError>;
+
+    async fn list_comments(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Page<Comment>, AppError>;
+    async fn create_comment(&self, number: u64, body: String) -> Result<Comment, AppError>;
+    async fn update_comment(&self, comment_id: u64, body: String) -> Result<Comment, AppError>;
+
+    async fn list_comment_reactions(&self, comment_id: u64) -> Result<Vec<Reaction>, AppError>;
+    async fn add_comment_reaction(
+        &self,
+        comment_id: u64,
+        content: ReactionContent,
+    ) -> Result<Reaction, AppError>;
+    /// Removes reaction `reaction_id`, whose kind is `content`.
+    async fn delete_comment_reaction(
+        &self,
+        comment_id: u64,
+        reaction_id: u64,
+        content: ReactionContent,
+    ) -> Result<(), AppError>;
+
+    async fn list_timeline(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Page<TimelineEvent>, AppError>;
+
+    /// Fetches the next page of comments and timeline events of an issue in a
+    /// single request. Lists whose cursor is [`PageCursor::Done`] are skipped.
+    ///
+    /// Returns `None` when the tracker cannot do this, in which case callers
+    /// fall back to [`list_comments`](Self::list_comments) and friends.
+    async fn conversation(
+        &self,
+        _number: u64,
+        _comments: &PageCursor,
+        _timeline: &PageCursor,
+    ) -> Result<Option<Conversation>, AppError> {
+        Ok(None)
+    }
+
+    /// Fetches draft, review and check state for the given pull requests.
+    /// Numbers that are not pull requests are left out of the result.
+    async fn pull_request_summaries(
+        &self,
+        _numbers: &[u64],
+    ) -> Result<Vec<PullRequestSummary>, AppError> {
+        Ok(Vec::new())
+    }
+
+    /// Lists every file a pull request changes, with its patch where GitHub
+    /// provides one.
+    async fn list_pull_request_files(&self, number: u64) -> Result<Vec<DiffEntry>, AppError>;
+
+    /// Lists the check runs and commit statuses on the head commit of a pull
+    /// request.
+    async fn pull_request_checks(&self, number: u64) -> Result<PullRequestChecks, AppError>;
+    async fn rerun_check(&self, target: RerunTarget) -> Result<(), AppError>;
+
+    /// Lists the review threads of a pull request.
+    async fn review_threads(&self, _number: u64) -> Result<Vec<ReviewThread>, AppError> {
+        Ok(Vec::new())
+    }
+    async fn set_review_thread_resolved(
+        &self,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<(), AppError>;
+    /// Submits a review together with all of its line comments.
+    async fn submit_review(&self, number: u64, review: NewReview) -> Result<(), AppError>;
+
+    /// Fetches mergeability, repository merge settings and default commit
+    /// messages for a pull request.
+    async fn merge_status(&self, number: u64) -> Result<MergeStatus, AppError>;
+    async fn merge_pull_request(&self, number: u64, request: MergeRequest) -> Result<(), AppError>;
+    /// Enables auto-merge, which merges the pull request once every
+    /// requirement is met. `pull_request_id` is [`MergeStatus::id`].
+    async fn enable_auto_merge(
+        &self,
+        pull_request_id: &str,
+        request: MergeRequest,
+    ) -> Result<(), AppError>;
+    async fn disable_auto_merge(&self, pull_request_id: &str) -> Result<(), AppError>;
+    async fn delete_branch(&self, branch: &str) -> Result<(), AppError>;
+
+    /// Lists open and closed milestones.
+    async fn list_milestones(&self) -> Result<Vec<Milestone>, AppError>;
+    async fn create_milestone(&self, milestone: MilestoneEdit) -> Result<Milestone, AppError>;
+    async fn update_milestone(
+        &self,
+        number: u64,
+        edit: MilestoneEdit,
+    ) -> Result<Milestone, AppError>;
+
+    async fn list_labels(&self, page: u32, per_page: u8) -> Result<Page<Label>, AppError>;
+    async fn get_label(&self, name: &str) -> Result<Label, AppError>;
+    async fn create_label(
+        &self,
+        name: &str,
+        color: &str,
+        description: &str,
+    ) -> Result<Label, AppError>;
+    async fn add_labels(&self, number: u64, labels: &[String]) -> Result<Vec<Label>, AppError>;
+    async fn remove_label(&self, number: u64, name: &str) -> Result<Vec<Label>, AppError>;
+    async fn update_label(&self, name: &str, edit: LabelEdit) -> Result<Label, AppError>;
+    /// Deletes the label from the repository, and so from every issue.
+    async fn delete_label(&self, name: &str) -> Result<(), AppError>;
+    /// Number of open issues per label name.
+    async fn label_open_issue_counts(&self) -> Result<HashMap<String, u64>, AppError>;
+    /// Number of open issues assigned to each of `logins`.
+    async fn assignee_open_issue_counts(
+        &self,
+        logins: &[String],
+    ) -> Result<HashMap<String, u64>, AppError>;
+}
+
+impl fmt::Debug for dyn IssueTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IssueTracker")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_tokens_keep_quoted_values() {
+        assert_eq!(
+            query_tokens(r#"crash  milestone:"Next release" is:open"#),
+            vec!["crash", r#"milestone:"Next release""#, "is:open"]
+        );
+        assert!(query_tokens("   ").is_empty());
+    }
+
+    #[test]
+    fn clearing_a_milestone_sends_null() {
+        let edit = IssueEdit {
+            milestone: Some(None),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(edit).unwrap(),
+            serde_json::json!({ "milestone": null })
+        );
+    }
+}
@@ -0,0 +1,462 @@
+use std::{collections::HashMap, path::Path};
+
+use async_trait::async_trait;
+use octocrab::{
+    Page,
+    models::{
+        Author, IssueState, Label, Milestone,
+        issues::{Comment, Issue, IssueStateReason},
+        reactions::{Reaction, ReactionContent},
+        repos::DiffEntry,
+        timelines::TimelineEvent,
+    },
+};
+
+use crate::{
+    backend::{
+        Conversation, IssueEdit, IssueTracker, LabelEdit, MergeRequest, MergeStatus, MilestoneEdit,
+        NewIssue, NewReview, PageCursor, PullRequestChecks, PullRequestSummary, RerunTarget,
+        ReviewThread, SearchParams, cache::RepoCache, query, query_tokens,
+        templates::IssueTemplate,
+    },
+    errors::AppError,
+};
+
+/// Read-only tracker serving a [`RepoCache`] written by earlier online sessions.
+///
+/// Every mutation fails with [`AppError::Offline`].
+pub struct OfflineBackend {
+    cache: RepoCache,
+}
+
+impl OfflineBackend {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            cache: RepoCache::read(path),
+        }
+    }
+
+    fn read_only<T>(action: &str) -> Result<T, AppError> {
+        Err(AppError::Offline(format!(
+            "cannot {action} in offline mode"
+        )))
+    }
+
+    fn single_page<T>(items: Vec<T>, page: u32) -> Page<T> {
+        let items = if page <= 1 { items } else { vec![] };
+        let mut result = Page::default();
+        result.total_count = Some(items.len() as u64);
+        result.items = items;
+        result
+    }
+}
+
+/// The subset of GitHub search syntax understood when searching the cache.
+/// Anything else (`repo:`, `sort:`, ...) is ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CachedQuery {
+    state: Option<IssueState>,
+    pull_requests: Option<bool>,
+    labels: Vec<String>,
+    milestone: Option<String>,
+    terms: Vec<String>,
+}
+
+impl CachedQuery {
+    fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        for token in query_tokens(query) {
+            match token.split_once(':') {
+                Some(("is", "open")) => parsed.state = Some(IssueState::Open),
+                Some(("is", "closed")) => parsed.state = Some(IssueState::Closed),
+                Some(("is", "issue")) => parsed.pull_requests = Some(false),
+                Some(("is", "pr")) => parsed.pull_requests = Some(true),
+                Some(("label", label)) => parsed.labels.push(query::unescape(label).to_lowercase()),
+                Some(("milestone", title)) => {
+                    parsed.milestone = Some(query::unescape(title).to_lowercase())
+                }
+                Some(_) => {}
+                None => parsed.terms.push(token.to_lowercase()),
+            }
+        }
+        parsed
+    }
+
+    fn matches(&self, issue: &Issue) -> bool {
+        if self
+            .state
+            .as_ref()
+            .is_some_and(|state| *state != issue.state)
+        {
+            return false;
+        }
+        if self
+            .pull_requests
+            .is_some_and(|prs| prs != issue.pull_request.is_some())
+        {
+            return false;
+        }
+        let has_label = |name: &String| {
+            issue
+                .labels
+                .iter()
+                .any(|label| label.name.to_lowercase() == *name)
+        };
+        if !self.labels.iter().all(has_label) {
+            return false;
+        }
+        if let Some(title) = &self.milestone
+            && issue
+                .milestone
+                .as_ref()
+                .is_none_or(|milestone| milestone.title.to_lowercase() != *title)
+        {
+            return false;
+        }
+        let haystack = format!(
+            "{} {}",
+            issue.title,
+            issue.body.as_deref().unwrap_or_default()
+        )
+        .to_lowercase();
+        self.terms.iter().all(|term| haystack.contains(term))
+    }
+}
+
+#[async_trait]
+impl IssueTracker for OfflineBackend {
+    async fn current_user(&self) -> Result<String, AppError> {
+        Ok(self
+            .cache
+            .current_user
+            .clone()
+            .unwrap_or_else(|| "offline".to_string()))
+    }
+
+    fn is_offline(&self) -> bool {
+        true
+    }
+
+    /// Returns every cached match on the first page, newest first.
+    async fn search_issues(&self, params: SearchParams) -> Result<Page<Issue>, AppError> {
+        let query = CachedQuery::parse(&params.query);
+        let items = self
+            .cache
+            .issues
+            .values()
+            .rev()
+            .filter(|issue| query.matches(issue))
+            .cloned()
+            .collect();
+        Ok(Self::single_page(items, params.page))
+    }
+
+    async fn next_issue_page(&self, _page: &Page<Issue>) -> Result<Option<Page<Issue>>, AppError> {
+        Ok(None)
+    }
+
+    async fn get_issue(&self, number: u64) -> Result<Issue, AppError> {
+        self.cache
+            .issues
+            .get(&number)
+            .cloned()
+            .ok_or_else(|| AppError::Offline(format!("issue #{number} is not cached")))
+    }
+
+    async fn create_issue(&self, _issue: NewIssue) -> Result<Issue, AppError> {
+        Self::read_only("create issues")
+    }
+
+    async fn issue_templates(&self) -> Result<Vec<IssueTemplate>, AppError> {
+        Ok(self.cache.issue_templates.clone())
+    }
+
+    async fn set_issue_state(
+        &self,
+        _number: u64,
+        _state: IssueState,
+        _reason: Option<IssueStateReason>,
+    ) -> Result<Issue, AppError> {
+        Self::read_only("change issue state")
+    }
+
+    async fn close_as_duplicate(&self, _number: u64, _original: u64) -> Result<Issue, AppError> {
+        Self::read_only("change issue state")
+    }
+
+    async fn update_issue(&self, _number: u64, _edit: IssueEdit) -> Result<Issue, AppError> {
+        Self::read_only("edit issues")
+    }
+
+    async fn add_assignees(&self, _number: u64, _assignees: &[&str]) -> Result<Issue, AppError> {
+        Self::read_only("assign users")
+    }
+
+    async fn remove_assignees(&self, _number: u64, _assignees: &[&str]) -> Result<Issue, AppError> {
+        Self::read_only("unassign users")
+    }
+
+    async fn list_assignees(&self) -> Result<Vec<Author>, AppError> {
+        Ok(self.cache.assignees.clone())
+    }
+
+    async fn list_comments(
+        &self,
+        number: u64,
+        page: u32,
+        _per_page: u8,
+    ) -> Result<Page<Comment>, AppError> {
+        let comments =
+            self.cache.comments.get(&number).cloned().ok_or_else(|| {
+                AppError::Offline(format!("comments for #{number} are not cached"))
+            })?;
+        Ok(Self::single_page(comments, page))
+    }
+
+    async fn create_comment(&self, _number: u64, _body: String) -> Result<Comment, AppError> {
+        Self::read_only("post comments")
+    }
+
+    async fn update_comment(&self, _comment_id: u64, _body: String) -> Result<Comment, AppError> {
+        Self::read_only("edit comments")
+    }
+
+    async fn list_comment_reactions(&self, comment_id: u64) -> Result<Vec<Reaction>, AppError> {
+        Ok(self
+            .cache
+            .reactions
+            .get(&comment_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn add_comment_reaction(
+        &self,
+        _comment_id: u64,
+        _content: ReactionContent,
+    ) -> Result<Reaction, AppError> {
+        Self::read_only("add reactions")
+    }
+
+    async fn delete_comment_reaction(
+        &self,
+        _comment_id: u64,
+        _reaction_id: u64,
+        _content: ReactionContent,
+    ) -> Result<(), AppError> {
+        Self::read_only("remove reactions")
+    }
+
+    async fn list_timeline(
+        &self,
+        number: u64,
+        page: u32,
+        _per_page: u8,
+    ) -> Result<Page<TimelineEvent>, AppError> {
+        let events =
+            self.cache.timeline.get(&number).cloned().ok_or_else(|| {
+                AppError::Offline(format!("timeline for #{number} is not cached"))
+            })?;
+        Ok(Self::single_page(events, page))
+    }
+
+    /// Serves the whole cached conversation as a single page, or `None` if it
+    /// was only ever loaded through the REST endpoints.
+    async fn conversation(
+        &self,
+        number: u64,
+        comments: &PageCursor,
+        timeline: &PageCursor,
+    ) -> Result<Option<Conversation>, AppError> {
+        let Some(cached) = self.cache.conversations.get(&number) else {
+            return Ok(None);
+        };
+        let mut page = Conversation {
+            body: cached.body.clone(),
+            next_comments: PageCursor::Done,
+            next_timeline: PageCursor::Done,
+            ..Default::default()
+        };
+        if *comments == PageCursor::Start {
+            page.comments = cached.comments.clone();
+        }
+        if *timeline == PageCursor::Start {
+            page.timeline = cached.timeline.clone();
+        }
+        Ok(Some(page))
+    }
+
+    async fn pull_request_summaries(
+        &self,
+        numbers: &[u64],
+    ) -> Result<Vec<PullRequestSummary>, AppError> {
+        Ok(numbers
+            .iter()
+            .filter_map(|number| self.cache.pull_requests.get(number).cloned())
+            .collect())
+    }
+
+    async fn list_pull_request_files(&self, number: u64) -> Result<Vec<DiffEntry>, AppError> {
+        self.cache
+            .pull_request_files
+            .get(&number)
+            .cloned()
+            .ok_or_else(|| AppError::Offline(format!("files of #{number} are not cached")))
+    }
+
+    async fn pull_request_checks(&self, number: u64) -> Result<PullRequestChecks, AppError> {
+        self.cache
+            .pull_request_checks
+            .get(&number)
+            .cloned()
+            .ok_or_else(|| AppError::Offline(format!("checks of #{number} are not cached")))
+    }
+
+    async fn rerun_check(&self, _target: RerunTarget) -> Result<(), AppError> {
+        Self::read_only("re-run checks")
+    }
+
+    async fn review_threads(&self, number: u64) -> Result<Vec<ReviewThread>, AppError> {
+        Ok(self
+            .cache
+            .review_threads
+            .get(&number)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn set_review_thread_resolved(
+        &self,
+        _thread_id: &str,
+        _resolved: bool,
+    ) -> Result<(), AppError> {
+        Self::read_only("resolve review threads")
+    }
+
+    async fn submit_review(&self, _number: u64, _review: NewReview) -> Result<(), AppError> {
+        Self::read_only("submit reviews")
+    }
+
+    async fn merge_status(&self, number: u64) -> Result<MergeStatus, AppError> {
+        Err(AppError::Offline(format!(
+            "mergeability of #{number} is only known online"
+        )))
+    }
+
+    async fn merge_pull_request(
+        &self,
+        _number: u64,
+        _request: MergeRequest,
+    ) -> Result<(), AppError> {
+        Self::read_only("merge pull requests")
+    }
+
+    async fn enable_auto_merge(
+        &self,
+        _pull_request_id: &str,
+        _request: MergeRequest,
+    ) -> Result<(), AppError> {
+        Self::read_only("enable auto-merge")
+    }
+
+    async fn disable_auto_merge(&self, _pull_request_id: &str) -> Result<(), AppError> {
+        Self::read_only("disable auto-merge")
+    }
+
+    async fn delete_branch(&self, _branch: &str) -> Result<(), AppError> {
+        Self::read_only("delete branches")
+    }
+
+    async fn list_milestones(&self) -> Result<Vec<Milestone>, AppError> {
+        Ok(self.cache.milestones.clone())
+    }
+
+    async fn create_milestone(&self, _milestone: MilestoneEdit) -> Result<Milestone, AppError> {
+        Self::read_only("create milestones")
+    }
+
+    async fn update_milestone(
+        &self,
+        _number: u64,
+        _edit: MilestoneEdit,
+    ) -> Result<Milestone, AppError> {
+        Self::read_only("edit milestones")
+    }
+
+    async fn list_labels(&self, page: u32, _per_page: u8) -> Result<Page<Label>, AppError> {
+        Ok(Self::single_page(self.cache.labels.clone(), page))
+    }
+
+    async fn get_label(&self, name: &str) -> Result<Label, AppError> {
+        self.cache
+            .labels
+            .iter()
+            .find(|label| label.name.eq_ignore_ascii_case(name))
+            .cloned()
+            .ok_or_else(|| AppError::Offline(format!("label {name} is not cached")))
+    }
+
+    async fn create_label(
+        &self,
+        _name: &str,
+        _color: &str,
+        _description: &str,
+    ) -> Result<Label, AppError> {
+        Self::read_only("create labels")
+    }
+
+    async fn add_labels(&self, _number: u64, _labels: &[String]) -> Result<Vec<Label>, AppError> {
+        Self::read_only("add labels")
+    }
+
+    async fn remove_label(&self, _number: u64, _name: &str) -> Result<Vec<Label>, AppError> {
+        Self::read_only("remove labels")
+    }
+
+    async fn update_label(&self, _name: &str, _edit: LabelEdit) -> Result<Label, AppError> {
+        Self::read_only("edit labels")
+    }
+
+    async fn delete_label(&self, _name: &str) -> Result<(), AppError> {
+        Self::read_only("delete labels")
+    }
+
+    async fn label_open_issue_counts(&self) -> Result<HashMap<String, u64>, AppError> {
+        Ok(self.cache.label_open_issue_counts.clone())
+    }
+
+    async fn assignee_open_issue_counts(
+        &self,
+        logins: &[String],
+    ) -> Result<HashMap<String, u64>, AppError> {
+        Ok(logins
+            .iter()
+            .filter_map(|login| {
+                let count = self.cache.assignee_open_issue_counts.get(login)?;
+                Some((login.clone(), *count))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_query_parses_supported_qualifiers() {
+        let query = CachedQuery::parse(
+            r#"repo:o/r Crash is:issue is:closed label:bug milestone:"Next Release" sort:created"#,
+        );
+        assert_eq!(
+            query,
+            CachedQuery {
+                state: Some(IssueState::Closed),
+                pull_requests: Some(false),
+                labels: vec!["bug".to_string()],
+                milestone: Some("next release".to_string()),
+                terms: vec!["crash".to_string()],
+            }
+        );
+    }
+}
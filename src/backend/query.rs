@@ -0,0 +1,694 @@
+//! Parsing and checking of GitHub issue search queries, so that the search bar
+//! can highlight qualifiers, report mistakes before searching and complete
+//! qualifier values.
+
+use std::ops::Range;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Which kind of items a search returns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    #[default]
+    Issues,
+    PullRequests,
+    Both,
+}
+
+impl ItemKind {
+    pub const ALL: [ItemKind; 3] = [ItemKind::Issues, ItemKind::PullRequests, ItemKind::Both];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ItemKind::Issues => "Issues",
+            ItemKind::PullRequests => "PRs",
+            ItemKind::Both => "Both",
+        }
+    }
+
+    /// Title of the list showing items of this kind.
+    pub fn title(self) -> &'static str {
+        match self {
+            ItemKind::Issues => "Issues",
+            ItemKind::PullRequests => "Pull Requests",
+            ItemKind::Both => "Issues & Pull Requests",
+        }
+    }
+
+    /// The `is:` qualifier restricting a search to this kind, if any.
+    pub fn qualifier(self) -> Option<&'static str> {
+        match self {
+            ItemKind::Issues => Some("is:issue"),
+            ItemKind::PullRequests => Some("is:pr"),
+            ItemKind::Both => None,
+        }
+    }
+}
+
+/// What the value of a qualifier is, for checking and completing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// A login, or `@me`.
+    User,
+    Label,
+    Milestone,
+    /// A date, a comparison such as `>=2024-01-31`, or a range such as
+    /// `2024-01-01..2024-01-31`.
+    Date,
+    /// A number, a comparison such as `>10`, or a range such as `5..10`.
+    Number,
+    /// One of a fixed set of words.
+    OneOf(&'static [&'static str]),
+    /// A `sort:` order such as `updated-desc`.
+    Sort,
+    /// Any text, such as a branch or team name.
+    Text,
+}
+
+struct Qualifier {
+    name: &'static str,
+    value: ValueKind,
+    /// Whether several values may be given separated by commas.
+    list: bool,
+}
+
+const fn qualifier(name: &'static str, value: ValueKind) -> Qualifier {
+    Qualifier {
+        name,
+        value,
+        list: false,
+    }
+}
+
+const IS_VALUES: &[&str] = &[
+    "open", "closed", "issue", "pr", "merged", "unmerged", "draft", "locked", "unlocked", "queued",
+    "blocked", "blocking", "public", "private", "archived",
+];
+const BOOLEAN: ValueKind = ValueKind::OneOf(&["true", "false"]);
+
+const QUALIFIERS: &[Qualifier] = &[
+    qualifier("author", ValueKind::User),
+    qualifier("assignee", ValueKind::User),
+    qualifier("mentions", ValueKind::User),
+    qualifier("commenter", ValueKind::User),
+    qualifier("involves", ValueKind::User),
+    qualifier("review-requested", ValueKind::User),
+    qualifier("user-review-requested", ValueKind::User),
+    qualifier("reviewed-by", ValueKind::User),
+    qualifier("team-review-requested", ValueKind::Text),
+    qualifier("team", ValueKind::Text),
+    Qualifier {
+        name: "label",
+        value: ValueKind::Label,
+        list: true,
+    },
+    qualifier("milestone", ValueKind::Milestone),
+    qualifier("project", ValueKind::Text),
+    qualifier("base", ValueKind::Text),
+    qualifier("head", ValueKind::Text),
+    qualifier("language", ValueKind::Text),
+    qualifier("created", ValueKind::Date),
+    qualifier("updated", ValueKind::Date),
+    qualifier("closed", ValueKind::Date),
+    qualifier("merged", ValueKind::Date),
+    qualifier("comments", ValueKind::Number),
+    qualifier("interactions", ValueKind::Number),
+    qualifier("reactions", ValueKind::Number),
+    qualifier("is", ValueKind::OneOf(IS_VALUES)),
+    qualifier("state", ValueKind::OneOf(&["open", "closed"])),
+    qualifier("type", ValueKind::OneOf(&["issue", "pr"])),
+    qualifier("linked", ValueKind::OneOf(&["issue", "pr"])),
+    qualifier(
+        "reason",
+        ValueKind::OneOf(&["completed", "not planned", "reopened"]),
+    ),
+    qualifier(
+        "review",
+        ValueKind::OneOf(&["none", "required", "approved", "changes_requested"]),
+    ),
+    qualifier(
+        "status",
+        ValueKind::OneOf(&["pending", "success", "failure"]),
+    ),
+    qualifier("draft", BOOLEAN),
+    qualifier("archived", BOOLEAN),
+    qualifier(
+        "no",
+        ValueKind::OneOf(&["label", "milestone", "assignee", "project"]),
+    ),
+    Qualifier {
+        name: "in",
+        value: ValueKind::OneOf(&["title", "body", "comments"]),
+        list: true,
+    },
+    qualifier("sort", ValueKind::Sort),
+];
+
+/// Qualifiers that would search outside the repository being browsed.
+const SCOPE_QUALIFIERS: &[&str] = &["repo", "org", "user", "owner"];
+
+const SORT_FIELDS: &[&str] = &[
+    "created",
+    "updated",
+    "comments",
+    "reactions",
+    "interactions",
+    "reactions-+1",
+    "reactions--1",
+    "reactions-smile",
+    "reactions-tada",
+    "reactions-heart",
+    "reactions-thinking_face",
+    "reactions-rocket",
+    "reactions-eyes",
+];
+
+/// `sort:` values offered as completions.
+pub const SORT_COMPLETIONS: &[&str] = &[
+    "created-desc",
+    "created-asc",
+    "updated-desc",
+    "updated-asc",
+    "comments-desc",
+    "reactions-desc",
+    "interactions-desc",
+];
+
+/// One whitespace separated part of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    /// Byte range of the term in the query.
+    pub range: Range<usize>,
+    /// Set for `key:value` terms.
+    pub qualifier: Option<QualifierTerm>,
+    /// Why the query cannot be searched with this term.
+    pub error: Option<String>,
+    /// Why the term may not search as intended. GitHub knows more qualifiers
+    /// and values than are checked here, so this does not stop the search.
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualifierTerm {
+    /// The key, lowercased and without the `-` of a negation.
+    pub key: String,
+    /// The value as typed, including any quotes.
+    pub value: String,
+    pub negated: bool,
+    /// Byte offset of the value in the query.
+    pub value_start: usize,
+}
+
+/// A parsed search query.
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    query: String,
+    pub terms: Vec<Term>,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut terms = token_ranges(query)
+            .into_iter()
+            .map(|range| parse_term(query, range))
+            .collect::<Vec<_>>();
+        let mut sorts = terms.iter_mut().filter(|term| {
+            term.qualifier
+                .as_ref()
+                .is_some_and(|qualifier| qualifier.key == "sort")
+        });
+        if sorts.next().is_some() {
+            for term in sorts {
+                term.error
+                    .get_or_insert_with(|| "only one `sort:` can be used".to_string());
+            }
+        }
+        Self {
+            query: query.to_string(),
+            terms,
+        }
+    }
+
+    /// The first mistake that stops the query from being searched, if any.
+    pub fn first_error(&self) -> Option<&str> {
+        self.terms.iter().find_map(|term| term.error.as_deref())
+    }
+
+    /// The first term that may not search as intended, if any.
+    pub fn first_warning(&self) -> Option<&str> {
+        self.terms.iter().find_map(|term| term.warning.as_deref())
+    }
+
+    /// Values of the qualifiers named `key` that are not negated, without
+    /// quotes.
+    pub fn values<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.terms
+            .iter()
+            .filter_map(|term| term.qualifier.as_ref())
+            .filter(move |qualifier| qualifier.key == key && !qualifier.negated)
+            .map(|qualifier| unquote(&qualifier.value))
+    }
+
+    /// The field and direction of the `sort:` qualifier, which the search API
+    /// takes separately from the query.
+    pub fn sort(&self) -> Option<(&str, &'static str)> {
+        let value = self.values("sort").next()?;
+        Some(match value.rsplit_once('-') {
+            Some((field, "asc")) => (field, "asc"),
+            Some((field, "desc")) => (field, "desc"),
+            _ => (value, "desc"),
+        })
+    }
+
+    /// The query without its `sort:` qualifier.
+    pub fn without_sort(&self) -> String {
+        self.terms
+            .iter()
+            .filter(|term| {
+                term.qualifier
+                    .as_ref()
+                    .is_none_or(|qualifier| qualifier.key != "sort")
+            })
+            .map(|term| &self.query[term.range.clone()])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Byte ranges of the whitespace separated parts of `query`, keeping quoted
+/// values such as `milestone:"Next release"` in one piece. Inside quotes a
+/// backslash escapes the next character.
+pub fn token_ranges(query: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (idx, ch) in query.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => {
+                quoted = !quoted;
+                start.get_or_insert(idx);
+            }
+            ch if ch.is_whitespace() && !quoted => {
+                if let Some(start) = start.take() {
+                    ranges.push(start..idx);
+                }
+            }
+            _ => {
+                start.get_or_insert(idx);
+            }
+        }
+    }
+    if let Some(start) = start {
+        ranges.push(start..query.len());
+    }
+    ranges
+}
+
+/// Quotes `value` if it has to be quoted to be used as a qualifier value,
+/// escaping quotes and backslashes in it.
+pub fn quote(value: &str) -> String {
+    if value.contains(|c: char| c.is_whitespace() || c == '"') {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("\"{escaped}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+/// The text of a value written by [`quote`].
+pub fn unescape(value: &str) -> String {
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+    let mut text = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => text.extend(chars.next()),
+            ch => text.push(ch),
+        }
+    }
+    text
+}
+
+/// Number of quotes in `text` that open or close a quoted part.
+fn count_quotes(text: &str) -> usize {
+    let mut count = 0;
+    let mut escaped = false;
+    for ch in text.chars() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if count % 2 == 1 => escaped = true,
+            '"' => count += 1,
+            _ => {}
+        }
+    }
+    count
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// The qualifier value being typed at the end of `before_cursor`: what kind
+/// of value it is, and the part typed so far. For qualifiers that take a
+/// list, only the part after the last comma is returned.
+pub fn value_at_end(before_cursor: &str) -> Option<(ValueKind, &str)> {
+    let range = token_ranges(before_cursor).pop()?;
+    if range.end != before_cursor.len() {
+        return None;
+    }
+    let token = &before_cursor[range];
+    let (key, value) = token.strip_prefix('-').unwrap_or(token).split_once(':')?;
+    let qualifier = lookup(key)?;
+    let value = if qualifier.list {
+        value.rsplit(',').next().unwrap_or_default()
+    } else {
+        value
+    };
+    Some((qualifier.value, value))
+}
+
+fn lookup(key: &str) -> Option<&'static Qualifier> {
+    QUALIFIERS
+        .iter()
+        .find(|qualifier| qualifier.name.eq_ignore_ascii_case(key))
+}
+
+fn parse_term(query: &str, range: Range<usize>) -> Term {
+    let text = &query[range.clone()];
+    let mut term = Term {
+        range: range.clone(),
+        qualifier: None,
+        error: None,
+        warning: None,
+    };
+    if count_quotes(text) % 2 == 1 {
+        term.error = Some("missing closing quote".to_string());
+    }
+    let negated = text.starts_with('-');
+    let body = if negated { &text[1..] } else { text };
+    // Quoted phrases are text, even when they contain a colon.
+    let Some((key, value)) = body.split_once(':').filter(|(key, _)| {
+        !key.is_empty() && key.chars().all(|c| c.is_ascii_alphabetic() || c == '-')
+    }) else {
+        return term;
+    };
+    let key_lower = key.to_ascii_lowercase();
+    match check_qualifier(&key_lower, value, negated) {
+        Some(Problem::Error(error)) => term.error = term.error.or(Some(error)),
+        Some(Problem::Warning(warning)) => term.warning = Some(warning),
+        None => {}
+    }
+    term.qualifier = Some(QualifierTerm {
+        key: key_lower,
+        value: value.to_string(),
+        negated,
+        value_start: range.start + usize::from(negated) + key.len() + 1,
+    });
+    term
+}
+
+enum Problem {
+    /// The query cannot be searched.
+    Error(String),
+    /// The term is likely a mistake, but GitHub may still accept it.
+    Warning(String),
+}
+
+fn check_qualifier(key: &str, value: &str, negated: bool) -> Option<Problem> {
+    if SCOPE_QUALIFIERS.contains(&key) {
+        return Some(Problem::Error(format!(
+            "`{key}:` cannot be used, searches stay in this repository"
+        )));
+    }
+    let Some(qualifier) = lookup(key) else {
+        let mut warning = format!("unknown qualifier `{key}:`");
+        if let Some(suggestion) = suggest(key) {
+            warning.push_str(&format!(", did you mean `{suggestion}:`?"));
+        }
+        return Some(Problem::Warning(warning));
+    };
+    // `sort:` is sent to the API separately, which rejects anything else.
+    let problem = if qualifier.value == ValueKind::Sort {
+        Problem::Error
+    } else {
+        Problem::Warning
+    };
+    if value.is_empty() {
+        return Some(problem(format!("`{key}:` needs a value")));
+    }
+    if negated && qualifier.value == ValueKind::Sort {
+        return Some(problem("`sort:` cannot be negated".to_string()));
+    }
+    let values = if qualifier.list {
+        value.split(',').collect()
+    } else {
+        vec![value]
+    };
+    values
+        .into_iter()
+        .find_map(|value| check_value(key, qualifier.value, value))
+        .map(problem)
+}
+
+fn check_value(key: &str, kind: ValueKind, value: &str) -> Option<String> {
+    let valid = match kind {
+        ValueKind::User => {
+            value == "@me" || is_login(value) || (key == "author" && value.starts_with("app/"))
+        }
+        ValueKind::Label | ValueKind::Milestone | ValueKind::Text => !unquote(value).is_empty(),
+        ValueKind::Date => check_range(value, is_date),
+        ValueKind::Number => check_range(value, |number| number.parse::<u64>().is_ok()),
+        ValueKind::OneOf(choices) => {
+            choices.contains(&unquote(value).to_ascii_lowercase().as_str())
+        }
+        ValueKind::Sort => {
+            let value = value.to_ascii_lowercase();
+            let field = value
+                .strip_suffix("-asc")
+                .or_else(|| value.strip_suffix("-desc"))
+                .unwrap_or(&value);
+            SORT_FIELDS.contains(&field)
+        }
+    };
+    if valid {
+        return None;
+    }
+    Some(match kind {
+        ValueKind::User => format!("`{value}` is not a GitHub login"),
+        ValueKind::Label | ValueKind::Milestone | ValueKind::Text => {
+            format!("`{key}:` needs a value")
+        }
+        ValueKind::Date => format!(
+            "`{key}:` takes a date such as 2024-01-31, >=2024-01-31 or 2024-01-01..2024-01-31"
+        ),
+        ValueKind::Number => format!("`{key}:` takes a number such as 10, >10 or 5..10"),
+        ValueKind::OneOf(choices) => format!("`{key}:` takes one of {}", choices.join(", ")),
+        ValueKind::Sort => format!(
+            "`sort:` takes one of {}, optionally followed by -asc or -desc",
+            SORT_FIELDS[..5].join(", ")
+        ),
+    })
+}
+
+fn is_login(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 39
+        && !value.starts_with('-')
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn is_date(value: &str) -> bool {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+        || NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").is_ok()
+        || NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").is_ok()
+        || DateTime::parse_from_rfc3339(value).is_ok()
+}
+
+/// Checks a value that is `one`, a comparison with it, or a `..` range whose
+/// open end is `*`.
+fn check_range(value: &str, one: impl Fn(&str) -> bool) -> bool {
+    if let Some((from, to)) = value.split_once("..") {
+        return (from != "*" || to != "*") && (from == "*" || one(from)) && (to == "*" || one(to));
+    }
+    let value = [">=", "<=", ">", "<"]
+        .iter()
+        .find_map(|op| value.strip_prefix(op))
+        .unwrap_or(value);
+    one(value)
+}
+
+/// The known qualifier closest to a misspelled `key`.
+fn suggest(key: &str) -> Option<&'static str> {
+    QUALIFIERS
+        .iter()
+        .map(|qualifier| (qualifier.name, edit_distance(key, qualifier.name)))
+        .filter(|(name, distance)| *distance <= 2 && *distance < name.len())
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(name, _)| name)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(current)
+            };
+            prev = current;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(query: &str) -> Vec<String> {
+        SearchQuery::parse(query)
+            .terms
+            .into_iter()
+            .filter_map(|term| term.error)
+            .collect()
+    }
+
+    fn warnings(query: &str) -> Vec<String> {
+        SearchQuery::parse(query)
+            .terms
+            .into_iter()
+            .filter_map(|term| term.warning)
+            .collect()
+    }
+
+    #[test]
+    fn qualifiers_are_split_from_text() {
+        let query = SearchQuery::parse(r#"crash -label:"needs triage" author:@me"#);
+        let terms = &query.terms;
+        assert_eq!(terms.len(), 3);
+        assert!(terms[0].qualifier.is_none());
+        let label = terms[1].qualifier.as_ref().unwrap();
+        assert_eq!(label.key, "label");
+        assert_eq!(label.value, r#""needs triage""#);
+        assert!(label.negated);
+        assert_eq!(label.value_start, 13);
+        assert_eq!(query.values("author").collect::<Vec<_>>(), vec!["@me"]);
+        assert_eq!(query.values("label").count(), 0);
+        assert_eq!(query.first_error(), None);
+    }
+
+    #[test]
+    fn valid_values_pass() {
+        for query in [
+            "assignee:jane-doe mentions:@me author:app/dependabot",
+            "created:>=2024-01-31 updated:2024-01-01..2024-02-01 closed:*..2024-03-01",
+            "created:2024-01-31T10:00:00Z comments:>10 reactions:5..10",
+            "is:open no:assignee -no:label in:title,body milestone:\"v1.0\"",
+            "sort:updated-asc label:bug,docs",
+            r#""error: timeout""#,
+            "review-requested:@me reviewed-by:jane linked:pr reason:\"not planned\"",
+            "is:queued draft:false archived:true status:success base:main head:fix/crash",
+        ] {
+            assert_eq!(errors(query), Vec::<String>::new(), "{query}");
+            assert_eq!(warnings(query), Vec::<String>::new(), "{query}");
+        }
+    }
+
+    #[test]
+    fn likely_mistakes_are_warnings() {
+        assert_eq!(
+            warnings("asignee:jane"),
+            vec!["unknown qualifier `asignee:`, did you mean `assignee:`?"]
+        );
+        assert_eq!(
+            warnings("milestones-due:soon"),
+            vec!["unknown qualifier `milestones-due:`"]
+        );
+        assert_eq!(warnings("author:"), vec!["`author:` needs a value"]);
+        assert_eq!(
+            warnings("author:jane_doe"),
+            vec!["`jane_doe` is not a GitHub login"]
+        );
+        assert_eq!(warnings("no:reviewer").len(), 1);
+        assert_eq!(warnings("created:yesterday").len(), 1);
+        assert_eq!(warnings("created:*..*").len(), 1);
+        assert_eq!(warnings("comments:many").len(), 1);
+        let query = SearchQuery::parse("asignee:jane created:yesterday");
+        assert_eq!(query.first_error(), None);
+    }
+
+    #[test]
+    fn queries_that_cannot_be_searched_are_errors() {
+        assert_eq!(errors("-sort:created"), vec!["`sort:` cannot be negated"]);
+        assert_eq!(errors("sort:oldest").len(), 1);
+        assert_eq!(
+            errors("sort:created sort:updated"),
+            vec!["only one `sort:` can be used"]
+        );
+        assert_eq!(errors("repo:other/repo").len(), 1);
+        assert_eq!(errors(r#"milestone:"v1"#), vec!["missing closing quote"]);
+    }
+
+    #[test]
+    fn quoted_values_escape_quotes() {
+        let title = r#"the "big" one \ two"#;
+        let quoted = quote(title);
+        assert_eq!(quoted, r#""the \"big\" one \\ two""#);
+        assert_eq!(unescape(&quoted), title);
+        assert_eq!(quote("v2"), "v2");
+
+        let query = format!("crash milestone:{quoted} is:open");
+        let query = SearchQuery::parse(&query);
+        assert_eq!(query.first_error(), None);
+        assert_eq!(query.terms.len(), 3);
+    }
+
+    #[test]
+    fn sort_is_taken_out_of_the_query() {
+        let query = SearchQuery::parse("crash sort:comments-asc is:open");
+        assert_eq!(query.sort(), Some(("comments", "asc")));
+        assert_eq!(query.without_sort(), "crash is:open");
+        assert_eq!(
+            SearchQuery::parse("sort:reactions-+1").sort(),
+            Some(("reactions-+1", "desc"))
+        );
+        assert_eq!(SearchQuery::parse("crash").sort(), None);
+    }
+
+    #[test]
+    fn value_being_typed_is_found() {
+        assert_eq!(
+            value_at_end("crash -assignee:ja"),
+            Some((ValueKind::User, "ja"))
+        );
+        assert_eq!(
+            value_at_end("label:bug,doc"),
+            Some((ValueKind::Label, "doc"))
+        );
+        assert_eq!(
+            value_at_end(r#"milestone:"Next re"#),
+            Some((ValueKind::Milestone, r#""Next re"#))
+        );
+        assert_eq!(value_at_end("is:open "), None);
+        assert_eq!(value_at_end("crash"), None);
+        assert_eq!(value_at_end("unknown:x"), None);
+    }
+}
@@ -0,0 +1,487 @@
+//! Issue templates from a repository's `.github/ISSUE_TEMPLATE` directory:
+//! markdown templates with front matter, and YAML issue forms.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Directory GitHub reads issue templates from.
+pub const TEMPLATE_DIR: &str = ".github/ISSUE_TEMPLATE";
+
+/// A template the issue composer can start from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IssueTemplate {
+    pub name: String,
+    pub about: Option<String>,
+    /// Title the new issue starts with.
+    pub title: Option<String>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub kind: TemplateKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum TemplateKind {
+    /// A markdown template; `body` pre-fills the description.
+    Markdown { body: String },
+    /// A YAML issue form, whose answers make up the description.
+    Form { fields: Vec<FormField> },
+}
+
+/// One element of an issue form.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    pub label: String,
+    pub description: Option<String>,
+    pub required: bool,
+    pub kind: FieldKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Text shown to the reporter; not part of the issue.
+    Markdown {
+        text: String,
+    },
+    Input {
+        placeholder: Option<String>,
+        value: Option<String>,
+    },
+    Textarea {
+        placeholder: Option<String>,
+        value: Option<String>,
+        /// Language the answer is rendered as a code block in.
+        render: Option<String>,
+    },
+    Dropdown {
+        options: Vec<String>,
+        multiple: bool,
+        default: Option<usize>,
+    },
+    Checkboxes {
+        options: Vec<CheckboxOption>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheckboxOption {
+    pub label: String,
+    pub required: bool,
+}
+
+/// The reporter's answer to a [`FormField`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// Markdown elements have nothing to answer.
+    None,
+    Text(String),
+    /// Indices of the chosen dropdown options.
+    Selected(Vec<usize>),
+    /// Whether each checkbox is ticked.
+    Checked(Vec<bool>),
+}
+
+impl FormField {
+    /// The answer the field starts with.
+    pub fn initial_value(&self) -> FieldValue {
+        match &self.kind {
+            FieldKind::Markdown { .. } => FieldValue::None,
+            FieldKind::Input { value, .. } | FieldKind::Textarea { value, .. } => {
+                FieldValue::Text(value.clone().unwrap_or_default())
+            }
+            FieldKind::Dropdown { default, .. } => {
+                FieldValue::Selected(default.iter().copied().collect())
+            }
+            FieldKind::Checkboxes { options } => FieldValue::Checked(vec![false; options.len()]),
+        }
+    }
+
+    fn is_answered(&self, value: &FieldValue) -> bool {
+        match (&self.kind, value) {
+            (FieldKind::Markdown { .. }, _) => true,
+            (FieldKind::Checkboxes { options }, FieldValue::Checked(checked)) => options
+                .iter()
+                .zip(checked)
+                .all(|(option, checked)| !option.required || *checked),
+            (_, FieldValue::Text(text)) => !self.required || !text.trim().is_empty(),
+            (_, FieldValue::Selected(selected)) => !self.required || !selected.is_empty(),
+            _ => !self.required,
+        }
+    }
+}
+
+/// Labels of the required fields that have not been answered.
+pub fn missing_fields<'a>(fields: &'a [FormField], values: &[FieldValue]) -> Vec<&'a str> {
+    fields
+        .iter()
+        .zip(values)
+        .filter(|(field, value)| !field.is_answered(value))
+        .map(|(field, _)| field.label.as_str())
+        .collect()
+}
+
+/// Renders the answers the way GitHub does for issues created from a form.
+pub fn form_body(fields: &[FormField], values: &[FieldValue]) -> String {
+    const NO_RESPONSE: &str = "_No response_";
+    let mut sections = Vec::new();
+    for (field, value) in fields.iter().zip(values) {
+        let answer = match (&field.kind, value) {
+            (FieldKind::Markdown { .. }, _) => continue,
+            (FieldKind::Checkboxes { options }, FieldValue::Checked(checked)) => options
+                .iter()
+                .zip(checked)
+                .map(|(option, checked)| {
+                    format!("- [{}] {}", if *checked { "X" } else { " " }, option.label)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            (FieldKind::Dropdown { options, .. }, FieldValue::Selected(selected))
+                if !selected.is_empty() =>
+            {
+                selected
+                    .iter()
+                    .filter_map(|idx| options.get(*idx).map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+            (FieldKind::Textarea { render, .. }, FieldValue::Text(text))
+                if !text.trim().is_empty() =>
+            {
+                match render {
+                    Some(lang) => format!("```{lang}\n{}\n```", text.trim_end()),
+                    None => text.trim_end().to_string(),
+                }
+            }
+            (_, FieldValue::Text(text)) if !text.trim().is_empty() => text.trim().to_string(),
+            _ => NO_RESPONSE.to_string(),
+        };
+        sections.push(format!("### {}\n\n{answer}", field.label));
+    }
+    sections.join("\n\n")
+}
+
+/// Parses a file from [`TEMPLATE_DIR`]. Returns `Ok(None)` for files that are
+/// not templates, such as the chooser's `config.yml`.
+pub fn parse_template(file_name: &str, contents: &str) -> Result<Option<IssueTemplate>, String> {
+    let lower = file_name.to_lowercase();
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+    if lower.ends_with(".md") {
+        parse_markdown(stem, contents).map(Some)
+    } else if (lower.ends_with(".yml") || lower.ends_with(".yaml"))
+        && !matches!(lower.as_str(), "config.yml" | "config.yaml")
+    {
+        parse_form(contents).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Accepts both `labels: [bug, ui]` and `labels: "bug, ui"`.
+fn string_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum List {
+        One(String),
+        Many(Vec<String>),
+    }
+    let values = match Option::<List>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(List::One(value)) => value.split(',').map(str::to_string).collect(),
+        Some(List::Many(values)) => values,
+    };
+    Ok(values
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect())
+}
+
+#[derive(Deserialize, Default)]
+struct FrontMatter {
+    name: Option<String>,
+    about: Option<String>,
+    title: Option<String>,
+    #[serde(default, deserialize_with = "string_list")]
+    labels: Vec<String>,
+    #[serde(default, deserialize_with = "string_list")]
+    assignees: Vec<String>,
+}
+
+fn parse_markdown(stem: &str, contents: &str) -> Result<IssueTemplate, String> {
+    let (front_matter, body) = split_front_matter(contents);
+    let front_matter: FrontMatter = match front_matter {
+        Some(yaml) if !yaml.trim().is_empty() => {
+            serde_norway::from_str(yaml).map_err(|err| err.to_string())?
+        }
+        _ => FrontMatter::default(),
+    };
+    Ok(IssueTemplate {
+        name: front_matter.name.unwrap_or_else(|| stem.to_string()),
+        about: front_matter.about,
+        title: front_matter.title.filter(|title| !title.is_empty()),
+        labels: front_matter.labels,
+        assignees: front_matter.assignees,
+        kind: TemplateKind::Markdown {
+            body: body.trim_start_matches(['\r', '\n']).to_string(),
+        },
+    })
+}
+
+/// Splits `---` delimited front matter off a markdown template.
+fn split_front_matter(contents: &str) -> (Option<&str>, &str) {
+    let Some(rest) = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))
+    else {
+        return (None, contents);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, contents)
+}
+
+#[derive(Deserialize)]
+struct RawForm {
+    name: String,
+    description: Option<String>,
+    title: Option<String>,
+    #[serde(default, deserialize_with = "string_list")]
+    labels: Vec<String>,
+    #[serde(default, deserialize_with = "string_list")]
+    assignees: Vec<String>,
+    body: Vec<RawField>,
+}
+
+#[derive(Deserialize)]
+struct RawField {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    attributes: RawAttributes,
+    #[serde(default)]
+    validations: RawValidations,
+}
+
+#[derive(Deserialize, Default)]
+struct RawAttributes {
+    label: Option<String>,
+    description: Option<String>,
+    placeholder: Option<String>,
+    value: Option<String>,
+    render: Option<String>,
+    #[serde(default)]
+    options: Vec<RawOption>,
+    #[serde(default)]
+    multiple: bool,
+    default: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawOption {
+    Plain(String),
+    Checkbox {
+        label: String,
+        #[serde(default)]
+        required: bool,
+    },
+}
+
+impl RawOption {
+    fn label(self) -> String {
+        match self {
+            RawOption::Plain(label) | RawOption::Checkbox { label, .. } => label,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct RawValidations {
+    #[serde(default)]
+    required: bool,
+}
+
+fn parse_form(contents: &str) -> Result<IssueTemplate, String> {
+    let form: RawForm = serde_norway::from_str(contents).map_err(|err| err.to_string())?;
+    let fields = form.body.into_iter().filter_map(form_field).collect();
+    Ok(IssueTemplate {
+        name: form.name,
+        about: form.description,
+        title: form.title.filter(|title| !title.is_empty()),
+        labels: form.labels,
+        assignees: form.assignees,
+        kind: TemplateKind::Form { fields },
+    })
+}
+
+/// Converts an element of the form's `body`, skipping unknown types.
+fn form_field(raw: RawField) -> Option<FormField> {
+    let RawAttributes {
+        label,
+        description,
+        placeholder,
+        value,
+        render,
+        options,
+        multiple,
+        default,
+    } = raw.attributes;
+    let kind = match raw.kind.as_str() {
+        "markdown" => FieldKind::Markdown {
+            text: value.unwrap_or_default(),
+        },
+        "input" => FieldKind::Input { placeholder, value },
+        "textarea" => FieldKind::Textarea {
+            placeholder,
+            value,
+            render,
+        },
+        "dropdown" => FieldKind::Dropdown {
+            options: options.into_iter().map(RawOption::label).collect(),
+            multiple,
+            default,
+        },
+        "checkboxes" => FieldKind::Checkboxes {
+            options: options
+                .into_iter()
+                .map(|option| match option {
+                    RawOption::Plain(label) => CheckboxOption {
+                        label,
+                        required: false,
+                    },
+                    RawOption::Checkbox { label, required } => CheckboxOption { label, required },
+                })
+                .collect(),
+        },
+        _ => return None,
+    };
+    Some(FormField {
+        label: label.unwrap_or_default(),
+        description,
+        required: raw.validations.required,
+        kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUG_FORM: &str = r#"
+name: Bug report
+description: Something is broken
+title: "[Bug]: "
+labels: ["bug", "triage"]
+body:
+  - type: markdown
+    attributes:
+      value: Thanks for taking the time!
+  - type: input
+    id: version
+    attributes:
+      label: Version
+    validations:
+      required: true
+  - type: textarea
+    attributes:
+      label: Logs
+      render: shell
+  - type: dropdown
+    attributes:
+      label: Browsers
+      multiple: true
+      options:
+        - Firefox
+        - Chrome
+  - type: checkboxes
+    attributes:
+      label: Terms
+      options:
+        - label: I searched for duplicates
+          required: true
+        - label: I want to fix it
+"#;
+
+    #[test]
+    fn markdown_templates_read_their_front_matter() {
+        let contents = "---\nname: Feature\nabout: Suggest an idea\nlabels: enhancement, ui\nassignees: ''\n---\n\n## Problem\n";
+        let template = parse_template("feature.md", contents).unwrap().unwrap();
+        assert_eq!(template.name, "Feature");
+        assert_eq!(template.labels, vec!["enhancement", "ui"]);
+        assert!(template.assignees.is_empty());
+        assert_eq!(
+            template.kind,
+            TemplateKind::Markdown {
+                body: "## Problem\n".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn config_and_other_files_are_not_templates() {
+        assert_eq!(
+            parse_template("config.yml", "blank_issues_enabled: false"),
+            Ok(None)
+        );
+        assert_eq!(parse_template("README.txt", "hi"), Ok(None));
+    }
+
+    #[test]
+    fn forms_become_fields() {
+        let template = parse_template("bug.yml", BUG_FORM).unwrap().unwrap();
+        assert_eq!(template.title.as_deref(), Some("[Bug]: "));
+        let TemplateKind::Form { fields } = template.kind else {
+            panic!("expected a form");
+        };
+        assert_eq!(fields.len(), 5);
+        assert!(fields[1].required);
+        assert_eq!(
+            fields[3].kind,
+            FieldKind::Dropdown {
+                options: vec!["Firefox".to_string(), "Chrome".to_string()],
+                multiple: true,
+                default: None,
+            }
+        );
+    }
+
+    #[test]
+    fn required_fields_must_be_answered() {
+        let template = parse_template("bug.yml", BUG_FORM).unwrap().unwrap();
+        let TemplateKind::Form { fields } = template.kind else {
+            panic!("expected a form");
+        };
+        let mut values = fields
+            .iter()
+            .map(FormField::initial_value)
+            .collect::<Vec<_>>();
+        assert_eq!(missing_fields(&fields, &values), vec!["Version", "Terms"]);
+        values[1] = FieldValue::Text("1.2".to_string());
+        values[4] = FieldValue::Checked(vec![true, false]);
+        assert!(missing_fields(&fields, &values).is_empty());
+    }
+
+    #[test]
+    fn form_answers_render_like_github() {
+        let template = parse_template("bug.yml", BUG_FORM).unwrap().unwrap();
+        let TemplateKind::Form { fields } = template.kind else {
+            panic!("expected a form");
+        };
+        let values = vec![
+            FieldValue::None,
+            FieldValue::Text("1.2".to_string()),
+            FieldValue::Text("panic!\n".to_string()),
+            FieldValue::Selected(vec![1]),
+            FieldValue::Checked(vec![true, false]),
+        ];
+        assert_eq!(
+            form_body(&fields, &values),
+            "### Version\n\n1.2\n\n### Logs\n\n```shell\npanic!\n```\n\n### Browsers\n\nChrome\n\n### Terms\n\n- [X] I searched for duplicates\n- [ ] I want to fix it"
+        );
+    }
+}
@@ -2,10 +2,12 @@ use clap::Parser;
 use gitv_tui::{
     app::{
         App,
-        cli::{Cli, generate_man_pages},
+        cli::{Cli, Command, generate_man_pages},
+        labels,
     },
     auth::AuthProvider,
     errors::AppError,
+    github::GithubHost,
     logging,
 };
 
@@ -22,12 +24,19 @@ async fn main() -> anyhow::Result<(), AppError> {
         return Ok(());
     }
     if let Some(ref token) = cli.args.set_token {
-        let auth = gitv_tui::auth::keyring::KeyringAuth::new("gitv")?;
+        let host = GithubHost::resolve(cli.args.host.as_deref());
+        let auth = gitv_tui::auth::keyring::KeyringAuth::new("gitv", &host)?;
 
         auth.set_token(token)?;
         return Ok(());
     }
 
+    if let Some(Command::Labels(command)) = cli.command {
+        let host = GithubHost::resolve(cli.args.host.as_deref());
+        let client = App::github_client(&cli.args, &host)?;
+        return labels::run(command, client).await;
+    }
+
     let mut app = App::new(cli).await?;
     app.run().await
 }
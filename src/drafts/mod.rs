@@ -0,0 +1,289 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{github::GithubHost, store};
+
+/// What a draft is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DraftKey {
+    /// A comment on the issue or pull request with this number.
+    Comment(u64),
+    /// The issue being written in the issue composer.
+    NewIssue,
+}
+
+impl DraftKey {
+    /// Key of the draft in the drafts file, such as `12` or `new`.
+    fn to_file_key(self) -> String {
+        match self {
+            DraftKey::Comment(number) => number.to_string(),
+            DraftKey::NewIssue => "new".to_string(),
+        }
+    }
+
+    fn from_file_key(key: &str) -> Option<Self> {
+        match key {
+            "new" => Some(DraftKey::NewIssue),
+            number => number.parse().ok().map(DraftKey::Comment),
+        }
+    }
+}
+
+/// Unsent text. Comments only use `body`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Draft {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub labels: String,
+    #[serde(default)]
+    pub assignees: String,
+    #[serde(default)]
+    pub body: String,
+    /// Seconds since the unix epoch.
+    #[serde(default)]
+    pub saved_at: u64,
+}
+
+impl Draft {
+    pub fn comment(body: String) -> Self {
+        Self {
+            body,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        [&self.title, &self.labels, &self.assignees, &self.body]
+            .iter()
+            .all(|text| text.trim().is_empty())
+    }
+
+    /// Whether the text of both drafts is the same.
+    fn same_text(&self, other: &Draft) -> bool {
+        (&self.title, &self.labels, &self.assignees, &self.body)
+            == (&other.title, &other.labels, &other.assignees, &other.body)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+struct DraftsFile {
+    drafts: BTreeMap<String, Draft>,
+}
+
+impl DraftsFile {
+    fn apply(&mut self, changes: &BTreeMap<String, Option<Draft>>) {
+        for (key, draft) in changes {
+            match draft {
+                Some(draft) => self.drafts.insert(key.clone(), draft.clone()),
+                None => self.drafts.remove(key),
+            };
+        }
+    }
+}
+
+/// Unsent comments and issues, stored in the data dir per repository.
+///
+/// Changes are kept in memory until [`flush`](Self::flush), which the app
+/// runs in the background on every tick. Only the drafts this instance
+/// changed are written, so other instances on the same repository keep
+/// theirs.
+#[derive(Debug, Clone)]
+pub struct Drafts {
+    path: PathBuf,
+    file: Arc<Mutex<DraftsFile>>,
+    /// Drafts saved (`Some`) or discarded (`None`) since the last flush.
+    changes: Arc<Mutex<BTreeMap<String, Option<Draft>>>>,
+    /// Held while flushing, so flushes finish in the order they started.
+    flushing: Arc<Mutex<()>>,
+}
+
+impl Drafts {
+    pub fn open(host: &GithubHost, owner: &str, repo: &str) -> Self {
+        Self::at(store::repo_file("drafts", host, owner, repo))
+    }
+
+    fn at(path: PathBuf) -> Self {
+        Self {
+            file: Arc::new(Mutex::new(store::read(&path))),
+            path,
+            changes: Arc::default(),
+            flushing: Arc::default(),
+        }
+    }
+
+    fn file(&self) -> MutexGuard<'_, DraftsFile> {
+        self.file.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn changes(&self) -> MutexGuard<'_, BTreeMap<String, Option<Draft>>> {
+        self.changes.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn change(&self, key: String, draft: Option<Draft>) {
+        let mut file = self.file();
+        match &draft {
+            Some(draft) => file.drafts.insert(key.clone(), draft.clone()),
+            None => file.drafts.remove(&key),
+        };
+        self.changes().insert(key, draft);
+    }
+
+    pub fn get(&self, key: DraftKey) -> Option<Draft> {
+        self.file().drafts.get(&key.to_file_key()).cloned()
+    }
+
+    /// Stores `draft`, or discards the stored one if `draft` is empty.
+    pub fn save(&self, key: DraftKey, mut draft: Draft) {
+        if draft.is_empty() {
+            self.discard(key);
+            return;
+        }
+        let key = key.to_file_key();
+        if self
+            .file()
+            .drafts
+            .get(&key)
+            .is_some_and(|saved| saved.same_text(&draft))
+        {
+            return;
+        }
+        draft.saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.change(key, Some(draft));
+    }
+
+    pub fn discard(&self, key: DraftKey) {
+        let key = key.to_file_key();
+        if self.file().drafts.contains_key(&key) {
+            self.change(key, None);
+        }
+    }
+
+    pub fn has_comment(&self, number: u64) -> bool {
+        self.file()
+            .drafts
+            .contains_key(&DraftKey::Comment(number).to_file_key())
+    }
+
+    /// The drafts of this repository, most recently saved first.
+    pub fn list(&self) -> Vec<(DraftKey, Draft)> {
+        let mut drafts = self
+            .file()
+            .drafts
+            .iter()
+            .filter_map(|(key, draft)| DraftKey::from_file_key(key).map(|key| (key, draft.clone())))
+            .collect::<Vec<_>>();
+        drafts.sort_by_key(|(_, draft)| std::cmp::Reverse(draft.saved_at));
+        drafts
+    }
+
+    /// Writes the changes since the last flush to disk, and picks up the
+    /// drafts other instances wrote.
+    pub fn flush(&self) {
+        let _flushing = self.flushing.lock().unwrap_or_else(|err| err.into_inner());
+        let changes = std::mem::take(&mut *self.changes());
+        if changes.is_empty() {
+            return;
+        }
+        // The copy keeps the file lock and disk I/O away from the UI's lock.
+        let mut merged = self.file().clone();
+        store::update(&self.path, &mut merged, |file| file.apply(&changes));
+        let mut file = self.file();
+        merged.apply(&self.changes());
+        *file = merged;
+    }
+
+    /// Runs [`flush`](Self::flush) on the blocking pool if anything changed.
+    pub fn flush_in_background(&self) {
+        if self.changes().is_empty() {
+            return;
+        }
+        let drafts = self.clone();
+        tokio::task::spawn_blocking(move || drafts.flush());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::tests::temp_file;
+
+    #[test]
+    fn file_keys_round_trip() {
+        for key in [DraftKey::Comment(12), DraftKey::NewIssue] {
+            assert_eq!(DraftKey::from_file_key(&key.to_file_key()), Some(key));
+        }
+    }
+
+    #[test]
+    fn drafts_are_restored_after_a_restart() {
+        let path = temp_file("drafts.json");
+        let drafts = Drafts::at(path.clone());
+        drafts.flush();
+        assert!(!path.exists(), "nothing changed, so nothing is written");
+
+        let issue = Draft {
+            title: "Crash on start".to_string(),
+            ..Default::default()
+        };
+        drafts.save(DraftKey::NewIssue, issue.clone());
+        drafts.save(DraftKey::Comment(12), Draft::comment("Thanks!".to_string()));
+        drafts.flush();
+
+        let reopened = Drafts::at(path);
+        let restored = reopened.get(DraftKey::NewIssue).unwrap();
+        assert!(restored.same_text(&issue));
+        assert!(reopened.has_comment(12));
+        assert_eq!(reopened.list().len(), 2);
+    }
+
+    #[test]
+    fn empty_and_discarded_drafts_are_removed() {
+        let path = temp_file("drafts.json");
+        let drafts = Drafts::at(path.clone());
+        drafts.save(DraftKey::Comment(1), Draft::comment("one".to_string()));
+        drafts.save(DraftKey::Comment(2), Draft::comment("two".to_string()));
+        drafts.flush();
+
+        drafts.save(DraftKey::Comment(1), Draft::comment("  ".to_string()));
+        drafts.discard(DraftKey::Comment(2));
+        assert!(drafts.list().is_empty());
+        drafts.flush();
+        assert!(Drafts::at(path).list().is_empty());
+    }
+
+    #[test]
+    fn instances_on_the_same_repo_keep_each_others_drafts() {
+        let path = temp_file("drafts.json");
+        let first = Drafts::at(path.clone());
+        let second = Drafts::at(path.clone());
+        first.save(DraftKey::Comment(1), Draft::comment("one".to_string()));
+        first.save(DraftKey::Comment(2), Draft::comment("two".to_string()));
+        first.flush();
+        second.save(DraftKey::Comment(3), Draft::comment("three".to_string()));
+        second.flush();
+        assert_eq!(second.list().len(), 3);
+
+        // `second` still has #1 in memory, but only writes its own changes.
+        first.discard(DraftKey::Comment(1));
+        first.flush();
+        second.save(DraftKey::Comment(3), Draft::comment("three!".to_string()));
+        second.flush();
+
+        let mut keys = Drafts::at(path)
+            .list()
+            .into_iter()
+            .map(|(key, _)| key.to_file_key())
+            .collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, vec!["2", "3"]);
+    }
+}
@@ -19,12 +19,51 @@ pub enum AppError {
     TokioMpsc,
     #[error(transparent)]
     InitLoggingError(#[from] tracing_subscriber::util::TryInitError),
+    #[error("GitHub GraphQL error: {0}")]
+    GraphQl(String),
+    #[error("rate limited by GitHub; try again in {0}s")]
+    RateLimited(u64),
+    #[error("unavailable offline: {0}")]
+    Offline(String),
     #[error("error setting global {0}")]
     ErrorSettingGlobal(&'static str),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+impl AppError {
+    /// Whether the remote reported that the requested resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            AppError::Octocrab(octocrab::Error::GitHub { source, .. })
+                if source.status_code.as_u16() == 404
+        )
+    }
+
+    /// Whether the failure is likely transient, so the request is worth retrying
+    /// later (network errors, server errors, rate limits). Offline mode is
+    /// read-only for the whole session, so its refusals are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AppError::RateLimited(_) => true,
+            AppError::Octocrab(octocrab::Error::GitHub { source, .. }) => {
+                let status = source.status_code.as_u16();
+                status >= 500
+                    || status == 408
+                    || status == 429
+                    || (status == 403 && source.message.to_lowercase().contains("rate limit"))
+            }
+            AppError::Octocrab(
+                octocrab::Error::Hyper { .. }
+                | octocrab::Error::Service { .. }
+                | octocrab::Error::Http { .. },
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
 impl<T> From<SendError<T>> for AppError {
     fn from(_: SendError<T>) -> Self {
         AppError::TokioMpsc
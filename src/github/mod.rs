@@ -1,7 +1,109 @@
-use crate::errors::AppError;
+use std::future::Future;
 
+use octocrab::Page;
+use serde::{Deserialize, de::DeserializeOwned};
+
+use crate::{
+    errors::AppError,
+    github::rate_limit::{Limit, RateLimiter, Resource},
+};
+
+pub mod rate_limit;
+mod transport;
+
+/// Host name of github.com; anything else is treated as GitHub Enterprise Server.
+pub const DEFAULT_HOST: &str = "github.com";
+/// Environment variable consulted when `--host` is not given.
+pub const HOST_ENV_VAR: &str = "GH_HOST";
+
+/// The GitHub instance gitv talks to, and the URLs derived from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubHost(String);
+
+impl Default for GithubHost {
+    fn default() -> Self {
+        Self(DEFAULT_HOST.to_string())
+    }
+}
+
+impl std::fmt::Display for GithubHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl GithubHost {
+    /// Accepts a bare host name or a URL such as `https://ghe.example.com/`.
+    pub fn new(host: &str) -> Self {
+        let host = host.trim();
+        let host = host
+            .split_once("://")
+            .map_or(host, |(_, rest)| rest)
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match host.as_str() {
+            "" | "api.github.com" => Self::default(),
+            _ => Self(host),
+        }
+    }
+
+    /// Uses `flag` if given, otherwise [`HOST_ENV_VAR`], otherwise github.com.
+    pub fn resolve(flag: Option<&str>) -> Self {
+        flag.map(Self::new)
+            .or_else(|| {
+                std::env::var(HOST_ENV_VAR)
+                    .ok()
+                    .map(|host| Self::new(&host))
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_HOST
+    }
+
+    /// Base URI of the REST API.
+    pub fn api_url(&self) -> String {
+        if self.is_default() {
+            "https://api.github.com".to_string()
+        } else {
+            format!("https://{}/api/v3", self.0)
+        }
+    }
+
+    /// Base URI that `/graphql` is resolved against. On GHES this is not
+    /// under the REST prefix.
+    pub fn graphql_url(&self) -> String {
+        if self.is_default() {
+            "https://api.github.com".to_string()
+        } else {
+            format!("https://{}/api", self.0)
+        }
+    }
+
+    pub fn web_url(&self) -> String {
+        format!("https://{}", self.0)
+    }
+
+    pub fn issue_url(&self, owner: &str, repo: &str, number: u64) -> String {
+        format!("{}/{owner}/{repo}/issues/{number}", self.web_url())
+    }
+}
+
+/// Octocrab clients for one host. Requests made through [`GithubClient::send`]
+/// share rate-limit backoff per API resource. Every response updates the known quota, and
+/// repeated GETs are sent with `If-None-Match` and answered from memory on
+/// `304 Not Modified`.
 pub struct GithubClient {
     inner: octocrab::Octocrab,
+    graphql: octocrab::Octocrab,
+    limiter: RateLimiter,
 }
 
 impl std::ops::Deref for GithubClient {
@@ -13,16 +115,268 @@ impl std::ops::Deref for GithubClient {
 }
 
 impl GithubClient {
-    pub fn new(token: Option<String>) -> Result<Self, AppError> {
-        let mut builder = octocrab::Octocrab::builder();
-        if let Some(token) = token {
-            builder = builder.personal_token(token);
-        }
-        let inner = builder.build()?;
-        Ok(Self { inner })
+    pub fn new(token: Option<String>, host: &GithubHost) -> Result<Self, AppError> {
+        let inner = transport::build(host.api_url(), token.clone())?;
+        let graphql = if host.is_default() {
+            inner.clone()
+        } else {
+            transport::build(host.graphql_url(), token)?
+        };
+        Ok(Self {
+            inner,
+            graphql,
+            limiter: RateLimiter::default(),
+        })
     }
 
     pub fn inner(&self) -> &octocrab::Octocrab {
         &self.inner
     }
+
+    /// Sends a REST `request` once any rate-limit pause is over. If GitHub
+    /// reports a rate limit, later requests are held back until it should
+    /// have lifted.
+    pub async fn send<T>(
+        &self,
+        request: impl Future<Output = octocrab::Result<T>>,
+    ) -> Result<T, AppError> {
+        self.send_to(Resource::Core, request).await
+    }
+
+    /// Like [`GithubClient::send`], for a search request.
+    pub async fn send_search<T>(
+        &self,
+        request: impl Future<Output = octocrab::Result<T>>,
+    ) -> Result<T, AppError> {
+        self.send_to(Resource::Search, request).await
+    }
+
+    async fn send_to<T>(
+        &self,
+        resource: Resource,
+        request: impl Future<Output = octocrab::Result<T>>,
+    ) -> Result<T, AppError> {
+        self.limiter.wait(resource).await?;
+        match request.await {
+            Ok(value) => {
+                self.limiter.succeeded();
+                Ok(value)
+            }
+            Err(err) => {
+                if let Some(limit) = Limit::of(&err) {
+                    self.rate_limited(limit, resource).await;
+                }
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the page after `page`, if there is one.
+    pub async fn next_page<T: DeserializeOwned>(
+        &self,
+        page: &Page<T>,
+    ) -> Result<Option<Page<T>>, AppError> {
+        let resource = page
+            .next
+            .as_ref()
+            .map_or(Resource::Core, |uri| Resource::of_path(uri.path()));
+        self.send_to(
+            resource,
+            self.inner.get_page(&relative_uri(page.next.as_ref())),
+        )
+        .await
+    }
+
+    async fn rate_limited(&self, limit: Limit, resource: Resource) {
+        // Querying the quota does not count against it.
+        let quotas = match limit {
+            Limit::Primary => self
+                .inner
+                .ratelimit()
+                .get()
+                .await
+                .map(rate_limit::quotas_from)
+                .unwrap_or_default(),
+            Limit::Secondary => Vec::new(),
+        };
+        self.limiter.limited(limit, resource, quotas);
+    }
+
+    /// Runs a GraphQL query. GitHub reports query errors with a `200 OK`, so
+    /// they are surfaced here as [`AppError::GraphQl`].
+    pub async fn graphql_query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, AppError> {
+        let payload = serde_json::json!({ "query": query, "variables": variables });
+        let response: GraphQlResponse<T> = self
+            .send_to(Resource::Graphql, self.graphql.graphql(&payload))
+            .await?;
+        if response
+            .errors
+            .iter()
+            .any(|err| err.kind.as_deref() == Some("RATE_LIMITED"))
+        {
+            self.rate_limited(Limit::Primary, Resource::Graphql).await;
+        }
+        if !response.errors.is_empty() {
+            let messages = response
+                .errors
+                .into_iter()
+                .map(|err| err.message)
+                .collect::<Vec<_>>();
+            return Err(AppError::GraphQl(messages.join("; ")));
+        }
+        response
+            .data
+            .ok_or_else(|| AppError::GraphQl("response contained no data".to_string()))
+    }
+}
+
+/// Strips the host from a `next` link. octocrab only sends the token with
+/// absolute URLs pointing at api.github.com, so links from a GHES host must
+/// go out as a path for the base URI to resolve.
+fn relative_uri(uri: Option<&http::Uri>) -> Option<http::Uri> {
+    let path = uri?.path_and_query()?.clone();
+    http::Uri::builder().path_and_query(path).build().ok()
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_normalises_urls_and_defaults() {
+        assert_eq!(
+            GithubHost::new("https://GHE.example.com/org"),
+            GithubHost::new("ghe.example.com")
+        );
+        assert!(GithubHost::new("api.github.com").is_default());
+        assert!(GithubHost::new("").is_default());
+
+        let ghes = GithubHost::new("ghe.example.com");
+        assert_eq!(ghes.api_url(), "https://ghe.example.com/api/v3");
+        assert_eq!(ghes.graphql_url(), "https://ghe.example.com/api");
+        assert_eq!(
+            ghes.issue_url("o", "r", 3),
+            "https://ghe.example.com/o/r/issues/3"
+        );
+    }
+
+    /// Answers one connection per response, in order, and returns the
+    /// requests received, lowercased.
+    fn serve(
+        responses: Vec<&'static str>,
+    ) -> (std::net::SocketAddr, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                let head_len = loop {
+                    if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break pos + 4;
+                    }
+                    let read = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                };
+                let request = String::from_utf8(request).unwrap().to_lowercase();
+                let body_len = request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length: "))
+                    .map_or(0, |len| len.trim().parse().unwrap());
+                let mut body = request.len() - head_len;
+                while body < body_len {
+                    body += stream.read(&mut buf).unwrap();
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+        (addr, server)
+    }
+
+    fn test_client(addr: std::net::SocketAddr) -> GithubClient {
+        let inner =
+            transport::build(format!("http://{addr}/api/v3"), Some("secret".to_string())).unwrap();
+        GithubClient {
+            graphql: inner.clone(),
+            inner,
+            limiter: RateLimiter::default(),
+        }
+    }
+
+    const EMPTY_LIST: &str = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                              content-length: 2\r\nconnection: close\r\n\r\n[]";
+
+    #[tokio::test]
+    async fn next_pages_from_ghes_are_authenticated() {
+        let (addr, server) = serve(vec![EMPTY_LIST]);
+        let client = test_client(addr);
+        let mut page = Page::<serde_json::Value>::default();
+        page.next = Some(
+            format!("http://{addr}/api/v3/repositories/1/issues?page=2")
+                .parse()
+                .unwrap(),
+        );
+        client.next_page(&page).await.unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /api/v3/repositories/1/issues?page=2 "));
+        assert!(requests[0].contains("authorization: bearer secret"));
+    }
+
+    #[tokio::test]
+    async fn only_gets_are_conditional_and_every_response_updates_quota() {
+        let (addr, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\netag: \"v1\"\r\n\
+             content-length: 2\r\nconnection: close\r\n\r\n[]",
+            "HTTP/1.1 304 Not Modified\r\nx-ratelimit-resource: transport-test\r\n\
+             x-ratelimit-limit: 10\r\nx-ratelimit-remaining: 0\r\nx-ratelimit-reset: 0\r\n\
+             connection: close\r\n\r\n",
+            EMPTY_LIST,
+        ]);
+        let client = test_client(addr);
+        let route = "/repos/o/r/issues/1";
+        for _ in 0..2 {
+            let items: Vec<u64> = client.get(route, None::<&()>).await.unwrap();
+            assert!(items.is_empty());
+        }
+        let _: Vec<u64> = client
+            .patch(route, Some(&serde_json::json!({ "title": "t" })))
+            .await
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+        assert!(requests[2].starts_with("patch "));
+        assert!(!requests[2].contains("if-none-match"));
+        let quota = rate_limit::lowest_quota().unwrap();
+        assert_eq!(
+            (quota.resource.as_str(), quota.remaining),
+            ("transport-test", 0)
+        );
+    }
 }
@@ -0,0 +1,273 @@
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use http::HeaderMap;
+use tracing::warn;
+
+use crate::errors::AppError;
+
+/// Longest pause a request will sit out before failing with
+/// [`AppError::RateLimited`] instead.
+const MAX_WAIT: Duration = Duration::from_secs(30);
+/// GitHub asks clients to wait at least a minute after a secondary limit.
+const SECONDARY_BACKOFF: Duration = Duration::from_secs(60);
+const MAX_SECONDARY_BACKOFF: Duration = Duration::from_secs(15 * 60);
+
+/// Last known quota per API resource (`core`, `search`, `graphql`, ...).
+static QUOTAS: Mutex<BTreeMap<String, Quota>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quota {
+    pub resource: String,
+    pub limit: u64,
+    pub remaining: u64,
+    /// Seconds since the unix epoch at which the quota refills.
+    pub reset: u64,
+}
+
+impl Quota {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let get = |name: &str| headers.get(name)?.to_str().ok();
+        Some(Self {
+            resource: get("x-ratelimit-resource").unwrap_or("core").to_string(),
+            limit: get("x-ratelimit-limit")?.parse().ok()?,
+            remaining: get("x-ratelimit-remaining")?.parse().ok()?,
+            reset: get("x-ratelimit-reset")?.parse().ok()?,
+        })
+    }
+
+    /// Time until the quota refills, if it is exhausted.
+    fn exhausted_for(&self) -> Option<Duration> {
+        (self.remaining == 0).then(|| Duration::from_secs(self.reset.saturating_sub(now_secs())))
+    }
+}
+
+/// The quota closest to running out, for the status bar.
+pub fn lowest_quota() -> Option<Quota> {
+    let quotas = QUOTAS.lock().unwrap_or_else(|err| err.into_inner());
+    quotas
+        .values()
+        .filter(|quota| quota.limit > 0)
+        .min_by_key(|quota| quota.remaining * 1000 / quota.limit)
+        .cloned()
+}
+
+fn record(quota: Quota) {
+    let mut quotas = QUOTAS.lock().unwrap_or_else(|err| err.into_inner());
+    quotas.insert(quota.resource.clone(), quota);
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// API resource with a primary quota of its own. Running out of one does not
+/// hold back requests to the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Resource {
+    Core,
+    Search,
+    Graphql,
+}
+
+impl Resource {
+    /// Name used by the `X-RateLimit-Resource` header.
+    fn name(self) -> &'static str {
+        match self {
+            Resource::Core => "core",
+            Resource::Search => "search",
+            Resource::Graphql => "graphql",
+        }
+    }
+
+    /// The REST resource a request to `path` counts against.
+    pub(crate) fn of_path(path: &str) -> Self {
+        if path.contains("/search/") {
+            Resource::Search
+        } else {
+            Resource::Core
+        }
+    }
+}
+
+/// Which limit a failed request ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Limit {
+    Primary,
+    Secondary,
+}
+
+impl Limit {
+    pub(crate) fn of(err: &octocrab::Error) -> Option<Self> {
+        let octocrab::Error::GitHub { source, .. } = err else {
+            return None;
+        };
+        let status = source.status_code.as_u16();
+        let message = source.message.to_lowercase();
+        if message.contains("secondary rate limit") {
+            Some(Limit::Secondary)
+        } else if status == 429 || (status == 403 && message.contains("rate limit")) {
+            Some(Limit::Primary)
+        } else {
+            None
+        }
+    }
+}
+
+/// Pauses requests after GitHub reports a rate limit.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    /// Resources whose primary quota ran out, until it refills.
+    blocked: BTreeMap<Resource, Instant>,
+    /// Secondary limits apply to every request.
+    secondary_until: Option<Instant>,
+    secondary_backoff: Option<Duration>,
+}
+
+impl LimiterState {
+    fn blocked_until(&self, resource: Resource) -> Option<Instant> {
+        self.blocked
+            .get(&resource)
+            .copied()
+            .max(self.secondary_until)
+    }
+}
+
+impl RateLimiter {
+    fn state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Waits out a short block of `resource`, or fails right away if it is a
+    /// long one.
+    pub(crate) async fn wait(&self, resource: Resource) -> Result<(), AppError> {
+        let Some(until) = self.state().blocked_until(resource) else {
+            return Ok(());
+        };
+        let remaining = until.saturating_duration_since(Instant::now());
+        if remaining > MAX_WAIT {
+            return Err(AppError::RateLimited(remaining.as_secs()));
+        }
+        tokio::time::sleep(remaining).await;
+        Ok(())
+    }
+
+    pub(crate) fn succeeded(&self) {
+        let mut state = self.state();
+        let now = Instant::now();
+        state.secondary_backoff = None;
+        state.blocked.retain(|_, until| *until > now);
+        if state.secondary_until.is_some_and(|until| until <= now) {
+            state.secondary_until = None;
+        }
+    }
+
+    /// Blocks further requests after a request to `resource` hit `limit`: a
+    /// primary limit blocks that resource only, a secondary one everything.
+    /// `quotas` is a fresh reading of the primary limits, if one could be
+    /// fetched.
+    pub(crate) fn limited(&self, limit: Limit, resource: Resource, quotas: Vec<Quota>) {
+        quotas.into_iter().for_each(record);
+        let mut state = self.state();
+        let delay = match limit {
+            Limit::Primary => QUOTAS
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .get(resource.name())
+                .and_then(Quota::exhausted_for)
+                .unwrap_or(SECONDARY_BACKOFF),
+            Limit::Secondary => {
+                let delay = state
+                    .secondary_backoff
+                    .map_or(SECONDARY_BACKOFF, |prev| prev * 2)
+                    .min(MAX_SECONDARY_BACKOFF);
+                state.secondary_backoff = Some(delay);
+                delay
+            }
+        };
+        warn!(
+            ?limit,
+            resource = resource.name(),
+            delay_secs = delay.as_secs(),
+            "rate limited by GitHub"
+        );
+        let until = Instant::now() + delay;
+        match limit {
+            Limit::Primary => {
+                state.blocked.insert(resource, until);
+            }
+            Limit::Secondary => state.secondary_until = Some(until),
+        }
+    }
+}
+
+/// Records the quota reported in a response's `X-RateLimit-*` headers.
+pub(crate) fn record_headers(headers: &HeaderMap) {
+    if let Some(quota) = Quota::from_headers(headers) {
+        record(quota);
+    }
+}
+
+pub(crate) fn quotas_from(limits: octocrab::models::RateLimit) -> Vec<Quota> {
+    let resources = limits.resources;
+    [
+        ("core", Some(resources.core)),
+        ("search", Some(resources.search)),
+        ("graphql", resources.graphql),
+    ]
+    .into_iter()
+    .filter_map(|(resource, rate)| {
+        let rate = rate?;
+        Some(Quota {
+            resource: resource.to_string(),
+            limit: rate.limit as u64,
+            remaining: rate.remaining as u64,
+            reset: rate.reset,
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secondary_backoff_doubles_until_capped() {
+        let limiter = RateLimiter::default();
+        let mut delays = Vec::new();
+        for _ in 0..6 {
+            limiter.limited(Limit::Secondary, Resource::Core, Vec::new());
+            delays.push(limiter.state().secondary_backoff.unwrap().as_secs());
+        }
+        assert_eq!(delays, vec![60, 120, 240, 480, 900, 900]);
+
+        limiter.succeeded();
+        assert_eq!(limiter.state().secondary_backoff, None);
+    }
+
+    #[tokio::test]
+    async fn an_exhausted_resource_only_blocks_itself() {
+        let limiter = RateLimiter::default();
+        // Without a quota reading the block lasts a minute, longer than a
+        // request waits.
+        limiter.limited(Limit::Primary, Resource::Search, Vec::new());
+
+        assert!(matches!(
+            limiter.wait(Resource::Search).await,
+            Err(AppError::RateLimited(_))
+        ));
+        assert!(limiter.wait(Resource::Core).await.is_ok());
+        assert!(limiter.wait(Resource::Graphql).await.is_ok());
+    }
+}
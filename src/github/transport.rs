@@ -0,0 +1,241 @@
+//! The HTTP stack under [`GithubClient`](super::GithubClient): octocrab's
+//! own client, with conditional requests for GETs and quota tracking for
+//! every response.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, ready},
+};
+
+use bytes::Bytes;
+use futures::future::{BoxFuture, Either};
+use http::{HeaderMap, Method, Request, Response, Uri};
+use http_body_util::{BodyExt, combinators::BoxBody};
+use octocrab::{
+    AuthState, Octocrab, OctocrabBuilder,
+    service::middleware::cache::{
+        CacheKey, CacheStorage, CacheWriter, CachedResponse, HttpCache, HttpCacheFuture,
+        HttpCacheLayer,
+    },
+};
+use tower::{BoxError, Layer, Service};
+
+use crate::{errors::AppError, github::rate_limit};
+
+type ResBody = BoxBody<Bytes, octocrab::Error>;
+
+/// Responses kept for conditional requests.
+const CACHED_RESPONSES: usize = 512;
+
+/// Builds a client for the API at `base_uri`.
+pub(crate) fn build(base_uri: String, token: Option<String>) -> Result<Octocrab, AppError> {
+    let mut builder = Octocrab::builder().base_uri(base_uri)?;
+    if let Some(token) = token {
+        builder = builder.personal_token(token);
+    }
+    let transport = Transport(builder.build()?);
+    let service = ConditionalGets {
+        cached: HttpCacheLayer::new(Some(Arc::new(BoundedCache::new(CACHED_RESPONSES))))
+            .layer(transport.clone()),
+        plain: transport,
+    };
+    match OctocrabBuilder::new_empty()
+        .with_service(service)
+        .with_auth(AuthState::None)
+        .build()
+    {
+        Ok(client) => Ok(client),
+        Err(never) => match never {},
+    }
+}
+
+/// Sends requests through an octocrab client built the usual way, and reads
+/// the quota headers of every response, including errors and `304`s.
+#[derive(Clone)]
+struct Transport(Octocrab);
+
+impl<B> Service<Request<B>> for Transport
+where
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response<ResBody>;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let client = self.0.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = body.collect().await.map_err(Into::into)?.to_bytes();
+            let response = client.execute(Request::from_parts(parts, body)).await?;
+            rate_limit::record_headers(response.headers());
+            Ok(response)
+        })
+    }
+}
+
+/// Answers repeated GETs from memory on `304 Not Modified`. Other methods
+/// bypass the cache, so writes are never sent as conditional requests.
+struct ConditionalGets<S> {
+    cached: HttpCache<S>,
+    plain: S,
+}
+
+impl<S, B> Service<Request<B>> for ConditionalGets<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Either<HttpCacheFuture<S::Future>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.cached.poll_ready(cx))?;
+        self.plain.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        if request.method() == Method::GET {
+            Either::Left(self.cached.call(request))
+        } else {
+            Either::Right(self.plain.call(request))
+        }
+    }
+}
+
+/// In-memory responses to earlier GETs. Unlike octocrab's own memory cache it
+/// holds at most `capacity` responses, forgetting the least recently used.
+struct BoundedCache {
+    capacity: usize,
+    entries: Arc<Mutex<CacheEntries>>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    responses: HashMap<Uri, CacheEntry>,
+    /// Bumped on every use, to tell which entry was used least recently.
+    clock: u64,
+}
+
+struct CacheEntry {
+    key: CacheKey,
+    response: CachedResponse,
+    used: u64,
+}
+
+impl BoundedCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Arc::default(),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, CacheEntries> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl CacheStorage for BoundedCache {
+    fn try_hit(&self, uri: &Uri) -> Option<CacheKey> {
+        let mut entries = self.entries();
+        entries.clock += 1;
+        let clock = entries.clock;
+        // A hit is followed by a `load` once the `304` arrives, so it counts
+        // as a use: the entry is the last to be forgotten in the meantime.
+        let entry = entries.responses.get_mut(uri)?;
+        entry.used = clock;
+        Some(entry.key.clone())
+    }
+
+    fn load(&self, uri: &Uri) -> Option<CachedResponse> {
+        let entries = self.entries();
+        Some(entries.responses.get(uri)?.response.clone())
+    }
+
+    fn writer(&self, uri: &Uri, key: CacheKey, headers: HeaderMap) -> Box<dyn CacheWriter> {
+        Box::new(BoundedWriter {
+            capacity: self.capacity,
+            entries: self.entries.clone(),
+            uri: uri.clone(),
+            key: Some(key),
+            response: CachedResponse {
+                body: Vec::new(),
+                headers,
+            },
+        })
+    }
+}
+
+struct BoundedWriter {
+    capacity: usize,
+    entries: Arc<Mutex<CacheEntries>>,
+    uri: Uri,
+    key: Option<CacheKey>,
+    response: CachedResponse,
+}
+
+impl CacheWriter for BoundedWriter {
+    fn write_body(&mut self, data: &[u8]) {
+        self.response.body.extend_from_slice(data);
+    }
+}
+
+impl Drop for BoundedWriter {
+    /// The writer is dropped once the whole body was received.
+    fn drop(&mut self) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.clock += 1;
+        let used = entries.clock;
+        if !entries.responses.contains_key(&self.uri)
+            && entries.responses.len() >= self.capacity
+            && let Some(oldest) = entries
+                .responses
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(uri, _)| uri.clone())
+        {
+            entries.responses.remove(&oldest);
+        }
+        let entry = CacheEntry {
+            key,
+            response: std::mem::take(&mut self.response),
+            used,
+        };
+        entries.responses.insert(self.uri.clone(), entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(cache: &BoundedCache, path: &str) {
+        let uri: Uri = path.parse().unwrap();
+        let mut writer = cache.writer(&uri, CacheKey::ETag(path.to_string()), HeaderMap::new());
+        writer.write_body(path.as_bytes());
+    }
+
+    #[test]
+    fn the_least_recently_used_response_is_forgotten() {
+        let cache = BoundedCache::new(2);
+        store(&cache, "/a");
+        store(&cache, "/b");
+        assert!(cache.try_hit(&Uri::from_static("/a")).is_some());
+        store(&cache, "/c");
+
+        assert_eq!(cache.entries().responses.len(), 2);
+        assert!(cache.load(&Uri::from_static("/a")).is_some());
+        assert!(cache.load(&Uri::from_static("/b")).is_none());
+        assert_eq!(cache.load(&Uri::from_static("/c")).unwrap().body, b"/c");
+    }
+}
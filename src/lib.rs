@@ -1,9 +1,14 @@
 pub mod app;
 pub mod auth;
+pub mod backend;
 pub mod bookmarks;
+pub mod drafts;
 pub mod errors;
 pub mod github;
 pub mod logging;
+pub mod outbox;
+pub mod searches;
+pub mod store;
 pub mod ui;
 
 pub mod prelude;
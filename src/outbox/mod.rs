@@ -0,0 +1,563 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use octocrab::models::{
+    IssueState, Label,
+    issues::{Comment, Issue, IssueStateReason},
+    reactions::{Reaction, ReactionContent},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Notify, mpsc::Sender};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{backend::Backend, errors::AppError, github::GithubHost, store};
+
+/// Number of operations queued for the current repository, shown in the status bar.
+pub static PENDING_OPS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+const BASE_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+/// How long an instance has to send an operation it claimed. Claims that
+/// lapse, say because the instance was killed, are picked up by others.
+const CLAIM_LEASE: Duration = Duration::from_secs(2 * 60);
+
+/// What a replayed operation got back, for the UI to show.
+#[derive(Debug, Clone)]
+pub enum Replayed {
+    Comment {
+        number: u64,
+        comment: Box<Comment>,
+    },
+    /// The reactions on a comment after adding or removing one.
+    Reactions {
+        comment_id: u64,
+        reactions: Vec<Reaction>,
+        login: String,
+    },
+    Labels {
+        number: u64,
+        labels: Vec<Label>,
+    },
+    Closed(Box<Issue>),
+}
+
+/// Progress of the replay loop started by [`Outbox::spawn_replay`].
+#[derive(Debug, Clone)]
+pub enum OutboxEvent {
+    Sent {
+        op: PendingOp,
+        result: Replayed,
+    },
+    /// An operation failed again; its error is recorded on the entry.
+    Failed,
+}
+
+/// A mutation that failed with a transient error and is waiting to be replayed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingOp {
+    Comment {
+        number: u64,
+        body: String,
+    },
+    AddReaction {
+        comment_id: u64,
+        content: ReactionContent,
+        login: String,
+    },
+    RemoveReaction {
+        comment_id: u64,
+        content: ReactionContent,
+        login: String,
+    },
+    AddLabels {
+        number: u64,
+        labels: Vec<String>,
+    },
+    RemoveLabel {
+        number: u64,
+        name: String,
+    },
+    Close {
+        number: u64,
+        reason: IssueStateReason,
+    },
+}
+
+impl PendingOp {
+    pub fn describe(&self) -> String {
+        match self {
+            PendingOp::Comment { number, body } => {
+                let first_line = body.lines().next().unwrap_or_default();
+                format!("Comment on #{number}: {first_line}")
+            }
+            PendingOp::AddReaction {
+                comment_id,
+                content,
+                ..
+            } => format!("Add {content:?} reaction to comment {comment_id}"),
+            PendingOp::RemoveReaction {
+                comment_id,
+                content,
+                ..
+            } => format!("Remove {content:?} reaction from comment {comment_id}"),
+            PendingOp::AddLabels { number, labels } => {
+                format!("Add label(s) {} to #{number}", labels.join(", "))
+            }
+            PendingOp::RemoveLabel { number, name } => {
+                format!("Remove label {name} from #{number}")
+            }
+            PendingOp::Close { number, reason } => format!("Close #{number} as {reason:?}"),
+        }
+    }
+
+    /// Performs the operation.
+    async fn replay(&self, backend: &Backend) -> Result<Replayed, AppError> {
+        match self {
+            PendingOp::Comment { number, body } => {
+                let comment = backend.create_comment(*number, body.clone()).await?;
+                Ok(Replayed::Comment {
+                    number: *number,
+                    comment: Box::new(comment),
+                })
+            }
+            PendingOp::AddReaction {
+                comment_id,
+                content,
+                login,
+            } => {
+                backend
+                    .add_comment_reaction(*comment_id, content.clone())
+                    .await?;
+                Self::reload_reactions(backend, *comment_id, login).await
+            }
+            PendingOp::RemoveReaction {
+                comment_id,
+                content,
+                login,
+            } => {
+                let reactions = backend.list_comment_reactions(*comment_id).await?;
+                let own = reactions.iter().find(|reaction| {
+                    reaction.content == *content && reaction.user.login.eq_ignore_ascii_case(login)
+                });
+                if let Some(reaction) = own {
+                    backend
+                        .delete_comment_reaction(*comment_id, reaction.id.0, content.clone())
+                        .await?;
+                }
+                Self::reload_reactions(backend, *comment_id, login).await
+            }
+            PendingOp::AddLabels { number, labels } => {
+                let labels = backend.add_labels(*number, labels).await?;
+                Ok(Replayed::Labels {
+                    number: *number,
+                    labels,
+                })
+            }
+            PendingOp::RemoveLabel { number, name } => {
+                let labels = backend.remove_label(*number, name).await?;
+                Ok(Replayed::Labels {
+                    number: *number,
+                    labels,
+                })
+            }
+            PendingOp::Close { number, reason } => {
+                let issue = backend
+                    .set_issue_state(*number, IssueState::Closed, Some(reason.clone()))
+                    .await?;
+                Ok(Replayed::Closed(Box::new(issue)))
+            }
+        }
+    }
+
+    async fn reload_reactions(
+        backend: &Backend,
+        comment_id: u64,
+        login: &str,
+    ) -> Result<Replayed, AppError> {
+        let reactions = backend.list_comment_reactions(comment_id).await?;
+        Ok(Replayed::Reactions {
+            comment_id,
+            reactions,
+            login: login.to_string(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PendingEntry {
+    pub id: u64,
+    pub op: PendingOp,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Seconds since the unix epoch.
+    pub queued_at: u64,
+    /// Set once the operation failed with a non-transient error; it is kept so
+    /// the user can inspect it, but never retried.
+    #[serde(default)]
+    pub gave_up: bool,
+    /// Set while an instance is sending the operation, so others leave it be.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    claim: Option<Claim>,
+    #[serde(skip)]
+    next_attempt: Option<Instant>,
+}
+
+impl PendingEntry {
+    fn is_due(&self, now: Instant, unix_now: u64) -> bool {
+        !self.gave_up
+            && self.claim_lapses_in(unix_now).is_none()
+            && self.next_attempt.is_none_or(|at| at <= now)
+    }
+
+    /// Whether an instance is sending the operation right now.
+    pub fn is_sending(&self) -> bool {
+        self.claim_lapses_in(unix_now()).is_some()
+    }
+
+    fn claim_lapses_in(&self, unix_now: u64) -> Option<Duration> {
+        self.claim
+            .as_ref()
+            .filter(|claim| claim.until > unix_now)
+            .map(|claim| Duration::from_secs(claim.until - unix_now))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Claim {
+    /// Process sending the operation.
+    pid: u32,
+    /// Seconds since the unix epoch.
+    until: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct OutboxFile {
+    next_id: u64,
+    entries: Vec<PendingEntry>,
+}
+
+/// Durable queue of failed mutations, stored in the data dir per repository.
+///
+/// Other gitv instances on the same repository share the file: changes are
+/// merged with theirs, and their entries show up on the next reload.
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    path: PathBuf,
+    file: Arc<Mutex<OutboxFile>>,
+    notify: Arc<Notify>,
+}
+
+impl Outbox {
+    pub fn open(host: &GithubHost, owner: &str, repo: &str) -> Self {
+        Self::at(store::repo_file("outbox", host, owner, repo))
+    }
+
+    fn at(path: PathBuf) -> Self {
+        let file: OutboxFile = store::read(&path);
+        update_count(&file);
+        Self {
+            path,
+            file: Arc::new(Mutex::new(file)),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    fn file(&self) -> MutexGuard<'_, OutboxFile> {
+        self.file.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Applies `change` to the outbox on disk.
+    fn update<R>(&self, change: impl FnOnce(&mut OutboxFile) -> R) -> R {
+        let mut file = self.file();
+        let known = retry_times(&file);
+        let result = store::update(&self.path, &mut *file, |file| {
+            restore_retry_times(file, &known);
+            change(file)
+        });
+        update_count(&file);
+        result
+    }
+
+    /// Picks up changes made by other instances.
+    fn reload(&self) {
+        let mut file = self.file();
+        let known = retry_times(&file);
+        store::reload(&self.path, &mut *file);
+        restore_retry_times(&mut file, &known);
+        update_count(&file);
+    }
+
+    /// Queues `op` for replay if `err` is transient. Returns whether it was queued.
+    pub fn enqueue_if_retryable(&self, op: PendingOp, err: &AppError) -> bool {
+        if !err.is_retryable() {
+            return false;
+        }
+        let queued_at = unix_now();
+        let id = self.update(|file| {
+            let id = file.next_id;
+            file.next_id += 1;
+            file.entries.push(PendingEntry {
+                id,
+                op,
+                attempts: 1,
+                last_error: Some(err.to_string().replace('\n', " ")),
+                queued_at,
+                gave_up: false,
+                claim: None,
+                next_attempt: Some(Instant::now() + backoff(1)),
+            });
+            id
+        });
+        info!(id, "queued operation for retry");
+        self.notify.notify_one();
+        true
+    }
+
+    pub fn pending(&self) -> Vec<PendingEntry> {
+        self.reload();
+        self.file().entries.clone()
+    }
+
+    pub fn remove(&self, id: u64) {
+        self.update(|file| file.entries.retain(|e| e.id != id));
+    }
+
+    /// Makes an entry due immediately, including ones that were given up on.
+    pub fn retry_now(&self, id: u64) {
+        self.update(|file| {
+            if let Some(entry) = file.entries.iter_mut().find(|e| e.id == id) {
+                entry.gave_up = false;
+            }
+        });
+        // Set after the update, which would otherwise restore the old time.
+        if let Some(entry) = self.file().entries.iter_mut().find(|e| e.id == id) {
+            entry.next_attempt = None;
+        }
+        self.notify.notify_one();
+    }
+
+    /// Claims the next due entry, so no other instance sends it as well.
+    /// Otherwise returns when the next entry becomes due, if any.
+    fn claim_next_due(&self) -> Result<PendingEntry, Option<Instant>> {
+        let now = Instant::now();
+        let unix_now = unix_now();
+        self.update(|file| {
+            if let Some(entry) = file.entries.iter_mut().find(|e| e.is_due(now, unix_now)) {
+                if let Some(claim) = &entry.claim {
+                    info!(
+                        id = entry.id,
+                        pid = claim.pid,
+                        "taking over lapsed operation"
+                    );
+                }
+                entry.claim = Some(Claim {
+                    pid: std::process::id(),
+                    until: unix_now + CLAIM_LEASE.as_secs(),
+                });
+                return Ok(entry.clone());
+            }
+            Err(file
+                .entries
+                .iter()
+                .filter(|e| !e.gave_up)
+                .filter_map(|e| {
+                    let lapse = e.claim_lapses_in(unix_now).map(|lapse| now + lapse);
+                    e.next_attempt.max(lapse)
+                })
+                .min())
+        })
+    }
+
+    fn record_failure(&self, id: u64, err: &AppError) {
+        self.update(|file| {
+            if let Some(entry) = file.entries.iter_mut().find(|e| e.id == id) {
+                entry.attempts = entry.attempts.saturating_add(1);
+                entry.last_error = Some(err.to_string().replace('\n', " "));
+                entry.gave_up = !err.is_retryable();
+                entry.claim = None;
+            }
+        });
+        if let Some(entry) = self.file().entries.iter_mut().find(|e| e.id == id) {
+            entry.next_attempt = Some(Instant::now() + backoff(entry.attempts));
+        }
+    }
+
+    /// Replays due operations until `cancel` fires, reporting each result on
+    /// `events`. Entries left over from a previous session are due
+    /// immediately, unless another instance claimed them first.
+    pub fn spawn_replay(
+        &self,
+        backend: Backend,
+        events: Sender<OutboxEvent>,
+        cancel: CancellationToken,
+    ) {
+        let outbox = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let entry = match outbox.claim_next_due() {
+                    Ok(entry) => entry,
+                    Err(next) => {
+                        let sleep = next.map_or(MAX_BACKOFF, |at| {
+                            at.saturating_duration_since(Instant::now())
+                        });
+                        tokio::select! {
+                            _ = cancel.cancelled() => break,
+                            _ = outbox.notify.notified() => {}
+                            _ = tokio::time::sleep(sleep) => {}
+                        }
+                        continue;
+                    }
+                };
+                let event = match entry.op.replay(&backend).await {
+                    Ok(result) => {
+                        outbox.remove(entry.id);
+                        OutboxEvent::Sent {
+                            op: entry.op,
+                            result,
+                        }
+                    }
+                    Err(err) => {
+                        warn!(id = entry.id, error = %err, "queued operation failed again");
+                        outbox.record_failure(entry.id, &err);
+                        OutboxEvent::Failed
+                    }
+                };
+                let _ = events.send(event).await;
+            }
+        });
+    }
+}
+
+fn backoff(attempts: u32) -> Duration {
+    BASE_BACKOFF
+        .saturating_mul(2_u32.saturating_pow(attempts.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn update_count(file: &OutboxFile) {
+    PENDING_OPS_COUNT.store(file.entries.len(), Ordering::Relaxed);
+}
+
+/// Retry times only live in memory.
+fn retry_times(file: &OutboxFile) -> HashMap<u64, Option<Instant>> {
+    file.entries
+        .iter()
+        .map(|entry| (entry.id, entry.next_attempt))
+        .collect()
+}
+
+/// Puts back the retry times of a reloaded file. Entries another instance
+/// queued wait out their backoff, giving that instance the chance to send
+/// them first.
+fn restore_retry_times(file: &mut OutboxFile, known: &HashMap<u64, Option<Instant>>) {
+    for entry in &mut file.entries {
+        entry.next_attempt = match known.get(&entry.id) {
+            Some(at) => *at,
+            None => Some(Instant::now() + backoff(entry.attempts)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::offline::OfflineBackend, store::tests::temp_file};
+
+    fn remove_label(number: u64) -> PendingOp {
+        PendingOp::RemoveLabel {
+            number,
+            name: "bug".to_string(),
+        }
+    }
+
+    #[test]
+    fn only_transient_failures_are_queued_and_they_survive_a_restart() {
+        let path = temp_file("outbox.json");
+        let outbox = Outbox::at(path.clone());
+        assert!(outbox.enqueue_if_retryable(remove_label(1), &AppError::RateLimited(5)));
+        assert!(!outbox.enqueue_if_retryable(
+            remove_label(2),
+            &AppError::Offline("cannot remove labels in offline mode".to_string())
+        ));
+
+        let reopened = Outbox::at(path);
+        let pending = reopened.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].op, remove_label(1));
+        // Left over from the previous session, so it is sent right away.
+        assert_eq!(
+            reopened.claim_next_due().map(|entry| entry.id),
+            Ok(pending[0].id)
+        );
+    }
+
+    #[test]
+    fn instances_on_the_same_repo_keep_each_others_operations() {
+        let path = temp_file("outbox.json");
+        let first = Outbox::at(path.clone());
+        let second = Outbox::at(path);
+        first.enqueue_if_retryable(remove_label(1), &AppError::RateLimited(5));
+        second.enqueue_if_retryable(remove_label(2), &AppError::RateLimited(5));
+        first.remove(first.pending()[0].id);
+
+        let ops = second
+            .pending()
+            .into_iter()
+            .map(|entry| entry.op)
+            .collect::<Vec<_>>();
+        assert_eq!(ops, vec![remove_label(2)]);
+        // Queued by the other instance, so it waits for that one to send it.
+        assert!(first.claim_next_due().is_err());
+    }
+
+    #[test]
+    fn an_operation_is_sent_by_one_instance_only() {
+        let path = temp_file("outbox.json");
+        Outbox::at(path.clone()).enqueue_if_retryable(remove_label(1), &AppError::RateLimited(5));
+        // Both start after the operation was queued, so it is due for both.
+        let first = Outbox::at(path.clone());
+        let second = Outbox::at(path);
+        let claimed = first.claim_next_due().unwrap();
+        assert!(second.claim_next_due().is_err());
+        assert!(second.pending()[0].is_sending());
+
+        first.record_failure(claimed.id, &AppError::RateLimited(5));
+        assert!(!second.pending()[0].is_sending());
+    }
+
+    #[tokio::test]
+    async fn replay_gives_up_on_permanent_failures_until_retried() {
+        let path = temp_file("outbox.json");
+        Outbox::at(path.clone()).enqueue_if_retryable(remove_label(1), &AppError::RateLimited(5));
+        let outbox = Outbox::at(path.clone());
+        let backend: Backend = Arc::new(OfflineBackend::new(&path.with_extension("cache")));
+        let (events_tx, mut events) = tokio::sync::mpsc::channel(1);
+        let cancel = CancellationToken::new();
+        outbox.spawn_replay(backend, events_tx, cancel.clone());
+
+        assert!(matches!(events.recv().await, Some(OutboxEvent::Failed)));
+        cancel.cancel();
+        let entry = &outbox.pending()[0];
+        assert!(entry.gave_up);
+        assert_eq!(entry.attempts, 2);
+        assert!(outbox.claim_next_due().is_err());
+
+        outbox.retry_now(entry.id);
+        assert_eq!(outbox.claim_next_due().map(|due| due.id), Ok(entry.id));
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{backend::query::ItemKind, github::GithubHost, store};
+
+/// Number of recent queries kept per repository.
+const HISTORY_LEN: usize = 50;
+
+/// The issue states a search is limited to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StateFilter {
+    #[default]
+    Open,
+    Closed,
+    All,
+}
+
+/// A named search, with everything the search bar was set to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    /// The Search Labels field, with labels separated by `;`.
+    #[serde(default)]
+    pub labels: String,
+    #[serde(default)]
+    pub state: StateFilter,
+    #[serde(default)]
+    pub kind: ItemKind,
+    /// Number key (1-9) that runs the search with Alt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct RepoSearches {
+    #[serde(default)]
+    saved: Vec<SavedSearch>,
+    /// Recent queries, most recent first.
+    #[serde(default)]
+    history: Vec<String>,
+    /// Name of the search the app starts with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    startup: Option<String>,
+}
+
+impl RepoSearches {
+    fn position(&self, name: &str) -> Option<usize> {
+        self.saved
+            .iter()
+            .position(|search| search.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Adds `search`, replacing a saved search with the same name but keeping
+    /// its number key.
+    fn save(&mut self, mut search: SavedSearch) {
+        match self.position(&search.name) {
+            Some(idx) => {
+                search.key = search.key.or(self.saved[idx].key);
+                self.saved[idx] = search;
+            }
+            None => self.saved.push(search),
+        }
+    }
+
+    fn remove(&mut self, name: &str) {
+        if let Some(idx) = self.position(name) {
+            self.saved.remove(idx);
+        }
+        if self
+            .startup
+            .as_deref()
+            .is_some_and(|startup| startup.eq_ignore_ascii_case(name))
+        {
+            self.startup = None;
+        }
+    }
+
+    /// Binds `key` to the search named `name`, taking it from any other
+    /// search, or unbinds it if the search already had it.
+    fn toggle_key(&mut self, name: &str, key: u8) {
+        let Some(idx) = self.position(name) else {
+            return;
+        };
+        if self.saved[idx].key == Some(key) {
+            self.saved[idx].key = None;
+            return;
+        }
+        for search in &mut self.saved {
+            if search.key == Some(key) {
+                search.key = None;
+            }
+        }
+        self.saved[idx].key = Some(key);
+    }
+
+    fn record(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.history.retain(|recent| recent != query);
+        self.history.insert(0, query.to_string());
+        self.history.truncate(HISTORY_LEN);
+    }
+}
+
+/// Saved searches and recent queries, stored in the data dir per repository.
+/// Changes are written right away, merged with those of other instances.
+#[derive(Debug, Clone)]
+pub struct SavedSearches {
+    path: PathBuf,
+    file: Arc<Mutex<RepoSearches>>,
+}
+
+impl SavedSearches {
+    pub fn open(host: &GithubHost, owner: &str, repo: &str) -> Self {
+        Self::at(store::repo_file("searches", host, owner, repo))
+    }
+
+    fn at(path: PathBuf) -> Self {
+        Self {
+            file: Arc::new(Mutex::new(store::read(&path))),
+            path,
+        }
+    }
+
+    fn file(&self) -> MutexGuard<'_, RepoSearches> {
+        self.file.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&RepoSearches) -> T) -> T {
+        f(&self.file())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut RepoSearches)) {
+        store::update(&self.path, &mut *self.file(), f);
+    }
+
+    /// The saved searches of this repository, in the order they were saved.
+    pub fn list(&self) -> Vec<SavedSearch> {
+        self.read(|searches| searches.saved.clone())
+    }
+
+    pub fn save(&self, search: SavedSearch) {
+        self.update(|searches| searches.save(search));
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.update(|searches| searches.remove(name));
+    }
+
+    pub fn toggle_key(&self, name: &str, key: u8) {
+        self.update(|searches| searches.toggle_key(name, key));
+    }
+
+    pub fn by_key(&self, key: u8) -> Option<SavedSearch> {
+        self.read(|searches| {
+            searches
+                .saved
+                .iter()
+                .find(|search| search.key == Some(key))
+                .cloned()
+        })
+    }
+
+    /// The search the app starts with, if one is set.
+    pub fn startup(&self) -> Option<SavedSearch> {
+        self.read(|searches| {
+            let name = searches.startup.as_deref()?;
+            searches
+                .position(name)
+                .map(|idx| searches.saved[idx].clone())
+        })
+    }
+
+    /// Makes the app start with the search named `name`, or with the default
+    /// search if `name` is `None`.
+    pub fn set_startup(&self, name: Option<&str>) {
+        self.update(|searches| searches.startup = name.map(str::to_string));
+    }
+
+    /// Recent queries, most recent first.
+    pub fn history(&self) -> Vec<String> {
+        self.read(|searches| searches.history.clone())
+    }
+
+    /// Adds `query` to the top of the history.
+    pub fn record(&self, query: &str) {
+        if self.read(|searches| searches.history.first().map(String::as_str) == Some(query.trim()))
+        {
+            return;
+        }
+        self.update(|searches| searches.record(query));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::tests::temp_file;
+
+    fn search(name: &str) -> SavedSearch {
+        SavedSearch {
+            name: name.to_string(),
+            query: "no:label".to_string(),
+            labels: String::new(),
+            state: StateFilter::Open,
+            kind: ItemKind::Issues,
+            key: None,
+        }
+    }
+
+    #[test]
+    fn saving_under_an_existing_name_replaces_the_search() {
+        let mut searches = RepoSearches::default();
+        searches.save(search("needs-triage"));
+        searches.toggle_key("needs-triage", 1);
+        let mut changed = search("Needs-Triage");
+        changed.query = "no:label no:assignee".to_string();
+        searches.save(changed);
+        assert_eq!(searches.saved.len(), 1);
+        assert_eq!(searches.saved[0].query, "no:label no:assignee");
+        assert_eq!(searches.saved[0].key, Some(1));
+    }
+
+    #[test]
+    fn number_keys_belong_to_one_search() {
+        let mut searches = RepoSearches::default();
+        searches.save(search("a"));
+        searches.save(search("b"));
+        searches.toggle_key("a", 2);
+        searches.toggle_key("b", 2);
+        assert_eq!(searches.saved[0].key, None);
+        assert_eq!(searches.saved[1].key, Some(2));
+        searches.toggle_key("b", 2);
+        assert_eq!(searches.saved[1].key, None);
+    }
+
+    #[test]
+    fn history_keeps_recent_queries_once() {
+        let mut searches = RepoSearches::default();
+        for query in ["crash", "is:closed", " crash ", ""] {
+            searches.record(query);
+        }
+        assert_eq!(searches.history, vec!["crash", "is:closed"]);
+        for idx in 0..HISTORY_LEN + 5 {
+            searches.record(&format!("query {idx}"));
+        }
+        assert_eq!(searches.history.len(), HISTORY_LEN);
+        assert_eq!(searches.history[0], format!("query {}", HISTORY_LEN + 4));
+    }
+
+    #[test]
+    fn instances_on_the_same_repo_keep_each_others_searches() {
+        let path = temp_file("searches.json");
+        let first = SavedSearches::at(path.clone());
+        let second = SavedSearches::at(path.clone());
+        first.save(search("needs-triage"));
+        first.record("crash");
+        second.save(search("stale"));
+        second.record("is:closed");
+
+        let reopened = SavedSearches::at(path);
+        let names = reopened
+            .list()
+            .into_iter()
+            .map(|search| search.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["needs-triage", "stale"]);
+        assert_eq!(reopened.history(), vec!["is:closed", "crash"]);
+    }
+}
@@ -0,0 +1,137 @@
+//! Per-repository JSON files in the data dir, such as the outbox, drafts and
+//! saved searches.
+//!
+//! Several gitv instances may have the same file open, so every change is
+//! made to a fresh read of the file while holding a lock on it. An instance
+//! never writes a stale copy over another instance's changes.
+
+use std::{
+    fs::{File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use tracing::error;
+
+use crate::{github::GithubHost, logging::get_data_dir};
+
+/// File of `kind` (`outbox`, `drafts`, ...) for `owner/repo` on `host`.
+/// Repositories on github.com keep the layout they had before other hosts
+/// were supported.
+pub fn repo_file(kind: &str, host: &GithubHost, owner: &str, repo: &str) -> PathBuf {
+    let mut dir = get_data_dir().join(kind);
+    if !host.is_default() {
+        dir.push(host.name());
+    }
+    dir.join(owner).join(format!("{repo}.json"))
+}
+
+/// Reads the file at `path`, or the default value if it is missing or
+/// unreadable.
+pub fn read<T: DeserializeOwned + Default>(path: &Path) -> T {
+    read_existing(path).unwrap_or_default()
+}
+
+fn read_existing<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = std::fs::read(path).ok()?;
+    serde_json::from_slice(&contents).ok()
+}
+
+/// Replaces `current` with the file at `path`, if it can be read.
+pub fn reload<T: DeserializeOwned>(path: &Path, current: &mut T) {
+    if let Ok(_lock) = lock(path)
+        && let Some(contents) = read_existing(path)
+    {
+        *current = contents;
+    }
+}
+
+/// Re-reads `current` from `path`, applies `change` and writes the result
+/// back, all under the file's lock. If the file cannot be locked or written,
+/// the change is still made to `current` and the error is logged.
+pub fn update<T, R>(path: &Path, current: &mut T, change: impl FnOnce(&mut T) -> R) -> R
+where
+    T: Serialize + DeserializeOwned,
+{
+    let lock = lock(path);
+    if lock.is_ok()
+        && let Some(contents) = read_existing(path)
+    {
+        *current = contents;
+    }
+    let result = change(current);
+    if let Err(err) = lock.and_then(|_lock| write(path, current)) {
+        error!(path = %path.display(), error = %err, "failed to write data file");
+    }
+    result
+}
+
+/// Takes an exclusive lock on `path`, held until the returned file is dropped.
+fn lock(path: &Path) -> io::Result<File> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_extension("lock"))?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// Writes through a temporary file, so readers never see half a file.
+fn write<T: Serialize>(path: &Path, contents: &T) -> io::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(contents)?)?;
+    std::fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        collections::BTreeMap,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+
+    /// A path in a fresh temporary directory.
+    pub(crate) fn temp_file(name: &str) -> PathBuf {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "gitv-test-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join(name)
+    }
+
+    #[test]
+    fn updates_keep_changes_made_by_other_instances() {
+        let path = temp_file("store.json");
+        let mut first = read::<BTreeMap<String, u32>>(&path);
+        let mut second = read::<BTreeMap<String, u32>>(&path);
+        update(&path, &mut first, |map| map.insert("first".to_string(), 1));
+        // `second` was read before the first change was written.
+        update(&path, &mut second, |map| {
+            map.insert("second".to_string(), 2)
+        });
+        assert_eq!(second.len(), 2);
+
+        reload(&path, &mut first);
+        assert_eq!(first, second);
+        assert_eq!(read::<BTreeMap<String, u32>>(&path), second);
+    }
+
+    #[test]
+    fn repo_files_are_kept_apart_per_host() {
+        let github = repo_file("outbox", &GithubHost::default(), "acme", "tool");
+        let enterprise = repo_file("outbox", &GithubHost::new("ghe.acme.com"), "acme", "tool");
+        assert_ne!(github, enterprise);
+        assert!(github.ends_with("outbox/acme/tool.json"));
+        assert!(enterprise.ends_with("outbox/ghe.acme.com/acme/tool.json"));
+    }
+}
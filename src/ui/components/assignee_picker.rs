@@ -0,0 +1,429 @@
+//! The popup for choosing assignees among the users the repository allows,
+//! shared by the issue list and the issue composer.
+
+use std::collections::HashMap;
+
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    text_input::{TextInput, TextInputState},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, Paragraph,
+        StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{span, vertical};
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::{backend::Backend, ui::Action};
+
+/// Background colors of the initials badges, picked per login.
+const BADGE_COLORS: [Color; 6] = [
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+pub(crate) enum AssigneeOutcome {
+    Continue,
+    Close,
+    /// The checked logins, or the highlighted one if none is checked and the
+    /// picker does not edit a whole list.
+    Apply(Vec<String>),
+}
+
+/// State of the assignee picker.
+pub(crate) struct AssigneePickerState {
+    heading: String,
+    users: Vec<String>,
+    /// Lowercased logins that are not offered, such as current assignees.
+    exclude: Vec<String>,
+    checked: Vec<String>,
+    /// Whether the picker edits a whole list, so that Enter applies exactly
+    /// the checked users, even none.
+    whole_list: bool,
+    /// Set until the assignable users arrive.
+    awaiting_users: bool,
+    /// Open issues assigned to each user, once loaded.
+    counts: HashMap<String, u64>,
+    filter: TextInputState,
+    list_state: TuiListState,
+    /// Set while the users are fetched or the choice is saved.
+    pub(crate) loading: bool,
+    pub(crate) saving: bool,
+    pub(crate) throbber_state: ThrobberState,
+    pub(crate) error: Option<String>,
+}
+
+impl AssigneePickerState {
+    fn new(heading: String, users: Vec<String>, loading: bool) -> Self {
+        let filter = TextInputState::default();
+        filter.focus.set(true);
+        Self {
+            heading,
+            users,
+            exclude: Vec::new(),
+            checked: Vec::new(),
+            whole_list: false,
+            awaiting_users: loading,
+            counts: HashMap::new(),
+            filter,
+            list_state: TuiListState::default().with_selected(Some(0)),
+            loading,
+            saving: false,
+            throbber_state: ThrobberState::default(),
+            error: None,
+        }
+    }
+
+    /// Opens the picker on the repository's assignable users, leaving out
+    /// `exclude` and with `checked` already checked.
+    pub(crate) fn assignable(
+        heading: impl Into<String>,
+        exclude: &[String],
+        checked: Vec<String>,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) -> Self {
+        let mut picker = Self::new(heading.into(), Vec::new(), true);
+        picker.exclude = exclude.iter().map(|login| login.to_lowercase()).collect();
+        picker.checked = checked;
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+            let users = match backend.list_assignees().await {
+                Ok(users) => users.into_iter().map(|user| user.login).collect::<Vec<_>>(),
+                Err(err) => {
+                    let _ = action_tx
+                        .send(Action::AssignableUsersError {
+                            message: err.to_string().replace('\n', " "),
+                        })
+                        .await;
+                    return;
+                }
+            };
+            let _ = action_tx
+                .send(Action::AssignableUsersLoaded {
+                    users: users.clone(),
+                })
+                .await;
+            load_counts(backend, action_tx, users).await;
+        });
+        picker
+    }
+
+    /// Makes Enter apply exactly the checked users, for editing a whole list
+    /// of assignees rather than adding to one.
+    pub(crate) fn whole_list(mut self) -> Self {
+        self.whole_list = true;
+        self
+    }
+
+    /// Opens the picker on a fixed list of users, such as the current
+    /// assignees when removing some.
+    pub(crate) fn fixed(
+        heading: impl Into<String>,
+        users: Vec<String>,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) -> Self {
+        tokio::spawn(load_counts(
+            backend.clone(),
+            action_tx.clone(),
+            users.clone(),
+        ));
+        Self::new(heading.into(), users, false)
+    }
+
+    /// The users to choose from whose login matches the filter: the checked
+    /// ones that the repository does not know first, then the rest.
+    fn choices(&self) -> Vec<&str> {
+        let filter = self.filter.text().trim().to_lowercase();
+        let unknown = self.checked.iter().filter(|login| {
+            !self.loading
+                && !self
+                    .users
+                    .iter()
+                    .any(|user| user.eq_ignore_ascii_case(login))
+        });
+        unknown
+            .chain(self.users.iter())
+            .filter(|login| {
+                let lower = login.to_lowercase();
+                !self.exclude.contains(&lower) && lower.contains(&filter)
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn is_checked(&self, login: &str) -> bool {
+        self.checked
+            .iter()
+            .any(|checked| checked.eq_ignore_ascii_case(login))
+    }
+
+    fn is_known(&self, login: &str) -> bool {
+        self.loading
+            || self
+                .users
+                .iter()
+                .any(|user| user.eq_ignore_ascii_case(login))
+    }
+
+    fn select(&mut self, delta: isize) {
+        let len = self.choices().len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        self.list_state.select(Some(next));
+    }
+
+    fn highlighted(&self) -> Option<String> {
+        let choices = self.choices();
+        let idx = self.list_state.selected()?;
+        choices.get(idx).map(|login| login.to_string())
+    }
+
+    fn toggle_highlighted(&mut self) {
+        let Some(login) = self.highlighted() else {
+            return;
+        };
+        if self.is_checked(&login) {
+            self.checked
+                .retain(|checked| !checked.eq_ignore_ascii_case(&login));
+        } else {
+            self.checked.push(login);
+        }
+    }
+
+    /// Handles a key press.
+    pub(crate) fn handle_event(&mut self, event: &crossterm::event::Event) -> AssigneeOutcome {
+        if self.saving {
+            return AssigneeOutcome::Continue;
+        }
+        match event {
+            ct_event!(keycode press Esc) => return AssigneeOutcome::Close,
+            ct_event!(keycode press Up) => self.select(-1),
+            ct_event!(keycode press Down) => self.select(1),
+            ct_event!(key press ' ') => self.toggle_highlighted(),
+            ct_event!(keycode press Enter) => {
+                let logins = if self.checked.is_empty() && !self.whole_list {
+                    self.highlighted().into_iter().collect()
+                } else {
+                    self.checked.clone()
+                };
+                if let Some(unknown) = logins.iter().find(|login| !self.is_known(login)) {
+                    self.error = Some(format!("{unknown} cannot be assigned"));
+                    return AssigneeOutcome::Continue;
+                }
+                return AssigneeOutcome::Apply(logins);
+            }
+            crossterm::event::Event::Paste(text) => {
+                self.filter.insert_str(text.trim());
+                self.list_state.select(Some(0));
+            }
+            _ => {
+                let before = self.filter.text().to_string();
+                self.filter.handle(event, rat_widget::event::Regular);
+                if self.filter.text() != before {
+                    self.list_state.select(Some(0));
+                }
+            }
+        }
+        AssigneeOutcome::Continue
+    }
+
+    /// Applies the result of a request this picker started.
+    pub(crate) fn update(&mut self, action: &Action) {
+        match action {
+            Action::AssignableUsersLoaded { users } if self.awaiting_users => {
+                self.awaiting_users = false;
+                self.loading = self.saving;
+                self.users = users.clone();
+                self.users.sort_by_key(|login| login.to_lowercase());
+            }
+            Action::AssignableUsersError { message } if self.awaiting_users => {
+                self.awaiting_users = false;
+                self.loading = self.saving;
+                self.error = Some(message.clone());
+            }
+            Action::AssigneeCountsLoaded { counts } => {
+                self.counts.extend(counts.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// Shows that the choice is being saved; the host closes the picker once
+    /// it is, or reports the error with [`Self::save_failed`].
+    pub(crate) fn start_saving(&mut self) {
+        self.saving = true;
+        self.loading = true;
+        self.error = None;
+    }
+
+    pub(crate) fn save_failed(&mut self, message: String) {
+        self.saving = false;
+        self.loading = false;
+        self.error = Some(message);
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        self.filter.screen_cursor()
+    }
+}
+
+async fn load_counts(
+    backend: Backend,
+    action_tx: tokio::sync::mpsc::Sender<Action>,
+    users: Vec<String>,
+) {
+    match backend.assignee_open_issue_counts(&users).await {
+        Ok(counts) => {
+            let _ = action_tx
+                .send(Action::AssigneeCountsLoaded { counts })
+                .await;
+        }
+        Err(err) => tracing::error!("Failed to load open issue counts of assignees: {err}"),
+    }
+}
+
+/// Up to two letters standing in for a user's avatar: the first letters of
+/// the first two words of the login, or its first two letters.
+fn initials(login: &str) -> String {
+    let words = login
+        .split(['-', '_', '.'])
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let mut letters = match words.as_slice() {
+        [first, second, ..] => first
+            .chars()
+            .take(1)
+            .chain(second.chars().take(1))
+            .collect(),
+        [word] => {
+            // Treat camelCase logins as two words.
+            let second = word.chars().skip(1).find(char::is_ascii_uppercase);
+            match second {
+                Some(second) => word.chars().take(1).chain([second]).collect::<String>(),
+                None => word.chars().take(2).collect(),
+            }
+        }
+        [] => String::new(),
+    };
+    letters.make_ascii_uppercase();
+    letters
+}
+
+fn badge(login: &str) -> Span<'static> {
+    let hash = login.bytes().fold(0usize, |acc, byte| {
+        acc.wrapping_mul(31).wrapping_add(byte as usize)
+    });
+    let color = BADGE_COLORS[hash % BADGE_COLORS.len()];
+    span!(" {:<2} ", initials(login))
+        .fg(Color::Black)
+        .bg(color)
+        .bold()
+}
+
+fn user_item(picker: &AssigneePickerState, login: &str) -> ListItem<'static> {
+    let check = if picker.is_checked(login) {
+        span!("[x] ").cyan()
+    } else {
+        span!("[ ] ").dim()
+    };
+    let mut spans = vec![check, badge(login), span!(" "), span!(login.to_string())];
+    if !picker.is_known(login) {
+        spans.push(span!("  cannot be assigned").red());
+    } else if let Some(count) = picker.counts.get(login) {
+        spans.push(span!("  {count} open").dim());
+    }
+    ListItem::new(Line::from(spans))
+}
+
+pub(crate) fn render_assignee_picker(
+    picker: &mut AssigneePickerState,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let popup_area = area.centered(Constraint::Percentage(50), Constraint::Percentage(60));
+    Clear.render(popup_area, buf);
+    let mut heading = picker.heading.clone();
+    if !picker.checked.is_empty() {
+        heading.push_str(&format!(" | {} checked", picker.checked.len()));
+    }
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title(match &picker.error {
+            Some(err) => format!("{heading} | {err}"),
+            None => heading,
+        })
+        .title_bottom("Type to filter  ↑/↓: select  Space: check  Enter: apply  Esc: cancel");
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let [filter_area, list_area] = vertical![==3, *=1].areas(inner);
+    TextInput::new()
+        .block(
+            Block::bordered()
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title("Filter"),
+        )
+        .render(filter_area, buf, &mut picker.filter);
+
+    let choices = picker.choices();
+    if choices.is_empty() && !picker.loading {
+        Paragraph::new(span!("No matching users").dim()).render(list_area, buf);
+    } else {
+        let items = choices
+            .iter()
+            .map(|login| user_item(picker, login))
+            .collect::<Vec<_>>();
+        let list = TuiList::new(items)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        StatefulWidget::render(list, list_area, buf, &mut picker.list_state);
+    }
+
+    if picker.loading {
+        let title_area = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y,
+            width: 12,
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label(if picker.saving { "Saving" } else { "Loading" })
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, title_area, buf, &mut picker.throbber_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initials_come_from_the_words_of_the_login() {
+        assert_eq!(initials("jane-doe"), "JD");
+        assert_eq!(initials("john_q_public"), "JQ");
+        assert_eq!(initials("JohnSmith"), "JS");
+        assert_eq!(initials("octocat"), "OC");
+        assert_eq!(initials("x"), "X");
+        assert_eq!(initials("-"), "");
+    }
+}
@@ -0,0 +1,539 @@
+//! Applying one change to many issues at once, for the issue list's
+//! multi-select mode.
+
+use octocrab::models::IssueState;
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    text_input::{TextInput, TextInputState},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{line, span};
+use ratatui_toaster::ToastType;
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    backend::{Backend, IssueEdit},
+    errors::AppError,
+    ui::{Action, CloseIssueReason, toast_action},
+};
+
+type ActionTx = tokio::sync::mpsc::Sender<Action>;
+
+/// A change applied to every selected issue.
+#[derive(Debug, Clone)]
+pub(crate) enum BulkOp {
+    Close(CloseIssueReason),
+    AddLabels(Vec<String>),
+    RemoveLabels(Vec<String>),
+    Assign(Vec<String>),
+    Unassign(Vec<String>),
+    /// Number and title of the milestone, or `None` to clear it.
+    SetMilestone(Option<(u64, String)>),
+}
+
+/// An issue a [`BulkOp`] is applied to.
+#[derive(Debug, Clone)]
+pub(crate) struct BulkTarget {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    /// Names of the labels the issue has, so removals skip the ones it lacks.
+    pub(crate) labels: Vec<String>,
+}
+
+impl BulkOp {
+    fn heading(&self, count: usize) -> String {
+        let issues = issues(count);
+        match self {
+            BulkOp::Close(reason) => {
+                format!("Closing {issues} as {}", reason.label().to_lowercase())
+            }
+            BulkOp::AddLabels(labels) => format!("Labelling {issues} {}", labels.join(", ")),
+            BulkOp::RemoveLabels(labels) => {
+                format!("Removing {} from {issues}", labels.join(", "))
+            }
+            BulkOp::Assign(users) => format!("Assigning {issues} to {}", users.join(", ")),
+            BulkOp::Unassign(users) => format!("Unassigning {} from {issues}", users.join(", ")),
+            BulkOp::SetMilestone(Some((_, title))) => format!("Adding {issues} to {title}"),
+            BulkOp::SetMilestone(None) => format!("Clearing the milestone of {issues}"),
+        }
+    }
+
+    fn done(&self, count: usize) -> String {
+        let issues = issues(count);
+        match self {
+            BulkOp::Close(_) => format!("Closed {issues}"),
+            BulkOp::AddLabels(_) => format!("Labelled {issues}"),
+            BulkOp::RemoveLabels(_) => format!("Removed labels from {issues}"),
+            BulkOp::Assign(_) => format!("Assigned {issues}"),
+            BulkOp::Unassign(_) => format!("Unassigned {issues}"),
+            BulkOp::SetMilestone(Some((_, title))) => format!("Added {issues} to {title}"),
+            BulkOp::SetMilestone(None) => format!("Cleared the milestone of {issues}"),
+        }
+    }
+
+    /// Applies the change to one issue and reports the updated issue to the UI.
+    async fn apply(
+        &self,
+        backend: &Backend,
+        action_tx: &ActionTx,
+        target: &BulkTarget,
+    ) -> Result<(), AppError> {
+        let number = target.number;
+        let issue = match self {
+            BulkOp::Close(reason) => {
+                backend
+                    .set_issue_state(number, IssueState::Closed, Some(reason.to_octocrab()))
+                    .await?
+            }
+            BulkOp::AddLabels(labels) => {
+                let labels = backend.add_labels(number, labels).await?;
+                let _ = action_tx
+                    .send(Action::IssueLabelsUpdated { number, labels })
+                    .await;
+                return Ok(());
+            }
+            BulkOp::RemoveLabels(labels) => {
+                let mut remaining = None;
+                for label in labels_to_remove(labels, &target.labels) {
+                    remaining = Some(backend.remove_label(number, label).await?);
+                }
+                if let Some(labels) = remaining {
+                    let _ = action_tx
+                        .send(Action::IssueLabelsUpdated { number, labels })
+                        .await;
+                }
+                return Ok(());
+            }
+            BulkOp::Assign(users) => {
+                let users = users.iter().map(String::as_str).collect::<Vec<_>>();
+                backend.add_assignees(number, &users).await?
+            }
+            BulkOp::Unassign(users) => {
+                let users = users.iter().map(String::as_str).collect::<Vec<_>>();
+                backend.remove_assignees(number, &users).await?
+            }
+            BulkOp::SetMilestone(milestone) => {
+                let edit = IssueEdit {
+                    milestone: Some(milestone.as_ref().map(|(number, _)| *number)),
+                    ..Default::default()
+                };
+                backend.update_issue(number, edit).await?
+            }
+        };
+        let _ = action_tx
+            .send(Action::IssueUpdated {
+                issue: Box::new(issue),
+            })
+            .await;
+        Ok(())
+    }
+}
+
+fn issues(count: usize) -> String {
+    if count == 1 {
+        "1 issue".to_string()
+    } else {
+        format!("{count} issues")
+    }
+}
+
+/// The requested labels the issue actually has, spelled the way the issue
+/// spells them.
+fn labels_to_remove<'a>(requested: &[String], current: &'a [String]) -> Vec<&'a str> {
+    current
+        .iter()
+        .filter(|label| requested.iter().any(|r| r.eq_ignore_ascii_case(label)))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Splits a comma separated list, dropping empty entries.
+fn split_values(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// What a [`BulkPromptState`] asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BulkPromptKind {
+    Close,
+    AddLabels,
+    RemoveLabels,
+}
+
+pub(crate) enum PromptOutcome {
+    Continue,
+    Cancel,
+    Submit(BulkOp),
+}
+
+/// Popup asking for the close reason, labels or users of a bulk change.
+pub(crate) struct BulkPromptState {
+    kind: BulkPromptKind,
+    count: usize,
+    input: TextInputState,
+    reason_state: TuiListState,
+    error: Option<String>,
+}
+
+impl BulkPromptState {
+    pub(crate) fn new(kind: BulkPromptKind, count: usize) -> Self {
+        let input = TextInputState::default();
+        input.focus.set(true);
+        Self {
+            kind,
+            count,
+            input,
+            reason_state: TuiListState::default().with_selected(Some(0)),
+            error: None,
+        }
+    }
+
+    pub(crate) fn handle_event(&mut self, event: &crossterm::event::Event) -> PromptOutcome {
+        match event {
+            ct_event!(keycode press Esc) => return PromptOutcome::Cancel,
+            ct_event!(keycode press Up) if self.kind == BulkPromptKind::Close => {
+                self.reason_state.select_previous();
+            }
+            ct_event!(keycode press Down) if self.kind == BulkPromptKind::Close => {
+                self.reason_state.select_next();
+            }
+            ct_event!(keycode press Enter) => return self.submit(),
+            _ if self.kind == BulkPromptKind::Close => {}
+            crossterm::event::Event::Paste(text) => {
+                self.input.insert_str(text.replace('\n', ","));
+            }
+            _ => {
+                self.input.handle(event, rat_widget::event::Regular);
+            }
+        }
+        PromptOutcome::Continue
+    }
+
+    fn submit(&mut self) -> PromptOutcome {
+        let values = split_values(self.input.text());
+        let op = match self.kind {
+            BulkPromptKind::Close => {
+                let reason = self
+                    .reason_state
+                    .selected()
+                    .and_then(|idx| CloseIssueReason::ALL.get(idx).copied())
+                    .unwrap_or(CloseIssueReason::Completed);
+                BulkOp::Close(reason)
+            }
+            _ if values.is_empty() => {
+                self.error = Some("Enter at least one name.".to_string());
+                return PromptOutcome::Continue;
+            }
+            BulkPromptKind::AddLabels => BulkOp::AddLabels(values),
+            BulkPromptKind::RemoveLabels => BulkOp::RemoveLabels(values),
+        };
+        PromptOutcome::Submit(op)
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        if self.kind == BulkPromptKind::Close {
+            return None;
+        }
+        self.input.screen_cursor()
+    }
+
+    fn heading(&self) -> String {
+        let issues = issues(self.count);
+        match self.kind {
+            BulkPromptKind::Close => format!("Close {issues}"),
+            BulkPromptKind::AddLabels => format!("Add labels to {issues}"),
+            BulkPromptKind::RemoveLabels => format!("Remove labels from {issues}"),
+        }
+    }
+}
+
+pub(crate) fn render_bulk_prompt(state: &mut BulkPromptState, area: Rect, buf: &mut Buffer) {
+    let heading = match &state.error {
+        Some(err) => format!("{} | {err}", state.heading()),
+        None => state.heading(),
+    };
+    if state.kind == BulkPromptKind::Close {
+        let popup_area = area.centered(Constraint::Percentage(30), Constraint::Length(5));
+        Clear.render(popup_area, buf);
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::new().yellow())
+            .title(heading)
+            .title_bottom("Enter: close all  Esc: cancel");
+        let items = CloseIssueReason::ALL
+            .iter()
+            .map(|reason| ListItem::new(reason.label()))
+            .collect::<Vec<_>>();
+        let list = TuiList::new(items)
+            .block(block)
+            .highlight_style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ");
+        StatefulWidget::render(list, popup_area, buf, &mut state.reason_state);
+        return;
+    }
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Length(3));
+    Clear.render(popup_area, buf);
+    let input = TextInput::new().block(
+        Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::new().yellow())
+            .title(heading)
+            .title_bottom("Separate names with commas  Enter: apply  Esc: cancel"),
+    );
+    input.render(popup_area, buf, &mut state.input);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ItemStatus {
+    Pending,
+    Done,
+    Failed(String),
+}
+
+struct BulkItem {
+    number: u64,
+    title: String,
+    status: ItemStatus,
+}
+
+/// Progress of a bulk change, item by item.
+pub(crate) struct BulkRunState {
+    heading: String,
+    items: Vec<BulkItem>,
+    list_state: TuiListState,
+    cancel: CancellationToken,
+    finished: bool,
+    pub(crate) throbber_state: ThrobberState,
+}
+
+impl BulkRunState {
+    /// Starts applying `op` to the targets one after another.
+    pub(crate) fn start(
+        op: BulkOp,
+        targets: Vec<BulkTarget>,
+        backend: &Backend,
+        action_tx: &ActionTx,
+    ) -> Self {
+        let cancel = CancellationToken::new();
+        let heading = op.heading(targets.len());
+        let items = targets
+            .iter()
+            .map(|target| BulkItem {
+                number: target.number,
+                title: target.title.clone(),
+                status: ItemStatus::Pending,
+            })
+            .collect();
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        let token = cancel.clone();
+        tokio::spawn(async move {
+            let mut done = 0;
+            let mut failed = 0;
+            for target in &targets {
+                if token.is_cancelled() {
+                    break;
+                }
+                let error = match op.apply(&backend, &action_tx, target).await {
+                    Ok(()) => {
+                        done += 1;
+                        None
+                    }
+                    Err(err) => {
+                        failed += 1;
+                        Some(err.to_string().replace('\n', " "))
+                    }
+                };
+                let _ = action_tx
+                    .send(Action::BulkItemFinished {
+                        number: target.number,
+                        error,
+                    })
+                    .await;
+            }
+            let _ = action_tx.send(Action::BulkFinished).await;
+            let skipped = targets.len() - done - failed;
+            let (message, toast_type) = match (failed, skipped) {
+                (0, 0) => (op.done(done), ToastType::Success),
+                (0, _) => (
+                    format!("{}; stopped before {}", op.done(done), issues(skipped)),
+                    ToastType::Warning,
+                ),
+                _ => (
+                    format!("{}; {} failed", op.done(done), issues(failed)),
+                    ToastType::Error,
+                ),
+            };
+            let _ = action_tx.send(toast_action(message, toast_type)).await;
+        });
+        Self {
+            heading,
+            items,
+            list_state: TuiListState::default(),
+            cancel,
+            finished: false,
+            throbber_state: ThrobberState::default(),
+        }
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        !self.finished
+    }
+
+    /// Issues the change did not reach, because it failed or was stopped.
+    pub(crate) fn unfinished(&self) -> Vec<u64> {
+        self.items
+            .iter()
+            .filter(|item| item.status != ItemStatus::Done)
+            .map(|item| item.number)
+            .collect()
+    }
+
+    /// Handles a key press. Returns `false` once the popup should close.
+    pub(crate) fn handle_event(&mut self, event: &crossterm::event::Event) -> bool {
+        match event {
+            ct_event!(keycode press Esc) if !self.finished => self.cancel.cancel(),
+            ct_event!(keycode press Esc) | ct_event!(keycode press Enter) => return false,
+            ct_event!(keycode press Up) => self.list_state.select_previous(),
+            ct_event!(keycode press Down) => self.list_state.select_next(),
+            _ => {}
+        }
+        true
+    }
+
+    pub(crate) fn update(&mut self, action: &Action) {
+        match action {
+            Action::BulkItemFinished { number, error } => {
+                if let Some(item) = self.items.iter_mut().find(|item| item.number == *number) {
+                    item.status = match error {
+                        Some(message) => ItemStatus::Failed(message.clone()),
+                        None => ItemStatus::Done,
+                    };
+                }
+            }
+            Action::BulkFinished => self.finished = true,
+            _ => {}
+        }
+    }
+
+    fn counts(&self) -> (usize, usize) {
+        let done = self
+            .items
+            .iter()
+            .filter(|item| item.status == ItemStatus::Done)
+            .count();
+        let failed = self
+            .items
+            .iter()
+            .filter(|item| matches!(item.status, ItemStatus::Failed(_)))
+            .count();
+        (done, failed)
+    }
+}
+
+fn run_item(item: &BulkItem, in_flight: bool) -> ListItem<'static> {
+    let (marker, detail) = match &item.status {
+        ItemStatus::Done => (span!("✓ ").green(), None),
+        ItemStatus::Failed(message) => (span!("✗ ").red(), Some(message.clone())),
+        ItemStatus::Pending if in_flight => (span!("… ").cyan(), None),
+        ItemStatus::Pending => (span!("· ").dim(), None),
+    };
+    let mut lines = vec![line![
+        marker,
+        span!("#{}", item.number).dim(),
+        " ",
+        span!(item.title.clone()),
+    ]];
+    if let Some(detail) = detail {
+        lines.push(line!["  ", span!(detail).red()]);
+    }
+    ListItem::new(lines)
+}
+
+pub(crate) fn render_bulk_run(state: &mut BulkRunState, area: Rect, buf: &mut Buffer) {
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(60));
+    Clear.render(popup_area, buf);
+    let (done, failed) = state.counts();
+    let total = state.items.len();
+    let mut summary = format!("{} | {done}/{total} done", state.heading);
+    if failed > 0 {
+        summary.push_str(&format!(", {failed} failed"));
+    }
+    let footer = if state.finished {
+        "Enter/Esc: close (issues that were not changed stay selected)"
+    } else if state.cancel.is_cancelled() {
+        "Stopping after the current issue..."
+    } else {
+        "Esc: stop"
+    };
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title(summary)
+        .title_bottom(footer);
+    let in_flight = (!state.finished)
+        .then(|| {
+            state
+                .items
+                .iter()
+                .position(|item| item.status == ItemStatus::Pending)
+        })
+        .flatten();
+    let items = state
+        .items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| run_item(item, Some(idx) == in_flight))
+        .collect::<Vec<_>>();
+    let list = TuiList::new(items)
+        .block(block)
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(list, popup_area, buf, &mut state.list_state);
+
+    if !state.finished {
+        let title_area = Rect {
+            x: popup_area.right().saturating_sub(12),
+            y: popup_area.y,
+            width: 10,
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label("Working")
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, title_area, buf, &mut state.throbber_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{labels_to_remove, split_values};
+
+    #[test]
+    fn values_are_trimmed_and_empty_ones_dropped() {
+        assert_eq!(split_values(" bug, ,ui ,"), vec!["bug", "ui"]);
+        assert!(split_values(" , ").is_empty());
+    }
+
+    #[test]
+    fn only_labels_the_issue_has_are_removed() {
+        let current = vec!["Bug".to_string(), "ui".to_string()];
+        let requested = vec!["bug".to_string(), "docs".to_string()];
+        assert_eq!(labels_to_remove(&requested, &current), vec!["Bug"]);
+    }
+}
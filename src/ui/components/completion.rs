@@ -0,0 +1,653 @@
+//! Completion of `@mentions` and `#references` in markdown inputs, of labels
+//! in the comma separated labels field of the issue composer, and of
+//! qualifier values in the search bar.
+
+use std::collections::HashSet;
+
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use rat_widget::{
+    event::ct_event, text::TextRange, text_input::TextInputState, textarea::TextAreaState,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{line, span};
+use tracing::warn;
+
+use crate::{
+    backend::{
+        Backend, SearchParams,
+        query::{self, SORT_COMPLETIONS, ValueKind},
+    },
+    ui::Action,
+};
+
+const MAX_MATCHES: usize = 8;
+const POPUP_WIDTH: u16 = 48;
+
+/// What is being completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompletionKind {
+    /// `@login` in markdown.
+    Mention,
+    /// `#number` in markdown.
+    Reference,
+    Label,
+    /// The value of a search qualifier such as `author:`.
+    Qualifier(ValueKind),
+}
+
+/// Data the completions are drawn from, loaded once per component.
+#[derive(Debug, Clone)]
+pub enum CompletionData {
+    /// Logins of the users issues can be assigned to.
+    Users(Vec<String>),
+    /// Recently updated issues and pull requests.
+    Issues(Vec<IssueRef>),
+    /// Names and descriptions of the repository's labels.
+    Labels(Vec<(String, Option<String>)>),
+    /// Titles of the repository's milestones.
+    Milestones(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct IssueRef {
+    pub number: u64,
+    pub title: String,
+    pub pull_request: bool,
+}
+
+/// The `@` or `#` token that ends `before_cursor`, with the text typed after
+/// the trigger. Tokens must start a word, so e-mail addresses are left alone.
+pub(crate) fn markdown_token(before_cursor: &str) -> Option<(CompletionKind, &str)> {
+    let token = before_cursor
+        .rsplit(|c: char| c.is_whitespace() || c == '(')
+        .next()?;
+    let (kind, query) = if let Some(query) = token.strip_prefix('@') {
+        (CompletionKind::Mention, query)
+    } else if let Some(query) = token.strip_prefix('#') {
+        (CompletionKind::Reference, query)
+    } else {
+        return None;
+    };
+    query
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        .then_some((kind, query))
+}
+
+/// The value being typed in a comma separated list.
+pub(crate) fn csv_token(before_cursor: &str) -> &str {
+    before_cursor
+        .rsplit(',')
+        .next()
+        .unwrap_or_default()
+        .trim_start()
+}
+
+/// The values of a comma separated list, lowercased.
+fn csv_values(text: &str) -> HashSet<String> {
+    text.split(',')
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Indices of the `choices` matching `query`, best first. Choices whose text
+/// starts with the query rank above other fuzzy matches.
+pub(crate) fn rank<'a>(query: &str, choices: impl IntoIterator<Item = &'a str>) -> Vec<usize> {
+    let query = query.to_lowercase();
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut scored = choices
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, choice)| {
+            if query.is_empty() {
+                return Some((idx, 0, false));
+            }
+            let score = matcher.fuzzy_match(choice, &query)?;
+            Some((idx, score, choice.to_lowercase().starts_with(&query)))
+        })
+        .collect::<Vec<_>>();
+    scored.sort_by_key(|(idx, score, prefix)| (!prefix, std::cmp::Reverse(*score), *idx));
+    scored.into_iter().map(|(idx, _, _)| idx).collect()
+}
+
+struct Candidate {
+    /// Text that replaces the token.
+    insert: String,
+    label: String,
+    detail: Option<String>,
+}
+
+struct Popup {
+    kind: CompletionKind,
+    query: String,
+    /// Values already in a comma separated field, which are not offered again.
+    exclude: HashSet<String>,
+    /// Length of the token being replaced, in characters.
+    token_len: usize,
+    matches: Vec<Candidate>,
+    list_state: TuiListState,
+}
+
+/// Outcome of a key press while completions are shown.
+pub(crate) enum CompletionKey {
+    /// The key is not for the popup.
+    Ignored,
+    Handled,
+    /// Replace the last `token_len` characters before the cursor with `text`.
+    Accept {
+        token_len: usize,
+        text: String,
+    },
+}
+
+/// Completion state of a component.
+#[derive(Default)]
+pub(crate) struct Completer {
+    users: Option<Vec<String>>,
+    issues: Option<Vec<IssueRef>>,
+    labels: Option<Vec<(String, Option<String>)>>,
+    milestones: Option<Vec<String>>,
+    /// Authors in the thread being answered; mentioned before other users.
+    participants: Vec<String>,
+    requested: HashSet<&'static str>,
+    popup: Option<Popup>,
+}
+
+impl Completer {
+    pub(crate) fn set_participants(&mut self, participants: Vec<String>) {
+        self.participants = participants;
+    }
+
+    pub(crate) fn close(&mut self) {
+        self.popup = None;
+    }
+
+    /// The `values` of a comma separated list that the repository does not
+    /// know about. Nothing is reported before the repository's labels or
+    /// users are loaded.
+    pub(crate) fn unknown(&self, kind: CompletionKind, values: &[String]) -> Vec<String> {
+        let known = match kind {
+            CompletionKind::Label => self
+                .labels
+                .as_ref()
+                .map(|labels| labels.iter().map(|(name, _)| name).collect::<Vec<_>>()),
+            _ => self.users.as_ref().map(|users| users.iter().collect()),
+        };
+        let Some(known) = known else {
+            return Vec::new();
+        };
+        values
+            .iter()
+            .filter(|value| !known.iter().any(|known| known.eq_ignore_ascii_case(value)))
+            .cloned()
+            .collect()
+    }
+
+    /// Shows completions for the markdown token before the cursor, if any.
+    pub(crate) fn complete_markdown(
+        &mut self,
+        before_cursor: &str,
+        backend: &Backend,
+        repo: &str,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) {
+        match markdown_token(before_cursor) {
+            Some((kind, query)) => {
+                self.load(kind, backend, repo, action_tx);
+                self.open(kind, query, HashSet::new());
+            }
+            None => self.popup = None,
+        }
+    }
+
+    /// Shows completions for the value of the search qualifier before the
+    /// cursor, if any.
+    pub(crate) fn complete_search(
+        &mut self,
+        before_cursor: &str,
+        backend: &Backend,
+        repo: &str,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) {
+        match query::value_at_end(before_cursor) {
+            Some((kind, value)) => {
+                let kind = CompletionKind::Qualifier(kind);
+                self.load(kind, backend, repo, action_tx);
+                self.open(kind, value, HashSet::new());
+            }
+            None => self.popup = None,
+        }
+    }
+
+    /// Shows completions for the value being typed in a comma separated
+    /// field. `text` is the whole field, so that entered values are skipped.
+    pub(crate) fn complete_list(
+        &mut self,
+        kind: CompletionKind,
+        text: &str,
+        before_cursor: &str,
+        backend: &Backend,
+        repo: &str,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) {
+        self.load(kind, backend, repo, action_tx);
+        let query = csv_token(before_cursor);
+        let mut exclude = csv_values(text);
+        exclude.remove(&query.trim().to_lowercase());
+        self.open(kind, query, exclude);
+    }
+
+    fn open(&mut self, kind: CompletionKind, query: &str, exclude: HashSet<String>) {
+        let selected = self
+            .popup
+            .as_ref()
+            .filter(|popup| popup.kind == kind && popup.query == query)
+            .and_then(|popup| popup.list_state.selected());
+        let mut popup = Popup {
+            kind,
+            query: query.to_string(),
+            exclude,
+            token_len: query.chars().count() + usize::from(kind_has_trigger(kind)),
+            matches: Vec::new(),
+            list_state: TuiListState::default(),
+        };
+        popup.matches = self.candidates(&popup);
+        if popup.matches.is_empty() {
+            self.popup = None;
+            return;
+        }
+        popup
+            .list_state
+            .select(Some(selected.unwrap_or(0).min(popup.matches.len() - 1)));
+        self.popup = Some(popup);
+    }
+
+    fn candidates(&self, popup: &Popup) -> Vec<Candidate> {
+        let query = popup.query.as_str();
+        match popup.kind {
+            CompletionKind::Mention => {
+                let mut seen = HashSet::new();
+                let participants = self.participants.as_slice();
+                let users = participants
+                    .iter()
+                    .chain(self.users.iter().flatten())
+                    .filter(|login| {
+                        let login = login.to_lowercase();
+                        !popup.exclude.contains(&login) && seen.insert(login)
+                    })
+                    .collect::<Vec<_>>();
+                rank(query, users.iter().map(|login| login.as_str()))
+                    .into_iter()
+                    .take(MAX_MATCHES)
+                    .map(|idx| {
+                        let login = users[idx];
+                        let participant = participants.contains(login);
+                        Candidate {
+                            insert: format!("@{login} "),
+                            label: format!("@{login}"),
+                            detail: participant.then(|| "in this thread".to_string()),
+                        }
+                    })
+                    .collect()
+            }
+            CompletionKind::Reference => {
+                let issues = self.issues.as_deref().unwrap_or_default();
+                let choices = issues
+                    .iter()
+                    .map(|issue| format!("{} {}", issue.number, issue.title))
+                    .collect::<Vec<_>>();
+                rank(query, choices.iter().map(String::as_str))
+                    .into_iter()
+                    .take(MAX_MATCHES)
+                    .map(|idx| {
+                        let issue = &issues[idx];
+                        Candidate {
+                            insert: format!("#{} ", issue.number),
+                            label: format!("#{}", issue.number),
+                            detail: Some(if issue.pull_request {
+                                format!("PR: {}", issue.title)
+                            } else {
+                                issue.title.clone()
+                            }),
+                        }
+                    })
+                    .collect()
+            }
+            CompletionKind::Qualifier(kind) => qualifier_candidates(self, kind, query),
+            CompletionKind::Label => {
+                let labels = self
+                    .labels
+                    .iter()
+                    .flatten()
+                    .filter(|(name, _)| !popup.exclude.contains(&name.to_lowercase()))
+                    .collect::<Vec<_>>();
+                rank(query, labels.iter().map(|(name, _)| name.as_str()))
+                    .into_iter()
+                    .take(MAX_MATCHES)
+                    .map(|idx| {
+                        let (name, description) = labels[idx];
+                        Candidate {
+                            insert: format!("{name}, "),
+                            label: name.clone(),
+                            detail: description.clone().filter(|d| !d.is_empty()),
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Starts loading what `kind` completes from, unless that was done before.
+    fn load(
+        &mut self,
+        kind: CompletionKind,
+        backend: &Backend,
+        repo: &str,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) {
+        let source = match kind {
+            CompletionKind::Mention | CompletionKind::Qualifier(ValueKind::User) => "users",
+            CompletionKind::Reference => "issues",
+            CompletionKind::Label | CompletionKind::Qualifier(ValueKind::Label) => "labels",
+            CompletionKind::Qualifier(ValueKind::Milestone) => "milestones",
+            CompletionKind::Qualifier(_) => return,
+        };
+        if !self.requested.insert(source) {
+            return;
+        }
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        let query = format!("repo:{repo} sort:updated-desc");
+        tokio::spawn(async move {
+            let data = match source {
+                "users" => backend.list_assignees().await.map(|users| {
+                    CompletionData::Users(users.into_iter().map(|user| user.login).collect())
+                }),
+                "issues" => backend
+                    .search_issues(SearchParams::new(query).per_page(100))
+                    .await
+                    .map(|page| {
+                        CompletionData::Issues(
+                            page.items
+                                .into_iter()
+                                .map(|issue| IssueRef {
+                                    number: issue.number,
+                                    title: issue.title,
+                                    pull_request: issue.pull_request.is_some(),
+                                })
+                                .collect(),
+                        )
+                    }),
+                "milestones" => backend.list_milestones().await.map(|milestones| {
+                    CompletionData::Milestones(
+                        milestones
+                            .into_iter()
+                            .map(|milestone| milestone.title)
+                            .collect(),
+                    )
+                }),
+                _ => load_labels(&backend).await.map(CompletionData::Labels),
+            };
+            match data {
+                Ok(data) => {
+                    let _ = action_tx.send(Action::CompletionsLoaded(data)).await;
+                }
+                Err(err) => warn!(error = %err, source, "failed to load completions"),
+            }
+        });
+    }
+
+    /// Stores loaded completion data and refreshes the open popup.
+    pub(crate) fn update(&mut self, data: &CompletionData) {
+        match data {
+            CompletionData::Users(users) => self.users = Some(users.clone()),
+            CompletionData::Issues(issues) => self.issues = Some(issues.clone()),
+            CompletionData::Labels(labels) => self.labels = Some(labels.clone()),
+            CompletionData::Milestones(titles) => self.milestones = Some(titles.clone()),
+        }
+        if let Some(popup) = self.popup.take() {
+            self.open(popup.kind, &popup.query, popup.exclude);
+        }
+    }
+
+    /// Handles a key press while the popup is open.
+    pub(crate) fn handle_event(&mut self, event: &crossterm::event::Event) -> CompletionKey {
+        let Some(popup) = self.popup.as_mut() else {
+            return CompletionKey::Ignored;
+        };
+        let selected = popup.list_state.selected().unwrap_or(0);
+        match event {
+            ct_event!(keycode press Up) => {
+                popup.list_state.select(Some(selected.saturating_sub(1)))
+            }
+            ct_event!(keycode press Down) => popup
+                .list_state
+                .select(Some((selected + 1).min(popup.matches.len() - 1))),
+            ct_event!(keycode press Esc) => self.popup = None,
+            ct_event!(keycode press Enter) => {
+                let Some(popup) = self.popup.take() else {
+                    return CompletionKey::Handled;
+                };
+                let Some(candidate) = popup.matches.into_iter().nth(selected) else {
+                    return CompletionKey::Handled;
+                };
+                return CompletionKey::Accept {
+                    token_len: popup.token_len,
+                    text: candidate.insert,
+                };
+            }
+            _ => return CompletionKey::Ignored,
+        }
+        CompletionKey::Handled
+    }
+
+    /// Draws the popup below the text cursor at `cursor`, or above it if there
+    /// is no room below.
+    pub(crate) fn render(&mut self, cursor: Option<(u16, u16)>, buf: &mut Buffer) {
+        let (Some(popup), Some((x, y))) = (self.popup.as_mut(), cursor) else {
+            return;
+        };
+        let screen = *buf.area();
+        let height = popup.matches.len() as u16 + 2;
+        let width = POPUP_WIDTH.min(screen.width);
+        let y = if y + 1 + height <= screen.bottom() {
+            y + 1
+        } else {
+            y.saturating_sub(height).max(screen.y)
+        };
+        let area = Rect {
+            x: x.min(screen.right().saturating_sub(width)),
+            y,
+            width,
+            height: height.min(screen.height),
+        };
+        Clear.render(area, buf);
+        let items = popup
+            .matches
+            .iter()
+            .map(|candidate| {
+                let mut line = line![span!(candidate.label.clone()).bold()];
+                if let Some(detail) = &candidate.detail {
+                    line.push_span(span!("  {detail}").dim());
+                }
+                ListItem::new(line)
+            })
+            .collect::<Vec<_>>();
+        let list = TuiList::new(items)
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .border_style(Style::new().cyan()),
+            )
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        StatefulWidget::render(list, area, buf, &mut popup.list_state);
+    }
+}
+
+fn kind_has_trigger(kind: CompletionKind) -> bool {
+    matches!(kind, CompletionKind::Mention | CompletionKind::Reference)
+}
+
+/// Values for a search qualifier that match what has been typed. Values with
+/// spaces are quoted, and a space is inserted after the value.
+fn qualifier_candidates(completer: &Completer, kind: ValueKind, typed: &str) -> Vec<Candidate> {
+    let query = typed.trim_start_matches('"');
+    let detailed = |value: String, detail: Option<String>| Candidate {
+        insert: format!("{} ", query::quote(&value)),
+        label: value,
+        detail,
+    };
+    let mut choices: Vec<(String, Option<String>)> = match kind {
+        ValueKind::User => std::iter::once(("@me".to_string(), Some("you".to_string())))
+            .chain(
+                completer
+                    .users
+                    .iter()
+                    .flatten()
+                    .map(|login| (login.clone(), None)),
+            )
+            .collect(),
+        ValueKind::Label => completer.labels.clone().unwrap_or_default(),
+        ValueKind::Milestone => completer
+            .milestones
+            .iter()
+            .flatten()
+            .map(|title| (title.clone(), None))
+            .collect(),
+        ValueKind::Date => {
+            let today = chrono::Local::now().date_naive();
+            [
+                (">=", 7, "last week"),
+                (">=", 30, "last 30 days"),
+                (">=", 365, "last year"),
+                ("<", 365, "over a year ago"),
+            ]
+            .into_iter()
+            .map(|(op, days, detail)| {
+                let date = today - chrono::Duration::days(days);
+                (
+                    format!("{op}{}", date.format("%Y-%m-%d")),
+                    Some(detail.to_string()),
+                )
+            })
+            .collect()
+        }
+        ValueKind::Number | ValueKind::Text => Vec::new(),
+        ValueKind::OneOf(values) => values
+            .iter()
+            .map(|value| (value.to_string(), None))
+            .collect(),
+        ValueKind::Sort => SORT_COMPLETIONS
+            .iter()
+            .map(|value| (value.to_string(), None))
+            .collect(),
+    };
+    // A complete value needs no completion, so that Enter runs the search.
+    let value = query.trim_end_matches('"');
+    if choices
+        .iter()
+        .any(|(choice, _)| choice.eq_ignore_ascii_case(value))
+    {
+        return Vec::new();
+    }
+    let ranked = rank(query, choices.iter().map(|(value, _)| value.as_str()));
+    ranked
+        .into_iter()
+        .take(MAX_MATCHES)
+        .map(|idx| {
+            let (value, detail) = std::mem::take(&mut choices[idx]);
+            detailed(value, detail.filter(|detail| !detail.is_empty()))
+        })
+        .collect()
+}
+
+async fn load_labels(
+    backend: &Backend,
+) -> Result<Vec<(String, Option<String>)>, crate::errors::AppError> {
+    const PER_PAGE: u8 = 100;
+    let mut labels = Vec::new();
+    for page in 1.. {
+        let items = backend.list_labels(page, PER_PAGE).await?.items;
+        let last = items.len() < PER_PAGE as usize;
+        labels.extend(
+            items
+                .into_iter()
+                .map(|label| (label.name, label.description)),
+        );
+        if last {
+            break;
+        }
+    }
+    Ok(labels)
+}
+
+/// Text on the cursor's line up to the cursor.
+pub(crate) fn textarea_before_cursor(state: &TextAreaState) -> String {
+    let cursor = state.cursor();
+    state
+        .str_slice(TextRange::new((0, cursor.y), cursor))
+        .into_owned()
+}
+
+/// Replaces the `len` characters before the cursor with `text`.
+pub(crate) fn textarea_replace(state: &mut TextAreaState, len: usize, text: &str) {
+    let cursor = state.cursor();
+    let start = cursor.x.saturating_sub(len as _);
+    state.delete_range(TextRange::new((start, cursor.y), cursor));
+    state.insert_str(text);
+}
+
+pub(crate) fn input_before_cursor(state: &TextInputState) -> String {
+    state.str_slice(0..state.cursor()).into_owned()
+}
+
+/// Replaces the `len` characters before the cursor with `text`.
+pub(crate) fn input_replace(state: &mut TextInputState, len: usize, text: &str) {
+    let cursor = state.cursor();
+    state.delete_range(cursor.saturating_sub(len as _)..cursor);
+    state.insert_str(text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_start_a_word() {
+        assert_eq!(
+            markdown_token("thanks @jay"),
+            Some((CompletionKind::Mention, "jay"))
+        );
+        assert_eq!(
+            markdown_token("see (#12"),
+            Some((CompletionKind::Reference, "12"))
+        );
+        assert_eq!(markdown_token("#"), Some((CompletionKind::Reference, "")));
+        assert_eq!(markdown_token("mail me@example.com"), None);
+        assert_eq!(markdown_token("@jay "), None);
+        assert_eq!(markdown_token("@jay/x"), None);
+    }
+
+    #[test]
+    fn list_tokens_are_the_last_value() {
+        assert_eq!(csv_token("bug, docu"), "docu");
+        assert_eq!(csv_token("bug,"), "");
+        assert_eq!(csv_token("enhancement"), "enhancement");
+    }
+
+    #[test]
+    fn prefix_matches_rank_first() {
+        let choices = ["good first issue", "docs", "dependencies", "bug"];
+        assert_eq!(rank("d", choices), vec![1, 2, 0]);
+        assert_eq!(rank("gfi", choices), vec![0]);
+        assert_eq!(rank("", choices), vec![0, 1, 2, 3]);
+    }
+}
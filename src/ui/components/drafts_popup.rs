@@ -0,0 +1,184 @@
+//! The popup listing unsent comments and issues of the repository, opened from
+//! the issue list to resume or discard them.
+
+use chrono::{DateTime, Local};
+use rat_widget::event::ct_event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, Paragraph,
+        StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{line, span};
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::drafts::{Draft, DraftKey, Drafts};
+
+pub(crate) enum DraftsOutcome {
+    Continue,
+    Close,
+    /// Open the issue or composer the draft belongs to.
+    Resume(DraftKey),
+}
+
+/// State of the drafts popup.
+pub(crate) struct DraftsPopupState {
+    /// The drafts, newest first, with the title of their issue if it is known.
+    entries: Vec<(DraftKey, Draft, Option<String>)>,
+    list_state: TuiListState,
+    /// Issue being fetched so that its draft can be resumed.
+    pub(crate) opening: Option<u64>,
+    pub(crate) throbber_state: ThrobberState,
+    pub(crate) error: Option<String>,
+}
+
+impl DraftsPopupState {
+    /// Lists the drafts of the repository. `title_of` looks up the titles of
+    /// issues that are already loaded.
+    pub(crate) fn open(drafts: &Drafts, title_of: impl Fn(u64) -> Option<String>) -> Self {
+        let entries = drafts
+            .list()
+            .into_iter()
+            .map(|(key, draft)| {
+                let title = match key {
+                    DraftKey::Comment(number) => title_of(number),
+                    DraftKey::NewIssue => None,
+                };
+                (key, draft, title)
+            })
+            .collect::<Vec<_>>();
+        Self {
+            list_state: TuiListState::default().with_selected((!entries.is_empty()).then_some(0)),
+            entries,
+            opening: None,
+            throbber_state: ThrobberState::default(),
+            error: None,
+        }
+    }
+
+    /// Handles a key press.
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &crossterm::event::Event,
+        drafts: &Drafts,
+    ) -> DraftsOutcome {
+        if self.opening.is_some() {
+            if matches!(event, ct_event!(keycode press Esc)) {
+                return DraftsOutcome::Close;
+            }
+            return DraftsOutcome::Continue;
+        }
+        let selected = self.list_state.selected();
+        match event {
+            ct_event!(keycode press Esc) => return DraftsOutcome::Close,
+            ct_event!(keycode press Up) => self.list_state.select_previous(),
+            ct_event!(keycode press Down) => self.list_state.select_next(),
+            ct_event!(keycode press Enter) => {
+                if let Some((key, _, _)) = selected.and_then(|idx| self.entries.get(idx)) {
+                    return DraftsOutcome::Resume(*key);
+                }
+            }
+            ct_event!(key press 'd') | ct_event!(keycode press Delete) => {
+                let Some(idx) = selected.filter(|idx| *idx < self.entries.len()) else {
+                    return DraftsOutcome::Continue;
+                };
+                let (key, _, _) = self.entries.remove(idx);
+                drafts.discard(key);
+                self.error = None;
+                if self.entries.is_empty() {
+                    self.list_state.select(None);
+                } else {
+                    self.list_state
+                        .select(Some(idx.min(self.entries.len() - 1)));
+                }
+            }
+            _ => {}
+        }
+        DraftsOutcome::Continue
+    }
+}
+
+fn draft_item(key: DraftKey, draft: &Draft, title: Option<&str>) -> ListItem<'static> {
+    let heading = match key {
+        DraftKey::Comment(number) => line![
+            span!("#{number} ").dim(),
+            span!(title.unwrap_or("Comment").to_string()).bold(),
+        ],
+        DraftKey::NewIssue => line![
+            span!("New issue ").dim(),
+            span!(if draft.title.trim().is_empty() {
+                "Untitled".to_string()
+            } else {
+                draft.title.trim().to_string()
+            })
+            .bold(),
+        ],
+    };
+    let preview = draft
+        .body
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("")
+        .to_string();
+    let saved = DateTime::from_timestamp(draft.saved_at as i64, 0)
+        .map(|at| {
+            at.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    ListItem::new(vec![
+        heading,
+        line![span!("  saved {saved}  ").dim(), span!(preview).italic()],
+    ])
+}
+
+pub(crate) fn render_drafts_popup(popup: &mut DraftsPopupState, area: Rect, buf: &mut Buffer) {
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(50));
+    Clear.render(popup_area, buf);
+    let mut title = "Drafts".to_string();
+    if let Some(err) = &popup.error {
+        title.push_str(" | ");
+        title.push_str(err);
+    }
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title(title)
+        .title_bottom("↑/↓: select  Enter: resume  d: discard  Esc: close");
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    if popup.entries.is_empty() {
+        Paragraph::new(span!("No drafts for this repository").dim()).render(inner, buf);
+    } else {
+        let items = popup
+            .entries
+            .iter()
+            .map(|(key, draft, title)| draft_item(*key, draft, title.as_deref()))
+            .collect::<Vec<_>>();
+        let list = TuiList::new(items)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        StatefulWidget::render(list, inner, buf, &mut popup.list_state);
+    }
+
+    if let Some(number) = popup.opening {
+        let title_area = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y,
+            width: 20.min(popup_area.width.saturating_sub(2)),
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label(format!("Opening #{number}"))
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, title_area, buf, &mut popup.throbber_state);
+    }
+}
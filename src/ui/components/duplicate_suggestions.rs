@@ -0,0 +1,281 @@
+//! Existing issues whose titles match the one being composed, shown next to
+//! the issue composer to head off duplicates.
+
+use std::time::Duration;
+
+use octocrab::models::{
+    IssueState,
+    issues::{Issue, IssueStateReason},
+};
+use rat_widget::{
+    event::ct_event,
+    focus::{FocusBuilder, FocusFlag, Navigation},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{
+        Block, List as TuiList, ListItem, ListState as TuiListState, Paragraph, StatefulWidget,
+        Widget, Wrap,
+    },
+};
+use ratatui_macros::{line, span};
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    backend::{Backend, SearchParams},
+    ui::Action,
+};
+
+/// How long the title has to stay unchanged before it is searched for.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+const MAX_MATCHES: u8 = 5;
+
+/// The words of `title` worth searching for, or `None` if there are too few.
+/// The title prefix of the template the issue was started from is left out.
+pub(crate) fn suggestion_query(title: &str, template_title: Option<&str>) -> Option<String> {
+    let title = template_title
+        .and_then(|prefix| title.strip_prefix(prefix.trim_end()))
+        .unwrap_or(title);
+    let words = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    if words.iter().map(String::len).sum::<usize>() < 4 {
+        return None;
+    }
+    Some(words.join(" "))
+}
+
+/// State of the side panel with possible duplicates.
+pub(crate) struct DuplicateSuggestions {
+    /// Query of the latest search, and the one results must match.
+    query: Option<String>,
+    matches: Vec<Issue>,
+    list_state: TuiListState,
+    pub(crate) focus: FocusFlag,
+    area: Rect,
+    loading: bool,
+    throbber_state: ThrobberState,
+    error: Option<String>,
+    cancel: CancellationToken,
+}
+
+impl DuplicateSuggestions {
+    pub(crate) fn new() -> Self {
+        Self {
+            query: None,
+            matches: Vec::new(),
+            list_state: TuiListState::default(),
+            focus: FocusFlag::new().with_name("duplicate_suggestions"),
+            area: Rect::default(),
+            loading: false,
+            throbber_state: ThrobberState::default(),
+            error: None,
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.cancel.cancel();
+        self.query = None;
+        self.matches.clear();
+        self.list_state.select(None);
+        self.loading = false;
+        self.error = None;
+        self.focus.set(false);
+    }
+
+    /// Whether there is anything to show.
+    pub(crate) fn is_visible(&self) -> bool {
+        self.query.is_some()
+    }
+
+    pub(crate) fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Searches for issues matching `query` once it has settled. An earlier
+    /// search that is still waiting or running is dropped.
+    pub(crate) fn search(
+        &mut self,
+        query: Option<String>,
+        repo: &str,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) {
+        if query == self.query {
+            return;
+        }
+        let Some(query) = query else {
+            self.clear();
+            return;
+        };
+        self.cancel.cancel();
+        self.cancel = CancellationToken::new();
+        self.query = Some(query.clone());
+        self.loading = true;
+        let cancel = self.cancel.clone();
+        let search = format!("{query} repo:{repo} is:issue in:title");
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+            let result = tokio::select! {
+                _ = cancel.cancelled() => return,
+                result = async {
+                    tokio::time::sleep(DEBOUNCE).await;
+                    backend
+                        .search_issues(SearchParams::new(search).per_page(MAX_MATCHES))
+                        .await
+                } => result,
+            };
+            let result = result
+                .map(|page| page.items)
+                .map_err(|err| err.to_string().replace('\n', " "));
+            let _ = action_tx
+                .send(Action::DuplicateSuggestionsLoaded { query, result })
+                .await;
+        });
+    }
+
+    /// Applies search results for the current query.
+    pub(crate) fn update(&mut self, action: &Action) {
+        let Action::DuplicateSuggestionsLoaded { query, result } = action else {
+            return;
+        };
+        if self.query.as_ref() != Some(query) {
+            return;
+        }
+        self.loading = false;
+        match result {
+            Ok(issues) => {
+                self.error = None;
+                self.matches = issues.clone();
+                self.list_state
+                    .select((!self.matches.is_empty()).then_some(0));
+            }
+            Err(message) => self.error = Some(message.clone()),
+        }
+    }
+
+    pub(crate) fn tick(&mut self) {
+        if self.loading {
+            self.throbber_state.calc_next();
+        }
+    }
+
+    /// Handles a key press while the panel has focus. Returns the issue to
+    /// open, if any.
+    pub(crate) fn handle_event(&mut self, event: &crossterm::event::Event) -> Option<Issue> {
+        if !self.focus.get() || self.matches.is_empty() {
+            return None;
+        }
+        let selected = self.list_state.selected().unwrap_or(0);
+        match event {
+            ct_event!(keycode press Up) => self.list_state.select(Some(selected.saturating_sub(1))),
+            ct_event!(keycode press Down) => self
+                .list_state
+                .select(Some((selected + 1).min(self.matches.len() - 1))),
+            ct_event!(keycode press Enter) => return self.matches.get(selected).cloned(),
+            _ => {}
+        }
+        None
+    }
+
+    pub(crate) fn build_focus(&self, builder: &mut FocusBuilder) {
+        if !self.matches.is_empty() {
+            builder.widget_with_flags(self.focus.clone(), self.area, 0, Navigation::Regular);
+        }
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.area = area;
+        let mut block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(if self.focus.get() {
+                Style::new().yellow()
+            } else {
+                Style::new().dim()
+            });
+        if !self.loading {
+            block = block.title("Possible duplicates");
+        }
+        if self.focus.get() {
+            block = block.title_bottom("↑/↓: select  Enter: open");
+        }
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if let Some(err) = &self.error {
+            Paragraph::new(span!(err.as_str()).red())
+                .wrap(Wrap { trim: true })
+                .render(inner, buf);
+        } else if self.matches.is_empty() && !self.loading {
+            Paragraph::new(span!("No similar issues").dim()).render(inner, buf);
+        } else {
+            let items = self.matches.iter().map(match_item).collect::<Vec<_>>();
+            let mut list = TuiList::new(items);
+            if self.focus.get() {
+                list = list.highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+            }
+            StatefulWidget::render(list, inner, buf, &mut self.list_state);
+        }
+
+        if self.loading {
+            let title_area = Rect {
+                x: area.x + 1,
+                y: area.y,
+                width: 12,
+                height: 1,
+            };
+            let throbber = Throbber::default()
+                .label("Searching")
+                .style(Style::new().fg(Color::Cyan))
+                .throbber_set(BRAILLE_SIX_DOUBLE)
+                .use_type(WhichUse::Spin);
+            StatefulWidget::render(throbber, title_area, buf, &mut self.throbber_state);
+        }
+    }
+}
+
+fn match_item(issue: &Issue) -> ListItem<'static> {
+    let state = match (&issue.state, &issue.state_reason) {
+        (IssueState::Open, _) => span!("open").green(),
+        (_, Some(IssueStateReason::Duplicate)) => span!("duplicate").dim(),
+        _ => span!("closed").magenta(),
+    };
+    ListItem::new(vec![
+        line![span!(issue.title.clone()).bold()],
+        line![
+            span!("#{} ", issue.number).dim(),
+            state,
+            span!("  {} comments", issue.comments).dim(),
+        ],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_titles_are_not_searched() {
+        assert_eq!(suggestion_query("a b", None), None);
+        assert_eq!(
+            suggestion_query("Crash on: start-up!", None).as_deref(),
+            Some("crash on start up")
+        );
+    }
+
+    #[test]
+    fn template_prefixes_are_left_out() {
+        assert_eq!(suggestion_query("[Bug]: ", Some("[Bug]: ")), None);
+        assert_eq!(
+            suggestion_query("[Bug]: crash", Some("[Bug]: ")).as_deref(),
+            Some("crash")
+        );
+    }
+}
@@ -2,8 +2,8 @@ use async_trait::async_trait;
 use crossterm::event;
 use futures::{StreamExt, stream};
 use octocrab::models::{
-    CommentId, Event as IssueEvent, IssueState, issues::Comment as ApiComment,
-    reactions::ReactionContent, timelines::TimelineEvent,
+    Event as IssueEvent, IssueState, issues::Comment as ApiComment, reactions::ReactionContent,
+    timelines::TimelineEvent,
 };
 use pulldown_cmark::{
     BlockQuoteKind, CodeBlockKind, Event as MdEvent, Options, Parser, Tag, TagEnd, TextMergeStream,
@@ -35,17 +35,32 @@ use syntect::{
 };
 use textwrap::{core::display_width, wrap};
 use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
-use tracing::trace;
+use tokio_util::sync::CancellationToken;
+use tracing::{trace, warn};
 
 use crate::{
-    app::GITHUB_CLIENT,
+    backend::{
+        Backend, Conversation, ConversationComment, ConversationEvent, PageCursor, ReviewThread,
+    },
+    drafts::{Draft, DraftKey, Drafts},
     errors::AppError,
+    github::GithubHost,
+    outbox::{Outbox, PendingOp},
     ui::{
-        Action,
+        Action, CloseIssueReason,
         components::{
             Component,
+            completion::{Completer, CompletionKey, textarea_before_cursor, textarea_replace},
             help::HelpElementKind,
+            issue_edit::{
+                IssueTitleEditState, edit_body_in_editor, render_issue_title_edit, reopen_issue,
+                run_external_editor,
+            },
             issue_list::{IssueClosePopupState, MainScreen, render_issue_close_popup},
+            merge_popup::{MergePopupState, render_merge_popup},
+            milestone_picker::{
+                MilestonePickerState, PickerOutcome, PickerTarget, render_milestone_picker,
+            },
         },
         layout::Layout,
         toast_action,
@@ -63,24 +78,42 @@ pub const HELP: &[HelpElementKind] = &[
     crate::help_keybind!("t", "toggle timeline events"),
     crate::help_keybind!("f", "toggle fullscreen body view"),
     crate::help_keybind!("C", "close selected issue"),
+    crate::help_keybind!("O", "reopen closed issue"),
+    crate::help_keybind!("T", "edit issue title"),
+    crate::help_keybind!("E", "edit issue description in external editor"),
+    crate::help_keybind!("d", "view pull request diff"),
+    crate::help_keybind!("c", "view pull request CI checks"),
+    crate::help_keybind!("M", "merge pull request"),
+    crate::help_keybind!("m", "set milestone"),
+    crate::help_keybind!("v", "resolve / unresolve selected review thread"),
     crate::help_keybind!("l", "copy link to selected message"),
     crate::help_keybind!("Enter (popup)", "confirm close reason"),
+    crate::help_keybind!(
+        "Duplicate (popup)",
+        "enter the original issue number, then Enter"
+    ),
     crate::help_keybind!("Ctrl+P", "toggle comment input/preview"),
-    crate::help_keybind!("e", "edit selected comment in external editor"),
+    crate::help_keybind!(
+        "e",
+        "edit selected comment or description in external editor"
+    ),
     crate::help_keybind!("r", "add reaction to selected comment"),
     crate::help_keybind!("R", "remove reaction from selected comment"),
     crate::help_keybind!("Ctrl+Enter / Alt+Enter", "send comment"),
     crate::help_keybind!("Esc", "exit fullscreen / return to issue list"),
 ];
 
-struct SyntectAssets {
-    syntaxes: SyntaxSet,
-    theme: Theme,
+/// Items requested per page of comments or timeline events.
+const PAGE_SIZE: u8 = 100;
+
+pub(crate) struct SyntectAssets {
+    pub(crate) syntaxes: SyntaxSet,
+    pub(crate) theme: Theme,
 }
 
 static SYNTECT_ASSETS: OnceLock<SyntectAssets> = OnceLock::new();
 
-fn syntect_assets() -> &'static SyntectAssets {
+pub(crate) fn syntect_assets() -> &'static SyntectAssets {
     SYNTECT_ASSETS.get_or_init(|| {
         let syntaxes = SyntaxSet::load_defaults_nonewlines();
         let theme_set = ThemeSet::load_defaults();
@@ -102,6 +135,12 @@ pub struct IssueConversationSeed {
     pub created_ts: i64,
     pub body: Option<Arc<str>>,
     pub title: Option<Arc<str>>,
+    /// Comment count reported by the issue, used to show how many are still loading.
+    pub comments: u32,
+    pub is_pull_request: bool,
+    pub state: IssueState,
+    /// Number of the milestone the issue belongs to.
+    pub milestone: Option<u64>,
 }
 
 impl IssueConversationSeed {
@@ -113,6 +152,10 @@ impl IssueConversationSeed {
             created_ts: issue.created_at.timestamp(),
             body: issue.body.as_ref().map(|b| Arc::<str>::from(b.as_str())),
             title: Some(Arc::<str>::from(issue.title.as_str())),
+            comments: issue.comments,
+            is_pull_request: issue.pull_request.is_some(),
+            state: issue.state.clone(),
+            milestone: issue.milestone.as_ref().map(|m| m.number as u64),
         }
     }
 }
@@ -141,6 +184,18 @@ impl CommentView {
             my_reactions: None,
         }
     }
+
+    pub fn from_conversation(comment: ConversationComment) -> Self {
+        Self {
+            id: comment.id,
+            author: Arc::<str>::from(comment.author),
+            created_at: Arc::<str>::from(comment.created_at.format("%Y-%m-%d %H:%M").to_string()),
+            created_ts: comment.created_at.timestamp(),
+            body: Arc::<str>::from(comment.body),
+            reactions: Some(comment.reactions),
+            my_reactions: Some(comment.my_reactions),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -166,31 +221,53 @@ impl TimelineEventView {
 
         let id = event.id.map(|id| id.0).unwrap_or(fallback_id);
         let when = event.created_at.or(event.updated_at).or(event.submitted_at);
+        let actor = event.actor.as_ref().or(event.user.as_ref());
+        let details = timeline_event_details(&event);
+        Some(Self::new(
+            id,
+            when,
+            actor.map(|a| a.login.as_str()),
+            event.event,
+            details,
+        ))
+    }
+
+    fn from_conversation(event: ConversationEvent, id: u64) -> Self {
+        Self::new(
+            id,
+            Some(event.created_at),
+            event.actor.as_deref(),
+            event.event,
+            event.details,
+        )
+    }
+
+    fn new(
+        id: u64,
+        when: Option<chrono::DateTime<chrono::Utc>>,
+        actor: Option<&str>,
+        event: IssueEvent,
+        details: String,
+    ) -> Self {
         let created_ts = when.map(|d| d.timestamp()).unwrap_or(0);
         let created_at = Arc::<str>::from(
             when.map(|d| d.format("%Y-%m-%d %H:%M").to_string())
                 .unwrap_or_else(|| "unknown time".to_string()),
         );
-        let actor = event
-            .actor
-            .as_ref()
-            .or(event.user.as_ref())
-            .map(|a| Arc::<str>::from(a.login.as_str()))
-            .unwrap_or_else(|| Arc::<str>::from("github"));
-        let (icon, action) = timeline_event_meta(&event.event);
-        let details = timeline_event_details(&event);
+        let actor = Arc::<str>::from(actor.unwrap_or("github"));
+        let (icon, action) = timeline_event_meta(&event);
         let summary = Arc::<str>::from(format!("{} {}", actor.as_ref(), action));
 
-        Some(Self {
+        Self {
             id,
             created_at,
             created_ts,
             actor,
-            event: event.event,
+            event,
             icon,
             summary,
             details: Arc::<str>::from(details),
-        })
+        }
     }
 }
 
@@ -202,20 +279,36 @@ pub struct IssueConversation {
     cache_comments: Vec<CommentView>,
     timeline_cache_number: Option<u64>,
     cache_timeline: Vec<TimelineEventView>,
+    /// Review threads of the current pull request, sorted by file and line.
+    review_threads: Vec<ReviewThread>,
+    review_threads_number: Option<u64>,
+    thread_cache: HashMap<usize, MarkdownRender>,
     markdown_cache: HashMap<u64, MarkdownRender>,
     body_cache: Option<MarkdownRender>,
     body_cache_number: Option<u64>,
     markdown_width: usize,
     loading: HashSet<u64>,
+    /// Issues whose first page of comments is shown while later pages load.
+    paging: HashSet<u64>,
     timeline_loading: HashSet<u64>,
+    /// Issues whose first page of timeline is shown while later pages load.
+    timeline_paging: HashSet<u64>,
+    /// Cancels loading the conversation when the issue is left.
+    conversation_cancel: CancellationToken,
     posting: bool,
     error: Option<String>,
     post_error: Option<String>,
     reaction_error: Option<String>,
     close_error: Option<String>,
     timeline_error: Option<String>,
+    review_threads_error: Option<String>,
+    host: GithubHost,
     owner: String,
     repo: String,
+    backend: Backend,
+    outbox: Outbox,
+    drafts: Drafts,
+    completer: Completer,
     current_user: String,
     list_state: ListState<RowSelection>,
     message_keys: Vec<MessageKey>,
@@ -231,6 +324,9 @@ pub struct IssueConversation {
     body_paragraph_state: ParagraphState,
     reaction_mode: Option<ReactionMode>,
     close_popup: Option<IssueClosePopupState>,
+    merge_popup: Option<MergePopupState>,
+    title_edit: Option<IssueTitleEditState>,
+    milestone_picker: Option<MilestonePickerState>,
     index: usize,
 }
 
@@ -246,6 +342,8 @@ enum MessageKey {
     IssueBody(u64),
     Comment(u64),
     Timeline(u64),
+    /// Index into the review threads.
+    ReviewThread(usize),
 }
 
 #[derive(Debug, Clone, Default)]
@@ -302,21 +400,33 @@ impl IssueConversation {
             cache_comments: Vec::new(),
             timeline_cache_number: None,
             cache_timeline: Vec::new(),
+            review_threads: Vec::new(),
+            review_threads_number: None,
+            thread_cache: HashMap::new(),
             markdown_cache: HashMap::new(),
             paragraph_state: Default::default(),
             body_cache: None,
             body_cache_number: None,
             markdown_width: 0,
             loading: HashSet::new(),
+            paging: HashSet::new(),
             timeline_loading: HashSet::new(),
+            timeline_paging: HashSet::new(),
+            conversation_cancel: CancellationToken::new(),
             posting: false,
             error: None,
             post_error: None,
             reaction_error: None,
             close_error: None,
             timeline_error: None,
+            review_threads_error: None,
+            host: app_state.host,
             owner: app_state.owner,
             repo: app_state.repo,
+            backend: app_state.backend,
+            outbox: app_state.outbox,
+            drafts: app_state.drafts,
+            completer: Completer::default(),
             current_user: app_state.current_user,
             list_state: ListState::default(),
             message_keys: Vec::new(),
@@ -331,6 +441,9 @@ impl IssueConversation {
             body_paragraph_state: ParagraphState::default(),
             reaction_mode: None,
             close_popup: None,
+            merge_popup: None,
+            title_edit: None,
+            milestone_picker: None,
             index: 0,
         }
     }
@@ -405,6 +518,9 @@ impl IssueConversation {
             } else if let Some(err) = &self.timeline_error {
                 title.push_str(" | ");
                 title.push_str(err);
+            } else if let Some(err) = &self.review_threads_error {
+                title.push_str(" | ");
+                title.push_str(err);
             }
             list_block = list_block.title(title);
         }
@@ -482,6 +598,18 @@ impl IssueConversation {
             StatefulWidget::render(throbber, title_area, buf, &mut self.post_throbber_state);
         }
         self.render_close_popup(area.main_content, buf);
+        if let Some(popup) = self.merge_popup.as_mut() {
+            render_merge_popup(popup, area.main_content, buf);
+        }
+        if let Some(edit) = self.title_edit.as_mut() {
+            render_issue_title_edit(edit, area.main_content, buf);
+        }
+        if let Some(picker) = self.milestone_picker.as_mut() {
+            render_milestone_picker(picker, area.main_content, buf);
+        }
+        if self.input_state.is_focused() {
+            self.completer.render(self.input_state.screen_cursor(), buf);
+        }
     }
 
     fn build_items(&mut self, list_area: Rect, body_area: Rect) -> Vec<ListItem<'static>> {
@@ -493,6 +621,7 @@ impl IssueConversation {
         if self.markdown_width != width {
             self.markdown_width = width;
             self.markdown_cache.clear();
+            self.thread_cache.clear();
             self.body_cache = None;
             self.body_cache_number = None;
         }
@@ -585,9 +714,32 @@ impl IssueConversation {
                             self.message_keys.push(MessageKey::Timeline(entry.id));
                         }
                     }
-                    MessageKey::IssueBody(_) => {}
+                    MessageKey::IssueBody(_) | MessageKey::ReviewThread(_) => {}
+                }
+            }
+
+            if self.review_threads_number == Some(seed.number) {
+                for (idx, thread) in self.review_threads.iter().enumerate() {
+                    self.thread_cache.entry(idx).or_insert_with(|| {
+                        render_markdown(&review_thread_markdown(thread), width, 2)
+                    });
+                    items.push(build_review_thread_item(thread, preview_width));
+                    self.message_keys.push(MessageKey::ReviewThread(idx));
                 }
             }
+
+            if self.paging.contains(&seed.number) {
+                let remaining = (seed.comments as usize).saturating_sub(self.cache_comments.len());
+                let marker = if remaining > 0 {
+                    format!("{remaining} more comments loading...")
+                } else {
+                    "More comments loading...".to_string()
+                };
+                items.push(ListItem::new(line![Span::styled(
+                    marker,
+                    Style::new().dim().italic()
+                )]));
+            }
         }
 
         if items.is_empty() {
@@ -651,6 +803,7 @@ impl IssueConversation {
                 }
             }
             MessageKey::Comment(id) => self.markdown_cache.get(id),
+            MessageKey::ReviewThread(idx) => self.thread_cache.get(idx),
             MessageKey::Timeline(_) => None,
         }
     }
@@ -728,6 +881,15 @@ impl IssueConversation {
             MessageKey::Comment(id) => Some(*id),
             MessageKey::IssueBody(_) => None,
             MessageKey::Timeline(_) => None,
+            MessageKey::ReviewThread(_) => None,
+        }
+    }
+
+    fn selected_review_thread(&self) -> Option<&ReviewThread> {
+        let selected = self.list_state.selected_checked()?;
+        match self.message_keys.get(selected)? {
+            MessageKey::ReviewThread(idx) => self.review_threads.get(*idx),
+            _ => None,
         }
     }
 
@@ -745,26 +907,9 @@ impl IssueConversation {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        if action_tx
-            .send(Action::EditorModeChanged(true))
-            .await
-            .is_err()
-        {
-            return;
-        }
 
         tokio::spawn(async move {
-            let result = tokio::task::spawn_blocking(move || {
-                ratatui::restore();
-                let edited = edit::edit(&initial_body).map_err(|err| err.to_string());
-                let _ = ratatui::init();
-                edited
-            })
-            .await
-            .map_err(|err| err.to_string())
-            .and_then(|edited| edited.map_err(|err| err.replace('\n', " ")));
-
-            let _ = action_tx.send(Action::EditorModeChanged(false)).await;
+            let result = run_external_editor(&action_tx, initial_body).await;
             let _ = action_tx
                 .send(Action::IssueCommentEditFinished {
                     issue_number,
@@ -780,23 +925,10 @@ impl IssueConversation {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueCommentEditFinished {
-                        issue_number,
-                        comment_id,
-                        result: Err("GitHub client not initialized.".to_string()),
-                    })
-                    .await;
-                return;
-            };
-
-            let handler = client.inner().issues(owner, repo);
-            match handler.update_comment(CommentId(comment_id), body).await {
+            match backend.update_comment(comment_id, body).await {
                 Ok(comment) => {
                     let _ = action_tx
                         .send(Action::IssueCommentPatched {
@@ -843,6 +975,63 @@ impl IssueConversation {
         self.close_popup = Some(IssueClosePopupState::new(seed.number));
     }
 
+    fn open_merge_popup(&mut self) {
+        let Some(seed) = self.current.as_ref().filter(|seed| seed.is_pull_request) else {
+            return;
+        };
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        self.merge_popup = Some(MergePopupState::open(
+            seed.number,
+            self.title.clone().unwrap_or_default(),
+            &self.backend,
+            action_tx,
+        ));
+    }
+
+    fn handle_merge_popup_event(&mut self, event: &event::Event) -> bool {
+        let (Some(popup), Some(action_tx)) = (self.merge_popup.as_mut(), self.action_tx.as_ref())
+        else {
+            return false;
+        };
+        if !popup.handle_event(event, &self.backend, action_tx) {
+            self.merge_popup = None;
+        }
+        true
+    }
+
+    fn handle_title_edit_event(&mut self, event: &event::Event) -> bool {
+        let (Some(edit), Some(action_tx)) = (self.title_edit.as_mut(), self.action_tx.as_ref())
+        else {
+            return false;
+        };
+        if !edit.handle_event(event, &self.backend, action_tx) {
+            self.title_edit = None;
+        }
+        true
+    }
+
+    fn handle_milestone_picker_event(&mut self, event: &event::Event) -> bool {
+        let (Some(picker), Some(action_tx)) =
+            (self.milestone_picker.as_mut(), self.action_tx.as_ref())
+        else {
+            return false;
+        };
+        if let PickerOutcome::Close = picker.handle_event(event, &self.backend, action_tx) {
+            self.milestone_picker = None;
+        }
+        true
+    }
+
+    fn edit_issue_body(&self) {
+        let (Some(seed), Some(action_tx)) = (self.current.as_ref(), self.action_tx.clone()) else {
+            return;
+        };
+        let body = seed.body.as_deref().unwrap_or_default().to_string();
+        edit_body_in_editor(self.backend.clone(), action_tx, seed.number, body);
+    }
+
     fn render_close_popup(&mut self, area: Rect, buf: &mut Buffer) {
         let Some(popup) = self.close_popup.as_mut() else {
             return;
@@ -858,6 +1047,7 @@ impl IssueConversation {
             return;
         }
         let reason = popup.selected_reason();
+        let duplicate_of = popup.duplicate_of();
         let number = popup.issue_number;
         popup.loading = true;
         popup.error = None;
@@ -867,26 +1057,18 @@ impl IssueConversation {
             popup.error = Some("Action channel unavailable.".to_string());
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let outbox = self.outbox.clone();
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueCloseError {
-                        number,
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
+            let result = match duplicate_of {
+                Some(original) => backend.close_as_duplicate(number, original).await,
+                None => {
+                    backend
+                        .set_issue_state(number, IssueState::Closed, Some(reason.to_octocrab()))
+                        .await
+                }
             };
-            let issues = client.inner().issues(owner, repo);
-            match issues
-                .update(number)
-                .state(IssueState::Closed)
-                .state_reason(reason.to_octocrab())
-                .send()
-                .await
-            {
+            match result {
                 Ok(issue) => {
                     let _ = action_tx
                         .send(Action::IssueCloseSuccess {
@@ -895,6 +1077,20 @@ impl IssueConversation {
                         .await;
                 }
                 Err(err) => {
+                    let op = PendingOp::Close {
+                        number,
+                        reason: reason.to_octocrab(),
+                    };
+                    if duplicate_of.is_none() && outbox.enqueue_if_retryable(op, &err) {
+                        let _ = action_tx.send(Action::IssueCloseQueued { number }).await;
+                        let _ = action_tx
+                            .send(toast_action(
+                                "Close queued; it will be retried.",
+                                ToastType::Warning,
+                            ))
+                            .await;
+                        return;
+                    }
                     let _ = action_tx
                         .send(Action::IssueCloseError {
                             number,
@@ -916,6 +1112,15 @@ impl IssueConversation {
             }
             return true;
         }
+        if popup.is_choosing_original() {
+            let Some(action_tx) = self.action_tx.as_ref() else {
+                return true;
+            };
+            if popup.handle_original_event(event, &self.backend, action_tx) {
+                self.submit_close_popup().await;
+            }
+            return true;
+        }
         if matches!(event, ct_event!(keycode press Esc)) {
             self.close_popup = None;
             return true;
@@ -929,6 +1134,10 @@ impl IssueConversation {
             return true;
         }
         if matches!(event, ct_event!(keycode press Enter)) {
+            if popup.selected_reason() == CloseIssueReason::Duplicate {
+                popup.choose_original();
+                return true;
+            }
             self.submit_close_popup().await;
             return true;
         }
@@ -1075,21 +1284,28 @@ impl IssueConversation {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let outbox = self.outbox.clone();
         let current_user = self.current_user.clone();
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueReactionEditError {
-                        comment_id,
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
-            };
-            let handler = client.inner().issues(owner, repo);
-            if let Err(err) = handler.create_comment_reaction(comment_id, content).await {
+            if let Err(err) = backend
+                .add_comment_reaction(comment_id, content.clone())
+                .await
+            {
+                let op = PendingOp::AddReaction {
+                    comment_id,
+                    content,
+                    login: current_user,
+                };
+                if outbox.enqueue_if_retryable(op, &err) {
+                    let _ = action_tx
+                        .send(toast_action(
+                            "Reaction queued; it will be retried.",
+                            ToastType::Warning,
+                        ))
+                        .await;
+                    return;
+                }
                 let _ = action_tx
                     .send(Action::IssueReactionEditError {
                         comment_id,
@@ -1099,10 +1315,9 @@ impl IssueConversation {
                 return;
             }
 
-            match handler.list_comment_reactions(comment_id).send().await {
-                Ok(mut page) => {
-                    let (counts, mine) =
-                        to_reaction_snapshot(std::mem::take(&mut page.items), &current_user);
+            match backend.list_comment_reactions(comment_id).await {
+                Ok(items) => {
+                    let (counts, mine) = to_reaction_snapshot(items, &current_user);
                     let mut reactions = HashMap::new();
                     let mut own_reactions = HashMap::new();
                     reactions.insert(comment_id, counts);
@@ -1130,30 +1345,27 @@ impl IssueConversation {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let outbox = self.outbox.clone();
         let current_user = self.current_user.clone();
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueReactionEditError {
-                        comment_id,
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
+            let queue_removal = |err: &AppError| {
+                let op = PendingOp::RemoveReaction {
+                    comment_id,
+                    content: content.clone(),
+                    login: current_user.clone(),
+                };
+                outbox.enqueue_if_retryable(op, err)
             };
-            let handler = client.inner().issues(owner, repo);
-            match handler.list_comment_reactions(comment_id).send().await {
-                Ok(mut page) => {
-                    let mut items = std::mem::take(&mut page.items);
+            match backend.list_comment_reactions(comment_id).await {
+                Ok(mut items) => {
                     let to_delete = items
                         .iter()
                         .find(|reaction| {
                             reaction.content == content
                                 && reaction.user.login.eq_ignore_ascii_case(&current_user)
                         })
-                        .map(|reaction| reaction.id);
+                        .map(|reaction| reaction.id.0);
 
                     let Some(reaction_id) = to_delete else {
                         let _ = action_tx
@@ -1165,10 +1377,19 @@ impl IssueConversation {
                         return;
                     };
 
-                    if let Err(err) = handler
-                        .delete_comment_reaction(comment_id, reaction_id)
+                    if let Err(err) = backend
+                        .delete_comment_reaction(comment_id, reaction_id, content.clone())
                         .await
                     {
+                        if queue_removal(&err) {
+                            let _ = action_tx
+                                .send(toast_action(
+                                    "Reaction removal queued; it will be retried.",
+                                    ToastType::Warning,
+                                ))
+                                .await;
+                            return;
+                        }
                         let _ = action_tx
                             .send(Action::IssueReactionEditError {
                                 comment_id,
@@ -1205,6 +1426,15 @@ impl IssueConversation {
                         .await;
                 }
                 Err(err) => {
+                    if queue_removal(&err) {
+                        let _ = action_tx
+                            .send(toast_action(
+                                "Reaction removal queued; it will be retried.",
+                                ToastType::Warning,
+                            ))
+                            .await;
+                        return;
+                    }
                     let _ = action_tx
                         .send(Action::IssueReactionEditError {
                             comment_id,
@@ -1216,6 +1446,8 @@ impl IssueConversation {
         });
     }
 
+    /// Loads the comments and the timeline of an issue. Trackers with a
+    /// conversation query return both in one request per page.
     async fn fetch_comments(&mut self, number: u64) {
         if self.loading.contains(&number) {
             return;
@@ -1223,160 +1455,229 @@ impl IssueConversation {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
         let current_user = self.current_user.clone();
         self.loading.insert(number);
+        self.timeline_loading.insert(number);
         self.error = None;
+        self.timeline_error = None;
+        // A previous load of this issue would append to the new one.
+        self.conversation_cancel.cancel();
+        self.conversation_cancel = CancellationToken::new();
+        let cancel = self.conversation_cancel.clone();
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
+            let load = async {
+                let first = match backend
+                    .conversation(number, &PageCursor::Start, &PageCursor::Start)
+                    .await
+                {
+                    Ok(first) => first,
+                    Err(AppError::GraphQl(err)) => {
+                        warn!(number, error = %err, "conversation query failed, using REST");
+                        None
+                    }
+                    Err(err) => return Err(err),
+                };
+                match first {
+                    Some(conversation) => {
+                        stream_conversation(&backend, &action_tx, number, conversation).await
+                    }
+                    None => {
+                        fetch_comments_rest(&backend, &action_tx, number, &current_user).await?;
+                        fetch_timeline_rest(&backend, &action_tx, number).await
+                    }
+                }
+            };
+            let result = tokio::select! {
+                _ = cancel.cancelled() => return,
+                result = load => result,
+            };
+            if let Err(err) = result {
+                let message = err.to_string().replace('\n', " ");
                 let _ = action_tx
                     .send(Action::IssueCommentsError {
                         number,
-                        message: "GitHub client not initialized.".to_string(),
+                        message: message.clone(),
                     })
                     .await;
-                return;
-            };
-            let handler = client.inner().issues(owner, repo);
-            let page = handler
-                .list_comments(number)
-                .per_page(100u8)
-                .page(1u32)
-                .send()
-                .await;
-
-            match page {
-                Ok(mut p) => {
-                    let comments = std::mem::take(&mut p.items);
-                    let comment_ids = comments.iter().map(|c| c.id.0).collect::<Vec<_>>();
-                    let comments: Vec<CommentView> =
-                        comments.into_iter().map(CommentView::from_api).collect();
-                    trace!("Loaded {} comments for issue {}", comments.len(), number);
-                    let _ = action_tx
-                        .send(Action::IssueCommentsLoaded { number, comments })
-                        .await;
-                    let refer = &handler;
-                    let current_user = current_user.clone();
-                    let reaction_snapshots = stream::iter(comment_ids)
-                        .filter_map(|id| {
-                            let current_user = current_user.clone();
-                            async move {
-                                let reactions = refer.list_comment_reactions(id).send().await;
-                                let mut page = reactions.ok()?;
-                                Some((
-                                    id,
-                                    to_reaction_snapshot(
-                                        std::mem::take(&mut page.items),
-                                        &current_user,
-                                    ),
-                                ))
-                            }
-                        })
-                        .collect::<HashMap<_, _>>()
-                        .await;
-                    let mut reactions = HashMap::with_capacity(reaction_snapshots.len());
-                    let mut own_reactions = HashMap::with_capacity(reaction_snapshots.len());
-                    for (id, (counts, mine)) in reaction_snapshots {
-                        reactions.insert(id, counts);
-                        own_reactions.insert(id, mine);
-                    }
-                    let _ = action_tx
-                        .send(Action::IssueReactionsLoaded {
-                            reactions,
-                            own_reactions,
-                        })
-                        .await;
-                }
-                Err(err) => {
-                    let _ = action_tx
-                        .send(Action::IssueCommentsError {
-                            number,
-                            message: err.to_string().replace('\n', " "),
-                        })
-                        .await;
-                }
+                let _ = action_tx
+                    .send(Action::IssueTimelineError { number, message })
+                    .await;
             }
         });
     }
 
-    async fn fetch_timeline(&mut self, number: u64) {
-        if self.timeline_loading.contains(&number) {
-            return;
+    /// Stops loading the conversation of the issue being left. A partly
+    /// loaded conversation is dropped, so it is fetched again when the issue
+    /// is reopened.
+    fn stop_loading_conversation(&mut self) {
+        self.conversation_cancel.cancel();
+        let comments = self
+            .loading
+            .drain()
+            .chain(self.paging.drain())
+            .collect::<HashSet<_>>();
+        if self
+            .cache_number
+            .is_some_and(|number| comments.contains(&number))
+        {
+            self.cache_number = None;
+            self.cache_comments.clear();
+            self.markdown_cache.clear();
+        }
+        let timeline = self
+            .timeline_loading
+            .drain()
+            .chain(self.timeline_paging.drain())
+            .collect::<HashSet<_>>();
+        if self
+            .timeline_cache_number
+            .is_some_and(|number| timeline.contains(&number))
+        {
+            self.timeline_cache_number = None;
+            self.cache_timeline.clear();
         }
+    }
+
+    fn fetch_review_threads(&mut self, number: u64) {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
-        self.timeline_loading.insert(number);
-        self.timeline_error = None;
-
+        let backend = self.backend.clone();
+        self.review_threads_error = None;
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueTimelineError {
-                        number,
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
+            let action = match backend.review_threads(number).await {
+                Ok(threads) => Action::ReviewThreadsLoaded { number, threads },
+                Err(err) => Action::ReviewThreadsError {
+                    number,
+                    message: err.to_string().replace('\n', " "),
+                },
             };
-            let handler = client.inner().issues(owner, repo);
-            match handler
-                .list_timeline_events(number)
-                .per_page(100u8)
-                .page(1u32)
-                .send()
+            let _ = action_tx.send(action).await;
+        });
+    }
+
+    fn toggle_selected_thread(&mut self) {
+        let Some(number) = self.review_threads_number else {
+            return;
+        };
+        let Some(thread) = self.selected_review_thread() else {
+            return;
+        };
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        let thread_id = thread.id.clone();
+        let resolved = !thread.resolved;
+        tokio::spawn(async move {
+            match backend
+                .set_review_thread_resolved(&thread_id, resolved)
                 .await
             {
-                Ok(mut page) => {
-                    let events = std::mem::take(&mut page.items)
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(idx, event)| {
-                            TimelineEventView::from_api(event, (number << 32) | idx as u64)
-                        })
-                        .collect::<Vec<_>>();
+                Ok(()) => {
                     let _ = action_tx
-                        .send(Action::IssueTimelineLoaded { number, events })
+                        .send(Action::ReviewThreadResolved {
+                            number,
+                            thread_id,
+                            resolved,
+                        })
                         .await;
                 }
                 Err(err) => {
                     let _ = action_tx
-                        .send(Action::IssueTimelineError {
-                            number,
-                            message: err.to_string().replace('\n', " "),
-                        })
+                        .send(toast_action(
+                            format!("Failed to update review thread: {err}"),
+                            ToastType::Error,
+                        ))
                         .await;
                 }
             }
         });
     }
 
+    /// Handles a key press for the completion popup of the comment input.
+    fn handle_completion_event(&mut self, event: &event::Event) -> bool {
+        if !self.input_state.is_focused() {
+            return false;
+        }
+        match self.completer.handle_event(event) {
+            CompletionKey::Ignored => false,
+            CompletionKey::Handled => true,
+            CompletionKey::Accept { token_len, text } => {
+                textarea_replace(&mut self.input_state, token_len, &text);
+                self.save_draft();
+                true
+            }
+        }
+    }
+
+    /// Offers completions for the `@` or `#` token before the cursor.
+    fn refresh_completion(&mut self) {
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        if !self.input_state.is_focused() || self.textbox_state != InputState::Input {
+            self.completer.close();
+            return;
+        }
+        let mut participants = Vec::new();
+        if let Some(seed) = &self.current {
+            participants.push(seed.author.to_string());
+            if self.cache_number == Some(seed.number) {
+                participants.extend(
+                    self.cache_comments
+                        .iter()
+                        .rev()
+                        .map(|comment| comment.author.to_string()),
+                );
+            }
+        }
+        participants.retain(|login| !login.eq_ignore_ascii_case(&self.current_user));
+        self.completer.set_participants(participants);
+        let before = textarea_before_cursor(&self.input_state);
+        let repo = format!("{}/{}", self.owner, self.repo);
+        self.completer
+            .complete_markdown(&before, &self.backend, &repo, action_tx);
+    }
+
+    /// Saves the comment being written on the current issue.
+    fn save_draft(&self) {
+        if let Some(seed) = &self.current {
+            self.drafts.save(
+                DraftKey::Comment(seed.number),
+                Draft::comment(self.input_state.text()),
+            );
+        }
+    }
+
+    /// Puts the saved comment draft of `number`, if any, into the input.
+    fn restore_draft(&mut self, number: u64) {
+        let body = self
+            .drafts
+            .get(DraftKey::Comment(number))
+            .map(|draft| draft.body)
+            .unwrap_or_default();
+        self.input_state.set_text(body);
+        self.input_state.move_to_end(false);
+    }
+
+    /// Posts a comment. Its draft is kept until the comment is sent or queued.
     async fn send_comment(&mut self, number: u64, body: String) {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let outbox = self.outbox.clone();
+        let drafts = self.drafts.clone();
         self.posting = true;
         self.post_error = None;
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueCommentPostError {
-                        number,
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
-            };
-            let handler = client.inner().issues(owner, repo);
-            match handler.create_comment(number, body).await {
+            match backend.create_comment(number, body.clone()).await {
                 Ok(comment) => {
+                    drafts.discard(DraftKey::Comment(number));
                     let _ = action_tx
                         .send(Action::IssueCommentPosted {
                             number,
@@ -1388,6 +1689,21 @@ impl IssueConversation {
                         .await;
                 }
                 Err(err) => {
+                    if outbox.enqueue_if_retryable(PendingOp::Comment { number, body }, &err) {
+                        drafts.discard(DraftKey::Comment(number));
+                        let _ = action_tx
+                            .send(Action::IssueCommentPostError {
+                                number,
+                                message:
+                                    "Comment queued; it will be sent once GitHub is reachable."
+                                        .to_string(),
+                            })
+                            .await;
+                        let _ = action_tx
+                            .send(toast_action("Comment queued for retry", ToastType::Warning))
+                            .await;
+                        return;
+                    }
                     let _ = action_tx
                         .send(Action::IssueCommentPostError {
                             number,
@@ -1435,9 +1751,21 @@ impl Component for IssueConversation {
                 if self.handle_close_popup_event(event).await {
                     return Ok(());
                 }
+                if self.handle_merge_popup_event(event) {
+                    return Ok(());
+                }
+                if self.handle_title_edit_event(event) {
+                    return Ok(());
+                }
+                if self.handle_milestone_picker_event(event) {
+                    return Ok(());
+                }
                 if self.handle_reaction_mode_event(event).await {
                     return Ok(());
                 }
+                if self.handle_completion_event(event) {
+                    return Ok(());
+                }
 
                 match event {
                     event::Event::Key(key)
@@ -1448,11 +1776,14 @@ impl Component for IssueConversation {
                     {
                         self.show_timeline = !self.show_timeline;
                         self.timeline_error = None;
+                        // The timeline comes with the comments; it is only
+                        // missing if loading them failed.
                         if self.show_timeline
-                            && let Some(seed) = self.current.as_ref()
-                            && !self.has_timeline_for(seed.number)
+                            && let Some(number) = self.current.as_ref().map(|seed| seed.number)
+                            && !self.has_timeline_for(number)
+                            && !self.timeline_loading.contains(&number)
                         {
-                            self.fetch_timeline(seed.number).await;
+                            self.fetch_comments(number).await;
                         }
                         if let Some(tx) = self.action_tx.clone() {
                             let _ = tx.send(Action::ForceRender).await;
@@ -1477,17 +1808,15 @@ impl Component for IssueConversation {
                         match selected {
                             MessageKey::IssueBody(i) => {
                                 assert_eq!(*i, current.number);
-                                let link = format!(
-                                    "https://github.com/{}/{}/issues/{}",
-                                    self.owner, self.repo, i
-                                );
+                                let link = self.host.issue_url(&self.owner, &self.repo, *i);
                                 cli_clipboard::set_contents(link)
                                     .map_err(|_| anyhow!("Error copying to clipboard"))?;
                             }
                             MessageKey::Comment(id) => {
                                 let link = format!(
-                                    "https://github.com/{}/{}/issues/{}#issuecomment-{}",
-                                    self.owner, self.repo, current.number, id
+                                    "{}#issuecomment-{}",
+                                    self.host.issue_url(&self.owner, &self.repo, current.number),
+                                    id
                                 );
 
                                 cli_clipboard::set_contents(link)
@@ -1530,6 +1859,15 @@ impl Component for IssueConversation {
                         let seed = self.current.as_ref().ok_or_else(|| {
                             AppError::Other(anyhow!("no issue selected for comment editing"))
                         })?;
+                        if matches!(
+                            self.list_state
+                                .selected_checked()
+                                .and_then(|idx| self.message_keys.get(idx)),
+                            Some(MessageKey::IssueBody(_))
+                        ) {
+                            self.edit_issue_body();
+                            return Ok(());
+                        }
                         let comment = self
                             .selected_comment()
                             .ok_or_else(|| AppError::Other(anyhow!("select a comment to edit")))?;
@@ -1564,6 +1902,110 @@ impl Component for IssueConversation {
                         self.open_close_popup();
                         return Ok(());
                     }
+                    ct_event!(key press SHIFT-'M')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        self.open_merge_popup();
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'T')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        if let Some(seed) = self.current.as_ref() {
+                            let title = self.title.as_deref().unwrap_or_default();
+                            self.title_edit = Some(IssueTitleEditState::new(seed.number, title));
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press 'm')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        if let (Some(seed), Some(action_tx)) =
+                            (self.current.as_ref(), self.action_tx.as_ref())
+                        {
+                            let target = PickerTarget::Issue {
+                                number: seed.number,
+                                current: seed.milestone,
+                            };
+                            self.milestone_picker =
+                                Some(MilestonePickerState::open(target, &self.backend, action_tx));
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'E')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        self.edit_issue_body();
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'O')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        let (Some(seed), Some(action_tx)) =
+                            (self.current.as_ref(), self.action_tx.clone())
+                        else {
+                            return Ok(());
+                        };
+                        if seed.state == IssueState::Open {
+                            action_tx
+                                .send(toast_action(
+                                    format!("#{} is already open", seed.number),
+                                    ToastType::Info,
+                                ))
+                                .await?;
+                        } else {
+                            reopen_issue(self.backend.clone(), action_tx, seed.number);
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press 'v')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        self.toggle_selected_thread();
+                        return Ok(());
+                    }
+                    ct_event!(key press 'd')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        let Some(seed) = self.current.as_ref().filter(|s| s.is_pull_request) else {
+                            return Ok(());
+                        };
+                        if let Some(tx) = self.action_tx.clone() {
+                            tx.send(Action::EnterPullRequestDiff {
+                                number: seed.number,
+                                title: self.title.clone().unwrap_or_default(),
+                            })
+                            .await?;
+                            tx.send(Action::ChangeIssueScreen(MainScreen::PullRequestDiff))
+                                .await?;
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press 'c')
+                        if self.list_state.is_focused()
+                            || self.body_paragraph_state.is_focused() =>
+                    {
+                        let Some(seed) = self.current.as_ref().filter(|s| s.is_pull_request) else {
+                            return Ok(());
+                        };
+                        if let Some(tx) = self.action_tx.clone() {
+                            tx.send(Action::EnterPullRequestChecks {
+                                number: seed.number,
+                                title: self.title.clone().unwrap_or_default(),
+                            })
+                            .await?;
+                            tx.send(Action::ChangeIssueScreen(MainScreen::PullRequestChecks))
+                                .await?;
+                        }
+                        return Ok(());
+                    }
                     ct_event!(keycode press Tab) | ct_event!(keycode press BackTab)
                         if self.input_state.is_focused() =>
                     {
@@ -1644,6 +2086,7 @@ impl Component for IssueConversation {
                             self.input_state.move_to_line_end(false);
                             self.input_state.focus.set(true);
                             self.list_state.focus.set(false);
+                            self.save_draft();
                         }
                     }
 
@@ -1666,6 +2109,9 @@ impl Component for IssueConversation {
                             })?;
                             action_tx.send(Action::ForceRender).await?;
                         }
+                        if o == TextOutcome::TextChanged {
+                            self.save_draft();
+                        }
                         if o == TextOutcome::TextChanged || o2 == Outcome::Changed {
                             trace!("Input changed, forcing re-render");
                             let action_tx = self.action_tx.as_ref().ok_or_else(|| {
@@ -1678,6 +2124,7 @@ impl Component for IssueConversation {
                     }
                     event::Event::Paste(p) if self.input_state.is_focused() => {
                         self.input_state.insert_str(p);
+                        self.save_draft();
                         let action_tx = self.action_tx.as_ref().ok_or_else(|| {
                             AppError::Other(anyhow!(
                                 "issue conversation action channel unavailable"
@@ -1687,6 +2134,7 @@ impl Component for IssueConversation {
                     }
                     _ => {}
                 }
+                self.refresh_completion();
                 self.body_paragraph_state
                     .handle(event, rat_widget::event::Regular);
                 let outcome = self.list_state.handle(event, rat_widget::event::Regular);
@@ -1696,13 +2144,23 @@ impl Component for IssueConversation {
             }
             Action::EnterIssueDetails { seed } => {
                 let number = seed.number;
+                let switched = self.current.as_ref().is_none_or(|s| s.number != number);
+                if switched {
+                    self.stop_loading_conversation();
+                }
                 self.title = seed.title.clone();
                 self.current = Some(seed);
+                if switched {
+                    self.restore_draft(number);
+                }
                 self.post_error = None;
                 self.reaction_error = None;
                 self.close_error = None;
                 self.reaction_mode = None;
                 self.close_popup = None;
+                self.merge_popup = None;
+                self.title_edit = None;
+                self.milestone_picker = None;
                 self.timeline_error = None;
                 self.body_cache = None;
                 self.body_cache_number = Some(number);
@@ -1716,22 +2174,79 @@ impl Component for IssueConversation {
                     self.timeline_cache_number = None;
                     self.cache_timeline.clear();
                 }
-                if self.cache_number == Some(number) {
+                if self.cache_number == Some(number)
+                    && (self.has_timeline_for(number) || !self.show_timeline)
+                {
                     self.loading.remove(&number);
+                    self.timeline_loading.remove(&number);
                     self.error = None;
                 } else {
                     self.fetch_comments(number).await;
                 }
-                if self.show_timeline {
-                    if self.has_timeline_for(number) {
-                        self.timeline_loading.remove(&number);
-                    } else {
-                        self.fetch_timeline(number).await;
+                let is_pull_request = self.current.as_ref().is_some_and(|s| s.is_pull_request);
+                if self.review_threads_number != Some(number) {
+                    self.review_threads_number = None;
+                    self.review_threads.clear();
+                    self.thread_cache.clear();
+                    if is_pull_request {
+                        self.fetch_review_threads(number);
                     }
                 }
             }
-            Action::IssueCommentsLoaded { number, comments } => {
+            Action::ReviewThreadsLoaded {
+                number,
+                mut threads,
+            } => {
+                if self.current.as_ref().is_some_and(|s| s.number == number) {
+                    threads.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+                    self.review_threads = threads;
+                    self.review_threads_number = Some(number);
+                    self.thread_cache.clear();
+                    self.review_threads_error = None;
+                }
+            }
+            Action::ReviewThreadsError { number, message } => {
+                if self.current.as_ref().is_some_and(|s| s.number == number) {
+                    self.review_threads_error = Some(message);
+                }
+            }
+            Action::ReviewThreadResolved {
+                number,
+                thread_id,
+                resolved,
+            } => {
+                if self.review_threads_number == Some(number)
+                    && let Some(idx) = self.review_threads.iter().position(|t| t.id == thread_id)
+                {
+                    self.review_threads[idx].resolved = resolved;
+                    self.thread_cache.remove(&idx);
+                }
+            }
+            Action::ReviewSubmitted { number } => {
+                if self.current.as_ref().is_some_and(|s| s.number == number) {
+                    self.fetch_review_threads(number);
+                }
+            }
+            Action::IssueBodyLoaded { number, body } => {
+                if let Some(seed) = self.current.as_mut().filter(|s| s.number == number)
+                    && seed.body.as_deref() != Some(body.as_ref())
+                {
+                    seed.body = Some(body);
+                    self.body_cache = None;
+                }
+            }
+            // Pages of a load that was stopped are dropped.
+            Action::IssueCommentsLoaded {
+                number,
+                comments,
+                more,
+            } if self.loading.contains(&number) => {
                 self.loading.remove(&number);
+                if more {
+                    self.paging.insert(number);
+                } else {
+                    self.paging.remove(&number);
+                }
                 if self.current.as_ref().is_some_and(|s| s.number == number) {
                     self.cache_number = Some(number);
                     trace!("Setting {} comments for #{}", comments.len(), number);
@@ -1746,6 +2261,27 @@ impl Component for IssueConversation {
                     action_tx.send(Action::ForceRender).await?;
                 }
             }
+            Action::IssueCommentsAppended {
+                number,
+                comments,
+                more,
+            } => {
+                if !more {
+                    self.paging.remove(&number);
+                }
+                if self.cache_number == Some(number) {
+                    let known = self
+                        .cache_comments
+                        .iter()
+                        .map(|c| c.id)
+                        .collect::<HashSet<_>>();
+                    self.cache_comments
+                        .extend(comments.into_iter().filter(|c| !known.contains(&c.id)));
+                    if let Some(action_tx) = self.action_tx.as_ref() {
+                        let _ = action_tx.send(Action::ForceRender).await;
+                    }
+                }
+            }
             Action::IssueReactionsLoaded {
                 reactions,
                 own_reactions,
@@ -1781,12 +2317,22 @@ impl Component for IssueConversation {
             }
             Action::IssueCommentsError { number, message } => {
                 self.loading.remove(&number);
+                self.paging.remove(&number);
                 if self.current.as_ref().is_some_and(|s| s.number == number) {
                     self.error = Some(message);
                 }
             }
-            Action::IssueTimelineLoaded { number, events } => {
+            Action::IssueTimelineLoaded {
+                number,
+                events,
+                more,
+            } if self.timeline_loading.contains(&number) => {
                 self.timeline_loading.remove(&number);
+                if more {
+                    self.timeline_paging.insert(number);
+                } else {
+                    self.timeline_paging.remove(&number);
+                }
                 if self.current.as_ref().is_some_and(|s| s.number == number) {
                     self.timeline_cache_number = Some(number);
                     self.cache_timeline = events;
@@ -1796,8 +2342,24 @@ impl Component for IssueConversation {
                     }
                 }
             }
+            Action::IssueTimelineAppended {
+                number,
+                events,
+                more,
+            } => {
+                if !more {
+                    self.timeline_paging.remove(&number);
+                }
+                if self.timeline_cache_number == Some(number) {
+                    self.cache_timeline.extend(events);
+                    if let Some(action_tx) = self.action_tx.as_ref() {
+                        let _ = action_tx.send(Action::ForceRender).await;
+                    }
+                }
+            }
             Action::IssueTimelineError { number, message } => {
                 self.timeline_loading.remove(&number);
+                self.timeline_paging.remove(&number);
                 if self.current.as_ref().is_some_and(|s| s.number == number) {
                     self.timeline_error = Some(message);
                 }
@@ -1805,6 +2367,9 @@ impl Component for IssueConversation {
             Action::IssueCommentPostError { number, message } => {
                 self.posting = false;
                 if self.current.as_ref().is_some_and(|s| s.number == number) {
+                    if self.input_state.text().is_empty() {
+                        self.restore_draft(number);
+                    }
                     self.post_error = Some(message);
                 }
             }
@@ -1889,6 +2454,11 @@ impl Component for IssueConversation {
                     }
                 }
             }
+            Action::DuplicateOriginalLoaded { number, result } => {
+                if let Some(popup) = self.close_popup.as_mut() {
+                    popup.update_original(number, result);
+                }
+            }
             Action::IssueCloseError { number, message } => {
                 if let Some(popup) = self.close_popup.as_mut()
                     && popup.issue_number == number
@@ -1898,14 +2468,30 @@ impl Component for IssueConversation {
                     self.close_error = Some(message);
                 }
             }
+            Action::IssueCloseQueued { number } => {
+                if self
+                    .close_popup
+                    .as_ref()
+                    .is_some_and(|popup| popup.issue_number == number)
+                {
+                    self.close_popup = None;
+                    self.close_error = None;
+                }
+            }
+            Action::CompletionsLoaded(ref data) => self.completer.update(data),
             Action::ChangeIssueScreen(screen) => {
                 self.screen = screen;
+                self.completer.close();
                 match screen {
                     MainScreen::List => {
+                        self.stop_loading_conversation();
                         self.input_state.focus.set(false);
                         self.list_state.focus.set(false);
                         self.reaction_mode = None;
                         self.close_popup = None;
+                        self.merge_popup = None;
+                        self.title_edit = None;
+                        self.milestone_picker = None;
                     }
                     MainScreen::Details => {}
                     MainScreen::DetailsFullscreen => {
@@ -1914,11 +2500,23 @@ impl Component for IssueConversation {
                         self.paragraph_state.focus.set(false);
                         self.body_paragraph_state.focus.set(true);
                     }
+                    MainScreen::PullRequestDiff
+                    | MainScreen::PullRequestChecks
+                    | MainScreen::Milestones => {
+                        self.reaction_mode = None;
+                        self.close_popup = None;
+                        self.merge_popup = None;
+                        self.title_edit = None;
+                        self.milestone_picker = None;
+                    }
                     MainScreen::CreateIssue => {
                         self.input_state.focus.set(false);
                         self.list_state.focus.set(false);
                         self.reaction_mode = None;
                         self.close_popup = None;
+                        self.merge_popup = None;
+                        self.title_edit = None;
+                        self.milestone_picker = None;
                     }
                 }
             }
@@ -1934,6 +2532,72 @@ impl Component for IssueConversation {
                 {
                     popup.throbber_state.calc_next();
                 }
+                if let Some(popup) = self.merge_popup.as_mut()
+                    && popup.loading
+                {
+                    popup.throbber_state.calc_next();
+                }
+                if let Some(edit) = self.title_edit.as_mut()
+                    && edit.saving
+                {
+                    edit.throbber_state.calc_next();
+                }
+                if let Some(picker) = self.milestone_picker.as_mut()
+                    && picker.loading
+                {
+                    picker.throbber_state.calc_next();
+                }
+            }
+            Action::MilestonesLoaded { .. } | Action::MilestonesError { .. } => {
+                if let Some(picker) = self.milestone_picker.as_mut() {
+                    picker.update(&event);
+                }
+            }
+            Action::IssueUpdated { ref issue } => {
+                if let Some(edit) = self.title_edit.as_mut()
+                    && !edit.update(&event)
+                {
+                    self.title_edit = None;
+                }
+                if let Some(picker) = self.milestone_picker.as_mut()
+                    && !picker.update(&event)
+                {
+                    self.milestone_picker = None;
+                }
+                if let Some(seed) = self.current.as_mut().filter(|s| s.number == issue.number) {
+                    let title = Arc::<str>::from(issue.title.as_str());
+                    seed.title = Some(title.clone());
+                    seed.body = issue.body.as_deref().map(Arc::from);
+                    seed.state = issue.state.clone();
+                    seed.milestone = issue.milestone.as_ref().map(|m| m.number as u64);
+                    self.title = Some(title);
+                    self.body_cache = None;
+                }
+            }
+            Action::IssueUpdateError { .. } => {
+                if let Some(edit) = self.title_edit.as_mut() {
+                    edit.update(&event);
+                }
+                if let Some(picker) = self.milestone_picker.as_mut() {
+                    picker.update(&event);
+                }
+            }
+            Action::MergeStatusLoaded { .. }
+            | Action::MergeStatusError { .. }
+            | Action::MergeSubmitError { .. } => {
+                if let Some(popup) = self.merge_popup.as_mut() {
+                    popup.update(&event);
+                }
+            }
+            Action::MergeSubmitted { number } => {
+                if self
+                    .merge_popup
+                    .take_if(|popup| popup.number == number)
+                    .is_some()
+                    && let Some(action_tx) = self.action_tx.as_ref()
+                {
+                    let _ = action_tx.send(Action::RefreshIssueList).await;
+                }
             }
             _ => {}
         }
@@ -1941,7 +2605,25 @@ impl Component for IssueConversation {
     }
 
     fn cursor(&self) -> Option<(u16, u16)> {
-        self.input_state.screen_cursor()
+        self.merge_popup
+            .as_ref()
+            .and_then(MergePopupState::cursor)
+            .or_else(|| {
+                self.title_edit
+                    .as_ref()
+                    .and_then(IssueTitleEditState::cursor)
+            })
+            .or_else(|| {
+                self.milestone_picker
+                    .as_ref()
+                    .and_then(MilestonePickerState::cursor)
+            })
+            .or_else(|| {
+                self.close_popup
+                    .as_ref()
+                    .and_then(IssueClosePopupState::cursor)
+            })
+            .or_else(|| self.input_state.screen_cursor())
     }
 
     fn should_render(&self) -> bool {
@@ -1952,7 +2634,13 @@ impl Component for IssueConversation {
         self.in_details_mode()
             && (self.is_loading_current()
                 || self.posting
-                || self.close_popup.as_ref().is_some_and(|popup| popup.loading))
+                || self.close_popup.as_ref().is_some_and(|popup| popup.loading)
+                || self.merge_popup.as_ref().is_some_and(|popup| popup.loading)
+                || self.title_edit.as_ref().is_some_and(|edit| edit.saving)
+                || self
+                    .milestone_picker
+                    .as_ref()
+                    .is_some_and(|picker| picker.loading))
     }
 
     fn capture_focus_event(&self, event: &crossterm::event::Event) -> bool {
@@ -1962,7 +2650,11 @@ impl Component for IssueConversation {
         if self.screen == MainScreen::DetailsFullscreen {
             return true;
         }
-        if self.close_popup.is_some() {
+        if self.close_popup.is_some()
+            || self.merge_popup.is_some()
+            || self.title_edit.is_some()
+            || self.milestone_picker.is_some()
+        {
             return true;
         }
         if self.input_state.is_focused() {
@@ -2048,6 +2740,82 @@ fn build_comment_item(
     ListItem::new(lines)
 }
 
+fn build_review_thread_item(thread: &ReviewThread, preview_width: usize) -> ListItem<'static> {
+    let location = match thread.line {
+        Some(line) => format!("{}:{line}", thread.path),
+        None => thread.path.clone(),
+    };
+    let mut header = vec![Span::styled(
+        location,
+        Style::new().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+    )];
+    if thread.resolved {
+        header.push(Span::styled(
+            "  resolved",
+            Style::new().fg(Color::Green).dim(),
+        ));
+    }
+    if thread.outdated {
+        header.push(Span::styled(
+            "  outdated",
+            Style::new().fg(Color::Yellow).dim(),
+        ));
+    }
+    let first = thread.comments.first();
+    let preview = first.map_or_else(String::new, |comment| {
+        let text = comment
+            .body
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        truncate_preview(&format!("{}: {text}", comment.author), preview_width)
+    });
+    let replies = thread.comments.len().saturating_sub(1);
+    let mut preview_line = vec![Span::raw("  "), Span::raw(preview)];
+    if replies > 0 {
+        preview_line.push(Span::styled(format!("  +{replies}"), Style::new().dim()));
+    }
+    let style = if thread.resolved {
+        Style::new().dim()
+    } else {
+        Style::new()
+    };
+    ListItem::new(vec![Line::from(header), Line::from(preview_line)]).style(style)
+}
+
+/// The thread as markdown for the body pane: the end of the diff hunk it is
+/// attached to, followed by every comment.
+fn review_thread_markdown(thread: &ReviewThread) -> String {
+    const HUNK_CONTEXT: usize = 6;
+    let mut text = format!("`{}`", thread.path);
+    if let Some(line) = thread.line {
+        text.push_str(&format!(" line {line}"));
+    }
+    if thread.resolved {
+        text.push_str(" (resolved)");
+    }
+    text.push_str("\n\n");
+    let hunk = thread.diff_hunk.lines().collect::<Vec<_>>();
+    let tail = &hunk[hunk.len().saturating_sub(HUNK_CONTEXT)..];
+    if !tail.is_empty() {
+        text.push_str(&format!("```diff\n{}\n```\n\n", tail.join("\n")));
+    }
+    let comments = thread
+        .comments
+        .iter()
+        .map(|comment| {
+            format!(
+                "**{}** {}\n\n{}",
+                comment.author,
+                comment.created_at.format("%Y-%m-%d %H:%M"),
+                comment.body
+            )
+        })
+        .collect::<Vec<_>>();
+    text.push_str(&comments.join("\n\n---\n\n"));
+    text
+}
+
 fn build_comment_preview_item(
     author: &str,
     created_at: &str,
@@ -2358,7 +3126,191 @@ fn format_reaction_picker(selected: usize, options: &[ReactionContent]) -> Strin
     out
 }
 
-fn to_reaction_snapshot<I>(
+/// Views of `events`, the first of which is event number `start` of the
+/// issue's timeline.
+fn timeline_views(
+    number: u64,
+    events: Vec<ConversationEvent>,
+    start: usize,
+) -> Vec<TimelineEventView> {
+    events
+        .into_iter()
+        .enumerate()
+        .map(|(idx, event)| {
+            TimelineEventView::from_conversation(event, (number << 32) | (start + idx) as u64)
+        })
+        .collect()
+}
+
+/// Sends the first page of a conversation to the UI, then keeps fetching and
+/// appending pages until both comments and timeline are complete. Each page
+/// only carries the items it added.
+async fn stream_conversation(
+    backend: &Backend,
+    action_tx: &tokio::sync::mpsc::Sender<Action>,
+    number: u64,
+    mut page: Conversation,
+) -> Result<(), AppError> {
+    if let Some(body) = page.body.take() {
+        let _ = action_tx
+            .send(Action::IssueBodyLoaded {
+                number,
+                body: Arc::<str>::from(body),
+            })
+            .await;
+    }
+    let mut timeline_len = 0;
+    let mut timeline_more = true;
+    let mut first = true;
+    loop {
+        let more = !page.next_comments.is_done();
+        let comments = page
+            .comments
+            .into_iter()
+            .map(CommentView::from_conversation)
+            .collect();
+        let action = if first {
+            Action::IssueCommentsLoaded {
+                number,
+                comments,
+                more,
+            }
+        } else {
+            Action::IssueCommentsAppended {
+                number,
+                comments,
+                more,
+            }
+        };
+        let _ = action_tx.send(action).await;
+        let more = !page.next_timeline.is_done();
+        if first || !page.timeline.is_empty() || more != timeline_more {
+            let events = timeline_views(number, page.timeline, timeline_len);
+            timeline_len += events.len();
+            let action = if first {
+                Action::IssueTimelineLoaded {
+                    number,
+                    events,
+                    more,
+                }
+            } else {
+                Action::IssueTimelineAppended {
+                    number,
+                    events,
+                    more,
+                }
+            };
+            let _ = action_tx.send(action).await;
+        }
+        timeline_more = more;
+        first = false;
+
+        if page.next_comments.is_done() && page.next_timeline.is_done() {
+            return Ok(());
+        }
+        let next = backend
+            .conversation(number, &page.next_comments, &page.next_timeline)
+            .await?;
+        let Some(next) = next else {
+            return Ok(());
+        };
+        page = next;
+    }
+}
+
+/// Loads comments through the REST API a page at a time, each followed by the
+/// reactions of its comments.
+async fn fetch_comments_rest(
+    backend: &Backend,
+    action_tx: &tokio::sync::mpsc::Sender<Action>,
+    number: u64,
+    current_user: &str,
+) -> Result<(), AppError> {
+    for page in 1.. {
+        let comments = backend.list_comments(number, page, PAGE_SIZE).await?.items;
+        let more = comments.len() == PAGE_SIZE as usize;
+        let comment_ids = comments.iter().map(|c| c.id.0).collect::<Vec<_>>();
+        let comments: Vec<CommentView> = comments.into_iter().map(CommentView::from_api).collect();
+        trace!(
+            "Loaded {} comments for issue {} (page {})",
+            comments.len(),
+            number,
+            page
+        );
+        let action = if page == 1 {
+            Action::IssueCommentsLoaded {
+                number,
+                comments,
+                more,
+            }
+        } else {
+            Action::IssueCommentsAppended {
+                number,
+                comments,
+                more,
+            }
+        };
+        let _ = action_tx.send(action).await;
+
+        let reaction_snapshots = stream::iter(comment_ids)
+            .filter_map(|id| async move {
+                let reactions = backend.list_comment_reactions(id).await.ok()?;
+                Some((id, to_reaction_snapshot(reactions, current_user)))
+            })
+            .collect::<HashMap<_, _>>()
+            .await;
+        let mut reactions = HashMap::with_capacity(reaction_snapshots.len());
+        let mut own_reactions = HashMap::with_capacity(reaction_snapshots.len());
+        for (id, (counts, mine)) in reaction_snapshots {
+            reactions.insert(id, counts);
+            own_reactions.insert(id, mine);
+        }
+        let _ = action_tx
+            .send(Action::IssueReactionsLoaded {
+                reactions,
+                own_reactions,
+            })
+            .await;
+
+        if !more {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Loads the timeline through the REST API, for trackers without a
+/// conversation query.
+async fn fetch_timeline_rest(
+    backend: &Backend,
+    action_tx: &tokio::sync::mpsc::Sender<Action>,
+    number: u64,
+) -> Result<(), AppError> {
+    let mut events = Vec::new();
+    for page in 1.. {
+        let items = backend.list_timeline(number, page, PAGE_SIZE).await?.items;
+        let more = items.len() == PAGE_SIZE as usize;
+        events.extend(items);
+        if !more {
+            break;
+        }
+    }
+    let events = events
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, event)| TimelineEventView::from_api(event, (number << 32) | idx as u64))
+        .collect();
+    let _ = action_tx
+        .send(Action::IssueTimelineLoaded {
+            number,
+            events,
+            more: false,
+        })
+        .await;
+    Ok(())
+}
+
+pub(crate) fn to_reaction_snapshot<I>(
     reactions: I,
     current_user: &str,
 ) -> (Vec<(ReactionContent, u64)>, Vec<ReactionContent>)
@@ -3020,7 +3972,7 @@ fn resolve_syntax<'a>(syntaxes: &'a SyntaxSet, lang: Option<&str>) -> &'a Syntax
     syntaxes.find_syntax_plain_text()
 }
 
-fn syntect_style_to_ratatui(style: syntect::highlighting::Style) -> Style {
+pub(crate) fn syntect_style_to_ratatui(style: syntect::highlighting::Style) -> Style {
     let mut out = Style::new().fg(Color::Rgb(
         style.foreground.r,
         style.foreground.g,
@@ -11,23 +11,37 @@ use rat_widget::{
 };
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, StatefulWidget},
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, StatefulWidget, Widget,
+    },
 };
-use ratatui_macros::vertical;
+use ratatui_macros::{horizontal, line, span, vertical};
 use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+use tracing::warn;
 
 use crate::{
-    app::GITHUB_CLIENT,
+    backend::{
+        Backend, NewIssue,
+        templates::{IssueTemplate, TemplateKind},
+    },
+    drafts::{Draft, DraftKey, Drafts},
     errors::AppError,
     ui::{
         Action, AppState,
         components::{
             Component,
+            assignee_picker::{AssigneeOutcome, AssigneePickerState, render_assignee_picker},
+            completion::{
+                Completer, CompletionKey, CompletionKind, input_before_cursor, input_replace,
+                textarea_before_cursor, textarea_replace,
+            },
+            duplicate_suggestions::{DuplicateSuggestions, suggestion_query},
             help::HelpElementKind,
             issue_conversation::{IssueConversationSeed, render_markdown_lines},
             issue_detail::IssuePreviewSeed,
+            issue_form::IssueFormState,
             issue_list::MainScreen,
         },
         layout::Layout,
@@ -43,6 +57,19 @@ pub const HELP: &[HelpElementKind] = &[
     crate::help_keybind!("n", "open new issue composer (from issue list)"),
     crate::help_keybind!("Tab / Shift+Tab", "switch fields"),
     crate::help_keybind!("Ctrl+P", "toggle body input and markdown preview"),
+    crate::help_keybind!("Ctrl+T", "choose an issue template"),
+    crate::help_keybind!(
+        "Enter / Space (assignees)",
+        "pick assignees from the assignable users"
+    ),
+    crate::help_keybind!(
+        "Enter",
+        "open the selected possible duplicate; the draft is kept"
+    ),
+    crate::help_keybind!(
+        "Up / Down / Space",
+        "pick options of a form dropdown or checkbox"
+    ),
     crate::help_keybind!("Ctrl+Enter / Alt+Enter", "create issue"),
     crate::help_keybind!("Esc", "return to issue list"),
 ];
@@ -65,8 +92,7 @@ impl InputMode {
 
 pub struct IssueCreate {
     action_tx: Option<tokio::sync::mpsc::Sender<Action>>,
-    owner: String,
-    repo: String,
+    backend: Backend,
     screen: MainScreen,
     focus: FocusFlag,
     area: Rect,
@@ -83,14 +109,43 @@ pub struct IssueCreate {
     preview_cache_input: String,
     preview_cache_width: usize,
     preview_cache: Vec<ratatui::text::Line<'static>>,
+    /// The repository's issue templates, once loaded.
+    templates: Option<Vec<IssueTemplate>>,
+    /// Selection in the template picker, while it is open.
+    template_picker: Option<TuiListState>,
+    /// Name of the template the issue was started from.
+    template: Option<String>,
+    /// Title that template starts issues with.
+    template_title: Option<String>,
+    /// Fields of the issue form the issue was started from, which replace the
+    /// body editor.
+    form: Option<IssueFormState>,
+    /// `owner/repo`, to scope the duplicate search.
+    repo: String,
+    duplicates: DuplicateSuggestions,
+    /// Set while the user looks at a possible duplicate, so that coming back
+    /// to the composer restores the draft.
+    keep_draft: bool,
+    drafts: Drafts,
+    completer: Completer,
+    assignee_picker: Option<AssigneePickerState>,
+    /// Logins issues can be assigned to, once the picker has loaded them.
+    assignable: Option<Vec<String>>,
 }
 
 impl IssueCreate {
-    pub fn new(AppState { owner, repo, .. }: AppState) -> Self {
-        Self {
-            action_tx: None,
+    pub fn new(
+        AppState {
+            backend,
             owner,
             repo,
+            drafts,
+            ..
+        }: AppState,
+    ) -> Self {
+        Self {
+            action_tx: None,
+            backend,
             screen: MainScreen::List,
             focus: FocusFlag::new().with_name("issue_create"),
             area: Rect::default(),
@@ -107,6 +162,18 @@ impl IssueCreate {
             preview_cache_input: String::new(),
             preview_cache_width: 0,
             preview_cache: Vec::new(),
+            templates: None,
+            template_picker: None,
+            template: None,
+            template_title: None,
+            form: None,
+            repo: format!("{owner}/{repo}"),
+            duplicates: DuplicateSuggestions::new(),
+            keep_draft: false,
+            drafts,
+            completer: Completer::default(),
+            assignee_picker: None,
+            assignable: None,
         }
     }
 
@@ -125,6 +192,278 @@ impl IssueCreate {
         self.preview_cache_input.clear();
         self.preview_cache.clear();
         self.preview_cache_width = 0;
+        self.template = None;
+        self.template_title = None;
+        self.form = None;
+        self.template_picker = None;
+        self.duplicates.clear();
+        self.completer.close();
+        self.assignee_picker = None;
+        self.keep_draft = false;
+    }
+
+    /// Saves what has been written so far as the new issue draft.
+    fn save_draft(&self) {
+        self.drafts.save(
+            DraftKey::NewIssue,
+            Draft {
+                title: self.title_state.text().to_string(),
+                labels: self.labels_state.text().to_string(),
+                assignees: self.assignees_state.text().to_string(),
+                body: self.body_text(),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Fills the composer from the saved draft. A draft written in an issue
+    /// form comes back as a plain body.
+    fn restore_draft(&mut self, draft: Draft) {
+        self.title_state.set_text(draft.title);
+        self.title_state.move_to_line_end(false);
+        self.labels_state.set_text(draft.labels);
+        self.assignees_state.set_text(draft.assignees);
+        self.body_state.set_text(draft.body);
+    }
+
+    fn load_templates(&self) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            match backend.issue_templates().await {
+                Ok(templates) => {
+                    let _ = action_tx
+                        .send(Action::IssueTemplatesLoaded { templates })
+                        .await;
+                }
+                Err(err) => warn!(error = %err, "failed to load issue templates"),
+            }
+        });
+    }
+
+    /// Opens the template picker if the repository has templates.
+    fn open_template_picker(&mut self) {
+        if self.templates.as_ref().is_some_and(|t| !t.is_empty()) {
+            self.template_picker = Some(TuiListState::default().with_selected(Some(0)));
+        }
+    }
+
+    /// Starts the issue from the template at `idx`, or drops the current
+    /// template's form if `idx` is `None`.
+    fn apply_template(&mut self, idx: Option<usize>) {
+        self.template_picker = None;
+        self.form = None;
+        self.template = None;
+        self.template_title = None;
+        self.mode = InputMode::Input;
+        self.preview_state.focus.set(false);
+        self.body_state.focus.set(false);
+        self.labels_state.focus.set(false);
+        self.assignees_state.focus.set(false);
+        self.title_state.focus.set(true);
+        let Some(template) = idx
+            .and_then(|idx| self.templates.as_ref()?.get(idx))
+            .cloned()
+        else {
+            return;
+        };
+        if let Some(title) = &template.title {
+            self.title_state.set_text(title.as_str());
+            self.title_state.move_to_line_end(false);
+        }
+        self.template_title = template.title.clone();
+        self.labels_state.set_text(template.labels.join(", "));
+        self.assignees_state.set_text(template.assignees.join(", "));
+        match template.kind {
+            TemplateKind::Markdown { body } => self.body_state.set_text(body),
+            TemplateKind::Form { fields } => {
+                self.body_state.set_text("");
+                self.form = Some(IssueFormState::new(fields));
+            }
+        }
+        self.template = Some(template.name);
+    }
+
+    fn handle_template_picker_event(&mut self, event: &event::Event) {
+        let Some(list_state) = self.template_picker.as_mut() else {
+            return;
+        };
+        let len = self.templates.as_ref().map_or(0, Vec::len) + 1;
+        match event {
+            ct_event!(keycode press Up) => {
+                let selected = list_state.selected().unwrap_or(0);
+                list_state.select(Some(selected.saturating_sub(1)));
+            }
+            ct_event!(keycode press Down) => {
+                let selected = list_state.selected().unwrap_or(0);
+                list_state.select(Some((selected + 1).min(len - 1)));
+            }
+            ct_event!(keycode press Enter) => {
+                let selected = list_state.selected().unwrap_or(0);
+                self.apply_template(selected.checked_sub(1));
+            }
+            ct_event!(keycode press Esc) => self.template_picker = None,
+            _ => {}
+        }
+    }
+
+    /// Searches for issues like the one being written once the title settles.
+    fn refresh_duplicates(&mut self) {
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        let query = suggestion_query(self.title_state.text(), self.template_title.as_deref());
+        self.duplicates
+            .search(query, &self.repo, &self.backend, action_tx);
+    }
+
+    /// Opens a possible duplicate. The draft stays as it is until the composer
+    /// is opened again.
+    async fn open_duplicate(&mut self, issue: Issue) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        self.keep_draft = true;
+        let _ = action_tx
+            .send(Action::SelectedIssue {
+                number: issue.number,
+                labels: issue.labels.clone(),
+            })
+            .await;
+        let _ = action_tx
+            .send(Action::SelectedIssuePreview {
+                seed: IssuePreviewSeed::from_issue(&issue),
+            })
+            .await;
+        let _ = action_tx
+            .send(Action::EnterIssueDetails {
+                seed: IssueConversationSeed::from_issue(&issue),
+            })
+            .await;
+        let _ = action_tx
+            .send(Action::ChangeIssueScreen(MainScreen::Details))
+            .await;
+        let _ = action_tx
+            .send(toast_action(
+                "Draft kept. Press n to get back to it.",
+                ToastType::Info,
+            ))
+            .await;
+    }
+
+    /// Handles a key press for the completion popup of the focused field.
+    fn handle_completion_event(&mut self, event: &event::Event) -> bool {
+        match self.completer.handle_event(event) {
+            CompletionKey::Ignored => false,
+            CompletionKey::Handled => true,
+            CompletionKey::Accept { token_len, text } => {
+                if self.labels_state.is_focused() {
+                    input_replace(&mut self.labels_state, token_len, &text);
+                } else if self.body_state.is_focused() {
+                    textarea_replace(&mut self.body_state, token_len, &text);
+                }
+                self.refresh_completion();
+                self.save_draft();
+                true
+            }
+        }
+    }
+
+    /// Offers labels, or mentions and references in the body, depending on
+    /// the focused field.
+    fn refresh_completion(&mut self) {
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        if self.labels_state.is_focused() {
+            let before = input_before_cursor(&self.labels_state);
+            self.completer.complete_list(
+                CompletionKind::Label,
+                self.labels_state.text(),
+                &before,
+                &self.backend,
+                &self.repo,
+                action_tx,
+            );
+        } else if self.body_state.is_focused() && self.mode == InputMode::Input {
+            let before = textarea_before_cursor(&self.body_state);
+            self.completer
+                .complete_markdown(&before, &self.backend, &self.repo, action_tx);
+        } else {
+            self.completer.close();
+        }
+    }
+
+    /// Opens the assignee picker with the users already in the assignees
+    /// field checked.
+    fn open_assignee_picker(&mut self) {
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        let checked = Self::parse_csv(self.assignees_state.text()).unwrap_or_default();
+        self.completer.close();
+        self.assignee_picker = Some(
+            AssigneePickerState::assignable("Assignees", &[], checked, &self.backend, action_tx)
+                .whole_list(),
+        );
+    }
+
+    /// Handles a key press for the assignee picker, or one that opens it.
+    fn handle_assignee_picker_event(&mut self, event: &event::Event) -> bool {
+        let Some(picker) = self.assignee_picker.as_mut() else {
+            let opens = self.assignees_state.is_focused()
+                && match event {
+                    ct_event!(keycode press Enter) | ct_event!(key press ' ') => true,
+                    event::Event::Key(key) => {
+                        key.kind == event::KeyEventKind::Press
+                            && matches!(key.code, event::KeyCode::Char(_))
+                            && (key.modifiers - event::KeyModifiers::SHIFT).is_empty()
+                    }
+                    _ => false,
+                };
+            if !opens {
+                return false;
+            }
+            self.open_assignee_picker();
+            // A typed letter starts the filter.
+            if !matches!(
+                event,
+                ct_event!(keycode press Enter) | ct_event!(key press ' ')
+            ) && let Some(picker) = self.assignee_picker.as_mut()
+            {
+                picker.handle_event(event);
+            }
+            return true;
+        };
+        match picker.handle_event(event) {
+            AssigneeOutcome::Continue => {}
+            AssigneeOutcome::Close => self.assignee_picker = None,
+            AssigneeOutcome::Apply(logins) => {
+                self.assignee_picker = None;
+                self.assignees_state.set_text(logins.join(", "));
+                self.save_draft();
+            }
+        }
+        true
+    }
+
+    /// Whether a multi-line editor has focus; those keep every key but Tab.
+    fn editing_body(&self) -> bool {
+        self.body_state.is_focused()
+            || self
+                .form
+                .as_ref()
+                .is_some_and(IssueFormState::is_textarea_focused)
+    }
+
+    /// The body the issue is created with.
+    fn body_text(&self) -> String {
+        match &self.form {
+            Some(form) => form.body(),
+            None => self.body_state.text(),
+        }
     }
 
     fn parse_csv(input: &str) -> Option<Vec<String>> {
@@ -142,7 +481,7 @@ impl IssueCreate {
     }
 
     fn body_preview_lines(&mut self, width: usize) -> &[ratatui::text::Line<'static>] {
-        let body = self.body_state.text();
+        let body = self.body_text();
         if self.preview_cache_width != width || self.preview_cache_input != body {
             self.preview_cache_width = width;
             self.preview_cache_input.clear();
@@ -162,41 +501,57 @@ impl IssueCreate {
             return;
         }
 
-        let body = self.body_state.text().trim().to_string();
+        if let Some(missing) = self.form.as_ref().map(IssueFormState::missing)
+            && !missing.is_empty()
+        {
+            self.error = Some(format!("Required: {}", missing.join(", ")));
+            return;
+        }
+        let body = self.body_text().trim().to_string();
         let labels = Self::parse_csv(self.labels_state.text());
         let assignees = Self::parse_csv(self.assignees_state.text());
+        let unknown = self
+            .completer
+            .unknown(CompletionKind::Label, labels.as_deref().unwrap_or_default());
+        if !unknown.is_empty() {
+            self.error = Some(format!("Unknown labels: {}", unknown.join(", ")));
+            return;
+        }
+        if let (Some(assignable), Some(assignees)) = (&self.assignable, &assignees) {
+            let unknown = assignees
+                .iter()
+                .filter(|login| {
+                    !assignable
+                        .iter()
+                        .any(|user| user.eq_ignore_ascii_case(login))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if !unknown.is_empty() {
+                self.error = Some(format!("Cannot assign: {}", unknown.join(", ")));
+                return;
+            }
+        }
 
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let drafts = self.drafts.clone();
         self.creating = true;
         self.error = None;
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueCreateError {
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
+            let new_issue = NewIssue {
+                title,
+                body: (!body.is_empty()).then_some(body),
+                labels,
+                assignees,
             };
-            let issues = client.inner().issues(owner, repo);
-            let mut create = issues.create(title);
-            if !body.is_empty() {
-                create = create.body(body);
-            }
-            if let Some(labels) = labels {
-                create = create.labels(labels);
-            }
-            if let Some(assignees) = assignees {
-                create = create.assignees(assignees);
-            }
 
-            match create.send().await {
+            match backend.create_issue(new_issue).await {
                 Ok(issue) => {
+                    drafts.discard(DraftKey::NewIssue);
                     let _ = action_tx
                         .send(Action::IssueCreateSuccess {
                             issue: Box::new(issue),
@@ -252,11 +607,16 @@ impl IssueCreate {
 
     pub fn render(&mut self, area: Layout, buf: &mut Buffer) {
         self.area = area.main_content;
-        let [title_area, labels_area, assignees_area, body_area] = vertical![==3, ==3, ==3, *=1]
-            .areas(
-                area.main_content
-                    .union(area.text_search.union(area.label_search)),
-            );
+        let mut form_area = area
+            .main_content
+            .union(area.text_search.union(area.label_search));
+        if self.duplicates.is_visible() {
+            let [fields, suggestions] = horizontal![*=2, *=1].areas(form_area);
+            form_area = fields;
+            self.duplicates.render(suggestions, buf);
+        }
+        let [title_area, labels_area, assignees_area, body_area] =
+            vertical![==3, ==3, ==3, *=1].areas(form_area);
 
         let title_input = TextInput::new().block(
             Block::bordered()
@@ -278,11 +638,32 @@ impl IssueCreate {
             Block::bordered()
                 .border_type(ratatui::widgets::BorderType::Rounded)
                 .border_style(get_border_style(&self.assignees_state))
-                .title("Assignees (comma-separated)"),
+                .title("Assignees (Enter: pick)"),
         );
         assignees_input.render(assignees_area, buf, &mut self.assignees_state);
 
         match self.mode {
+            InputMode::Input if self.form.is_some() => {
+                let mut title = format!(
+                    "{} (Ctrl+P: Preview | Ctrl+T: Template | Ctrl+Enter: Create)",
+                    self.template.as_deref().unwrap_or("Form")
+                );
+                if let Some(err) = &self.error {
+                    title.push_str(" | ");
+                    title.push_str(err);
+                }
+                let mut block = Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .border_style(Style::new().dim());
+                if !self.creating {
+                    block = block.title(title);
+                }
+                let inner = block.inner(body_area);
+                block.render(body_area, buf);
+                if let Some(form) = self.form.as_mut() {
+                    form.render(inner, buf);
+                }
+            }
             InputMode::Input => {
                 let mut title = "Body (Ctrl+P: Preview | Ctrl+Enter: Create)".to_string();
                 if let Some(err) = &self.error {
@@ -334,9 +715,55 @@ impl IssueCreate {
                 .use_type(WhichUse::Spin);
             StatefulWidget::render(throbber, title_area, buf, &mut self.create_throbber_state);
         }
+
+        let cursor = self
+            .labels_state
+            .screen_cursor()
+            .or_else(|| self.body_state.screen_cursor());
+        self.completer.render(cursor, buf);
+
+        if let Some(list_state) = self.template_picker.as_mut() {
+            let templates = self.templates.as_deref().unwrap_or_default();
+            render_template_picker(templates, list_state, area.main_content, buf);
+        }
+        if let Some(picker) = self.assignee_picker.as_mut() {
+            render_assignee_picker(picker, area.main_content, buf);
+        }
     }
 }
 
+fn render_template_picker(
+    templates: &[IssueTemplate],
+    list_state: &mut TuiListState,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(60));
+    Clear.render(popup_area, buf);
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title("Choose a template")
+        .title_bottom("↑/↓: select  Enter: use  Esc: blank issue");
+    let items = std::iter::once(ListItem::new(line![span!("Blank issue").italic()]))
+        .chain(templates.iter().map(|template| {
+            let mut lines = vec![line![span!(template.name.clone()).bold()]];
+            if let Some(about) = &template.about {
+                lines.push(line![span!("  "), span!(about.clone()).dim()]);
+            }
+            if matches!(template.kind, TemplateKind::Form { .. }) {
+                lines[0].push_span(span!("  form").cyan());
+            }
+            ListItem::new(lines)
+        }))
+        .collect::<Vec<_>>();
+    let list = TuiList::new(items)
+        .block(block)
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    StatefulWidget::render(list, popup_area, buf, list_state);
+}
+
 #[async_trait(?Send)]
 impl Component for IssueCreate {
     fn render(&mut self, area: Layout, buf: &mut Buffer) {
@@ -353,6 +780,18 @@ impl Component for IssueCreate {
                 if self.screen != MainScreen::CreateIssue {
                     return Ok(());
                 }
+                if self.template_picker.is_some() {
+                    self.handle_template_picker_event(event);
+                    self.refresh_duplicates();
+                    self.save_draft();
+                    return Ok(());
+                }
+                if self.handle_assignee_picker_event(event) {
+                    return Ok(());
+                }
+                if self.handle_completion_event(event) {
+                    return Ok(());
+                }
                 match event {
                     ct_event!(keycode press Esc) => {
                         if let Some(action_tx) = self.action_tx.clone() {
@@ -367,21 +806,31 @@ impl Component for IssueCreate {
                         match self.mode {
                             InputMode::Input => {
                                 self.preview_state.focus.set(false);
-                                self.body_state.focus.set(true);
+                                match &self.form {
+                                    Some(form) => form.focus_first(),
+                                    None => self.body_state.focus.set(true),
+                                }
                             }
                             InputMode::Preview => {
                                 self.body_state.focus.set(false);
+                                if let Some(form) = &self.form {
+                                    form.clear_focus();
+                                }
                                 self.preview_state.focus.set(true);
                             }
                         }
                         return Ok(());
                     }
+                    ct_event!(key press CONTROL-'t') => {
+                        self.open_template_picker();
+                        return Ok(());
+                    }
                     ct_event!(keycode press CONTROL-Enter) | ct_event!(keycode press ALT-Enter) => {
                         self.submit().await;
                         return Ok(());
                     }
                     ct_event!(keycode press Tab) | ct_event!(keycode press SHIFT-Tab)
-                        if self.body_state.is_focused() =>
+                        if self.editing_body() =>
                     {
                         if let Some(action_tx) = self.action_tx.clone() {
                             let _ = action_tx.send(Action::ForceFocusChange).await;
@@ -395,6 +844,11 @@ impl Component for IssueCreate {
                 self.labels_state.handle(event, rat_widget::event::Regular);
                 self.assignees_state
                     .handle(event, rat_widget::event::Regular);
+                if let Some(issue) = self.duplicates.handle_event(event) {
+                    self.open_duplicate(issue).await;
+                    return Ok(());
+                }
+                self.refresh_duplicates();
 
                 if matches!(
                     event,
@@ -409,6 +863,16 @@ impl Component for IssueCreate {
                     action_tx.send(Action::ForceRender).await?;
                 }
                 match self.mode {
+                    InputMode::Input if self.form.is_some() => {
+                        if let Some(form) = self.form.as_mut()
+                            && form.handle_event(event)
+                        {
+                            let action_tx = self.action_tx.as_ref().ok_or_else(|| {
+                                AppError::Other(anyhow!("issue create action channel unavailable"))
+                            })?;
+                            action_tx.send(Action::ForceRender).await?;
+                        }
+                    }
                     InputMode::Input => {
                         if let event::Event::Key(key) = event
                             && key.code == event::KeyCode::Tab
@@ -430,15 +894,67 @@ impl Component for IssueCreate {
                         self.preview_state.handle(event, rat_widget::event::Regular);
                     }
                 }
+                self.refresh_completion();
+                self.save_draft();
             }
             Action::Tick => {
                 if self.creating {
                     self.create_throbber_state.calc_next();
                 }
+                if let Some(picker) = self.assignee_picker.as_mut()
+                    && picker.loading
+                {
+                    picker.throbber_state.calc_next();
+                }
+                self.duplicates.tick();
+            }
+            Action::EnterIssueCreate if self.keep_draft => {
+                self.screen = MainScreen::CreateIssue;
+                self.keep_draft = false;
+                self.title_state.focus.set(true);
             }
             Action::EnterIssueCreate => {
                 self.screen = MainScreen::CreateIssue;
                 self.reset_form();
+                if let Some(draft) = self.drafts.get(DraftKey::NewIssue) {
+                    self.restore_draft(draft);
+                    self.refresh_duplicates();
+                    if self.templates.is_none() {
+                        self.load_templates();
+                    }
+                    if let Some(action_tx) = self.action_tx.clone() {
+                        let _ = action_tx
+                            .send(toast_action("Restored your draft.", ToastType::Info))
+                            .await;
+                    }
+                    return Ok(());
+                }
+                match self.templates {
+                    Some(_) => self.open_template_picker(),
+                    None => self.load_templates(),
+                }
+            }
+            Action::DuplicateSuggestionsLoaded { .. } => self.duplicates.update(&event),
+            Action::CompletionsLoaded(ref data) => self.completer.update(data),
+            Action::AssignableUsersLoaded { ref users } => {
+                self.assignable = Some(users.clone());
+                if let Some(picker) = self.assignee_picker.as_mut() {
+                    picker.update(&event);
+                }
+            }
+            Action::AssignableUsersError { .. } | Action::AssigneeCountsLoaded { .. } => {
+                if let Some(picker) = self.assignee_picker.as_mut() {
+                    picker.update(&event);
+                }
+            }
+            Action::IssueTemplatesLoaded { templates } => {
+                self.templates = Some(templates);
+                let untouched = self.template.is_none()
+                    && self.title_state.text().is_empty()
+                    && self.body_state.text().is_empty();
+                if self.screen == MainScreen::CreateIssue && untouched {
+                    self.open_template_picker();
+                }
             }
             Action::IssueCreateSuccess { issue } => {
                 if self.screen == MainScreen::CreateIssue {
@@ -454,11 +970,17 @@ impl Component for IssueCreate {
             Action::ChangeIssueScreen(screen) => {
                 self.screen = screen;
                 if screen != MainScreen::CreateIssue {
+                    self.completer.close();
+                    self.assignee_picker = None;
                     self.title_state.focus.set(false);
                     self.labels_state.focus.set(false);
                     self.assignees_state.focus.set(false);
                     self.body_state.focus.set(false);
                     self.preview_state.focus.set(false);
+                    self.duplicates.focus.set(false);
+                    if let Some(form) = &self.form {
+                        form.clear_focus();
+                    }
                 }
             }
             _ => {}
@@ -467,11 +989,15 @@ impl Component for IssueCreate {
     }
 
     fn cursor(&self) -> Option<(u16, u16)> {
+        if let Some(picker) = &self.assignee_picker {
+            return picker.cursor();
+        }
         self.title_state
             .screen_cursor()
             .or_else(|| self.labels_state.screen_cursor())
             .or_else(|| self.assignees_state.screen_cursor())
             .or_else(|| self.body_state.screen_cursor())
+            .or_else(|| self.form.as_ref().and_then(IssueFormState::cursor))
     }
 
     fn should_render(&self) -> bool {
@@ -479,21 +1005,32 @@ impl Component for IssueCreate {
     }
 
     fn is_animating(&self) -> bool {
-        self.screen == MainScreen::CreateIssue && self.creating
+        self.screen == MainScreen::CreateIssue
+            && (self.creating
+                || self.duplicates.is_loading()
+                || self
+                    .assignee_picker
+                    .as_ref()
+                    .is_some_and(|picker| picker.loading))
     }
 
     fn capture_focus_event(&self, event: &event::Event) -> bool {
         if self.screen != MainScreen::CreateIssue {
             return false;
         }
+        if self.template_picker.is_some() || self.assignee_picker.is_some() {
+            return matches!(event, event::Event::Key(_));
+        }
         if !(self.title_state.is_focused()
             || self.labels_state.is_focused()
             || self.assignees_state.is_focused()
-            || self.body_state.is_focused())
+            || self.body_state.is_focused()
+            || self.form.as_ref().is_some_and(IssueFormState::is_focused)
+            || self.duplicates.focus.get())
         {
             return false;
         }
-        if self.body_state.is_focused()
+        if self.editing_body()
             && !matches!(
                 event,
                 ct_event!(keycode press Tab) | ct_event!(keycode press SHIFT-Tab)
@@ -527,10 +1064,16 @@ impl HasFocus for IssueCreate {
         builder.widget(&self.title_state);
         builder.widget(&self.labels_state);
         builder.widget(&self.assignees_state);
-        match self.mode {
-            InputMode::Input => builder.widget(&self.body_state),
-            InputMode::Preview => builder.widget(&self.preview_state),
-        };
+        match (self.mode, &self.form) {
+            (InputMode::Input, Some(form)) => form.build_focus(builder),
+            (InputMode::Input, None) => {
+                builder.widget(&self.body_state);
+            }
+            (InputMode::Preview, _) => {
+                builder.widget(&self.preview_state);
+            }
+        }
+        self.duplicates.build_focus(builder);
         builder.end(tag);
     }
 
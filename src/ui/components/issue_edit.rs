@@ -0,0 +1,282 @@
+//! Reopening issues and editing their title and body, shared by the issue list
+//! and the conversation view.
+
+use octocrab::models::{IssueState, issues::IssueStateReason};
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    text_input::{TextInput, TextInputState},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    widgets::{Block, Clear, StatefulWidget, Widget},
+};
+use ratatui_toaster::ToastType;
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::{
+    backend::{Backend, IssueEdit},
+    ui::{Action, toast_action},
+};
+
+type ActionTx = tokio::sync::mpsc::Sender<Action>;
+
+/// Suspends the UI while the user edits `initial` in their editor, and returns
+/// the edited text.
+pub(crate) async fn run_external_editor(
+    action_tx: &ActionTx,
+    initial: String,
+) -> Result<String, String> {
+    action_tx
+        .send(Action::EditorModeChanged(true))
+        .await
+        .map_err(|err| err.to_string())?;
+    let result = tokio::task::spawn_blocking(move || {
+        ratatui::restore();
+        let edited = edit::edit(&initial).map_err(|err| err.to_string());
+        let _ = ratatui::init();
+        edited
+    })
+    .await
+    .map_err(|err| err.to_string())
+    .and_then(|edited| edited.map_err(|err| err.replace('\n', " ")));
+    let _ = action_tx.send(Action::EditorModeChanged(false)).await;
+    result
+}
+
+/// Sends the edit and reports the updated issue, or the failure, back to the UI.
+pub(crate) async fn update_issue(
+    backend: &Backend,
+    action_tx: &ActionTx,
+    number: u64,
+    edit: IssueEdit,
+    done: String,
+) {
+    match backend.update_issue(number, edit).await {
+        Ok(issue) => {
+            let _ = action_tx
+                .send(Action::IssueUpdated {
+                    issue: Box::new(issue),
+                })
+                .await;
+            let _ = action_tx.send(toast_action(done, ToastType::Success)).await;
+        }
+        Err(err) => {
+            let message = err.to_string().replace('\n', " ");
+            let _ = action_tx
+                .send(toast_action(
+                    format!("Updating #{number} failed: {message}"),
+                    ToastType::Error,
+                ))
+                .await;
+            let _ = action_tx
+                .send(Action::IssueUpdateError { number, message })
+                .await;
+        }
+    }
+}
+
+/// Opens the issue body in the external editor and saves it if it changed.
+pub(crate) fn edit_body_in_editor(
+    backend: Backend,
+    action_tx: ActionTx,
+    number: u64,
+    body: String,
+) {
+    tokio::spawn(async move {
+        let result = run_external_editor(&action_tx, body.clone()).await;
+        let _ = action_tx.send(Action::ForceRender).await;
+        match result {
+            Ok(edited) if edited.trim_end() == body.trim_end() => {}
+            Ok(edited) => {
+                let edit = IssueEdit {
+                    body: Some(edited.trim_end().to_string()),
+                    ..Default::default()
+                };
+                update_issue(
+                    &backend,
+                    &action_tx,
+                    number,
+                    edit,
+                    format!("Updated the description of #{number}"),
+                )
+                .await;
+            }
+            Err(message) => {
+                let _ = action_tx
+                    .send(toast_action(
+                        format!("Editing #{number} failed: {message}"),
+                        ToastType::Error,
+                    ))
+                    .await;
+            }
+        }
+    });
+}
+
+pub(crate) fn reopen_issue(backend: Backend, action_tx: ActionTx, number: u64) {
+    tokio::spawn(async move {
+        match backend
+            .set_issue_state(number, IssueState::Open, Some(IssueStateReason::Reopened))
+            .await
+        {
+            Ok(issue) => {
+                let _ = action_tx
+                    .send(Action::IssueUpdated {
+                        issue: Box::new(issue),
+                    })
+                    .await;
+                let _ = action_tx
+                    .send(toast_action(
+                        format!("Reopened #{number}"),
+                        ToastType::Success,
+                    ))
+                    .await;
+            }
+            Err(err) => {
+                let _ = action_tx
+                    .send(toast_action(
+                        format!(
+                            "Reopening #{number} failed: {}",
+                            err.to_string().replace('\n', " ")
+                        ),
+                        ToastType::Error,
+                    ))
+                    .await;
+            }
+        }
+    });
+}
+
+/// Single-line input for renaming an issue.
+pub(crate) struct IssueTitleEditState {
+    pub(crate) number: u64,
+    original: String,
+    input: TextInputState,
+    pub(crate) saving: bool,
+    pub(crate) throbber_state: ThrobberState,
+    pub(crate) error: Option<String>,
+}
+
+impl IssueTitleEditState {
+    pub(crate) fn new(number: u64, title: &str) -> Self {
+        let mut input = TextInputState::default();
+        input.set_text(title);
+        input.move_to_line_end(false);
+        input.focus.set(true);
+        Self {
+            number,
+            original: title.to_string(),
+            input,
+            saving: false,
+            throbber_state: ThrobberState::default(),
+            error: None,
+        }
+    }
+
+    /// Handles a key press. Returns `false` once the input should close.
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &crossterm::event::Event,
+        backend: &Backend,
+        action_tx: &ActionTx,
+    ) -> bool {
+        if self.saving {
+            return true;
+        }
+        match event {
+            ct_event!(keycode press Esc) => return false,
+            ct_event!(keycode press Enter) => {
+                let title = self.input.text().trim().to_string();
+                if title.is_empty() {
+                    self.error = Some("Title cannot be empty.".to_string());
+                    return true;
+                }
+                if title == self.original {
+                    return false;
+                }
+                self.saving = true;
+                self.error = None;
+                let number = self.number;
+                let backend = backend.clone();
+                let action_tx = action_tx.clone();
+                tokio::spawn(async move {
+                    let edit = IssueEdit {
+                        title: Some(title),
+                        ..Default::default()
+                    };
+                    update_issue(
+                        &backend,
+                        &action_tx,
+                        number,
+                        edit,
+                        format!("Renamed #{number}"),
+                    )
+                    .await;
+                });
+            }
+            crossterm::event::Event::Paste(text) => {
+                self.input.insert_str(text.replace('\n', " "));
+            }
+            _ => {
+                self.input.handle(event, rat_widget::event::Regular);
+            }
+        }
+        true
+    }
+
+    /// Applies the outcome of a save. Returns `false` once the input should
+    /// close.
+    pub(crate) fn update(&mut self, action: &Action) -> bool {
+        match action {
+            Action::IssueUpdated { issue } if issue.number == self.number && self.saving => false,
+            Action::IssueUpdateError { number, message } if *number == self.number => {
+                self.saving = false;
+                self.error = Some(message.clone());
+                true
+            }
+            _ => true,
+        }
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        self.input.screen_cursor()
+    }
+}
+
+pub(crate) fn render_issue_title_edit(
+    state: &mut IssueTitleEditState,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Length(3));
+    Clear.render(popup_area, buf);
+    let heading = match &state.error {
+        Some(err) => format!("Title of #{} | {err}", state.number),
+        None => format!("Title of #{}", state.number),
+    };
+    let input = TextInput::new().block(
+        Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::new().yellow())
+            .title(heading)
+            .title_bottom("Enter: save  Esc: cancel"),
+    );
+    input.render(popup_area, buf, &mut state.input);
+    if state.saving {
+        let title_area = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y,
+            width: 10,
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label("Saving")
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, title_area, buf, &mut state.throbber_state);
+    }
+}
@@ -0,0 +1,416 @@
+//! The fields of a YAML issue form, shown in place of the body editor in the
+//! issue composer.
+
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, TextOutcome, ct_event},
+    focus::{FocusBuilder, FocusFlag, HasFocus, Navigation},
+    text_input::{TextInput, TextInputState},
+    textarea::{TextArea, TextAreaState, TextWrap},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph, StatefulWidget, Widget, Wrap},
+};
+use ratatui_macros::{line, span};
+
+use crate::{
+    backend::templates::{FieldKind, FieldValue, FormField, form_body, missing_fields},
+    ui::{components::issue_conversation::render_markdown_lines, utils::get_border_style},
+};
+
+/// Rows a textarea field takes, borders included.
+const TEXTAREA_HEIGHT: u16 = 7;
+
+enum FieldInput {
+    /// Markdown shown to the reporter.
+    Static,
+    Input(Box<TextInputState>),
+    Textarea(Box<TextAreaState>),
+    /// Dropdowns and checkboxes. `cursor` is the highlighted option.
+    Choice {
+        focus: FocusFlag,
+        area: Rect,
+        cursor: usize,
+        value: FieldValue,
+    },
+}
+
+/// Answers to an issue form.
+pub(crate) struct IssueFormState {
+    fields: Vec<FormField>,
+    inputs: Vec<FieldInput>,
+    /// First field shown, so that the focused one stays in view.
+    first: usize,
+}
+
+impl IssueFormState {
+    pub(crate) fn new(fields: Vec<FormField>) -> Self {
+        let inputs = fields
+            .iter()
+            .map(|field| match (&field.kind, field.initial_value()) {
+                (FieldKind::Markdown { .. }, _) => FieldInput::Static,
+                (FieldKind::Input { .. }, FieldValue::Text(text)) => {
+                    let mut state = TextInputState::default();
+                    state.set_text(text);
+                    FieldInput::Input(Box::new(state))
+                }
+                (FieldKind::Textarea { .. }, FieldValue::Text(text)) => {
+                    let mut state = TextAreaState::new();
+                    state.set_text(text);
+                    FieldInput::Textarea(Box::new(state))
+                }
+                (_, value) => FieldInput::Choice {
+                    focus: FocusFlag::new().with_name("issue_form_choice"),
+                    area: Rect::default(),
+                    cursor: 0,
+                    value,
+                },
+            })
+            .collect();
+        Self {
+            fields,
+            inputs,
+            first: 0,
+        }
+    }
+
+    fn values(&self) -> Vec<FieldValue> {
+        self.inputs
+            .iter()
+            .map(|input| match input {
+                FieldInput::Static => FieldValue::None,
+                FieldInput::Input(state) => FieldValue::Text(state.text().to_string()),
+                FieldInput::Textarea(state) => FieldValue::Text(state.text()),
+                FieldInput::Choice { value, .. } => value.clone(),
+            })
+            .collect()
+    }
+
+    /// The issue body made from the answers.
+    pub(crate) fn body(&self) -> String {
+        form_body(&self.fields, &self.values())
+    }
+
+    /// Labels of the required fields that still need an answer.
+    pub(crate) fn missing(&self) -> Vec<String> {
+        missing_fields(&self.fields, &self.values())
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Focuses the first field that takes an answer.
+    pub(crate) fn focus_first(&self) {
+        for input in &self.inputs {
+            let flag = match input {
+                FieldInput::Static => continue,
+                FieldInput::Input(state) => &state.focus,
+                FieldInput::Textarea(state) => &state.focus,
+                FieldInput::Choice { focus, .. } => focus,
+            };
+            flag.set(true);
+            return;
+        }
+    }
+
+    pub(crate) fn clear_focus(&self) {
+        for input in &self.inputs {
+            match input {
+                FieldInput::Static => {}
+                FieldInput::Input(state) => state.focus.set(false),
+                FieldInput::Textarea(state) => state.focus.set(false),
+                FieldInput::Choice { focus, .. } => focus.set(false),
+            }
+        }
+    }
+
+    fn focused(&self) -> Option<usize> {
+        self.inputs.iter().position(|input| match input {
+            FieldInput::Static => false,
+            FieldInput::Input(state) => state.is_focused(),
+            FieldInput::Textarea(state) => state.is_focused(),
+            FieldInput::Choice { focus, .. } => focus.get(),
+        })
+    }
+
+    pub(crate) fn is_focused(&self) -> bool {
+        self.focused().is_some()
+    }
+
+    /// Whether a textarea has focus. Those take every key but Tab.
+    pub(crate) fn is_textarea_focused(&self) -> bool {
+        self.focused()
+            .is_some_and(|idx| matches!(self.inputs[idx], FieldInput::Textarea(_)))
+    }
+
+    /// Hands a key press to the focused field. Returns `true` if it changed
+    /// anything on screen.
+    pub(crate) fn handle_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let Some(idx) = self.focused() else {
+            return false;
+        };
+        let field = &self.fields[idx];
+        match &mut self.inputs[idx] {
+            FieldInput::Static => false,
+            FieldInput::Input(state) => {
+                if let crossterm::event::Event::Paste(text) = event {
+                    state.insert_str(text.replace('\n', " "));
+                    return true;
+                }
+                state.handle(event, rat_widget::event::Regular) == TextOutcome::TextChanged
+            }
+            FieldInput::Textarea(state) => {
+                if let crossterm::event::Event::Paste(text) = event {
+                    state.insert_str(text);
+                    return true;
+                }
+                state.handle(event, rat_widget::event::Regular) == TextOutcome::TextChanged
+            }
+            FieldInput::Choice { cursor, value, .. } => {
+                let options = match &field.kind {
+                    FieldKind::Dropdown { options, .. } => options.len(),
+                    FieldKind::Checkboxes { options } => options.len(),
+                    _ => 0,
+                };
+                match event {
+                    ct_event!(keycode press Up) => *cursor = cursor.saturating_sub(1),
+                    ct_event!(keycode press Down) => {
+                        *cursor = (*cursor + 1).min(options.saturating_sub(1));
+                    }
+                    ct_event!(key press ' ') | ct_event!(keycode press Enter) => {
+                        toggle(&field.kind, value, *cursor);
+                    }
+                    _ => return false,
+                }
+                true
+            }
+        }
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        self.inputs.iter().find_map(|input| match input {
+            FieldInput::Input(state) => state.screen_cursor(),
+            FieldInput::Textarea(state) => state.screen_cursor(),
+            _ => None,
+        })
+    }
+}
+
+/// Picks or unpicks option `idx` of a dropdown or checkbox field.
+fn toggle(kind: &FieldKind, value: &mut FieldValue, idx: usize) {
+    match (kind, value) {
+        (FieldKind::Dropdown { multiple, .. }, FieldValue::Selected(selected)) => {
+            if let Some(pos) = selected.iter().position(|s| *s == idx) {
+                selected.remove(pos);
+            } else if *multiple {
+                selected.push(idx);
+                selected.sort_unstable();
+            } else {
+                *selected = vec![idx];
+            }
+        }
+        (FieldKind::Checkboxes { .. }, FieldValue::Checked(checked)) => {
+            if let Some(checked) = checked.get_mut(idx) {
+                *checked = !*checked;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn field_title(field: &FormField) -> String {
+    if field.required {
+        format!("{} *", field.label)
+    } else {
+        field.label.clone()
+    }
+}
+
+fn field_block<'a>(field: &'a FormField, border_style: Style) -> Block<'a> {
+    let mut block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(border_style)
+        .title(field_title(field));
+    if let Some(description) = &field.description {
+        block = block.title_bottom(line![span!(description.as_str()).dim()]);
+    }
+    block
+}
+
+impl IssueFormState {
+    /// Rows each field takes at the given width.
+    fn heights(&self, width: u16) -> Vec<u16> {
+        self.fields
+            .iter()
+            .map(|field| match &field.kind {
+                FieldKind::Markdown { text } => {
+                    render_markdown_lines(text, width.max(10) as usize, 0).len() as u16
+                }
+                FieldKind::Input { .. } => 3,
+                FieldKind::Textarea { .. } => TEXTAREA_HEIGHT,
+                FieldKind::Dropdown { options, .. } => options.len() as u16 + 2,
+                FieldKind::Checkboxes { options } => options.len() as u16 + 2,
+            })
+            .collect()
+    }
+
+    /// Scrolls so that the focused field is in view.
+    fn scroll_to_focus(&mut self, heights: &[u16], height: u16) {
+        let Some(focused) = self.focused() else {
+            return;
+        };
+        self.first = self.first.min(focused);
+        while self.first < focused && heights[self.first..=focused].iter().sum::<u16>() > height {
+            self.first += 1;
+        }
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let heights = self.heights(area.width);
+        self.scroll_to_focus(&heights, area.height);
+        let mut y = area.y;
+        for (idx, (field, input)) in self.fields.iter().zip(&mut self.inputs).enumerate() {
+            let height = heights[idx].min(area.bottom().saturating_sub(y));
+            let shown = idx >= self.first && height > 0 && (height == heights[idx] || y == area.y);
+            let field_area = if shown {
+                Rect {
+                    x: area.x,
+                    y,
+                    width: area.width,
+                    height,
+                }
+            } else {
+                Rect::default()
+            };
+            if shown {
+                y += height;
+            }
+            match input {
+                FieldInput::Static => {
+                    if let FieldKind::Markdown { text } = &field.kind
+                        && shown
+                    {
+                        let lines = render_markdown_lines(text, area.width.max(10) as usize, 0);
+                        Paragraph::new(lines)
+                            .wrap(Wrap { trim: false })
+                            .render(field_area, buf);
+                    }
+                }
+                FieldInput::Input(state) => {
+                    state.area = field_area;
+                    if shown {
+                        TextInput::new()
+                            .block(field_block(field, get_border_style(state.as_ref())))
+                            .render(field_area, buf, state);
+                    }
+                }
+                FieldInput::Textarea(state) => {
+                    state.area = field_area;
+                    if shown {
+                        TextArea::new()
+                            .block(field_block(field, get_border_style(state.as_ref())))
+                            .text_wrap(TextWrap::Word(4))
+                            .render(field_area, buf, state);
+                    }
+                }
+                FieldInput::Choice {
+                    focus,
+                    area: choice_area,
+                    cursor,
+                    value,
+                } => {
+                    *choice_area = field_area;
+                    if shown {
+                        render_choices(field, focus, *cursor, value, field_area, buf);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_choices(
+    field: &FormField,
+    focus: &FocusFlag,
+    cursor: usize,
+    value: &FieldValue,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let labels: Vec<(String, bool)> = match (&field.kind, value) {
+        (
+            FieldKind::Dropdown {
+                options, multiple, ..
+            },
+            FieldValue::Selected(selected),
+        ) => options
+            .iter()
+            .enumerate()
+            .map(|(idx, option)| {
+                let picked = selected.contains(&idx);
+                let marker = match (multiple, picked) {
+                    (true, true) => "[x]",
+                    (true, false) => "[ ]",
+                    (false, true) => "(•)",
+                    (false, false) => "( )",
+                };
+                (format!("{marker} {option}"), false)
+            })
+            .collect(),
+        (FieldKind::Checkboxes { options }, FieldValue::Checked(checked)) => options
+            .iter()
+            .zip(checked)
+            .map(|(option, checked)| {
+                let marker = if *checked { "[x]" } else { "[ ]" };
+                (format!("{marker} {}", option.label), option.required)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let lines = labels
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (label, required))| {
+            let mut line = Line::from(label);
+            if required {
+                line.push_span(span!(" *").red());
+            }
+            if focus.get() && idx == cursor {
+                line = line.style(Style::new().add_modifier(Modifier::REVERSED));
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+    let border_style = if focus.get() {
+        Style::new().cyan()
+    } else {
+        Style::default()
+    };
+    Paragraph::new(lines)
+        .block(field_block(field, border_style))
+        .render(area, buf);
+}
+
+impl IssueFormState {
+    /// Adds the fields that take an answer to the host's focus container.
+    pub(crate) fn build_focus(&self, builder: &mut FocusBuilder) {
+        for input in &self.inputs {
+            match input {
+                FieldInput::Static => {}
+                FieldInput::Input(state) => {
+                    builder.widget(state.as_ref());
+                }
+                FieldInput::Textarea(state) => {
+                    builder.widget(state.as_ref());
+                }
+                FieldInput::Choice { focus, area, .. } => {
+                    builder.widget_with_flags(focus.clone(), *area, 0, Navigation::Regular);
+                }
+            }
+        }
+    }
+}
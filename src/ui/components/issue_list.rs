@@ -1,14 +1,35 @@
 use crate::{
-    app::GITHUB_CLIENT,
+    backend::{
+        Backend, CheckState, PullRequestSummary, ReviewDecision, SearchParams, query::ItemKind,
+    },
     bookmarks::Bookmarks,
+    drafts::{DraftKey, Drafts},
     errors::AppError,
+    github::GithubHost,
+    outbox::{Outbox, PendingOp},
     ui::{
-        Action, CloseIssueReason, MergeStrategy,
+        Action, AppState, CloseIssueReason, MergeStrategy,
         components::{
-            Component, help::HelpElementKind, issue_conversation::IssueConversationSeed,
+            Component,
+            assignee_picker::{AssigneeOutcome, AssigneePickerState, render_assignee_picker},
+            bulk_edit::{
+                BulkOp, BulkPromptKind, BulkPromptState, BulkRunState, BulkTarget, PromptOutcome,
+                render_bulk_prompt, render_bulk_run,
+            },
+            drafts_popup::{DraftsOutcome, DraftsPopupState, render_drafts_popup},
+            help::HelpElementKind,
+            issue_conversation::IssueConversationSeed,
             issue_detail::IssuePreviewSeed,
+            issue_edit::{
+                IssueTitleEditState, edit_body_in_editor, render_issue_title_edit, reopen_issue,
+            },
+            merge_popup::{MergePopupState, render_merge_popup},
+            milestone_picker::{
+                MilestonePickerState, PickerOutcome, PickerTarget, render_milestone_picker,
+            },
         },
         layout::Layout,
+        toast_action,
         utils::get_border_style,
     },
 };
@@ -16,23 +37,23 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use octocrab::{
     Page,
-    issues::IssueHandler,
     models::{IssueState, issues::Issue},
 };
+use rat_cursor::HasScreenCursor;
 use rat_widget::{
     event::{HandleEvent, ct_event},
     focus::{HasFocus, Navigation},
     list::selection::RowSelection,
-    text_input::TextInputState,
+    text_input::{TextInput, TextInputState},
 };
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style, Stylize},
     symbols,
-    text::Line,
+    text::{Line, Span},
     widgets::{
-        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, Padding,
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, Padding, Paragraph,
         StatefulWidget, Widget,
     },
 };
@@ -47,51 +68,88 @@ use std::{
 };
 use textwrap::{Options, wrap};
 use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
-use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
-use tracing::trace;
+use tracing::{trace, warn};
 
 pub static LOADED_ISSUE_COUNT: AtomicU32 = AtomicU32::new(0);
 pub const HELP: &[HelpElementKind] = &[
     crate::help_text!("Issue List Help"),
     crate::help_keybind!("Up/Down", "navigate issues"),
-    crate::help_keybind!("Enter", "view issue details"),
+    crate::help_keybind!("Space", "select / deselect issue"),
+    crate::help_keybind!("V", "select every issue up to the last toggled one"),
+    crate::help_keybind!("Ctrl+A", "select all matching issues"),
+    crate::help_keybind!("Esc (selection)", "clear selection"),
+    crate::help_keybind!(
+        "C / a / A / m (selection)",
+        "close, assign, unassign or set milestone of selected issues"
+    ),
+    crate::help_keybind!("+ / -", "add / remove labels on selected issues"),
+    crate::help_keybind!("Enter", "view issue or pull request details"),
     crate::help_keybind!("b", "toggle bookmark"),
     crate::help_keybind!("B", "open bookmark finder"),
+    crate::help_keybind!("D", "open unsent drafts"),
+    crate::help_keybind!(
+        "Enter / d (drafts popup)",
+        "resume / discard selected draft"
+    ),
     crate::help_keybind!("C", "close selected issue"),
+    crate::help_keybind!("O", "reopen selected issue"),
+    crate::help_keybind!("T", "edit title of selected issue"),
+    crate::help_keybind!("E", "edit description in external editor"),
+    crate::help_keybind!("d", "view pull request diff"),
+    crate::help_keybind!("c", "view pull request CI checks"),
+    crate::help_keybind!("M", "merge selected pull request"),
+    crate::help_keybind!("m", "set milestone of selected issue"),
+    crate::help_keybind!("Alt+M", "open milestones"),
     crate::help_keybind!("l", "copy issue link to clipboard"),
     crate::help_keybind!("Enter (bookmark popup)", "open selected bookmark"),
     crate::help_keybind!("Esc (bookmark popup)", "close bookmark popup"),
     crate::help_keybind!("Enter (popup)", "confirm close reason"),
-    crate::help_keybind!("a", "add assignee(s)"),
-    crate::help_keybind!("A", "remove assignee(s)"),
+    crate::help_keybind!(
+        "Duplicate (popup)",
+        "enter the original issue number, then Enter"
+    ),
+    crate::help_keybind!("a", "pick assignee(s) to add"),
+    crate::help_keybind!("A", "pick current assignee(s) to remove"),
     crate::help_keybind!("n", "create new issue"),
-    crate::help_keybind!("Esc", "cancel popup / assign input"),
+    crate::help_keybind!("Esc", "cancel popup"),
 ];
-pub struct IssueList<'a> {
+pub struct IssueList {
     pub issues: Vec<IssueListItem>,
     pub page: Option<Arc<Page<Issue>>>,
     pub list_state: rat_widget::list::ListState<RowSelection>,
-    pub handler: IssueHandler<'a>,
+    pub backend: Backend,
+    outbox: Outbox,
     pub action_tx: Option<tokio::sync::mpsc::Sender<crate::ui::Action>>,
     pub throbber_state: ThrobberState,
-    pub assign_throbber_state: ThrobberState,
-    pub assign_input_state: rat_widget::text_input::TextInputState,
     bookmarks: Arc<RwLock<Bookmarks>>,
-    assign_loading: bool,
-    assign_done_rx: Option<oneshot::Receiver<()>>,
+    assignee_picker: Option<(AssigneeChange, AssigneePickerState)>,
     close_popup: Option<IssueClosePopupState>,
     close_error: Option<String>,
+    merge_popup: Option<MergePopupState>,
+    title_edit: Option<IssueTitleEditState>,
+    milestone_picker: Option<MilestonePickerState>,
+    /// Issues selected for a bulk change.
+    marked: HashSet<u64>,
+    /// Row last toggled with Space, where a range selection starts.
+    mark_anchor: Option<usize>,
+    selecting_all: bool,
+    bulk_prompt: Option<BulkPromptState>,
+    bulk_run: Option<BulkRunState>,
     bookmark_popup: Option<BookmarkPopupState>,
     bookmark_titles: HashMap<u64, Arc<str>>,
     bookmark_title_errors: HashMap<u64, Arc<str>>,
     bookmark_error: Option<String>,
+    drafts: Drafts,
+    drafts_popup: Option<DraftsPopupState>,
+    kind: ItemKind,
+    /// Details per pull request number; `None` once a lookup came back empty.
+    pull_requests: HashMap<u64, Option<PullRequestSummary>>,
+    host: GithubHost,
     pub owner: String,
     pub repo: String,
     index: usize,
     state: LoadingState,
-    inner_state: IssueListState,
-    assignment_mode: AssignmentMode,
     pub screen: MainScreen,
 }
 
@@ -102,6 +160,10 @@ pub(crate) struct IssueClosePopupState {
     pub(crate) throbber_state: ThrobberState,
     pub(crate) error: Option<String>,
     reason_state: TuiListState,
+    /// Number of the issue this one duplicates, while it is being entered.
+    original_input: Option<TextInputState>,
+    /// The entered original issue, and its title once looked up.
+    original: Option<(u64, Option<Result<String, String>>)>,
 }
 
 #[derive(Debug)]
@@ -124,6 +186,8 @@ impl IssueClosePopupState {
             throbber_state: ThrobberState::default(),
             error: None,
             reason_state,
+            original_input: None,
+            original: None,
         }
     }
 
@@ -141,20 +205,115 @@ impl IssueClosePopupState {
             .and_then(|idx| CloseIssueReason::ALL.get(idx).copied())
             .unwrap_or(CloseIssueReason::Completed)
     }
-}
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
-enum IssueListState {
-    #[default]
-    Normal,
-    AssigningInput,
+    /// Whether the popup asks for the issue this one duplicates.
+    pub(crate) fn is_choosing_original(&self) -> bool {
+        self.original_input.is_some()
+    }
+
+    pub(crate) fn choose_original(&mut self) {
+        let input = TextInputState::default();
+        input.focus.set(true);
+        self.original_input = Some(input);
+        self.error = None;
+    }
+
+    /// The issue this one duplicates, once it has been looked up.
+    pub(crate) fn duplicate_of(&self) -> Option<u64> {
+        match &self.original {
+            Some((number, Some(Ok(_)))) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Handles a key press while the original issue is entered, looking up
+    /// each number typed. Returns `true` once the issue should be closed.
+    pub(crate) fn handle_original_event(
+        &mut self,
+        event: &crossterm::event::Event,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) -> bool {
+        let Some(input) = self.original_input.as_mut() else {
+            return false;
+        };
+        match event {
+            ct_event!(keycode press Esc) => {
+                self.original_input = None;
+                self.original = None;
+                self.error = None;
+                return false;
+            }
+            ct_event!(keycode press Enter) => {
+                match &self.original {
+                    Some((_, Some(Ok(_)))) => return true,
+                    Some((_, None)) => self.error = Some("Still looking up the issue.".to_string()),
+                    _ => self.error = Some("Enter the number of another issue.".to_string()),
+                }
+                return false;
+            }
+            crossterm::event::Event::Paste(text) => {
+                input.insert_str(text.trim());
+            }
+            _ => {
+                input.handle(event, rat_widget::event::Regular);
+            }
+        }
+        let number = input
+            .text()
+            .trim()
+            .trim_start_matches('#')
+            .parse::<u64>()
+            .ok()
+            .filter(|number| *number != self.issue_number);
+        if number == self.original.as_ref().map(|(number, _)| *number) {
+            return false;
+        }
+        self.error = None;
+        self.original = number.map(|number| (number, None));
+        let Some(number) = number else {
+            return false;
+        };
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+            let result = match backend.get_issue(number).await {
+                Ok(issue) if issue.pull_request.is_some() => {
+                    Err(format!("#{number} is a pull request"))
+                }
+                Ok(issue) => Ok(issue.title),
+                Err(err) if err.is_not_found() => Err(format!("No issue #{number}")),
+                Err(err) => Err(err.to_string().replace('\n', " ")),
+            };
+            let _ = action_tx
+                .send(Action::DuplicateOriginalLoaded { number, result })
+                .await;
+        });
+        false
+    }
+
+    /// Applies the title lookup of an entered original issue.
+    pub(crate) fn update_original(&mut self, number: u64, result: Result<String, String>) {
+        if let Some((entered, title)) = self.original.as_mut()
+            && *entered == number
+        {
+            *title = Some(result);
+        }
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        self.original_input.as_ref()?.screen_cursor()
+    }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
-enum AssignmentMode {
-    #[default]
-    Add,
-    Remove,
+/// What the users picked in the assignee picker are for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AssigneeChange {
+    Assign(u64),
+    Unassign(u64),
+    /// Assign the issues selected for a bulk change.
+    AssignSelection,
+    UnassignSelection,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -171,46 +330,64 @@ pub enum MainScreen {
     Details,
     DetailsFullscreen,
     CreateIssue,
+    PullRequestDiff,
+    PullRequestChecks,
+    Milestones,
+}
+
+impl MainScreen {
+    /// Whether the screen takes over the whole terminal.
+    pub fn is_fullscreen(self) -> bool {
+        matches!(
+            self,
+            MainScreen::DetailsFullscreen
+                | MainScreen::PullRequestDiff
+                | MainScreen::PullRequestChecks
+                | MainScreen::Milestones
+        )
+    }
 }
 
-impl<'a> IssueList<'a> {
+impl IssueList {
     pub async fn new(
-        handler: IssueHandler<'a>,
-        owner: String,
-        repo: String,
+        AppState {
+            host,
+            owner,
+            repo,
+            backend,
+            outbox,
+            drafts,
+            searches,
+            ..
+        }: AppState,
         tx: tokio::sync::mpsc::Sender<Action>,
         bookmarks: Arc<RwLock<Bookmarks>>,
     ) -> Self {
         LOADED_ISSUE_COUNT.store(0, Ordering::Relaxed);
-        let owner_clone = owner.clone();
-        let repo_clone = repo.clone();
-        tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                return;
-            };
-            let Ok(mut p) = client
-                .inner()
-                .search()
-                .issues_and_pull_requests(&format!(
-                    "repo:{}/{} is:issue is:open",
-                    owner_clone, repo_clone
-                ))
-                .page(1u32)
-                .per_page(15u8)
-                .send()
-                .await
-            else {
-                return;
-            };
-            let items = std::mem::take(&mut p.items);
-            p.items = items;
+        let kind = ItemKind::default();
+        let mut query = format!("repo:{owner}/{repo} is:open");
+        if let Some(qualifier) = kind.qualifier() {
+            query.push(' ');
+            query.push_str(qualifier);
+        }
+        let search_backend = backend.clone();
+        // The search bar runs the startup search instead, when there is one.
+        if searches.startup().is_none() {
+            tokio::spawn(async move {
+                let Ok(mut p) = search_backend.search_issues(SearchParams::new(query)).await else {
+                    return;
+                };
+                let items = std::mem::take(&mut p.items);
+                p.items = items;
 
-            let _ = tx
-                .send(Action::NewPage(Arc::new(p), MergeStrategy::Append))
-                .await;
-        });
+                let _ = tx
+                    .send(Action::NewPage(Arc::new(p), MergeStrategy::Append))
+                    .await;
+            });
+        }
         Self {
             page: None,
+            host,
             owner,
             bookmarks,
             repo,
@@ -218,25 +395,65 @@ impl<'a> IssueList<'a> {
             action_tx: None,
             issues: vec![],
             list_state: rat_widget::list::ListState::default(),
-            assign_throbber_state: ThrobberState::default(),
-            assign_input_state: TextInputState::default(),
-            assign_loading: false,
-            assign_done_rx: None,
+            assignee_picker: None,
             close_popup: None,
+            merge_popup: None,
+            title_edit: None,
+            milestone_picker: None,
+            marked: HashSet::new(),
+            mark_anchor: None,
+            selecting_all: false,
+            bulk_prompt: None,
+            bulk_run: None,
             close_error: None,
             bookmark_popup: None,
             bookmark_titles: HashMap::new(),
             bookmark_title_errors: HashMap::new(),
             bookmark_error: None,
-            handler,
+            drafts,
+            drafts_popup: None,
+            kind,
+            pull_requests: HashMap::new(),
+            backend,
+            outbox,
             index: 0,
             screen: MainScreen::default(),
             state: LoadingState::default(),
-            inner_state: IssueListState::default(),
-            assignment_mode: AssignmentMode::default(),
         }
     }
 
+    /// Loads draft, review and check state for the pull requests on `page`
+    /// that haven't been seen since the last search.
+    fn fetch_pull_request_summaries(&self, page: &Page<Issue>) {
+        let numbers = page
+            .items
+            .iter()
+            .filter(|issue| {
+                issue.pull_request.is_some() && !self.pull_requests.contains_key(&issue.number)
+            })
+            .map(|issue| issue.number)
+            .collect::<Vec<_>>();
+        if numbers.is_empty() {
+            return;
+        }
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let summaries = match backend.pull_request_summaries(&numbers).await {
+                Ok(summaries) => summaries,
+                Err(err) => {
+                    warn!(error = %err, "failed to load pull request details");
+                    Vec::new()
+                }
+            };
+            let _ = action_tx
+                .send(Action::PullRequestSummariesLoaded { numbers, summaries })
+                .await;
+        });
+    }
+
     fn open_close_popup(&mut self) {
         let Some(selected) = self.list_state.selected_checked() else {
             self.close_error = Some("No issue selected.".to_string());
@@ -269,6 +486,7 @@ impl<'a> IssueList<'a> {
             return;
         }
         let reason = popup.selected_reason();
+        let duplicate_of = popup.duplicate_of();
         let number = popup.issue_number;
         popup.loading = true;
         popup.error = None;
@@ -278,26 +496,18 @@ impl<'a> IssueList<'a> {
             popup.error = Some("Action channel unavailable.".to_string());
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let outbox = self.outbox.clone();
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::IssueCloseError {
-                        number,
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
+            let result = match duplicate_of {
+                Some(original) => backend.close_as_duplicate(number, original).await,
+                None => {
+                    backend
+                        .set_issue_state(number, IssueState::Closed, Some(reason.to_octocrab()))
+                        .await
+                }
             };
-            let issues = client.inner().issues(owner, repo);
-            match issues
-                .update(number)
-                .state(IssueState::Closed)
-                .state_reason(reason.to_octocrab())
-                .send()
-                .await
-            {
+            match result {
                 Ok(issue) => {
                     let _ = action_tx
                         .send(Action::IssueCloseSuccess {
@@ -306,6 +516,20 @@ impl<'a> IssueList<'a> {
                         .await;
                 }
                 Err(err) => {
+                    let op = PendingOp::Close {
+                        number,
+                        reason: reason.to_octocrab(),
+                    };
+                    if duplicate_of.is_none() && outbox.enqueue_if_retryable(op, &err) {
+                        let _ = action_tx.send(Action::IssueCloseQueued { number }).await;
+                        let _ = action_tx
+                            .send(toast_action(
+                                "Close queued; it will be retried.",
+                                ToastType::Warning,
+                            ))
+                            .await;
+                        return;
+                    }
                     let _ = action_tx
                         .send(Action::IssueCloseError {
                             number,
@@ -327,6 +551,15 @@ impl<'a> IssueList<'a> {
             }
             return true;
         }
+        if popup.is_choosing_original() {
+            let Some(action_tx) = self.action_tx.as_ref() else {
+                return true;
+            };
+            if popup.handle_original_event(event, &self.backend, action_tx) {
+                self.submit_close_popup().await;
+            }
+            return true;
+        }
         if matches!(event, ct_event!(keycode press Esc)) {
             self.close_popup = None;
             return true;
@@ -340,12 +573,416 @@ impl<'a> IssueList<'a> {
             return true;
         }
         if matches!(event, ct_event!(keycode press Enter)) {
+            if popup.selected_reason() == CloseIssueReason::Duplicate {
+                popup.choose_original();
+                return true;
+            }
             self.submit_close_popup().await;
             return true;
         }
         true
     }
 
+    fn open_merge_popup(&mut self) {
+        let Some(issue) = self
+            .list_state
+            .selected_checked()
+            .and_then(|selected| self.issues.get(selected))
+            .filter(|issue| issue.pull_request.is_some())
+        else {
+            return;
+        };
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        self.merge_popup = Some(MergePopupState::open(
+            issue.number,
+            Arc::from(issue.title.as_str()),
+            &self.backend,
+            action_tx,
+        ));
+    }
+
+    fn handle_merge_popup_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let (Some(popup), Some(action_tx)) = (self.merge_popup.as_mut(), self.action_tx.as_ref())
+        else {
+            return false;
+        };
+        if !popup.handle_event(event, &self.backend, action_tx) {
+            self.merge_popup = None;
+        }
+        true
+    }
+
+    fn selected_issue(&self) -> Option<&IssueListItem> {
+        self.list_state
+            .selected_checked()
+            .and_then(|selected| self.issues.get(selected))
+    }
+
+    fn handle_title_edit_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let (Some(edit), Some(action_tx)) = (self.title_edit.as_mut(), self.action_tx.as_ref())
+        else {
+            return false;
+        };
+        if !edit.handle_event(event, &self.backend, action_tx) {
+            self.title_edit = None;
+        }
+        true
+    }
+
+    async fn handle_drafts_popup_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let Some(popup) = self.drafts_popup.as_mut() else {
+            return false;
+        };
+        match popup.handle_event(event, &self.drafts) {
+            DraftsOutcome::Continue => {}
+            DraftsOutcome::Close => self.close_drafts_popup(),
+            DraftsOutcome::Resume(DraftKey::NewIssue) => {
+                self.close_drafts_popup();
+                if let Some(action_tx) = self.action_tx.as_ref() {
+                    let _ = action_tx.send(Action::EnterIssueCreate).await;
+                    let _ = action_tx
+                        .send(Action::ChangeIssueScreen(MainScreen::CreateIssue))
+                        .await;
+                }
+            }
+            DraftsOutcome::Resume(DraftKey::Comment(number)) => {
+                if let Some(issue) = self.issues.iter().find(|i| i.number == number) {
+                    let issue = issue.0.clone();
+                    self.close_drafts_popup();
+                    self.open_issue(&issue).await;
+                } else {
+                    self.fetch_draft_issue(number);
+                }
+            }
+        }
+        true
+    }
+
+    fn close_drafts_popup(&mut self) {
+        self.drafts_popup = None;
+        if self.screen == MainScreen::List {
+            self.list_state.focus.set(true);
+        }
+    }
+
+    /// Fetches an issue that is not in the list, to resume its draft.
+    fn fetch_draft_issue(&mut self, number: u64) {
+        let (Some(popup), Some(action_tx)) = (self.drafts_popup.as_mut(), self.action_tx.clone())
+        else {
+            return;
+        };
+        popup.opening = Some(number);
+        popup.error = None;
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let action = match backend.get_issue(number).await {
+                Ok(issue) => Action::DraftIssueLoaded {
+                    issue: Box::new(issue),
+                },
+                Err(err) => Action::DraftIssueLoadError {
+                    number,
+                    message: err.to_string().replace('\n', " "),
+                },
+            };
+            let _ = action_tx.send(action).await;
+        });
+    }
+
+    /// Shows the conversation of `issue`.
+    async fn open_issue(&self, issue: &Issue) {
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        let _ = action_tx
+            .send(Action::SelectedIssue {
+                number: issue.number,
+                labels: issue.labels.clone(),
+            })
+            .await;
+        let _ = action_tx
+            .send(Action::SelectedIssuePreview {
+                seed: IssuePreviewSeed::from_issue(issue),
+            })
+            .await;
+        let _ = action_tx
+            .send(Action::EnterIssueDetails {
+                seed: IssueConversationSeed::from_issue(issue),
+            })
+            .await;
+        let _ = action_tx
+            .send(Action::ChangeIssueScreen(MainScreen::Details))
+            .await;
+    }
+
+    fn handle_milestone_picker_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let (Some(picker), Some(action_tx)) =
+            (self.milestone_picker.as_mut(), self.action_tx.as_ref())
+        else {
+            return false;
+        };
+        match picker.handle_event(event, &self.backend, action_tx) {
+            PickerOutcome::Continue => {}
+            PickerOutcome::Close => self.milestone_picker = None,
+            PickerOutcome::Apply(milestone) => {
+                self.milestone_picker = None;
+                self.start_bulk(BulkOp::SetMilestone(milestone));
+            }
+        }
+        true
+    }
+
+    async fn open_assignee_picker(&mut self, change: AssigneeChange) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let assignees_of = |issue: &Issue| {
+            issue
+                .assignees
+                .iter()
+                .map(|user| user.login.clone())
+                .collect::<Vec<_>>()
+        };
+        let picker = match change {
+            AssigneeChange::Assign(number) => {
+                let current = self
+                    .selected_issue()
+                    .map(|issue| assignees_of(issue))
+                    .unwrap_or_default();
+                AssigneePickerState::assignable(
+                    format!("Assign #{number} to"),
+                    &current,
+                    Vec::new(),
+                    &self.backend,
+                    &action_tx,
+                )
+            }
+            AssigneeChange::AssignSelection => AssigneePickerState::assignable(
+                format!("Assign {} selected issues to", self.marked.len()),
+                &[],
+                Vec::new(),
+                &self.backend,
+                &action_tx,
+            ),
+            AssigneeChange::Unassign(_) | AssigneeChange::UnassignSelection => {
+                let mut current = Vec::new();
+                for issue in self.issues.iter().filter(|issue| match change {
+                    AssigneeChange::Unassign(number) => issue.number == number,
+                    _ => self.marked.contains(&issue.number),
+                }) {
+                    for login in assignees_of(issue) {
+                        if !current.contains(&login) {
+                            current.push(login);
+                        }
+                    }
+                }
+                if current.is_empty() {
+                    let message = match change {
+                        AssigneeChange::Unassign(number) => format!("#{number} has no assignees"),
+                        _ => "The selected issues have no assignees".to_string(),
+                    };
+                    let _ = action_tx.send(toast_action(message, ToastType::Info)).await;
+                    return;
+                }
+                current.sort_by_key(|login| login.to_lowercase());
+                let heading = match change {
+                    AssigneeChange::Unassign(number) => format!("Unassign from #{number}"),
+                    _ => format!("Unassign from {} selected issues", self.marked.len()),
+                };
+                AssigneePickerState::fixed(heading, current, &self.backend, &action_tx)
+            }
+        };
+        self.assignee_picker = Some((change, picker));
+    }
+
+    fn handle_assignee_picker_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let (Some((change, picker)), Some(action_tx)) =
+            (self.assignee_picker.as_mut(), self.action_tx.clone())
+        else {
+            return false;
+        };
+        let logins = match picker.handle_event(event) {
+            AssigneeOutcome::Continue => return true,
+            AssigneeOutcome::Close => {
+                self.assignee_picker = None;
+                return true;
+            }
+            AssigneeOutcome::Apply(logins) if logins.is_empty() => return true,
+            AssigneeOutcome::Apply(logins) => logins,
+        };
+        let (number, add) = match *change {
+            AssigneeChange::Assign(number) => (number, true),
+            AssigneeChange::Unassign(number) => (number, false),
+            AssigneeChange::AssignSelection => {
+                self.assignee_picker = None;
+                self.start_bulk(BulkOp::Assign(logins));
+                return true;
+            }
+            AssigneeChange::UnassignSelection => {
+                self.assignee_picker = None;
+                self.start_bulk(BulkOp::Unassign(logins));
+                return true;
+            }
+        };
+        picker.start_saving();
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let logins = logins.iter().map(String::as_str).collect::<Vec<_>>();
+            let result = if add {
+                backend.add_assignees(number, &logins).await
+            } else {
+                backend.remove_assignees(number, &logins).await
+            };
+            let action = match result {
+                Ok(issue) => Action::IssueUpdated {
+                    issue: Box::new(issue),
+                },
+                Err(err) => Action::IssueUpdateError {
+                    number,
+                    message: err.to_string().replace('\n', " "),
+                },
+            };
+            let _ = action_tx.send(action).await;
+        });
+        true
+    }
+
+    fn toggle_mark(&mut self) {
+        let Some(selected) = self.list_state.selected_checked() else {
+            return;
+        };
+        let number = self.issues[selected].number;
+        if !self.marked.remove(&number) {
+            self.marked.insert(number);
+        }
+        self.mark_anchor = Some(selected);
+    }
+
+    fn mark_range(&mut self) {
+        let Some(selected) = self.list_state.selected_checked() else {
+            return;
+        };
+        let anchor = self
+            .mark_anchor
+            .unwrap_or(selected)
+            .min(self.issues.len() - 1);
+        let range = anchor.min(selected)..=anchor.max(selected);
+        self.marked
+            .extend(self.issues[range].iter().map(|issue| issue.number));
+        self.mark_anchor = Some(selected);
+    }
+
+    /// Loads every remaining page of the search, then selects all of it.
+    fn select_all(&mut self) {
+        if self.selecting_all || self.state == LoadingState::Loading {
+            return;
+        }
+        let Some(page) = self.page.clone().filter(|page| page.next.is_some()) else {
+            self.marked
+                .extend(self.issues.iter().map(|issue| issue.number));
+            return;
+        };
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        self.selecting_all = true;
+        self.state = LoadingState::Loading;
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let mut page = page;
+            loop {
+                match backend.next_issue_page(&page).await {
+                    Ok(Some(next)) => {
+                        let next = Arc::new(next);
+                        page = next.clone();
+                        let _ = action_tx
+                            .send(Action::NewPage(next, MergeStrategy::Append))
+                            .await;
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        let _ = action_tx
+                            .send(toast_action(
+                                format!(
+                                    "Loading every issue failed: {}",
+                                    err.to_string().replace('\n', " ")
+                                ),
+                                ToastType::Error,
+                            ))
+                            .await;
+                        break;
+                    }
+                }
+            }
+            let _ = action_tx.send(Action::SelectAllIssues).await;
+            let _ = action_tx.send(Action::FinishedLoading).await;
+        });
+    }
+
+    /// The selected issues in list order, or the issue under the cursor when
+    /// nothing is selected.
+    fn bulk_targets(&self) -> Vec<BulkTarget> {
+        let target = |issue: &IssueListItem| BulkTarget {
+            number: issue.number,
+            title: issue.title.clone(),
+            labels: issue
+                .labels
+                .iter()
+                .map(|label| label.name.clone())
+                .collect(),
+        };
+        if self.marked.is_empty() {
+            return self.selected_issue().map(target).into_iter().collect();
+        }
+        self.issues
+            .iter()
+            .filter(|issue| self.marked.contains(&issue.number))
+            .map(target)
+            .collect()
+    }
+
+    fn open_bulk_prompt(&mut self, kind: BulkPromptKind) {
+        let count = self.bulk_targets().len();
+        if count > 0 {
+            self.bulk_prompt = Some(BulkPromptState::new(kind, count));
+        }
+    }
+
+    fn start_bulk(&mut self, op: BulkOp) {
+        let targets = self.bulk_targets();
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        if targets.is_empty() {
+            return;
+        }
+        self.bulk_run = Some(BulkRunState::start(op, targets, &self.backend, action_tx));
+    }
+
+    fn handle_bulk_event(&mut self, event: &crossterm::event::Event) -> bool {
+        if let Some(run) = self.bulk_run.as_mut() {
+            if !run.handle_event(event) {
+                self.marked = run.unfinished().into_iter().collect();
+                self.mark_anchor = None;
+                self.bulk_run = None;
+            }
+            return true;
+        }
+        let Some(prompt) = self.bulk_prompt.as_mut() else {
+            return false;
+        };
+        match prompt.handle_event(event) {
+            PromptOutcome::Continue => {}
+            PromptOutcome::Cancel => self.bulk_prompt = None,
+            PromptOutcome::Submit(op) => {
+                self.bulk_prompt = None;
+                self.start_bulk(op);
+            }
+        }
+        true
+    }
+
     fn open_bookmark_popup(&mut self) {
         let mut issue_numbers = {
             let bookmarks = self.bookmarks.read().expect("bookmarks lock poisoned");
@@ -424,25 +1061,14 @@ impl<'a> IssueList<'a> {
             popup.loading_numbers.remove(&number);
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
         let cancel = popup.fetch_cancel.clone();
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::BookmarkTitleLoadError {
-                        number,
-                        message: Arc::<str>::from("GitHub client not initialized."),
-                    })
-                    .await;
-                return;
-            };
-            let issues = client.inner().issues(owner, repo);
             let title_result = tokio::select! {
                 _ = cancel.cancelled() => {
                     return;
                 }
-                result = issues.get(number) => {
+                result = backend.get_issue(number) => {
                     result
                 }
             };
@@ -513,25 +1139,14 @@ impl<'a> IssueList<'a> {
             popup.opening_issue = None;
             return Ok(());
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
         let cancel = popup.fetch_cancel.clone();
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::BookmarkedIssueLoadError {
-                        number,
-                        message: Arc::<str>::from("GitHub client not initialized."),
-                    })
-                    .await;
-                return;
-            };
-            let issues = client.inner().issues(owner, repo);
             let issue_result = tokio::select! {
                 _ = cancel.cancelled() => {
                     return;
                 }
-                result = issues.get(number) => {
+                result = backend.get_issue(number) => {
                     result
                 }
             };
@@ -661,23 +1276,18 @@ impl<'a> IssueList<'a> {
         }
     }
 
-    pub fn render(&mut self, mut area: Layout, buf: &mut Buffer) {
-        if self.assign_input_state.lost_focus() {
-            self.inner_state = IssueListState::Normal;
-        }
-
-        let mut assign_input_area = Rect::default();
-        if self.inner_state == IssueListState::AssigningInput {
-            let split = vertical![*=1, ==3].split(area.main_content);
-            area.main_content = split[0];
-            assign_input_area = split[1];
-        }
+    pub fn render(&mut self, area: Layout, buf: &mut Buffer) {
         let mut block = Block::bordered()
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(get_border_style(&self.list_state))
             .padding(Padding::horizontal(3));
         if self.state != LoadingState::Loading {
-            let mut title = format!("[{}] Issues", self.index);
+            let mut title = format!("[{}] {}", self.index, self.kind.title());
+            if self.selecting_all {
+                title.push_str(" | Selecting all...");
+            } else if !self.marked.is_empty() {
+                title.push_str(&format!(" | {} selected", self.marked.len()));
+            }
             if let Some(err) = &self.close_error {
                 title.push_str(" | ");
                 title.push_str(err);
@@ -713,38 +1323,32 @@ impl<'a> IssueList<'a> {
                 .use_type(WhichUse::Spin);
             StatefulWidget::render(full, title_area, buf, &mut self.throbber_state);
         }
-        if self.inner_state == IssueListState::AssigningInput {
-            let mut input_block = Block::bordered()
-                .border_type(ratatui::widgets::BorderType::Rounded)
-                .border_style(get_border_style(&self.assign_input_state));
-            if !self.assign_loading {
-                input_block = input_block.title(match self.assignment_mode {
-                    AssignmentMode::Add => "Assign to",
-                    AssignmentMode::Remove => "Remove assignee(s)",
-                });
-            }
-            let input = rat_widget::text_input::TextInput::new().block(input_block);
-            input.render(assign_input_area, buf, &mut self.assign_input_state);
-            if self.assign_loading {
-                let title_area = Rect {
-                    x: assign_input_area.x + 1,
-                    y: assign_input_area.y,
-                    width: 10,
-                    height: 1,
-                };
-                let full = Throbber::default()
-                    .label("Loading")
-                    .style(ratatui::style::Style::default().fg(ratatui::style::Color::Cyan))
-                    .throbber_set(BRAILLE_SIX_DOUBLE)
-                    .use_type(WhichUse::Spin);
-                StatefulWidget::render(full, title_area, buf, &mut self.assign_throbber_state);
-            }
-        }
         self.render_close_popup(area.main_content, buf);
+        if let Some(popup) = self.merge_popup.as_mut() {
+            render_merge_popup(popup, area.main_content, buf);
+        }
+        if let Some(edit) = self.title_edit.as_mut() {
+            render_issue_title_edit(edit, area.main_content, buf);
+        }
+        if let Some(picker) = self.milestone_picker.as_mut() {
+            render_milestone_picker(picker, area.main_content, buf);
+        }
+        if let Some((_, picker)) = self.assignee_picker.as_mut() {
+            render_assignee_picker(picker, area.main_content, buf);
+        }
+        if let Some(prompt) = self.bulk_prompt.as_mut() {
+            render_bulk_prompt(prompt, area.main_content, buf);
+        }
+        if let Some(run) = self.bulk_run.as_mut() {
+            render_bulk_run(run, area.main_content, buf);
+        }
         self.render_bookmark_popup(area.main_content, buf);
+        if let Some(popup) = self.drafts_popup.as_mut() {
+            render_drafts_popup(popup, area.main_content, buf);
+        }
     }
 
-    fn build_list_item(&self, issue: &'a IssueListItem, bookmarks: &Bookmarks) -> ListItem<'a> {
+    fn build_list_item<'a>(&self, issue: &'a IssueListItem, bookmarks: &Bookmarks) -> ListItem<'a> {
         let options = Options::with_termwidth();
         let binding = issue.body.clone().unwrap_or("No desc provided".to_string());
         let mut body = wrap(binding.trim(), options);
@@ -753,17 +1357,33 @@ impl<'a> IssueList<'a> {
         let bookmarked = bookmarks.is_bookmarked(&self.owner, &self.repo, issue.number);
         let bookmark_symbol = if bookmarked { " b " } else { "   " };
 
-        let lines = vec![
-            line![
-                span!(bookmark_symbol).style(if bookmarked {
-                    Style::new().reversed()
-                } else {
-                    Style::new()
-                }),
-                span!(issue.title.as_str()),
-                " ",
-                span!("#{}", issue.number).dim(),
-            ],
+        let mut title = line![span!(bookmark_symbol).style(if bookmarked {
+            Style::new().reversed()
+        } else {
+            Style::new()
+        }),];
+        if !self.marked.is_empty() {
+            title.push_span(if self.marked.contains(&issue.number) {
+                span!("✓ ").cyan().bold()
+            } else {
+                span!("· ").dim()
+            });
+        }
+        title.extend([
+            span!(issue.title.as_str()),
+            span!(" "),
+            span!("#{}", issue.number).dim(),
+        ]);
+        if self.drafts.has_comment(issue.number) {
+            title.push_span(span!(" ✎").yellow());
+        }
+        let summary = self.pull_requests.get(&issue.number);
+        if summary.is_some_and(|pr| pr.as_ref().is_some_and(|pr| pr.draft)) {
+            title.push_span(" ");
+            title.push_span(span!(" draft ").dim().reversed());
+        }
+        let mut lines = vec![
+            title,
             line![
                 span!(symbols::shade::FULL).style({
                     if matches!(issue.state, IssueState::Open) {
@@ -780,17 +1400,60 @@ impl<'a> IssueList<'a> {
                 )
                 .dim(),
             ],
-            line!["   ", span!(body.join(" ")).style(Style::new().dim())],
         ];
+        match summary {
+            Some(Some(pr)) => lines.push(pull_request_line(pr)),
+            None if issue.pull_request.is_some() => {
+                lines.push(line!["   ", span!("Loading pull request details...").dim()])
+            }
+            _ => {}
+        }
+        lines.push(line![
+            "   ",
+            span!(body.join(" ")).style(Style::new().dim())
+        ]);
         ListItem::new(lines)
     }
 }
 
+/// Branches, review decision and check state of a pull request row.
+fn pull_request_line(pr: &PullRequestSummary) -> Line<'static> {
+    let review = match pr.review_decision {
+        Some(ReviewDecision::Approved) => span!("approved").green(),
+        Some(ReviewDecision::ChangesRequested) => span!("changes requested").red(),
+        Some(ReviewDecision::ReviewRequired) => span!("review required").yellow(),
+        None => span!("no review").dim(),
+    };
+    let mut line = line![
+        "   ",
+        span!("{} <- {}", pr.base, pr.head).cyan(),
+        "  ",
+        review,
+    ];
+    if let Some(checks) = pr.checks {
+        line.push_span("  ");
+        line.push_span(checks_span(checks));
+    }
+    line
+}
+
+fn checks_span(state: CheckState) -> Span<'static> {
+    match state {
+        CheckState::Success => span!("checks passed").green(),
+        CheckState::Pending | CheckState::Expected => span!("checks pending").yellow(),
+        CheckState::Failure | CheckState::Error => span!("checks failing").red(),
+    }
+}
+
 pub(crate) fn render_issue_close_popup(
     popup: &mut IssueClosePopupState,
     area: Rect,
     buf: &mut Buffer,
 ) {
+    if popup.original_input.is_some() {
+        render_original_prompt(popup, area, buf);
+        return;
+    }
     let popup_area = area.centered(Constraint::Percentage(20), Constraint::Length(5));
     Clear.render(popup_area, buf);
 
@@ -832,6 +1495,55 @@ pub(crate) fn render_issue_close_popup(
     }
 }
 
+/// The step of the close popup that asks which issue this one duplicates.
+fn render_original_prompt(popup: &mut IssueClosePopupState, area: Rect, buf: &mut Buffer) {
+    let popup_area = area.centered(Constraint::Percentage(40), Constraint::Length(6));
+    Clear.render(popup_area, buf);
+    let heading = format!("Close #{} as a duplicate of", popup.issue_number);
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .title_bottom("Enter: close as duplicate  Esc: back")
+        .title(match &popup.error {
+            Some(err) => format!("{heading} | {err}"),
+            None => heading,
+        });
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let [input_area, title_area] = vertical![==3, ==1].areas(inner);
+    if let Some(input) = popup.original_input.as_mut() {
+        TextInput::new()
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .title("Original issue #"),
+            )
+            .render(input_area, buf, input);
+    }
+    let lookup = match &popup.original {
+        None => span!("Type the number of the original issue").dim(),
+        Some((number, None)) => span!("Looking up #{number}...").dim(),
+        Some((number, Some(Ok(title)))) => span!("#{number} {title}").bold(),
+        Some((_, Some(Err(err)))) => span!(err.as_str()).red(),
+    };
+    Paragraph::new(lookup).render(title_area, buf);
+
+    if popup.loading {
+        let throbber_area = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y,
+            width: 10,
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label("Closing")
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, throbber_area, buf, &mut popup.throbber_state);
+    }
+}
+
 pub struct IssueListItem(pub Issue);
 
 impl std::ops::Deref for IssueListItem {
@@ -849,7 +1561,7 @@ impl From<Issue> for IssueListItem {
 }
 
 #[async_trait(?Send)]
-impl Component for IssueList<'_> {
+impl Component for IssueList {
     fn render(&mut self, area: Layout, buf: &mut Buffer) {
         self.render(area, buf);
     }
@@ -864,30 +1576,45 @@ impl Component for IssueList<'_> {
                 if self.state == LoadingState::Loading {
                     self.throbber_state.calc_next();
                 }
-                if self.assign_loading {
-                    self.assign_throbber_state.calc_next();
-                }
                 if let Some(popup) = self.close_popup.as_mut()
                     && popup.loading
                 {
                     popup.throbber_state.calc_next();
                 }
+                if let Some(popup) = self.merge_popup.as_mut()
+                    && popup.loading
+                {
+                    popup.throbber_state.calc_next();
+                }
+                if let Some(edit) = self.title_edit.as_mut()
+                    && edit.saving
+                {
+                    edit.throbber_state.calc_next();
+                }
+                if let Some(picker) = self.milestone_picker.as_mut()
+                    && picker.loading
+                {
+                    picker.throbber_state.calc_next();
+                }
+                if let Some((_, picker)) = self.assignee_picker.as_mut()
+                    && picker.loading
+                {
+                    picker.throbber_state.calc_next();
+                }
+                if let Some(run) = self.bulk_run.as_mut()
+                    && run.is_running()
+                {
+                    run.throbber_state.calc_next();
+                }
                 if let Some(popup) = self.bookmark_popup.as_mut()
                     && !popup.loading_numbers.is_empty()
                 {
                     popup.throbber_state.calc_next();
                 }
-                if let Some(rx) = self.assign_done_rx.as_mut()
-                    && rx.try_recv().is_ok()
+                if let Some(popup) = self.drafts_popup.as_mut()
+                    && popup.opening.is_some()
                 {
-                    self.assign_done_rx = None;
-                    self.assign_loading = false;
-                    self.assign_input_state.set_text("");
-                    self.inner_state = IssueListState::Normal;
-                    self.list_state.focus.set(true);
-                    if let Some(action_tx) = self.action_tx.as_ref() {
-                        let _ = action_tx.send(Action::ForceRender).await;
-                    }
+                    popup.throbber_state.calc_next();
                 }
             }
             crate::ui::Action::AppEvent(ref event) => {
@@ -897,25 +1624,93 @@ impl Component for IssueList<'_> {
                 if self.handle_bookmark_popup_event(event).await? {
                     return Ok(());
                 }
+                if self.handle_drafts_popup_event(event).await {
+                    return Ok(());
+                }
                 if self.handle_close_popup_event(event).await {
                     return Ok(());
                 }
+                if self.handle_merge_popup_event(event) {
+                    return Ok(());
+                }
+                if self.handle_title_edit_event(event) {
+                    return Ok(());
+                }
+                if self.handle_milestone_picker_event(event) {
+                    return Ok(());
+                }
+                if self.handle_assignee_picker_event(event) {
+                    return Ok(());
+                }
+                if self.handle_bulk_event(event) {
+                    return Ok(());
+                }
 
+                let selecting = self.list_state.is_focused() && !self.marked.is_empty();
                 match event {
+                    ct_event!(key press ' ') if self.list_state.is_focused() => {
+                        self.toggle_mark();
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'V') if self.list_state.is_focused() => {
+                        self.mark_range();
+                        return Ok(());
+                    }
+                    ct_event!(key press CONTROL-'a') if self.list_state.is_focused() => {
+                        self.select_all();
+                        return Ok(());
+                    }
+                    ct_event!(keycode press Esc) if selecting => {
+                        self.marked.clear();
+                        self.mark_anchor = None;
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'C') if selecting => {
+                        self.open_bulk_prompt(BulkPromptKind::Close);
+                        return Ok(());
+                    }
+                    ct_event!(key press 'a') if selecting => {
+                        self.open_assignee_picker(AssigneeChange::AssignSelection)
+                            .await;
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'A') if selecting => {
+                        self.open_assignee_picker(AssigneeChange::UnassignSelection)
+                            .await;
+                        return Ok(());
+                    }
+                    ct_event!(key press 'm') if selecting => {
+                        if let Some(action_tx) = self.action_tx.as_ref() {
+                            let target = PickerTarget::Selection {
+                                count: self.marked.len(),
+                            };
+                            self.milestone_picker =
+                                Some(MilestonePickerState::open(target, &self.backend, action_tx));
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press '+') | ct_event!(key press SHIFT-'+')
+                        if self.list_state.is_focused() =>
+                    {
+                        self.open_bulk_prompt(BulkPromptKind::AddLabels);
+                        return Ok(());
+                    }
+                    ct_event!(key press '-') if self.list_state.is_focused() => {
+                        self.open_bulk_prompt(BulkPromptKind::RemoveLabels);
+                        return Ok(());
+                    }
                     ct_event!(key press 'a') if self.list_state.is_focused() => {
-                        self.inner_state = IssueListState::AssigningInput;
-                        self.assignment_mode = AssignmentMode::Add;
-                        self.assign_input_state.set_text("");
-                        self.assign_input_state.focus.set(true);
-                        self.list_state.focus.set(false);
+                        if let Some(number) = self.selected_issue().map(|issue| issue.number) {
+                            self.open_assignee_picker(AssigneeChange::Assign(number))
+                                .await;
+                        }
                         return Ok(());
                     }
                     ct_event!(key press SHIFT-'A') if self.list_state.is_focused() => {
-                        self.inner_state = IssueListState::AssigningInput;
-                        self.assignment_mode = AssignmentMode::Remove;
-                        self.assign_input_state.set_text("");
-                        self.assign_input_state.focus.set(true);
-                        self.list_state.focus.set(false);
+                        if let Some(number) = self.selected_issue().map(|issue| issue.number) {
+                            self.open_assignee_picker(AssigneeChange::Unassign(number))
+                                .await;
+                        }
                         return Ok(());
                     }
                     ct_event!(key press SHIFT-'B') if self.list_state.is_focused() => {
@@ -926,6 +1721,17 @@ impl Component for IssueList<'_> {
                         }
                         return Ok(());
                     }
+                    ct_event!(key press SHIFT-'D') if self.list_state.is_focused() => {
+                        let issues = &self.issues;
+                        self.drafts_popup = Some(DraftsPopupState::open(&self.drafts, |number| {
+                            issues
+                                .iter()
+                                .find(|issue| issue.number == number)
+                                .map(|issue| issue.title.clone())
+                        }));
+                        self.list_state.focus.set(false);
+                        return Ok(());
+                    }
                     ct_event!(key press 'b') => {
                         if let Some(selected) = self.list_state.selected_checked() {
                             let issue = &self.issues[selected].0;
@@ -962,34 +1768,123 @@ impl Component for IssueList<'_> {
                             .await?;
                         return Ok(());
                     }
-                    ct_event!(key press SHIFT-'C')
-                        if self.list_state.is_focused()
-                            && self.inner_state == IssueListState::Normal =>
-                    {
+                    ct_event!(key press 'd') if self.list_state.is_focused() => {
+                        let Some(issue) = self
+                            .list_state
+                            .selected_checked()
+                            .and_then(|selected| self.issues.get(selected))
+                            .filter(|issue| issue.pull_request.is_some())
+                        else {
+                            return Ok(());
+                        };
+                        if let Some(action_tx) = self.action_tx.as_ref() {
+                            action_tx
+                                .send(Action::EnterPullRequestDiff {
+                                    number: issue.number,
+                                    title: Arc::from(issue.title.as_str()),
+                                })
+                                .await?;
+                            action_tx
+                                .send(Action::ChangeIssueScreen(MainScreen::PullRequestDiff))
+                                .await?;
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press 'c') if self.list_state.is_focused() => {
+                        let Some(issue) = self
+                            .list_state
+                            .selected_checked()
+                            .and_then(|selected| self.issues.get(selected))
+                            .filter(|issue| issue.pull_request.is_some())
+                        else {
+                            return Ok(());
+                        };
+                        if let Some(action_tx) = self.action_tx.as_ref() {
+                            action_tx
+                                .send(Action::EnterPullRequestChecks {
+                                    number: issue.number,
+                                    title: Arc::from(issue.title.as_str()),
+                                })
+                                .await?;
+                            action_tx
+                                .send(Action::ChangeIssueScreen(MainScreen::PullRequestChecks))
+                                .await?;
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'C') if self.list_state.is_focused() => {
                         self.open_close_popup();
                         return Ok(());
                     }
-                    ct_event!(keycode press Esc)
-                        if self.inner_state == IssueListState::AssigningInput =>
-                    {
-                        self.assign_input_state.set_text("");
-                        self.inner_state = IssueListState::Normal;
-                        self.list_state.focus.set(true);
+                    ct_event!(key press SHIFT-'M') if self.list_state.is_focused() => {
+                        self.open_merge_popup();
+                        return Ok(());
+                    }
+                    ct_event!(key press 'm') if self.list_state.is_focused() => {
+                        if let Some(issue) = self.selected_issue()
+                            && let Some(action_tx) = self.action_tx.as_ref()
+                        {
+                            let target = PickerTarget::Issue {
+                                number: issue.number,
+                                current: issue.milestone.as_ref().map(|m| m.number as u64),
+                            };
+                            self.milestone_picker =
+                                Some(MilestonePickerState::open(target, &self.backend, action_tx));
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press ALT-'m') => {
                         if let Some(action_tx) = self.action_tx.as_ref() {
-                            action_tx.send(Action::ForceRender).await?;
+                            action_tx
+                                .send(Action::ChangeIssueScreen(MainScreen::Milestones))
+                                .await?;
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'T') if self.list_state.is_focused() => {
+                        if let Some(issue) = self.selected_issue() {
+                            self.title_edit =
+                                Some(IssueTitleEditState::new(issue.number, &issue.title));
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'E') if self.list_state.is_focused() => {
+                        if let Some(issue) = self.selected_issue()
+                            && let Some(action_tx) = self.action_tx.clone()
+                        {
+                            edit_body_in_editor(
+                                self.backend.clone(),
+                                action_tx,
+                                issue.number,
+                                issue.body.clone().unwrap_or_default(),
+                            );
+                        }
+                        return Ok(());
+                    }
+                    ct_event!(key press SHIFT-'O') if self.list_state.is_focused() => {
+                        let Some(issue) = self.selected_issue() else {
+                            return Ok(());
+                        };
+                        if let Some(action_tx) = self.action_tx.clone() {
+                            if issue.state == IssueState::Open {
+                                action_tx
+                                    .send(toast_action(
+                                        format!("#{} is already open", issue.number),
+                                        ToastType::Info,
+                                    ))
+                                    .await?;
+                            } else {
+                                reopen_issue(self.backend.clone(), action_tx, issue.number);
+                            }
                         }
                         return Ok(());
                     }
-
                     ct_event!(key press 'l') if self.list_state.is_focused() => {
                         let Some(selected) = self.list_state.selected_checked() else {
                             return Ok(());
                         };
                         let issue = &self.issues[selected].0;
-                        let link = format!(
-                            "https://github.com/{}/{}/issues/{}",
-                            self.owner, self.repo, issue.number
-                        );
+                        let link = self.host.issue_url(&self.owner, &self.repo, issue.number);
 
                         cli_clipboard::set_contents(link)
                             .map_err(|_| anyhow!("Error copying to clipboard"))?;
@@ -1006,68 +1901,6 @@ impl Component for IssueList<'_> {
 
                     _ => {}
                 }
-                if matches!(event, ct_event!(keycode press Enter))
-                    && self.inner_state == IssueListState::AssigningInput
-                    && !self.assign_loading
-                    && let Some(selected) = self.list_state.selected_checked()
-                {
-                    let issue = &self.issues[selected].0;
-                    let value: String = self.assign_input_state.value();
-                    let mut assignees = value
-                        .split(',')
-                        .map(|s| s.trim().to_string())
-                        .collect::<Vec<_>>();
-                    if !assignees.is_empty() {
-                        let tx = self
-                            .action_tx
-                            .as_ref()
-                            .ok_or_else(|| {
-                                AppError::Other(anyhow!("issue list action channel unavailable"))
-                            })?
-                            .clone();
-                        let (done_tx, done_rx) = oneshot::channel();
-                        self.assign_done_rx = Some(done_rx);
-                        self.assign_loading = true;
-                        let assignment_mode = self.assignment_mode;
-                        let number = issue.number;
-                        let owner = self.owner.clone();
-                        let repo = self.repo.clone();
-                        tokio::spawn(async move {
-                            let assignees = std::mem::take(&mut assignees);
-                            let assignees = assignees
-                                .iter()
-                                .filter_map(|s| if s.is_empty() { None } else { Some(&**s) })
-                                .collect::<Vec<_>>();
-
-                            let issue_handler = if let Some(client) = GITHUB_CLIENT.get() {
-                                client.inner().issues(owner, repo)
-                            } else {
-                                let _ = done_tx.send(());
-                                return;
-                            };
-                            let res = match assignment_mode {
-                                AssignmentMode::Add => {
-                                    issue_handler
-                                        .add_assignees(number, assignees.as_slice())
-                                        .await
-                                }
-                                AssignmentMode::Remove => {
-                                    issue_handler
-                                        .remove_assignees(number, assignees.as_slice())
-                                        .await
-                                }
-                            };
-                            if let Ok(issue) = res {
-                                let _ = tx
-                                    .send(crate::ui::Action::SelectedIssuePreview {
-                                        seed: IssuePreviewSeed::from_issue(&issue),
-                                    })
-                                    .await;
-                            }
-                            let _ = done_tx.send(());
-                        });
-                    }
-                }
                 if matches!(event, ct_event!(keycode press Enter)) && self.list_state.is_focused() {
                     if let Some(selected) = self.list_state.selected_checked() {
                         let issue = &self.issues[selected].0;
@@ -1091,8 +1924,6 @@ impl Component for IssueList<'_> {
                     return Ok(());
                 }
 
-                self.assign_input_state
-                    .handle(event, rat_widget::event::Regular);
                 if let rat_widget::event::Outcome::Changed =
                     self.list_state.handle(event, rat_widget::event::Regular)
                 {
@@ -1110,23 +1941,14 @@ impl Component for IssueList<'_> {
                                     ))
                                 })?
                                 .clone();
-                            let page_next = page.next.clone();
+                            let page = page.clone();
+                            let backend = self.backend.clone();
                             self.state = LoadingState::Loading;
                             tokio::spawn(async move {
-                                let Some(client) = GITHUB_CLIENT.get() else {
-                                    let _ = tx.send(crate::ui::Action::FinishedLoading).await;
-                                    return;
-                                };
-                                let p = client.inner().get_page::<Issue>(&page_next).await;
+                                let p = backend.next_issue_page(&page).await;
                                 if let Ok(pres) = p
-                                    && let Some(mut p) = pres
+                                    && let Some(p) = pres
                                 {
-                                    let items = std::mem::take(&mut p.items);
-                                    let items = items
-                                        .into_iter()
-                                        .filter(|i| i.pull_request.is_none())
-                                        .collect();
-                                    p.items = items;
                                     let _ = tx
                                         .send(crate::ui::Action::NewPage(
                                             Arc::new(p),
@@ -1165,7 +1987,11 @@ impl Component for IssueList<'_> {
                 trace!("New Page with {} issues", p.items.len());
                 match merge_strat {
                     MergeStrategy::Replace => {
-                        self.issues = p.items.iter().cloned().map(IssueListItem).collect()
+                        self.issues = p.items.iter().cloned().map(IssueListItem).collect();
+                        self.pull_requests.clear();
+                        self.marked
+                            .retain(|number| p.items.iter().any(|issue| issue.number == *number));
+                        self.mark_anchor = None;
                     }
                     MergeStrategy::Append => self
                         .issues
@@ -1173,12 +1999,34 @@ impl Component for IssueList<'_> {
                 }
                 let count = self.issues.len().min(u32::MAX as usize) as u32;
                 LOADED_ISSUE_COUNT.store(count, Ordering::Relaxed);
+                self.fetch_pull_request_summaries(&p);
                 self.page = Some(p);
                 self.state = LoadingState::Loaded;
             }
             crate::ui::Action::FinishedLoading => {
                 self.state = LoadingState::Loaded;
             }
+            crate::ui::Action::SelectAllIssues => {
+                self.selecting_all = false;
+                self.marked
+                    .extend(self.issues.iter().map(|issue| issue.number));
+            }
+            crate::ui::Action::BulkItemFinished { .. } | crate::ui::Action::BulkFinished => {
+                if let Some(run) = self.bulk_run.as_mut() {
+                    run.update(&event);
+                }
+            }
+            crate::ui::Action::ItemKindChanged(kind) => {
+                self.kind = kind;
+            }
+            crate::ui::Action::PullRequestSummariesLoaded { numbers, summaries } => {
+                for number in numbers {
+                    self.pull_requests.entry(number).or_insert(None);
+                }
+                for summary in summaries {
+                    self.pull_requests.insert(summary.number, Some(summary));
+                }
+            }
             crate::ui::Action::IssueCloseSuccess { issue } => {
                 let issue = *issue;
                 if let Some(existing) = self.issues.iter_mut().find(|i| i.0.number == issue.number)
@@ -1202,6 +2050,11 @@ impl Component for IssueList<'_> {
                     }
                 }
             }
+            crate::ui::Action::DuplicateOriginalLoaded { number, result } => {
+                if let Some(popup) = self.close_popup.as_mut() {
+                    popup.update_original(number, result);
+                }
+            }
             crate::ui::Action::IssueCloseError { number, message } => {
                 if let Some(popup) = self.close_popup.as_mut()
                     && popup.issue_number == number
@@ -1211,11 +2064,120 @@ impl Component for IssueList<'_> {
                     self.close_error = Some(message);
                 }
             }
+            crate::ui::Action::MergeStatusLoaded { .. }
+            | crate::ui::Action::MergeStatusError { .. }
+            | crate::ui::Action::MergeSubmitError { .. } => {
+                if let Some(popup) = self.merge_popup.as_mut() {
+                    popup.update(&event);
+                }
+            }
+            crate::ui::Action::AssignableUsersLoaded { .. }
+            | crate::ui::Action::AssignableUsersError { .. }
+            | crate::ui::Action::AssigneeCountsLoaded { .. } => {
+                if let Some((_, picker)) = self.assignee_picker.as_mut() {
+                    picker.update(&event);
+                }
+            }
+            crate::ui::Action::MilestonesLoaded { .. }
+            | crate::ui::Action::MilestonesError { .. } => {
+                if let Some(picker) = self.milestone_picker.as_mut() {
+                    picker.update(&event);
+                }
+            }
+            crate::ui::Action::IssueUpdated { ref issue } => {
+                if let Some(edit) = self.title_edit.as_mut()
+                    && !edit.update(&event)
+                {
+                    self.title_edit = None;
+                }
+                if let Some(picker) = self.milestone_picker.as_mut()
+                    && !picker.update(&event)
+                {
+                    self.milestone_picker = None;
+                }
+                self.assignee_picker.take_if(|(change, picker)| {
+                    let target = match change {
+                        AssigneeChange::Assign(number) | AssigneeChange::Unassign(number) => {
+                            Some(*number)
+                        }
+                        _ => None,
+                    };
+                    picker.saving && target == Some(issue.number)
+                });
+                let Some(idx) = self.issues.iter().position(|i| i.0.number == issue.number) else {
+                    return Ok(());
+                };
+                self.issues[idx].0 = (**issue).clone();
+                if self.list_state.selected_checked() == Some(idx)
+                    && let Some(action_tx) = self.action_tx.as_ref()
+                {
+                    action_tx
+                        .send(Action::SelectedIssuePreview {
+                            seed: IssuePreviewSeed::from_issue(issue),
+                        })
+                        .await?;
+                }
+            }
+            crate::ui::Action::IssueUpdateError {
+                number,
+                ref message,
+            } => {
+                if let Some(edit) = self.title_edit.as_mut() {
+                    edit.update(&event);
+                }
+                if let Some((
+                    AssigneeChange::Assign(target) | AssigneeChange::Unassign(target),
+                    picker,
+                )) = self.assignee_picker.as_mut()
+                    && *target == number
+                    && picker.saving
+                {
+                    picker.save_failed(message.clone());
+                }
+                if let Some(picker) = self.milestone_picker.as_mut() {
+                    picker.update(&event);
+                }
+            }
+            crate::ui::Action::MergeSubmitted { number } => {
+                if self
+                    .merge_popup
+                    .take_if(|popup| popup.number == number)
+                    .is_some()
+                    && let Some(action_tx) = self.action_tx.as_ref()
+                {
+                    let _ = action_tx.send(Action::RefreshIssueList).await;
+                }
+            }
+            crate::ui::Action::IssueCloseQueued { number } => {
+                if self
+                    .close_popup
+                    .as_ref()
+                    .is_some_and(|popup| popup.issue_number == number)
+                {
+                    self.close_popup = None;
+                    self.close_error = None;
+                }
+            }
             crate::ui::Action::IssueLabelsUpdated { number, labels } => {
                 if let Some(issue) = self.issues.iter_mut().find(|i| i.0.number == number) {
                     issue.0.labels = labels;
                 }
             }
+            crate::ui::Action::LabelUpdated { old_name, label } => {
+                for existing in self
+                    .issues
+                    .iter_mut()
+                    .flat_map(|issue| issue.0.labels.iter_mut())
+                    .filter(|existing| existing.name == old_name)
+                {
+                    *existing = label.clone();
+                }
+            }
+            crate::ui::Action::LabelDeleted { name } => {
+                for issue in self.issues.iter_mut() {
+                    issue.0.labels.retain(|label| label.name != name);
+                }
+            }
             crate::ui::Action::BookmarkTitleLoaded { number, title } => {
                 self.bookmark_titles.insert(number, title);
                 self.bookmark_title_errors.remove(&number);
@@ -1262,6 +2224,24 @@ impl Component for IssueList<'_> {
                         .await?;
                 }
             }
+            crate::ui::Action::DraftIssueLoaded { issue } => {
+                if self
+                    .drafts_popup
+                    .as_ref()
+                    .is_some_and(|popup| popup.opening == Some(issue.number))
+                {
+                    self.close_drafts_popup();
+                    self.open_issue(&issue).await;
+                }
+            }
+            crate::ui::Action::DraftIssueLoadError { number, message } => {
+                if let Some(popup) = self.drafts_popup.as_mut()
+                    && popup.opening == Some(number)
+                {
+                    popup.opening = None;
+                    popup.error = Some(message);
+                }
+            }
             crate::ui::Action::BookmarkedIssueLoadError { number, message } => {
                 if let Some(popup) = self.bookmark_popup.as_mut()
                     && popup.opening_issue == Some(number)
@@ -1276,7 +2256,12 @@ impl Component for IssueList<'_> {
                     self.list_state.focus.set(true);
                 } else {
                     self.close_popup = None;
+                    self.merge_popup = None;
+                    self.title_edit = None;
+                    self.milestone_picker = None;
+                    self.bulk_prompt = None;
                     self.close_bookmark_popup();
+                    self.drafts_popup = None;
                     self.list_state.focus.set(false);
                 }
             }
@@ -1292,12 +2277,26 @@ impl Component for IssueList<'_> {
     fn is_animating(&self) -> bool {
         self.screen == MainScreen::List
             && (self.state == LoadingState::Loading
-                || self.assign_loading
+                || self
+                    .assignee_picker
+                    .as_ref()
+                    .is_some_and(|(_, picker)| picker.loading)
                 || self.close_popup.as_ref().is_some_and(|popup| popup.loading)
+                || self.merge_popup.as_ref().is_some_and(|popup| popup.loading)
+                || self.title_edit.as_ref().is_some_and(|edit| edit.saving)
+                || self
+                    .milestone_picker
+                    .as_ref()
+                    .is_some_and(|picker| picker.loading)
+                || self.bulk_run.as_ref().is_some_and(BulkRunState::is_running)
                 || self
                     .bookmark_popup
                     .as_ref()
-                    .is_some_and(|popup| !popup.loading_numbers.is_empty()))
+                    .is_some_and(|popup| !popup.loading_numbers.is_empty())
+                || self
+                    .drafts_popup
+                    .as_ref()
+                    .is_some_and(|popup| popup.opening.is_some()))
     }
     fn set_index(&mut self, index: usize) {
         self.index = index;
@@ -1310,18 +2309,50 @@ impl Component for IssueList<'_> {
         }
     }
 
+    fn cursor(&self) -> Option<(u16, u16)> {
+        self.merge_popup
+            .as_ref()
+            .and_then(MergePopupState::cursor)
+            .or_else(|| {
+                self.title_edit
+                    .as_ref()
+                    .and_then(IssueTitleEditState::cursor)
+            })
+            .or_else(|| {
+                self.milestone_picker
+                    .as_ref()
+                    .and_then(MilestonePickerState::cursor)
+            })
+            .or_else(|| {
+                self.assignee_picker
+                    .as_ref()
+                    .and_then(|(_, picker)| picker.cursor())
+            })
+            .or_else(|| self.bulk_prompt.as_ref().and_then(BulkPromptState::cursor))
+            .or_else(|| {
+                self.close_popup
+                    .as_ref()
+                    .and_then(IssueClosePopupState::cursor)
+            })
+    }
+
     fn capture_focus_event(&self, _event: &crossterm::event::Event) -> bool {
-        self.close_popup.is_some() || self.bookmark_popup.is_some()
+        self.close_popup.is_some()
+            || self.merge_popup.is_some()
+            || self.title_edit.is_some()
+            || self.milestone_picker.is_some()
+            || self.assignee_picker.is_some()
+            || self.bulk_prompt.is_some()
+            || self.bulk_run.is_some()
+            || self.bookmark_popup.is_some()
+            || self.drafts_popup.is_some()
     }
 }
 
-impl HasFocus for IssueList<'_> {
+impl HasFocus for IssueList {
     fn build(&self, builder: &mut rat_widget::focus::FocusBuilder) {
         let tag = builder.start(self);
         builder.widget(&self.list_state);
-        if self.inner_state == IssueListState::AssigningInput {
-            builder.widget(&self.assign_input_state);
-        }
         builder.end(tag);
     }
     fn area(&self) -> ratatui::layout::Rect {
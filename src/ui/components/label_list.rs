@@ -1,12 +1,12 @@
 use std::{
     cmp::min,
+    collections::HashMap,
     slice,
     str::FromStr,
     time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
-use octocrab::Error as OctoError;
 use octocrab::models::Label;
 use rat_cursor::HasScreenCursor;
 use rat_widget::{
@@ -27,8 +27,9 @@ use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse
 use tracing::error;
 
 use crate::{
-    app::GITHUB_CLIENT,
+    backend::{Backend, LabelEdit},
     errors::AppError,
+    outbox::{Outbox, PendingOp},
     ui::{
         Action, AppState, COLOR_PROFILE,
         components::{Component, help::HelpElementKind, issue_list::MainScreen},
@@ -42,20 +43,22 @@ use ratatui_toaster::ToastType;
 
 const MARKER: &str = ratatui::symbols::marker::DOT;
 const STATUS_TTL: Duration = Duration::from_secs(3);
-const DEFAULT_COLOR: &str = "ededed";
+pub(crate) const DEFAULT_COLOR: &str = "ededed";
 pub const HELP: &[HelpElementKind] = &[
     crate::help_text!("Label List Help"),
     crate::help_keybind!("Up/Down", "select label"),
     crate::help_keybind!("a", "add label to selected issue"),
     crate::help_keybind!("d", "remove selected label from issue"),
+    crate::help_keybind!("e", "edit name, description and color of selected label"),
+    crate::help_keybind!("D", "delete selected label from the repository"),
     crate::help_keybind!("f", "open popup label regex search"),
     crate::help_keybind!("Ctrl+I", "toggle case-insensitive search (popup)"),
-    crate::help_keybind!("Enter", "submit add/create input"),
+    crate::help_keybind!("Enter", "submit add/create input or save label edit"),
     crate::help_keybind!("Arrows", "navigate label color picker"),
-    crate::help_keybind!("Tab / Shift+Tab", "switch input and picker focus"),
+    crate::help_keybind!("Tab / Shift+Tab", "switch between inputs and picker"),
     crate::help_keybind!("Type hex", "set color manually"),
     crate::help_keybind!("Esc", "cancel current label edit flow"),
-    crate::help_keybind!("y / n", "confirm or cancel creating missing label"),
+    crate::help_keybind!("y / n", "confirm or cancel creating or deleting a label"),
 ];
 
 #[derive(Debug)]
@@ -67,11 +70,14 @@ pub struct LabelList {
     mode: LabelEditMode,
     status_message: Option<StatusMessage>,
     pending_status: Option<String>,
-    owner: String,
-    repo: String,
+    backend: Backend,
+    outbox: Outbox,
     screen: MainScreen,
     popup_search: Option<PopupLabelSearchState>,
     label_search_request_seq: u64,
+    /// Number of open issues per label name, loaded with the first issue.
+    open_counts: HashMap<String, u64>,
+    counts_requested: bool,
     index: usize,
 }
 
@@ -92,6 +98,60 @@ enum LabelEditMode {
         input: TextInputState,
         picker: ColorPickerState,
     },
+    Editing(Box<LabelEditorState>),
+    ConfirmDelete {
+        name: String,
+    },
+}
+
+/// Form for changing the name, description and color of a label.
+#[derive(Debug)]
+struct LabelEditorState {
+    original: Label,
+    name: TextInputState,
+    description: TextInputState,
+    color: TextInputState,
+    picker: ColorPickerState,
+}
+
+impl LabelEditorState {
+    fn new(label: &Label) -> Self {
+        let mut name = TextInputState::new_focused();
+        name.set_text(&label.name);
+        let mut description = TextInputState::default();
+        description.set_text(label.description.as_deref().unwrap_or_default());
+        let mut color = TextInputState::default();
+        color.set_text(&label.color);
+        Self {
+            picker: ColorPickerState::with_initial_hex(&label.color),
+            original: label.clone(),
+            name,
+            description,
+            color,
+        }
+    }
+
+    /// The fields that differ from the label, or an error for invalid input.
+    fn build_edit(&self) -> Result<LabelEdit, String> {
+        let name = LabelList::normalize_label_name(self.name.text())
+            .ok_or_else(|| "Label name required.".to_string())?;
+        let color = LabelList::normalize_color(self.color.text())?;
+        let description = self.description.text().trim().to_string();
+        let original = &self.original;
+        Ok(LabelEdit {
+            new_name: (name != original.name).then_some(name),
+            color: (!color.eq_ignore_ascii_case(&original.color)).then_some(color),
+            description: (description != original.description.as_deref().unwrap_or_default())
+                .then_some(description),
+        })
+    }
+
+    fn cursor(&self) -> Option<(u16, u16)> {
+        self.name
+            .screen_cursor()
+            .or_else(|| self.description.screen_cursor())
+            .or_else(|| self.color.screen_cursor())
+    }
 }
 
 #[derive(Debug)]
@@ -128,22 +188,7 @@ impl std::ops::Deref for LabelListItem {
     }
 }
 
-impl From<&LabelListItem> for ListItem<'_> {
-    fn from(value: &LabelListItem) -> Self {
-        let rgb = &value.0.color;
-        let mut c = Color::from_str(&format!("#{}", rgb)).unwrap_or(Color::Gray);
-        if let Some(profile) = COLOR_PROFILE.get() {
-            let adapted = profile.adapt_color(c);
-            if let Some(adapted) = adapted {
-                c = adapted;
-            }
-        }
-        let line = line![span!("{} {}", MARKER, value.0.name).fg(c)];
-        ListItem::new(line)
-    }
-}
-
-fn popup_list_item(value: &LabelListItem) -> ListItem<'_> {
+fn label_color(value: &LabelListItem) -> Color {
     let rgb = &value.0.color;
     let mut c = Color::from_str(&format!("#{}", rgb)).unwrap_or(Color::Gray);
     if let Some(profile) = COLOR_PROFILE.get() {
@@ -152,7 +197,25 @@ fn popup_list_item(value: &LabelListItem) -> ListItem<'_> {
             c = adapted;
         }
     }
+    c
+}
+
+fn open_count_span(count: Option<u64>) -> ratatui::text::Span<'static> {
+    match count {
+        Some(count) => span!("  {count} open").dim(),
+        None => span!(""),
+    }
+}
+
+fn list_item(value: &LabelListItem, count: Option<u64>) -> ListItem<'_> {
+    let line = line![
+        span!("{} {}", MARKER, value.0.name).fg(label_color(value)),
+        open_count_span(count),
+    ];
+    ListItem::new(line)
+}
 
+fn popup_list_item(value: &LabelListItem, count: Option<u64>) -> ListItem<'_> {
     let description = value
         .0
         .description
@@ -160,14 +223,21 @@ fn popup_list_item(value: &LabelListItem) -> ListItem<'_> {
         .filter(|desc| !desc.trim().is_empty())
         .unwrap_or("No description");
     let lines = vec![
-        line![span!("{} {}", MARKER, value.0.name).fg(c)],
+        line![
+            span!("{} {}", MARKER, value.0.name).fg(label_color(value)),
+            open_count_span(count),
+        ],
         line![span!("  {description}").dim()],
     ];
     ListItem::new(lines)
 }
 
 impl LabelList {
-    pub fn new(AppState { repo, owner, .. }: AppState) -> Self {
+    pub fn new(
+        AppState {
+            backend, outbox, ..
+        }: AppState,
+    ) -> Self {
         Self {
             state: Default::default(),
             labels: vec![],
@@ -176,11 +246,13 @@ impl LabelList {
             mode: LabelEditMode::Idle,
             status_message: None,
             pending_status: None,
-            owner,
-            repo,
+            backend,
+            outbox,
             screen: MainScreen::default(),
             popup_search: None,
             label_search_request_seq: 0,
+            open_counts: HashMap::new(),
+            counts_requested: false,
             index: 0,
         }
     }
@@ -203,18 +275,20 @@ impl LabelList {
         let title = if let Some(status) = &self.status_message {
             error!("Label list status: {}", status.message);
             format!(
-                "[{}] Labels (a:add d:remove) | {}",
+                "[{}] Labels (a:add d:remove e:edit) | {}",
                 self.index, status.message
             )
         } else {
-            format!("[{}] Labels (a:add d:remove)", self.index)
+            format!("[{}] Labels (a:add d:remove e:edit)", self.index)
         };
         let block = Block::bordered()
             .border_type(ratatui::widgets::BorderType::Rounded)
             .title(title)
             .border_style(get_border_style(&self.state));
         let list = rat_widget::list::List::<RowSelection>::new(
-            self.labels.iter().map(Into::<ListItem>::into),
+            self.labels
+                .iter()
+                .map(|label| list_item(label, self.open_counts.get(&label.name).copied())),
         )
         .select_style(Style::default().bg(Color::Black))
         .focus_style(Style::default().bold().bg(Color::Black))
@@ -243,6 +317,22 @@ impl LabelList {
                         )
                         .render(area, buf);
                 }
+                LabelEditMode::ConfirmDelete { name } => {
+                    let issues = match self.open_counts.get(name) {
+                        Some(1) => " It is on 1 open issue.".to_string(),
+                        Some(count) => format!(" It is on {count} open issues."),
+                        None => String::new(),
+                    };
+                    let prompt = format!("Delete \"{name}\" from the repository?{issues} (y/n)");
+                    Paragraph::new(prompt)
+                        .block(
+                            Block::bordered()
+                                .border_type(ratatui::widgets::BorderType::Rounded)
+                                .border_style(Style::default().red())
+                                .title("Delete label [y/n]"),
+                        )
+                        .render(area, buf);
+                }
                 LabelEditMode::CreateColor { input, .. } => {
                     let widget = TextInput::new().block(
                         Block::bordered()
@@ -253,7 +343,7 @@ impl LabelList {
                     widget.render(area, buf, input);
                     color_input_area = Some(area);
                 }
-                LabelEditMode::Idle => {
+                LabelEditMode::Idle | LabelEditMode::Editing(_) => {
                     if let Some(status) = &self.status_message {
                         Paragraph::new(status.message.clone()).render(area, buf);
                     }
@@ -263,6 +353,9 @@ impl LabelList {
 
         self.render_popup(area, buf);
         self.render_color_picker(area, buf, color_input_area);
+        if let LabelEditMode::Editing(editor) = &mut self.mode {
+            render_label_editor(editor, area.main_content, buf);
+        }
     }
 
     fn render_color_picker(&mut self, area: Layout, buf: &mut Buffer, anchor: Option<Rect>) {
@@ -378,11 +471,16 @@ impl LabelList {
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(get_border_style(&popup.list_state))
             .title("Matches");
-        let list =
-            rat_widget::list::List::<RowSelection>::new(popup.matches.iter().map(popup_list_item))
-                .select_style(Style::default().bg(Color::Black))
-                .focus_style(Style::default().bold().bg(Color::Black))
-                .block(list_block);
+        let open_counts = &self.open_counts;
+        let list = rat_widget::list::List::<RowSelection>::new(
+            popup
+                .matches
+                .iter()
+                .map(|label| popup_list_item(label, open_counts.get(&label.name).copied())),
+        )
+        .select_style(Style::default().bg(Color::Black))
+        .focus_style(Style::default().bold().bg(Color::Black))
+        .block(list_block);
         list.render(list_area, buf, &mut popup.list_state);
 
         if popup.matches.is_empty() && !popup.loading {
@@ -410,6 +508,7 @@ impl LabelList {
             LabelEditMode::Adding { .. }
                 | LabelEditMode::ConfirmCreate { .. }
                 | LabelEditMode::CreateColor { .. }
+                | LabelEditMode::ConfirmDelete { .. }
         )
     }
 
@@ -447,14 +546,7 @@ impl LabelList {
         let _ = self.state.select(Some(0));
     }
 
-    fn is_not_found(err: &OctoError) -> bool {
-        matches!(
-            err,
-            OctoError::GitHub { source, .. } if source.status_code.as_u16() == 404
-        )
-    }
-
-    fn normalize_label_name(input: &str) -> Option<String> {
+    pub(crate) fn normalize_label_name(input: &str) -> Option<String> {
         let trimmed = input.trim();
         if trimmed.is_empty() {
             None
@@ -463,7 +555,7 @@ impl LabelList {
         }
     }
 
-    fn normalize_color(input: &str) -> Result<String, String> {
+    pub(crate) fn normalize_color(input: &str) -> Result<String, String> {
         let trimmed = input.trim();
         if trimmed.is_empty() {
             return Ok(DEFAULT_COLOR.to_string());
@@ -557,28 +649,11 @@ impl LabelList {
             popup.error = Some("Action channel unavailable.".to_string());
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::LabelSearchError {
-                        request_id,
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
-            };
-            let crab = client.inner();
-            let handler = crab.issues(owner, repo);
-
-            let first = handler
-                .list_labels_for_repo()
-                .per_page(100u8)
-                .page(1u32)
-                .send()
-                .await;
+            let mut page_number = 1_u32;
+            let first = backend.list_labels(page_number, 100).await;
 
             let mut page = match first {
                 Ok(page) => page,
@@ -619,10 +694,9 @@ impl LabelList {
                 if page.next.is_none() {
                     break;
                 }
-                let next_page = crab.get_page::<Label>(&page.next).await;
-                match next_page {
-                    Ok(Some(next_page)) => page = next_page,
-                    Ok(None) => break,
+                page_number += 1;
+                match backend.list_labels(page_number, 100).await {
+                    Ok(next_page) => page = next_page,
                     Err(err) => {
                         let _ = action_tx
                             .send(Action::LabelSearchError {
@@ -711,22 +785,20 @@ impl LabelList {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let outbox = self.outbox.clone();
         self.pending_status = Some(format!("Added: {name}"));
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::LabelEditError {
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
+            let queue_add = |err: &AppError| {
+                let op = PendingOp::AddLabels {
+                    number: issue_number,
+                    labels: vec![name.clone()],
+                };
+                outbox.enqueue_if_retryable(op, err)
             };
-            let handler = client.inner().issues(owner, repo);
-            match handler.get_label(&name).await {
-                Ok(_) => match handler
+            match backend.get_label(&name).await {
+                Ok(_) => match backend
                     .add_labels(issue_number, slice::from_ref(&name))
                     .await
                 {
@@ -738,6 +810,11 @@ impl LabelList {
                             })
                             .await;
                     }
+                    Err(err) if queue_add(&err) => {
+                        let _ = action_tx
+                            .send(Action::LabelEditQueued { name: name.clone() })
+                            .await;
+                    }
                     Err(err) => {
                         let _ = action_tx
                             .send(toast_action(
@@ -753,7 +830,7 @@ impl LabelList {
                     }
                 },
                 Err(err) => {
-                    if LabelList::is_not_found(&err) {
+                    if err.is_not_found() {
                         let _ = action_tx
                             .send(toast_action(
                                 format!("Label not found: {}", &name),
@@ -763,6 +840,10 @@ impl LabelList {
                         let _ = action_tx
                             .send(Action::LabelMissing { name: name.clone() })
                             .await;
+                    } else if queue_add(&err) {
+                        let _ = action_tx
+                            .send(Action::LabelEditQueued { name: name.clone() })
+                            .await;
                     } else {
                         let _ = action_tx
                             .send(toast_action(
@@ -799,21 +880,12 @@ impl LabelList {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
+        let outbox = self.outbox.clone();
         self.pending_status = Some(format!("Removed: {name}"));
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::LabelEditError {
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
-            };
-            let handler = client.inner().issues(owner, repo);
-            match handler.remove_label(issue_number, &name).await {
+            match backend.remove_label(issue_number, &name).await {
                 Ok(labels) => {
                     let _ = action_tx
                         .send(Action::IssueLabelsUpdated {
@@ -822,6 +894,17 @@ impl LabelList {
                         })
                         .await;
                 }
+                Err(err)
+                    if outbox.enqueue_if_retryable(
+                        PendingOp::RemoveLabel {
+                            number: issue_number,
+                            name: name.clone(),
+                        },
+                        &err,
+                    ) =>
+                {
+                    let _ = action_tx.send(Action::LabelEditQueued { name }).await;
+                }
                 Err(err) => {
                     error!("Failed to remove label: {err}");
                     let _ = action_tx
@@ -842,22 +925,12 @@ impl LabelList {
         let Some(action_tx) = self.action_tx.clone() else {
             return;
         };
-        let owner = self.owner.clone();
-        let repo = self.repo.clone();
+        let backend = self.backend.clone();
         self.pending_status = Some(format!("Added: {name}"));
 
         tokio::spawn(async move {
-            let Some(client) = GITHUB_CLIENT.get() else {
-                let _ = action_tx
-                    .send(Action::LabelEditError {
-                        message: "GitHub client not initialized.".to_string(),
-                    })
-                    .await;
-                return;
-            };
-            let handler = client.inner().issues(owner, repo);
-            match handler.create_label(&name, &color, "").await {
-                Ok(_) => match handler
+            match backend.create_label(&name, &color, "").await {
+                Ok(_) => match backend
                     .add_labels(issue_number, slice::from_ref(&name))
                     .await
                 {
@@ -887,6 +960,154 @@ impl LabelList {
             }
         });
     }
+
+    fn selected_label(&mut self) -> Option<Label> {
+        let label = self
+            .state
+            .selected_checked()
+            .and_then(|idx| self.labels.get(idx))
+            .map(|label| label.0.clone());
+        if label.is_none() {
+            self.set_status("No label selected.");
+        }
+        label
+    }
+
+    fn load_open_counts(&mut self) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        self.counts_requested = true;
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            match backend.label_open_issue_counts().await {
+                Ok(counts) => {
+                    let _ = action_tx.send(Action::LabelCountsLoaded { counts }).await;
+                }
+                Err(err) => error!("Failed to load label issue counts: {err}"),
+            }
+        });
+    }
+
+    fn handle_update_label(&mut self, name: String, edit: LabelEdit) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        self.pending_status = Some(format!("Updated: {name}"));
+
+        tokio::spawn(async move {
+            match backend.update_label(&name, edit).await {
+                Ok(label) => {
+                    let _ = action_tx
+                        .send(Action::LabelUpdated {
+                            old_name: name,
+                            label,
+                        })
+                        .await;
+                }
+                Err(err) => {
+                    let _ = action_tx
+                        .send(toast_action(
+                            format!("Failed to update label: {err}"),
+                            ToastType::Error,
+                        ))
+                        .await;
+                    let _ = action_tx
+                        .send(Action::LabelEditError {
+                            message: err.to_string(),
+                        })
+                        .await;
+                }
+            }
+        });
+    }
+
+    fn handle_delete_label(&mut self, name: String) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        self.pending_status = Some(format!("Deleted: {name}"));
+
+        tokio::spawn(async move {
+            match backend.delete_label(&name).await {
+                Ok(()) => {
+                    let _ = action_tx
+                        .send(toast_action(
+                            format!("Deleted label {name}"),
+                            ToastType::Success,
+                        ))
+                        .await;
+                    let _ = action_tx.send(Action::LabelDeleted { name }).await;
+                }
+                Err(err) => {
+                    let _ = action_tx
+                        .send(toast_action(
+                            format!("Failed to delete label: {err}"),
+                            ToastType::Error,
+                        ))
+                        .await;
+                    let _ = action_tx
+                        .send(Action::LabelEditError {
+                            message: err.to_string(),
+                        })
+                        .await;
+                }
+            }
+        });
+    }
+}
+
+fn render_label_editor(editor: &mut LabelEditorState, bounds: Rect, buf: &mut Buffer) {
+    let popup_area = bounds.centered(Constraint::Max(72), Constraint::Length(14));
+    Clear.render(popup_area, buf);
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().cyan())
+        .title(format!("Edit label {}", editor.original.name))
+        .title_bottom("Tab: next field  Enter: save  Esc: cancel");
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let columns = TuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(20), Constraint::Length(34)])
+        .split(inner);
+    let fields = TuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(columns[0]);
+    for (input, title, area) in [
+        (&mut editor.name, "Name", fields[0]),
+        (&mut editor.description, "Description", fields[1]),
+        (&mut editor.color, "Color (#RRGGBB)", fields[2]),
+    ] {
+        TextInput::new()
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .border_style(get_border_style(input))
+                    .title(title),
+            )
+            .render(area, buf, input);
+    }
+    let mut preview = LabelListItem(editor.original.clone());
+    preview.0.name = editor.name.text().trim().to_string();
+    if let Ok(color) = LabelList::normalize_color(editor.color.text()) {
+        preview.0.color = color;
+    }
+    Paragraph::new(line![
+        span!(" Preview: ").dim(),
+        span!("{} {}", MARKER, preview.name).fg(label_color(&preview)),
+    ])
+    .render(fields[3], buf);
+    ColorPicker.render(columns[1], buf, &mut editor.picker);
 }
 
 #[async_trait(?Send)]
@@ -900,7 +1121,7 @@ impl Component for LabelList {
     async fn handle_event(&mut self, event: Action) -> Result<(), AppError> {
         match event {
             Action::AppEvent(ref event) => {
-                if self.screen == MainScreen::DetailsFullscreen {
+                if self.screen.is_fullscreen() {
                     return Ok(());
                 }
                 if self.handle_popup_event(event).await {
@@ -910,6 +1131,8 @@ impl Component for LabelList {
                 enum SubmitAction {
                     Add(String),
                     Create { name: String, color: String },
+                    Update { name: String, edit: LabelEdit },
+                    Delete(String),
                 }
 
                 let mut mode = std::mem::replace(&mut self.mode, LabelEditMode::Idle);
@@ -942,6 +1165,27 @@ impl Component for LabelList {
                                         handled = true;
                                     }
                                 }
+                                crossterm::event::KeyCode::Char('e') => {
+                                    if self.state.is_focused() {
+                                        if let Some(label) = self.selected_label() {
+                                            self.state.focus.set(false);
+                                            next_mode = Some(LabelEditMode::Editing(Box::new(
+                                                LabelEditorState::new(&label),
+                                            )));
+                                        }
+                                        handled = true;
+                                    }
+                                }
+                                crossterm::event::KeyCode::Char('D') => {
+                                    if self.state.is_focused() {
+                                        if let Some(label) = self.selected_label() {
+                                            next_mode = Some(LabelEditMode::ConfirmDelete {
+                                                name: label.name,
+                                            });
+                                        }
+                                        handled = true;
+                                    }
+                                }
                                 _ => {}
                             }
                         }
@@ -1059,6 +1303,57 @@ impl Component for LabelList {
                             }
                         }
                     }
+                    LabelEditMode::Editing(editor) => match event {
+                        ct_event!(keycode press Esc) => {
+                            next_mode = Some(LabelEditMode::Idle);
+                        }
+                        ct_event!(keycode press Tab)
+                        | ct_event!(keycode press BackTab)
+                        | ct_event!(keycode press SHIFT-BackTab) => {}
+                        ct_event!(keycode press Enter) => match editor.build_edit() {
+                            Ok(LabelEdit {
+                                new_name: None,
+                                color: None,
+                                description: None,
+                            }) => {
+                                self.set_status("Nothing to change.");
+                                next_mode = Some(LabelEditMode::Idle);
+                            }
+                            Ok(edit) => {
+                                submit_action = Some(SubmitAction::Update {
+                                    name: editor.original.name.clone(),
+                                    edit,
+                                });
+                                next_mode = Some(LabelEditMode::Idle);
+                            }
+                            Err(message) => self.set_status(message),
+                        },
+                        _ => {
+                            if matches!(editor.picker.handle(event, Regular), Outcome::Changed) {
+                                editor.color.set_text(editor.picker.selected_hex());
+                            } else if editor.color.is_focused() {
+                                editor.color.handle(event, Regular);
+                                if let Ok(color) = Self::normalize_color(editor.color.text()) {
+                                    editor.picker = ColorPickerState::with_initial_hex(&color);
+                                }
+                            } else {
+                                editor.name.handle(event, Regular);
+                                editor.description.handle(event, Regular);
+                            }
+                        }
+                    },
+                    LabelEditMode::ConfirmDelete { name } => match event {
+                        ct_event!(key press 'y') | ct_event!(key press SHIFT-'Y') => {
+                            submit_action = Some(SubmitAction::Delete(name.clone()));
+                            next_mode = Some(LabelEditMode::Idle);
+                        }
+                        ct_event!(key press 'n')
+                        | ct_event!(key press SHIFT-'N')
+                        | ct_event!(keycode press Esc) => {
+                            next_mode = Some(LabelEditMode::Idle);
+                        }
+                        _ => {}
+                    },
                 }
 
                 self.mode = next_mode.unwrap_or(mode);
@@ -1069,6 +1364,8 @@ impl Component for LabelList {
                         SubmitAction::Create { name, color } => {
                             self.handle_create_and_add(name, color).await
                         }
+                        SubmitAction::Update { name, edit } => self.handle_update_label(name, edit),
+                        SubmitAction::Delete(name) => self.handle_delete_label(name),
                     }
                 }
             }
@@ -1083,6 +1380,9 @@ impl Component for LabelList {
                     .collect();
                 self.current_issue_number = Some(number);
                 self.reset_selection(prev);
+                if !self.counts_requested {
+                    self.load_open_counts();
+                }
                 self.pending_status = None;
                 self.status_message = None;
                 self.set_mode(LabelEditMode::Idle);
@@ -1105,7 +1405,50 @@ impl Component for LabelList {
                         .unwrap_or_else(|| "Labels updated.".to_string());
                     self.set_status(status);
                     self.set_mode(LabelEditMode::Idle);
+                    self.load_open_counts();
+                }
+            }
+            Action::LabelUpdated { old_name, label } => {
+                if let Some(count) = self.open_counts.remove(&old_name) {
+                    self.open_counts.insert(label.name.clone(), count);
+                }
+                for item in self
+                    .labels
+                    .iter_mut()
+                    .chain(
+                        self.popup_search
+                            .iter_mut()
+                            .flat_map(|p| p.matches.iter_mut()),
+                    )
+                    .filter(|item| item.name == old_name)
+                {
+                    item.0 = label.clone();
                 }
+                let status = self
+                    .pending_status
+                    .take()
+                    .unwrap_or_else(|| format!("Updated: {}", label.name));
+                self.set_status(status);
+            }
+            Action::LabelDeleted { name } => {
+                self.open_counts.remove(&name);
+                let prev = self
+                    .state
+                    .selected_checked()
+                    .and_then(|idx| self.labels.get(idx).map(|label| label.name.clone()));
+                self.labels.retain(|label| label.name != name);
+                if let Some(popup) = self.popup_search.as_mut() {
+                    popup.matches.retain(|label| label.name != name);
+                }
+                self.reset_selection(prev);
+                let status = self
+                    .pending_status
+                    .take()
+                    .unwrap_or_else(|| format!("Deleted: {name}"));
+                self.set_status(status);
+            }
+            Action::LabelCountsLoaded { counts } => {
+                self.open_counts = counts;
             }
             Action::LabelSearchPageAppend {
                 request_id,
@@ -1161,6 +1504,11 @@ impl Component for LabelList {
                 self.set_status(format!("Error: {message}"));
                 self.set_mode(LabelEditMode::Idle);
             }
+            Action::LabelEditQueued { name } => {
+                self.pending_status = None;
+                self.set_status(format!("Queued: {name} (will retry)"));
+                self.set_mode(LabelEditMode::Idle);
+            }
             Action::Tick => {
                 if let Some(popup) = self.popup_search.as_mut()
                     && popup.loading
@@ -1170,7 +1518,7 @@ impl Component for LabelList {
             }
             Action::ChangeIssueScreen(screen) => {
                 self.screen = screen;
-                if screen == MainScreen::DetailsFullscreen {
+                if screen.is_fullscreen() {
                     self.mode = LabelEditMode::Idle;
                     self.popup_search = None;
                     self.status_message = None;
@@ -1183,7 +1531,7 @@ impl Component for LabelList {
     }
 
     fn should_render(&self) -> bool {
-        self.screen != MainScreen::DetailsFullscreen
+        !self.screen.is_fullscreen()
     }
 
     fn cursor(&self) -> Option<(u16, u16)> {
@@ -1193,6 +1541,7 @@ impl Component for LabelList {
         match &self.mode {
             LabelEditMode::Adding { input } => input.screen_cursor(),
             LabelEditMode::CreateColor { input, .. } => input.screen_cursor(),
+            LabelEditMode::Editing(editor) => editor.cursor(),
             _ => None,
         }
     }
@@ -1221,6 +1570,8 @@ impl Component for LabelList {
                 LabelEditMode::Adding { .. }
                     | LabelEditMode::ConfirmCreate { .. }
                     | LabelEditMode::CreateColor { .. }
+                    | LabelEditMode::Editing(_)
+                    | LabelEditMode::ConfirmDelete { .. }
             )
     }
 }
@@ -1236,6 +1587,12 @@ impl HasFocus for LabelList {
             builder.widget(input);
             builder.widget(picker);
         }
+        if let LabelEditMode::Editing(editor) = &self.mode {
+            builder.widget(&editor.name);
+            builder.widget(&editor.description);
+            builder.widget(&editor.color);
+            builder.widget(&editor.picker);
+        }
         builder.end(tag);
     }
     fn area(&self) -> ratatui::layout::Rect {
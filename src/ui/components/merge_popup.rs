@@ -0,0 +1,776 @@
+//! The merge dialog shared by the issue list and the conversation view.
+
+use std::sync::Arc;
+
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    text_input::{TextInput, TextInputState},
+    textarea::{TextArea, TextAreaState, TextWrap},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, StatefulWidget, Widget, Wrap},
+};
+use ratatui_macros::{line, span, vertical};
+use ratatui_toaster::ToastType;
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::{
+    backend::{
+        Backend, CheckState, MergeMethod, MergeRequest, MergeStateStatus, MergeStatus, Mergeable,
+        PullRequestState, ReviewDecision,
+    },
+    ui::{Action, toast_action, utils::get_border_style},
+};
+
+/// Something that keeps a pull request from being merged right now.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Blocker {
+    pub(crate) reason: String,
+    /// Whether auto-merge can wait for this to be resolved.
+    pub(crate) waits: bool,
+}
+
+impl Blocker {
+    fn new(reason: impl Into<String>, waits: bool) -> Self {
+        Self {
+            reason: reason.into(),
+            waits,
+        }
+    }
+}
+
+/// Explains, in the order a user would have to address them, why a pull
+/// request cannot be merged.
+pub(crate) fn merge_blockers(status: &MergeStatus) -> Vec<Blocker> {
+    match status.state {
+        PullRequestState::Merged => return vec![Blocker::new("Already merged.", false)],
+        PullRequestState::Closed => {
+            return vec![Blocker::new("Closed; reopen it to merge.", false)];
+        }
+        PullRequestState::Open => {}
+    }
+    let mut blockers = Vec::new();
+    if status.draft || status.merge_state == MergeStateStatus::Draft {
+        blockers.push(Blocker::new(
+            "Still a draft; mark it ready for review on GitHub first.",
+            false,
+        ));
+    }
+    if status.allowed_methods.is_empty() {
+        blockers.push(Blocker::new(
+            "The repository allows no merge method.",
+            false,
+        ));
+    }
+    if status.mergeable == Mergeable::Conflicting || status.merge_state == MergeStateStatus::Dirty {
+        blockers.push(Blocker::new(
+            format!(
+                "`{}` has conflicts with `{}` that must be resolved.",
+                status.head, status.base
+            ),
+            false,
+        ));
+    }
+    match status.merge_state {
+        MergeStateStatus::Behind => blockers.push(Blocker::new(
+            format!(
+                "`{}` is behind `{}` and must be updated first.",
+                status.head, status.base
+            ),
+            false,
+        )),
+        MergeStateStatus::Blocked => {
+            let before = blockers.len();
+            match status.review_decision {
+                Some(ReviewDecision::ChangesRequested) => {
+                    blockers.push(Blocker::new("A reviewer requested changes.", true));
+                }
+                Some(ReviewDecision::ReviewRequired) => {
+                    blockers.push(Blocker::new("An approving review is required.", true));
+                }
+                _ => {}
+            }
+            match status.checks {
+                Some(CheckState::Failure | CheckState::Error) => {
+                    blockers.push(Blocker::new("Required checks are failing.", true));
+                }
+                Some(CheckState::Pending | CheckState::Expected) => {
+                    blockers.push(Blocker::new("Required checks have not finished.", true));
+                }
+                _ => {}
+            }
+            if blockers.len() == before {
+                blockers.push(Blocker::new("Blocked by branch protection rules.", true));
+            }
+        }
+        MergeStateStatus::Unknown if status.mergeable == Mergeable::Unknown => {
+            blockers.push(Blocker::new(
+                "GitHub is still checking mergeability; press Ctrl+R to refresh.",
+                true,
+            ));
+        }
+        _ => {}
+    }
+    blockers
+}
+
+/// What submitting the dialog does, given the auto-merge checkbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Submit {
+    Merge,
+    EnableAutoMerge,
+    DisableAutoMerge,
+}
+
+fn plan_submit(status: &MergeStatus, auto_merge: bool) -> Result<Submit, String> {
+    let blockers = merge_blockers(status);
+    if let Some(blocker) = blockers.iter().find(|blocker| !blocker.waits) {
+        return Err(blocker.reason.clone());
+    }
+    match (auto_merge, blockers.first()) {
+        (true, _) if !status.auto_merge_allowed => {
+            Err("Auto-merge is disabled for this repository.".to_string())
+        }
+        (true, None) => Err("Nothing to wait for; uncheck auto-merge to merge now.".to_string()),
+        (true, Some(_)) => Ok(Submit::EnableAutoMerge),
+        (false, Some(_)) if status.auto_merge.is_some() => Ok(Submit::DisableAutoMerge),
+        (false, Some(blocker)) => Err(blocker.reason.clone()),
+        (false, None) => Ok(Submit::Merge),
+    }
+}
+
+fn method_label(method: MergeMethod) -> &'static str {
+    match method {
+        MergeMethod::Merge => "Merge commit",
+        MergeMethod::Squash => "Squash and merge",
+        MergeMethod::Rebase => "Rebase and merge",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Method,
+    Title,
+    Message,
+    DeleteBranch,
+    AutoMerge,
+}
+
+/// State of the merge dialog for one pull request.
+pub(crate) struct MergePopupState {
+    pub(crate) number: u64,
+    title: Arc<str>,
+    status: Option<MergeStatus>,
+    load_error: Option<String>,
+    method: MergeMethod,
+    commit_title: TextInputState,
+    commit_message: TextAreaState,
+    delete_branch: bool,
+    auto_merge: bool,
+    field: Field,
+    /// Set while the status is fetched or the merge is submitted.
+    pub(crate) loading: bool,
+    pub(crate) throbber_state: ThrobberState,
+    pub(crate) error: Option<String>,
+}
+
+impl MergePopupState {
+    /// Opens the dialog and starts checking mergeability.
+    pub(crate) fn open(
+        number: u64,
+        title: Arc<str>,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) -> Self {
+        let mut popup = Self {
+            number,
+            title,
+            status: None,
+            load_error: None,
+            method: MergeMethod::default(),
+            commit_title: TextInputState::default(),
+            commit_message: TextAreaState::new(),
+            delete_branch: false,
+            auto_merge: false,
+            field: Field::Method,
+            loading: false,
+            throbber_state: ThrobberState::default(),
+            error: None,
+        };
+        popup.refresh(backend, action_tx);
+        popup
+    }
+
+    fn refresh(&mut self, backend: &Backend, action_tx: &tokio::sync::mpsc::Sender<Action>) {
+        self.loading = true;
+        self.load_error = None;
+        self.error = None;
+        let number = self.number;
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+            let action = match backend.merge_status(number).await {
+                Ok(status) => Action::MergeStatusLoaded {
+                    number,
+                    status: Box::new(status),
+                },
+                Err(err) => Action::MergeStatusError {
+                    number,
+                    message: err.to_string().replace('\n', " "),
+                },
+            };
+            let _ = action_tx.send(action).await;
+        });
+    }
+
+    /// Applies a result of one of the requests this dialog started.
+    pub(crate) fn update(&mut self, action: &Action) {
+        match action {
+            Action::MergeStatusLoaded { number, status } if *number == self.number => {
+                self.loading = false;
+                self.set_status((**status).clone());
+            }
+            Action::MergeStatusError { number, message } if *number == self.number => {
+                self.loading = false;
+                self.load_error = Some(message.clone());
+            }
+            Action::MergeSubmitError { number, message } if *number == self.number => {
+                self.loading = false;
+                self.error = Some(message.clone());
+            }
+            _ => {}
+        }
+    }
+
+    fn set_status(&mut self, status: MergeStatus) {
+        let first_load = self.status.is_none();
+        if first_load {
+            self.method = status
+                .auto_merge
+                .filter(|method| status.allowed_methods.contains(method))
+                .or_else(|| status.allowed_methods.first().copied())
+                .unwrap_or_default();
+            self.auto_merge = status.auto_merge.is_some();
+            self.delete_branch = status.delete_branch_on_merge;
+        }
+        self.status = Some(status);
+        if first_load {
+            self.reset_message();
+        }
+        self.focus_field(self.field);
+    }
+
+    fn default_message(&self) -> (String, String) {
+        let Some(status) = &self.status else {
+            return Default::default();
+        };
+        let message = match self.method {
+            MergeMethod::Squash => &status.squash_message,
+            _ => &status.merge_message,
+        };
+        (message.title.clone(), message.body.clone())
+    }
+
+    fn reset_message(&mut self) {
+        let (title, body) = self.default_message();
+        self.commit_title.set_text(title);
+        self.commit_message.set_text(body);
+    }
+
+    /// Switches to the next allowed method, keeping the commit message if the
+    /// user edited it.
+    fn cycle_method(&mut self, forward: bool) {
+        let Some(status) = &self.status else {
+            return;
+        };
+        let methods = &status.allowed_methods;
+        let Some(current) = methods.iter().position(|m| *m == self.method) else {
+            return;
+        };
+        let next = if forward {
+            (current + 1) % methods.len()
+        } else {
+            (current + methods.len() - 1) % methods.len()
+        };
+        let (old_title, old_body) = self.default_message();
+        let untouched =
+            self.commit_title.text() == old_title && self.commit_message.text() == old_body;
+        self.method = methods[next];
+        if untouched {
+            self.reset_message();
+        }
+    }
+
+    fn can_delete_branch(&self) -> bool {
+        self.status
+            .as_ref()
+            .is_some_and(|status| !status.cross_repository && !status.delete_branch_on_merge)
+            && !self.auto_merge
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        let mut fields = vec![Field::Method];
+        if self.method != MergeMethod::Rebase {
+            fields.extend([Field::Title, Field::Message]);
+        }
+        if self.can_delete_branch() {
+            fields.push(Field::DeleteBranch);
+        }
+        if self
+            .status
+            .as_ref()
+            .is_some_and(|status| status.auto_merge_allowed)
+        {
+            fields.push(Field::AutoMerge);
+        }
+        fields
+    }
+
+    fn focus_field(&mut self, field: Field) {
+        let fields = self.fields();
+        self.field = if fields.contains(&field) {
+            field
+        } else {
+            Field::Method
+        };
+        self.commit_title.focus.set(self.field == Field::Title);
+        self.commit_message.focus.set(self.field == Field::Message);
+    }
+
+    fn step_field(&mut self, forward: bool) {
+        let fields = self.fields();
+        let current = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % fields.len()
+        } else {
+            (current + fields.len() - 1) % fields.len()
+        };
+        self.focus_field(fields[next]);
+    }
+
+    /// Handles a key press. Returns `false` once the dialog should close.
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &crossterm::event::Event,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) -> bool {
+        if self.loading {
+            if matches!(event, ct_event!(keycode press Esc)) {
+                self.loading = false;
+            }
+            return true;
+        }
+        match event {
+            ct_event!(keycode press Esc) => return false,
+            ct_event!(key press CONTROL-'r') => self.refresh(backend, action_tx),
+            ct_event!(keycode press CONTROL-Enter) | ct_event!(keycode press ALT-Enter) => {
+                self.submit(backend, action_tx);
+            }
+            ct_event!(keycode press Tab) => self.step_field(true),
+            ct_event!(keycode press SHIFT-BackTab) | ct_event!(keycode press BackTab) => {
+                self.step_field(false);
+            }
+            _ => match self.field {
+                Field::Method => match event {
+                    ct_event!(keycode press Left) | ct_event!(key press 'h') => {
+                        self.cycle_method(false);
+                    }
+                    ct_event!(keycode press Right) | ct_event!(key press 'l') => {
+                        self.cycle_method(true);
+                    }
+                    _ => {}
+                },
+                Field::Title => match event {
+                    ct_event!(keycode press Enter) => self.step_field(true),
+                    crossterm::event::Event::Paste(text) => {
+                        self.commit_title.insert_str(text.replace('\n', " "));
+                    }
+                    _ => {
+                        self.commit_title.handle(event, rat_widget::event::Regular);
+                    }
+                },
+                Field::Message => match event {
+                    crossterm::event::Event::Paste(text) => {
+                        self.commit_message.insert_str(text);
+                    }
+                    _ => {
+                        self.commit_message
+                            .handle(event, rat_widget::event::Regular);
+                    }
+                },
+                Field::DeleteBranch => {
+                    if matches!(
+                        event,
+                        ct_event!(key press ' ') | ct_event!(keycode press Enter)
+                    ) {
+                        self.delete_branch = !self.delete_branch;
+                    }
+                }
+                Field::AutoMerge => {
+                    if matches!(
+                        event,
+                        ct_event!(key press ' ') | ct_event!(keycode press Enter)
+                    ) {
+                        self.auto_merge = !self.auto_merge;
+                    }
+                }
+            },
+        }
+        true
+    }
+
+    fn submit(&mut self, backend: &Backend, action_tx: &tokio::sync::mpsc::Sender<Action>) {
+        let Some(status) = self.status.as_ref() else {
+            return;
+        };
+        let plan = match plan_submit(status, self.auto_merge) {
+            Ok(plan) => plan,
+            Err(err) => {
+                self.error = Some(err);
+                return;
+            }
+        };
+        let request = MergeRequest {
+            method: self.method,
+            title: self.commit_title.text().trim().to_string(),
+            message: self.commit_message.text().trim_end().to_string(),
+            sha: Some(status.head_sha.clone()),
+        };
+        if request.method != MergeMethod::Rebase && request.title.is_empty() {
+            self.error = Some("The commit title cannot be empty.".to_string());
+            return;
+        }
+        let number = self.number;
+        let id = status.id.clone();
+        let branch = (plan == Submit::Merge && self.delete_branch && self.can_delete_branch())
+            .then(|| status.head.clone());
+        self.loading = true;
+        self.error = None;
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+            let result = match plan {
+                Submit::Merge => backend.merge_pull_request(number, request).await,
+                Submit::EnableAutoMerge => backend.enable_auto_merge(&id, request).await,
+                Submit::DisableAutoMerge => backend.disable_auto_merge(&id).await,
+            };
+            if let Err(err) = result {
+                let _ = action_tx
+                    .send(Action::MergeSubmitError {
+                        number,
+                        message: err.to_string().replace('\n', " "),
+                    })
+                    .await;
+                return;
+            }
+            let toast = match (plan, branch) {
+                (Submit::Merge, Some(branch)) => match backend.delete_branch(&branch).await {
+                    Ok(()) => toast_action(
+                        format!("Merged #{number} and deleted {branch}"),
+                        ToastType::Success,
+                    ),
+                    Err(err) => toast_action(
+                        format!("Merged #{number}, but deleting {branch} failed: {err}"),
+                        ToastType::Warning,
+                    ),
+                },
+                (Submit::Merge, None) => {
+                    toast_action(format!("Merged #{number}"), ToastType::Success)
+                }
+                (Submit::EnableAutoMerge, _) => toast_action(
+                    format!("Auto-merge enabled on #{number}"),
+                    ToastType::Success,
+                ),
+                (Submit::DisableAutoMerge, _) => {
+                    toast_action(format!("Auto-merge disabled on #{number}"), ToastType::Info)
+                }
+            };
+            let _ = action_tx.send(Action::MergeSubmitted { number }).await;
+            let _ = action_tx.send(toast).await;
+        });
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        self.commit_title
+            .screen_cursor()
+            .or_else(|| self.commit_message.screen_cursor())
+    }
+
+    fn submit_label(&self) -> &'static str {
+        let Some(status) = &self.status else {
+            return "merge";
+        };
+        match plan_submit(status, self.auto_merge) {
+            Ok(Submit::EnableAutoMerge) => "enable auto-merge",
+            Ok(Submit::DisableAutoMerge) => "disable auto-merge",
+            _ => "merge",
+        }
+    }
+}
+
+fn status_lines(status: &MergeStatus) -> Vec<Line<'static>> {
+    let blockers = merge_blockers(status);
+    let mut lines = Vec::new();
+    if blockers.is_empty() {
+        lines.push(line![
+            span!("✓ Ready to merge ").green().bold(),
+            span!("{} into {}", status.head, status.base).green(),
+        ]);
+        if status.merge_state == MergeStateStatus::Unstable {
+            lines.push(line![
+                span!("! Some checks are failing, but none of them are required.").yellow()
+            ]);
+        }
+    } else {
+        lines.push(line![span!("✗ Merging is blocked:").red().bold()]);
+        lines.extend(
+            blockers
+                .iter()
+                .map(|blocker| line![span!("  • {}", blocker.reason).red()]),
+        );
+        if status.auto_merge.is_none()
+            && status.auto_merge_allowed
+            && blockers.iter().all(|blocker| blocker.waits)
+        {
+            lines.push(line![
+                span!("  Enable auto-merge to merge as soon as these are resolved.").dim()
+            ]);
+        }
+    }
+    if let Some(method) = status.auto_merge {
+        lines.push(line![
+            span!("Auto-merge is enabled ({}).", method_label(method)).cyan()
+        ]);
+    }
+    lines
+}
+
+fn checkbox(label: String, checked: bool, focused: bool, enabled: bool) -> Line<'static> {
+    let mark = if checked { "[x] " } else { "[ ] " };
+    let mut style = Style::new();
+    if focused {
+        style = style.yellow().bold();
+    }
+    if !enabled {
+        style = style.dim();
+    }
+    Line::from(vec![Span::styled(mark, style), Span::styled(label, style)])
+}
+
+pub(crate) fn render_merge_popup(popup: &mut MergePopupState, area: Rect, buf: &mut Buffer) {
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(70));
+    Clear.render(popup_area, buf);
+
+    let heading = format!("Merge #{} {}", popup.number, popup.title);
+    let mut block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title_bottom(format!(
+            "Tab: next field  ←/→: method  Space: toggle  Ctrl+Enter: {}  Ctrl+R: refresh  Esc: cancel",
+            popup.submit_label()
+        ));
+    block = match &popup.error {
+        Some(err) => block.title(format!("{heading} | {err}")),
+        None => block.title(heading),
+    };
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    if popup.loading {
+        let title_area = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y,
+            width: 12,
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label(if popup.status.is_some() {
+                "Working"
+            } else {
+                "Checking"
+            })
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, title_area, buf, &mut popup.throbber_state);
+    }
+    if let Some(err) = &popup.load_error {
+        Paragraph::new(line![
+            span!("Could not check mergeability: {err}").light_red()
+        ])
+        .wrap(Wrap { trim: false })
+        .render(inner, buf);
+        return;
+    }
+    let Some(status) = &popup.status else {
+        line![span!("Checking mergeability…").dim()].render(inner, buf);
+        return;
+    };
+
+    let status_lines = status_lines(status);
+    let [
+        status_area,
+        _,
+        method_area,
+        title_area,
+        message_area,
+        delete_area,
+        auto_area,
+    ] = vertical![
+        ==status_lines.len() as u16,
+        ==1,
+        ==1,
+        ==3,
+        *=1,
+        ==1,
+        ==1
+    ]
+    .areas(inner);
+    Paragraph::new(status_lines)
+        .wrap(Wrap { trim: false })
+        .render(status_area, buf);
+
+    let mut methods = vec![if popup.field == Field::Method {
+        span!("Method: ").yellow().bold()
+    } else {
+        span!("Method: ").dim()
+    }];
+    for method in &status.allowed_methods {
+        let label = span!(" {} ", method_label(*method));
+        methods.push(if *method == popup.method {
+            label.reversed().bold()
+        } else {
+            label
+        });
+        methods.push(span!(" "));
+    }
+    Line::from(methods).render(method_area, buf);
+
+    if popup.method == MergeMethod::Rebase {
+        let note = vertical![==1, *=1].areas::<2>(title_area.union(message_area))[0];
+        line![
+            span!("Rebasing replays each commit onto the base branch; no merge commit is created.")
+                .dim()
+        ]
+        .render(note, buf);
+    } else {
+        TextInput::new()
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .border_style(get_border_style(&popup.commit_title))
+                    .title("Commit title"),
+            )
+            .render(title_area, buf, &mut popup.commit_title);
+        TextArea::new()
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .border_style(get_border_style(&popup.commit_message))
+                    .title("Commit message"),
+            )
+            .text_wrap(TextWrap::Word(4))
+            .render(message_area, buf, &mut popup.commit_message);
+    }
+
+    let delete_label = if status.cross_repository {
+        format!("Delete {} after merging (branch is in a fork)", status.head)
+    } else if status.delete_branch_on_merge {
+        format!(
+            "Delete {} after merging (the repository does this automatically)",
+            status.head
+        )
+    } else {
+        format!("Delete {} after merging", status.head)
+    };
+    checkbox(
+        delete_label,
+        popup.delete_branch && !status.cross_repository,
+        popup.field == Field::DeleteBranch,
+        popup.can_delete_branch(),
+    )
+    .render(delete_area, buf);
+    let auto_label = if status.auto_merge_allowed {
+        "Enable auto-merge (merge once all requirements are met)".to_string()
+    } else {
+        "Enable auto-merge (disabled for this repository)".to_string()
+    };
+    checkbox(
+        auto_label,
+        popup.auto_merge,
+        popup.field == Field::AutoMerge,
+        status.auto_merge_allowed,
+    )
+    .render(auto_area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::CommitMessage;
+
+    fn status() -> MergeStatus {
+        MergeStatus {
+            id: "PR_1".to_string(),
+            number: 1,
+            state: PullRequestState::Open,
+            draft: false,
+            mergeable: Mergeable::Mergeable,
+            merge_state: MergeStateStatus::Clean,
+            review_decision: None,
+            checks: Some(CheckState::Success),
+            base: "main".to_string(),
+            head: "fix".to_string(),
+            head_sha: "abc".to_string(),
+            cross_repository: false,
+            allowed_methods: MergeMethod::ALL.to_vec(),
+            auto_merge_allowed: true,
+            auto_merge: None,
+            delete_branch_on_merge: false,
+            merge_message: CommitMessage::default(),
+            squash_message: CommitMessage::default(),
+        }
+    }
+
+    #[test]
+    fn blockers_explain_protected_branches() {
+        let mut blocked = status();
+        blocked.merge_state = MergeStateStatus::Blocked;
+        blocked.review_decision = Some(ReviewDecision::ReviewRequired);
+        blocked.checks = Some(CheckState::Pending);
+        let reasons = merge_blockers(&blocked)
+            .into_iter()
+            .map(|blocker| blocker.reason)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            vec![
+                "An approving review is required.",
+                "Required checks have not finished."
+            ]
+        );
+        assert_eq!(plan_submit(&blocked, true), Ok(Submit::EnableAutoMerge));
+        assert!(plan_submit(&blocked, false).is_err());
+        blocked.auto_merge = Some(MergeMethod::Squash);
+        assert_eq!(plan_submit(&blocked, false), Ok(Submit::DisableAutoMerge));
+    }
+
+    #[test]
+    fn conflicts_cannot_be_waited_out() {
+        let mut dirty = status();
+        dirty.mergeable = Mergeable::Conflicting;
+        dirty.merge_state = MergeStateStatus::Dirty;
+        assert_eq!(
+            plan_submit(&dirty, true),
+            Err("`fix` has conflicts with `main` that must be resolved.".to_string())
+        );
+        assert_eq!(plan_submit(&status(), false), Ok(Submit::Merge));
+        assert!(plan_submit(&status(), true).is_err());
+    }
+}
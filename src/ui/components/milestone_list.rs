@@ -0,0 +1,900 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use octocrab::models::Milestone;
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    focus::{FocusBuilder, FocusFlag, HasFocus, Navigation},
+    text_input::{TextInput, TextInputState},
+    textarea::{TextArea, TextAreaState, TextWrap},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{line, span, vertical};
+use ratatui_toaster::ToastType;
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::{
+    backend::{Backend, MilestoneEdit, MilestoneState},
+    errors::AppError,
+    ui::{
+        Action, AppState,
+        components::{Component, help::HelpElementKind, issue_list::MainScreen},
+        layout::Layout,
+        toast_action,
+        utils::get_border_style,
+    },
+};
+
+pub const HELP: &[HelpElementKind] = &[
+    crate::help_text!("Milestones Help"),
+    crate::help_keybind!("Up/Down, j/k", "select milestone"),
+    crate::help_keybind!("Enter", "show the issues in the selected milestone"),
+    crate::help_keybind!("n", "create milestone"),
+    crate::help_keybind!("e", "edit selected milestone"),
+    crate::help_keybind!("C", "close selected milestone"),
+    crate::help_keybind!("O", "reopen selected milestone"),
+    crate::help_keybind!("l", "copy link to selected milestone"),
+    crate::help_keybind!("u", "refresh"),
+    crate::help_keybind!("Tab / Shift+Tab (editor)", "move between fields"),
+    crate::help_keybind!("Ctrl+Enter / Alt+Enter (editor)", "save milestone"),
+    crate::help_keybind!("Esc", "cancel editor / leave milestones"),
+];
+
+const PROGRESS_WIDTH: usize = 20;
+
+/// Full-screen list of the repository's milestones with their progress.
+pub struct MilestoneList {
+    action_tx: Option<tokio::sync::mpsc::Sender<Action>>,
+    backend: Backend,
+    milestones: Vec<Milestone>,
+    list_state: TuiListState,
+    loading: bool,
+    /// Set while a milestone is closed or reopened.
+    updating: bool,
+    error: Option<String>,
+    editor: Option<MilestoneEditorState>,
+    /// Milestone waiting for the user to confirm closing it.
+    confirm_close: Option<u64>,
+    throbber_state: ThrobberState,
+    screen: MainScreen,
+    return_screen: MainScreen,
+    focus: FocusFlag,
+    area: Rect,
+    index: usize,
+}
+
+impl MilestoneList {
+    pub fn new(AppState { backend, .. }: AppState) -> Self {
+        Self {
+            action_tx: None,
+            backend,
+            milestones: Vec::new(),
+            list_state: TuiListState::default(),
+            loading: false,
+            updating: false,
+            error: None,
+            editor: None,
+            confirm_close: None,
+            throbber_state: ThrobberState::default(),
+            screen: MainScreen::default(),
+            return_screen: MainScreen::default(),
+            focus: FocusFlag::new().with_name("milestone_list"),
+            area: Rect::default(),
+            index: 0,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.screen == MainScreen::Milestones
+    }
+
+    fn load(&mut self) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        self.loading = true;
+        self.error = None;
+        load_milestones(self.backend.clone(), action_tx);
+    }
+
+    fn set_milestones(&mut self, mut milestones: Vec<Milestone>) {
+        let selected = self.selected().map(|m| m.number);
+        sort_milestones(&mut milestones);
+        self.milestones = milestones;
+        self.select_number(selected);
+    }
+
+    fn select_number(&mut self, number: Option<i64>) {
+        let idx = number
+            .and_then(|number| self.milestones.iter().position(|m| m.number == number))
+            .or_else(|| self.list_state.selected())
+            .map(|idx| idx.min(self.milestones.len().saturating_sub(1)));
+        self.list_state
+            .select(idx.filter(|_| !self.milestones.is_empty()));
+    }
+
+    fn selected(&self) -> Option<&Milestone> {
+        self.milestones.get(self.list_state.selected()?)
+    }
+
+    fn select(&mut self, delta: isize) {
+        let len = self.milestones.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        self.list_state.select(Some(next));
+    }
+
+    /// Creates the milestone if `number` is `None`, otherwise changes it.
+    fn save(&self, number: Option<u64>, edit: MilestoneEdit, done: String) {
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let result = match number {
+                Some(number) => backend.update_milestone(number, edit).await,
+                None => backend.create_milestone(edit).await,
+            };
+            match result {
+                Ok(milestone) => {
+                    let _ = action_tx
+                        .send(Action::MilestoneSaved {
+                            milestone: Box::new(milestone),
+                        })
+                        .await;
+                    let _ = action_tx.send(toast_action(done, ToastType::Success)).await;
+                }
+                Err(err) => {
+                    let _ = action_tx
+                        .send(Action::MilestoneSaveError {
+                            message: err.to_string().replace('\n', " "),
+                        })
+                        .await;
+                }
+            }
+        });
+    }
+
+    fn set_state(&mut self, number: u64, state: MilestoneState) {
+        let Some(milestone) = self.milestones.iter().find(|m| m.number as u64 == number) else {
+            return;
+        };
+        let done = match state {
+            MilestoneState::Open => format!("Reopened {}", milestone.title),
+            MilestoneState::Closed => format!("Closed {}", milestone.title),
+        };
+        self.updating = true;
+        let edit = MilestoneEdit {
+            state: Some(state),
+            ..Default::default()
+        };
+        self.save(Some(number), edit, done);
+    }
+
+    fn handle_editor_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let Some(editor) = self.editor.as_mut() else {
+            return false;
+        };
+        match editor.handle_event(event) {
+            EditorOutcome::Continue => {}
+            EditorOutcome::Close => self.editor = None,
+            EditorOutcome::Submit(edit) => {
+                let number = editor.number;
+                let title = edit.title.clone().unwrap_or_default();
+                let done = match number {
+                    Some(_) => format!("Updated {title}"),
+                    None => format!("Created {title}"),
+                };
+                self.save(number, edit, done);
+            }
+        }
+        true
+    }
+
+    async fn handle_key(&mut self, event: &crossterm::event::Event) -> Result<(), AppError> {
+        if self.handle_editor_event(event) {
+            return Ok(());
+        }
+        if let Some(number) = self.confirm_close {
+            match event {
+                ct_event!(key press 'y') => {
+                    self.confirm_close = None;
+                    self.set_state(number, MilestoneState::Closed);
+                }
+                ct_event!(key press 'n') | ct_event!(keycode press Esc) => {
+                    self.confirm_close = None;
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+        match event {
+            ct_event!(keycode press Esc) => {
+                if let Some(tx) = self.action_tx.clone() {
+                    tx.send(Action::ChangeIssueScreen(self.return_screen))
+                        .await?;
+                }
+            }
+            ct_event!(keycode press Up) | ct_event!(key press 'k') => self.select(-1),
+            ct_event!(keycode press Down) | ct_event!(key press 'j') => self.select(1),
+            ct_event!(keycode press Home) | ct_event!(key press 'g') => {
+                self.select(isize::MIN);
+            }
+            ct_event!(keycode press End) | ct_event!(key press SHIFT-'G') => {
+                self.select(isize::MAX);
+            }
+            ct_event!(keycode press Enter) => {
+                if let Some(title) = self.selected().map(|m| m.title.clone())
+                    && let Some(tx) = self.action_tx.clone()
+                {
+                    tx.send(Action::ChangeIssueScreen(MainScreen::List)).await?;
+                    tx.send(Action::FilterByMilestone { title }).await?;
+                }
+            }
+            ct_event!(key press 'n') => {
+                self.editor = Some(MilestoneEditorState::new(None));
+            }
+            ct_event!(key press 'e') => {
+                if let Some(milestone) = self.selected() {
+                    self.editor = Some(MilestoneEditorState::new(Some(milestone)));
+                }
+            }
+            ct_event!(key press SHIFT-'C') if !self.updating => {
+                if let Some(milestone) = self.selected() {
+                    if is_closed(milestone) {
+                        self.notify(format!("{} is already closed", milestone.title))
+                            .await?;
+                    } else {
+                        self.confirm_close = Some(milestone.number as u64);
+                    }
+                }
+            }
+            ct_event!(key press SHIFT-'O') if !self.updating => {
+                if let Some(milestone) = self.selected() {
+                    if is_closed(milestone) {
+                        self.set_state(milestone.number as u64, MilestoneState::Open);
+                    } else {
+                        self.notify(format!("{} is already open", milestone.title))
+                            .await?;
+                    }
+                }
+            }
+            ct_event!(key press 'u') => self.load(),
+            ct_event!(key press 'l') => {
+                if let Some(url) = self.selected().map(|m| m.html_url.to_string()) {
+                    cli_clipboard::set_contents(url)
+                        .map_err(|_| anyhow::anyhow!("Error copying to clipboard"))?;
+                    if let Some(tx) = self.action_tx.as_ref() {
+                        tx.send(toast_action("Copied Link", ToastType::Success))
+                            .await?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn notify(&self, message: String) -> Result<(), AppError> {
+        if let Some(tx) = self.action_tx.as_ref() {
+            tx.send(toast_action(message, ToastType::Info)).await?;
+        }
+        Ok(())
+    }
+
+    fn render_w(&mut self, area: Layout, buf: &mut Buffer) {
+        let area = area.main_content;
+        self.area = area;
+        let open = self.milestones.iter().filter(|m| !is_closed(m)).count();
+        let tally = line![
+            span!("{open} open").green(),
+            span!(" · ").dim(),
+            span!("{} closed", self.milestones.len() - open).magenta(),
+        ];
+        let footer = match self
+            .confirm_close
+            .and_then(|number| self.milestones.iter().find(|m| m.number as u64 == number))
+        {
+            Some(milestone) => line![
+                span!("Close {}? ", milestone.title).yellow().bold(),
+                span!("y: close  n: cancel"),
+            ],
+            None => line![span!(
+                "Enter: show issues  n: new  e: edit  C: close  O: reopen  l: copy link  u: refresh  Esc: back"
+            )],
+        };
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title(format!("[{}] Milestones", self.index))
+            .title(tally.right_aligned())
+            .title_bottom(footer);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.loading || self.updating {
+            let throbber_area = Rect {
+                x: area.x + 1,
+                y: area.y + area.height.saturating_sub(1),
+                width: 20,
+                height: 1,
+            };
+            let throbber = Throbber::default()
+                .label(if self.updating {
+                    "Saving"
+                } else {
+                    "Loading milestones"
+                })
+                .style(Style::new().fg(Color::Cyan))
+                .throbber_set(BRAILLE_SIX_DOUBLE)
+                .use_type(WhichUse::Spin);
+            StatefulWidget::render(throbber, throbber_area, buf, &mut self.throbber_state);
+        }
+        if let Some(err) = &self.error {
+            line![span!("Failed to load milestones: {err}").light_red()].render(inner, buf);
+        } else if self.milestones.is_empty() && !self.loading {
+            line![span!("This repository has no milestones yet. Press n to create one.").dim()]
+                .render(inner, buf);
+        } else {
+            let today = Local::now().date_naive();
+            let items = self
+                .milestones
+                .iter()
+                .map(|milestone| milestone_item(milestone, today))
+                .collect::<Vec<_>>();
+            let list = TuiList::new(items)
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("> ");
+            StatefulWidget::render(list, inner, buf, &mut self.list_state);
+        }
+
+        if let Some(editor) = self.editor.as_mut() {
+            render_milestone_editor(editor, area, buf);
+        }
+    }
+}
+
+pub(crate) fn load_milestones(backend: Backend, action_tx: tokio::sync::mpsc::Sender<Action>) {
+    tokio::spawn(async move {
+        let action = match backend.list_milestones().await {
+            Ok(milestones) => Action::MilestonesLoaded { milestones },
+            Err(err) => Action::MilestonesError {
+                message: err.to_string().replace('\n', " "),
+            },
+        };
+        let _ = action_tx.send(action).await;
+    });
+}
+
+pub(crate) fn is_closed(milestone: &Milestone) -> bool {
+    milestone.state.as_deref() == Some("closed")
+}
+
+/// Open milestones first, soonest due first, then closed ones, most recently
+/// closed first.
+fn sort_milestones(milestones: &mut [Milestone]) {
+    milestones.sort_by(|a, b| {
+        is_closed(a).cmp(&is_closed(b)).then_with(|| {
+            if is_closed(a) {
+                b.closed_at.cmp(&a.closed_at)
+            } else {
+                // `None` sorts before `Some`; milestones without a due date go last.
+                (a.due_on.is_none(), a.due_on, &a.title).cmp(&(
+                    b.due_on.is_none(),
+                    b.due_on,
+                    &b.title,
+                ))
+            }
+        })
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Urgency {
+    Overdue,
+    /// Due within two weeks.
+    Soon,
+    Later,
+}
+
+/// Describes a due date relative to `today`.
+fn due_text(due: NaiveDate, today: NaiveDate) -> (String, Urgency) {
+    let days = (due - today).num_days();
+    match days {
+        ..0 => {
+            let n = -days;
+            (format!("overdue by {n} day{}", plural(n)), Urgency::Overdue)
+        }
+        0 => ("due today".to_string(), Urgency::Soon),
+        1 => ("due tomorrow".to_string(), Urgency::Soon),
+        2..=14 => (format!("due in {days} days"), Urgency::Soon),
+        _ => (format!("due {}", due.format("%Y-%m-%d")), Urgency::Later),
+    }
+}
+
+fn plural(n: i64) -> &'static str {
+    if n == 1 { "" } else { "s" }
+}
+
+/// Due date of an open milestone, or closing date of a closed one.
+pub(crate) fn due_span(milestone: &Milestone, today: NaiveDate) -> Option<Span<'static>> {
+    if is_closed(milestone) {
+        let closed = milestone.closed_at?.date_naive();
+        return Some(span!("closed {}", closed.format("%Y-%m-%d")).dim());
+    }
+    let (text, urgency) = due_text(milestone.due_on?.date_naive(), today);
+    Some(match urgency {
+        Urgency::Overdue => span!(text).red(),
+        Urgency::Soon => span!(text).yellow(),
+        Urgency::Later => span!(text).dim(),
+    })
+}
+
+/// Closed and total issue counts.
+pub(crate) fn issue_counts(milestone: &Milestone) -> (u64, u64) {
+    let closed = milestone.closed_issues.unwrap_or_default().max(0) as u64;
+    let open = milestone.open_issues.unwrap_or_default().max(0) as u64;
+    (closed, closed + open)
+}
+
+/// Cells of a progress bar `width` cells wide to fill for `closed` of `total`.
+fn filled_cells(closed: u64, total: u64, width: usize) -> usize {
+    if total == 0 {
+        return 0;
+    }
+    ((closed as f64 / total as f64) * width as f64).round() as usize
+}
+
+fn progress_line(milestone: &Milestone) -> Line<'static> {
+    let (closed, total) = issue_counts(milestone);
+    let filled = filled_cells(closed, total, PROGRESS_WIDTH).min(PROGRESS_WIDTH);
+    let mut spans = vec![
+        span!("    "),
+        span!("{}", "━".repeat(filled)).green(),
+        span!("{}", "─".repeat(PROGRESS_WIDTH - filled)).dim(),
+    ];
+    match (closed * 100).checked_div(total) {
+        Some(percent) => {
+            spans.push(span!(" {:>3}%", percent).bold());
+            spans.push(span!("  {closed} of {total} closed").dim());
+        }
+        None => spans.push(span!("  no issues").dim()),
+    }
+    Line::from(spans)
+}
+
+fn milestone_item(milestone: &Milestone, today: NaiveDate) -> ListItem<'static> {
+    let mut header = vec![
+        if is_closed(milestone) {
+            span!("✓ ").magenta()
+        } else {
+            span!("○ ").green()
+        },
+        span!(milestone.title.clone()).bold(),
+    ];
+    if let Some(due) = due_span(milestone, today) {
+        header.push(span!("  "));
+        header.push(due);
+    }
+    let mut lines = vec![Line::from(header), progress_line(milestone)];
+    if let Some(description) = milestone
+        .description
+        .as_deref()
+        .and_then(|text| text.lines().find(|line| !line.trim().is_empty()))
+    {
+        lines.push(line![span!("    {}", description.trim()).dim()]);
+    }
+    ListItem::new(lines)
+}
+
+/// Parses a `YYYY-MM-DD` due date; an empty input means no due date.
+fn parse_due_date(input: &str) -> Result<Option<DateTime<Utc>>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let date = NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map_err(|_| format!("'{input}' is not a date like 2025-12-31"))?;
+    // GitHub keeps only the date; noon UTC lands on the same day in every
+    // time zone the date may be shown in.
+    Ok(date.and_hms_opt(12, 0, 0).map(|time| time.and_utc()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditorField {
+    Title,
+    Due,
+    Description,
+}
+
+enum EditorOutcome {
+    Continue,
+    Close,
+    Submit(MilestoneEdit),
+}
+
+/// Form for creating a milestone, or editing an existing one.
+struct MilestoneEditorState {
+    /// `None` when creating a milestone.
+    number: Option<u64>,
+    title: TextInputState,
+    due: TextInputState,
+    description: TextAreaState,
+    field: EditorField,
+    had_due: bool,
+    saving: bool,
+    throbber_state: ThrobberState,
+    error: Option<String>,
+}
+
+impl MilestoneEditorState {
+    fn new(milestone: Option<&Milestone>) -> Self {
+        let mut editor = Self {
+            number: milestone.map(|m| m.number as u64),
+            title: TextInputState::default(),
+            due: TextInputState::default(),
+            description: TextAreaState::new(),
+            field: EditorField::Title,
+            had_due: milestone.is_some_and(|m| m.due_on.is_some()),
+            saving: false,
+            throbber_state: ThrobberState::default(),
+            error: None,
+        };
+        if let Some(milestone) = milestone {
+            editor.title.set_text(&milestone.title);
+            if let Some(due) = milestone.due_on {
+                editor
+                    .due
+                    .set_text(due.date_naive().format("%Y-%m-%d").to_string());
+            }
+            editor
+                .description
+                .set_text(milestone.description.as_deref().unwrap_or_default());
+        }
+        editor.title.move_to_line_end(false);
+        editor.focus_field(EditorField::Title);
+        editor
+    }
+
+    fn focus_field(&mut self, field: EditorField) {
+        self.field = field;
+        self.title.focus.set(field == EditorField::Title);
+        self.due.focus.set(field == EditorField::Due);
+        self.description
+            .focus
+            .set(field == EditorField::Description);
+    }
+
+    fn step_field(&mut self, forward: bool) {
+        let next = match (self.field, forward) {
+            (EditorField::Title, true) | (EditorField::Description, false) => EditorField::Due,
+            (EditorField::Due, true) | (EditorField::Title, false) => EditorField::Description,
+            (EditorField::Description, true) | (EditorField::Due, false) => EditorField::Title,
+        };
+        self.focus_field(next);
+    }
+
+    fn handle_event(&mut self, event: &crossterm::event::Event) -> EditorOutcome {
+        if self.saving {
+            return EditorOutcome::Continue;
+        }
+        match event {
+            ct_event!(keycode press Esc) => return EditorOutcome::Close,
+            ct_event!(keycode press CONTROL-Enter) | ct_event!(keycode press ALT-Enter) => {
+                return match self.build_edit() {
+                    Ok(edit) => {
+                        self.saving = true;
+                        self.error = None;
+                        EditorOutcome::Submit(edit)
+                    }
+                    Err(err) => {
+                        self.error = Some(err);
+                        EditorOutcome::Continue
+                    }
+                };
+            }
+            ct_event!(keycode press Tab) => {
+                self.step_field(true);
+            }
+            ct_event!(keycode press SHIFT-BackTab) | ct_event!(keycode press BackTab) => {
+                self.step_field(false);
+            }
+            ct_event!(keycode press Enter) if self.field != EditorField::Description => {
+                self.step_field(true);
+            }
+            crossterm::event::Event::Paste(text) => match self.field {
+                EditorField::Title => {
+                    self.title.insert_str(text.replace('\n', " "));
+                }
+                EditorField::Due => {
+                    self.due.insert_str(text.trim());
+                }
+                EditorField::Description => {
+                    self.description.insert_str(text);
+                }
+            },
+            _ => {
+                match self.field {
+                    EditorField::Title => self.title.handle(event, rat_widget::event::Regular),
+                    EditorField::Due => self.due.handle(event, rat_widget::event::Regular),
+                    EditorField::Description => {
+                        self.description.handle(event, rat_widget::event::Regular)
+                    }
+                };
+            }
+        }
+        EditorOutcome::Continue
+    }
+
+    fn build_edit(&self) -> Result<MilestoneEdit, String> {
+        let title = self.title.text().trim().to_string();
+        if title.is_empty() {
+            return Err("The title cannot be empty.".to_string());
+        }
+        let due_on = parse_due_date(self.due.text())?;
+        Ok(MilestoneEdit {
+            title: Some(title),
+            description: Some(self.description.text().trim_end().to_string()),
+            due_on: (due_on.is_some() || self.had_due).then_some(due_on),
+            state: None,
+        })
+    }
+
+    fn cursor(&self) -> Option<(u16, u16)> {
+        self.title
+            .screen_cursor()
+            .or_else(|| self.due.screen_cursor())
+            .or_else(|| self.description.screen_cursor())
+    }
+}
+
+fn render_milestone_editor(editor: &mut MilestoneEditorState, area: Rect, buf: &mut Buffer) {
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Length(16));
+    Clear.render(popup_area, buf);
+    let heading = if editor.number.is_some() {
+        "Edit milestone"
+    } else {
+        "New milestone"
+    };
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title(match &editor.error {
+            Some(err) => format!("{heading} | {err}"),
+            None => heading.to_string(),
+        })
+        .title_bottom("Tab: next field  Ctrl+Enter: save  Esc: cancel");
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let [title_area, due_area, description_area] = vertical![==3, ==3, *=1].areas(inner);
+    TextInput::new()
+        .block(
+            Block::bordered()
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(get_border_style(&editor.title))
+                .title("Title"),
+        )
+        .render(title_area, buf, &mut editor.title);
+    TextInput::new()
+        .block(
+            Block::bordered()
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(get_border_style(&editor.due))
+                .title("Due date (YYYY-MM-DD, empty for none)"),
+        )
+        .render(due_area, buf, &mut editor.due);
+    TextArea::new()
+        .block(
+            Block::bordered()
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(get_border_style(&editor.description))
+                .title("Description"),
+        )
+        .text_wrap(TextWrap::Word(4))
+        .render(description_area, buf, &mut editor.description);
+
+    if editor.saving {
+        let title_area = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y,
+            width: 10,
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label("Saving")
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, title_area, buf, &mut editor.throbber_state);
+    }
+}
+
+impl HasFocus for MilestoneList {
+    fn build(&self, builder: &mut FocusBuilder) {
+        builder.leaf_widget(self);
+    }
+
+    fn focus(&self) -> FocusFlag {
+        self.focus.clone()
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn navigable(&self) -> Navigation {
+        if self.is_active() {
+            Navigation::Regular
+        } else {
+            Navigation::None
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Component for MilestoneList {
+    fn render(&mut self, area: Layout, buf: &mut Buffer) {
+        self.render_w(area, buf);
+    }
+
+    fn register_action_tx(&mut self, action_tx: tokio::sync::mpsc::Sender<Action>) {
+        self.action_tx = Some(action_tx);
+    }
+
+    async fn handle_event(&mut self, event: Action) -> Result<(), AppError> {
+        match event {
+            Action::MilestonesLoaded { milestones } => {
+                self.loading = false;
+                self.error = None;
+                self.set_milestones(milestones);
+            }
+            Action::MilestonesError { message } => {
+                if self.loading {
+                    self.loading = false;
+                    self.error = Some(message);
+                }
+            }
+            Action::MilestoneSaved { milestone } => {
+                self.updating = false;
+                self.editor = None;
+                let number = milestone.number;
+                let mut milestones = std::mem::take(&mut self.milestones);
+                match milestones.iter_mut().find(|m| m.number == number) {
+                    Some(existing) => *existing = *milestone,
+                    None => milestones.push(*milestone),
+                }
+                sort_milestones(&mut milestones);
+                self.milestones = milestones;
+                self.select_number(Some(number));
+            }
+            Action::MilestoneSaveError { message } => {
+                self.updating = false;
+                match self.editor.as_mut() {
+                    Some(editor) => {
+                        editor.saving = false;
+                        editor.error = Some(message);
+                    }
+                    None => {
+                        if let Some(tx) = self.action_tx.as_ref() {
+                            tx.send(toast_action(
+                                format!("Saving milestone failed: {message}"),
+                                ToastType::Error,
+                            ))
+                            .await?;
+                        }
+                    }
+                }
+            }
+            Action::ChangeIssueScreen(screen) => {
+                self.screen = screen;
+                if screen == MainScreen::Milestones {
+                    self.focus.set(true);
+                    self.load();
+                } else {
+                    self.return_screen = screen;
+                    self.focus.set(false);
+                    self.editor = None;
+                    self.confirm_close = None;
+                }
+            }
+            Action::AppEvent(ref event) if self.is_active() => self.handle_key(event).await?,
+            Action::Tick => {
+                if self.loading || self.updating {
+                    self.throbber_state.calc_next();
+                }
+                if let Some(editor) = self.editor.as_mut()
+                    && editor.saving
+                {
+                    editor.throbber_state.calc_next();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn should_render(&self) -> bool {
+        self.is_active()
+    }
+
+    fn is_animating(&self) -> bool {
+        self.is_active()
+            && (self.loading
+                || self.updating
+                || self.editor.as_ref().is_some_and(|editor| editor.saving))
+    }
+
+    fn cursor(&self) -> Option<(u16, u16)> {
+        self.editor.as_ref().and_then(MilestoneEditorState::cursor)
+    }
+
+    fn capture_focus_event(&self, _event: &crossterm::event::Event) -> bool {
+        self.is_active()
+    }
+
+    fn set_index(&mut self, index: usize) {
+        self.index = index;
+    }
+
+    fn set_global_help(&self) {
+        if let Some(action_tx) = &self.action_tx {
+            let _ = action_tx.try_send(Action::SetHelp(HELP));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn due_dates_read_relative_to_today() {
+        let today = date("2025-03-10");
+        assert_eq!(
+            due_text(date("2025-03-09"), today),
+            ("overdue by 1 day".to_string(), Urgency::Overdue)
+        );
+        assert_eq!(
+            due_text(date("2025-03-10"), today),
+            ("due today".to_string(), Urgency::Soon)
+        );
+        assert_eq!(
+            due_text(date("2025-03-15"), today),
+            ("due in 5 days".to_string(), Urgency::Soon)
+        );
+        assert_eq!(
+            due_text(date("2025-06-01"), today),
+            ("due 2025-06-01".to_string(), Urgency::Later)
+        );
+    }
+
+    #[test]
+    fn progress_rounds_to_whole_cells() {
+        assert_eq!(filled_cells(0, 0, 20), 0);
+        assert_eq!(filled_cells(1, 3, 20), 7);
+        assert_eq!(filled_cells(4, 4, 20), 20);
+    }
+
+    #[test]
+    fn due_date_input_is_optional() {
+        assert_eq!(parse_due_date("  "), Ok(None));
+        let due = parse_due_date("2025-12-31").unwrap().unwrap();
+        assert_eq!(due.date_naive(), date("2025-12-31"));
+        assert!(parse_due_date("31/12/2025").is_err());
+    }
+}
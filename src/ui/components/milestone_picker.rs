@@ -0,0 +1,314 @@
+//! The popup for setting or clearing an issue's milestone, shared by the issue
+//! list and the conversation view.
+
+use chrono::Local;
+use octocrab::models::Milestone;
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    text_input::{TextInput, TextInputState},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{line, span, vertical};
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::{
+    backend::{Backend, IssueEdit},
+    ui::{
+        Action,
+        components::{
+            issue_edit::update_issue,
+            milestone_list::{due_span, is_closed, issue_counts, load_milestones},
+        },
+    },
+};
+
+/// What the picked milestone is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PickerTarget {
+    /// One issue, with the number of its current milestone. The picker saves
+    /// the choice itself.
+    Issue { number: u64, current: Option<u64> },
+    /// The issues selected in the issue list. The host applies the choice.
+    Selection { count: usize },
+}
+
+pub(crate) enum PickerOutcome {
+    Continue,
+    Close,
+    /// Number and title of the picked milestone, or `None` for no milestone.
+    Apply(Option<(u64, String)>),
+}
+
+/// State of the milestone picker.
+pub(crate) struct MilestonePickerState {
+    target: PickerTarget,
+    milestones: Vec<Milestone>,
+    filter: TextInputState,
+    list_state: TuiListState,
+    /// Set while the milestones are fetched or the choice is saved.
+    pub(crate) loading: bool,
+    saving: bool,
+    pub(crate) throbber_state: ThrobberState,
+    error: Option<String>,
+}
+
+impl MilestonePickerState {
+    /// Opens the picker and starts loading the repository's milestones.
+    pub(crate) fn open(
+        target: PickerTarget,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) -> Self {
+        let filter = TextInputState::default();
+        filter.focus.set(true);
+        load_milestones(backend.clone(), action_tx.clone());
+        Self {
+            target,
+            milestones: Vec::new(),
+            filter,
+            list_state: TuiListState::default().with_selected(Some(0)),
+            loading: true,
+            saving: false,
+            throbber_state: ThrobberState::default(),
+            error: None,
+        }
+    }
+
+    /// Number of the issue's current milestone, or `None` when picking for a
+    /// selection.
+    fn current(&self) -> Option<Option<u64>> {
+        match self.target {
+            PickerTarget::Issue { current, .. } => Some(current),
+            PickerTarget::Selection { .. } => None,
+        }
+    }
+
+    /// The entries to choose from: no milestone, then the open milestones
+    /// (and the current one, even if closed) whose title matches the filter.
+    fn choices(&self) -> Vec<Option<&Milestone>> {
+        let filter = self.filter.text().trim().to_lowercase();
+        let current = self.current().flatten();
+        let matching = self.milestones.iter().filter(|milestone| {
+            (!is_closed(milestone) || Some(milestone.number as u64) == current)
+                && milestone.title.to_lowercase().contains(&filter)
+        });
+        std::iter::once(None).chain(matching.map(Some)).collect()
+    }
+
+    fn select(&mut self, delta: isize) {
+        let len = self.choices().len();
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        self.list_state.select(Some(next));
+    }
+
+    /// Handles a key press.
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &crossterm::event::Event,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) -> PickerOutcome {
+        if self.saving {
+            return PickerOutcome::Continue;
+        }
+        match event {
+            ct_event!(keycode press Esc) => return PickerOutcome::Close,
+            ct_event!(keycode press Up) => self.select(-1),
+            ct_event!(keycode press Down) => self.select(1),
+            ct_event!(keycode press Enter) => {
+                let choices = self.choices();
+                let Some(choice) = self
+                    .list_state
+                    .selected()
+                    .and_then(|idx| choices.get(idx).copied())
+                else {
+                    return PickerOutcome::Continue;
+                };
+                let milestone = choice.map(|m| (m.number as u64, m.title.clone()));
+                let PickerTarget::Issue { number, current } = self.target else {
+                    return PickerOutcome::Apply(milestone);
+                };
+                if milestone.as_ref().map(|(number, _)| *number) == current {
+                    return PickerOutcome::Close;
+                }
+                self.save(number, milestone, backend, action_tx);
+            }
+            crossterm::event::Event::Paste(text) => {
+                self.filter.insert_str(text.replace('\n', " "));
+                self.list_state.select(Some(0));
+            }
+            _ => {
+                let before = self.filter.text().to_string();
+                self.filter.handle(event, rat_widget::event::Regular);
+                if self.filter.text() != before {
+                    self.list_state.select(Some(0));
+                }
+            }
+        }
+        PickerOutcome::Continue
+    }
+
+    fn save(
+        &mut self,
+        number: u64,
+        milestone: Option<(u64, String)>,
+        backend: &Backend,
+        action_tx: &tokio::sync::mpsc::Sender<Action>,
+    ) {
+        self.saving = true;
+        self.loading = true;
+        self.error = None;
+        let backend = backend.clone();
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+            let (milestone, done) = match milestone {
+                Some((milestone, title)) => {
+                    (Some(milestone), format!("Added #{number} to {title}"))
+                }
+                None => (None, format!("Removed #{number} from its milestone")),
+            };
+            let edit = IssueEdit {
+                milestone: Some(milestone),
+                ..Default::default()
+            };
+            update_issue(&backend, &action_tx, number, edit, done).await;
+        });
+    }
+
+    /// Applies a result of one of the requests this picker started. Returns
+    /// `false` once the picker should close.
+    pub(crate) fn update(&mut self, action: &Action) -> bool {
+        match action {
+            Action::MilestonesLoaded { milestones } => {
+                self.loading = self.saving;
+                self.milestones = milestones.clone();
+                self.milestones.sort_by(|a, b| {
+                    (a.due_on.is_none(), a.due_on, &a.title).cmp(&(
+                        b.due_on.is_none(),
+                        b.due_on,
+                        &b.title,
+                    ))
+                });
+                if self.list_state.selected().is_none_or(|idx| idx == 0) {
+                    let current = self.current().and_then(|current| {
+                        self.choices()
+                            .iter()
+                            .position(|m| m.map(|m| m.number as u64) == current)
+                    });
+                    self.list_state.select(current.or(Some(0)));
+                }
+                true
+            }
+            Action::MilestonesError { message } if !self.saving => {
+                self.loading = false;
+                self.error = Some(message.clone());
+                true
+            }
+            Action::IssueUpdated { issue } if self.is_for(issue.number) && self.saving => false,
+            Action::IssueUpdateError { number, message } if self.is_for(*number) => {
+                self.saving = false;
+                self.loading = false;
+                self.error = Some(message.clone());
+                true
+            }
+            _ => true,
+        }
+    }
+
+    fn is_for(&self, issue: u64) -> bool {
+        matches!(self.target, PickerTarget::Issue { number, .. } if number == issue)
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        self.filter.screen_cursor()
+    }
+}
+
+/// `current` is the issue's milestone, if the picker is for one issue.
+fn choice_item(choice: Option<&Milestone>, current: Option<Option<u64>>) -> ListItem<'static> {
+    let Some(milestone) = choice else {
+        let marker = if current == Some(None) { "● " } else { "  " };
+        return ListItem::new(line![span!(marker).cyan(), span!("No milestone").italic()]);
+    };
+    let marker = if current == Some(Some(milestone.number as u64)) {
+        "● "
+    } else {
+        "  "
+    };
+    let mut spans = vec![span!(marker).cyan(), span!(milestone.title.clone()).bold()];
+    if let Some(due) = due_span(milestone, Local::now().date_naive()) {
+        spans.push(span!("  "));
+        spans.push(due);
+    }
+    let (closed, total) = issue_counts(milestone);
+    spans.push(span!("  {closed}/{total} closed").dim());
+    ListItem::new(Line::from(spans))
+}
+
+pub(crate) fn render_milestone_picker(
+    picker: &mut MilestonePickerState,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let popup_area = area.centered(Constraint::Percentage(50), Constraint::Percentage(60));
+    Clear.render(popup_area, buf);
+    let heading = match picker.target {
+        PickerTarget::Issue { number, .. } => format!("Milestone of #{number}"),
+        PickerTarget::Selection { count: 1 } => "Milestone of 1 issue".to_string(),
+        PickerTarget::Selection { count } => format!("Milestone of {count} issues"),
+    };
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title(match &picker.error {
+            Some(err) => format!("{heading} | {err}"),
+            None => heading,
+        })
+        .title_bottom("Type to filter  ↑/↓: select  Enter: set  Esc: cancel");
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let [filter_area, list_area] = vertical![==3, *=1].areas(inner);
+    TextInput::new()
+        .block(
+            Block::bordered()
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title("Filter"),
+        )
+        .render(filter_area, buf, &mut picker.filter);
+
+    let items = picker
+        .choices()
+        .into_iter()
+        .map(|choice| choice_item(choice, picker.current()))
+        .collect::<Vec<_>>();
+    let list = TuiList::new(items)
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    StatefulWidget::render(list, list_area, buf, &mut picker.list_state);
+
+    if picker.loading {
+        let title_area = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y,
+            width: 12,
+            height: 1,
+        };
+        let throbber = Throbber::default()
+            .label(if picker.saving { "Saving" } else { "Loading" })
+            .style(Style::new().fg(Color::Cyan))
+            .throbber_set(BRAILLE_SIX_DOUBLE)
+            .use_type(WhichUse::Spin);
+        StatefulWidget::render(throbber, title_area, buf, &mut picker.throbber_state);
+    }
+}
@@ -6,12 +6,26 @@ use crate::errors::AppError;
 use crate::ui::{Action, layout::Layout};
 use ratatui::crossterm::event::Event;
 
+pub mod assignee_picker;
+pub mod bulk_edit;
+pub mod completion;
+pub mod drafts_popup;
+pub mod duplicate_suggestions;
 pub mod help;
 pub mod issue_conversation;
 pub mod issue_create;
 pub mod issue_detail;
+pub mod issue_edit;
+pub mod issue_form;
 pub mod issue_list;
 pub mod label_list;
+pub mod merge_popup;
+pub mod milestone_list;
+pub mod milestone_picker;
+pub mod outbox_popup;
+pub mod pr_checks;
+pub mod pr_diff;
+pub mod saved_searches;
 pub mod search_bar;
 pub mod status_bar;
 pub mod title_bar;
@@ -0,0 +1,109 @@
+use crossterm::event::Event;
+use rat_widget::event::ct_event;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{line, span};
+
+use crate::outbox::{Outbox, PendingEntry};
+
+/// Lists operations waiting in the [`Outbox`] and lets the user retry or drop them.
+pub struct OutboxPopup {
+    outbox: Outbox,
+    entries: Vec<PendingEntry>,
+    state: TuiListState,
+}
+
+impl OutboxPopup {
+    pub fn new(outbox: Outbox) -> Self {
+        let mut popup = Self {
+            outbox,
+            entries: Vec::new(),
+            state: TuiListState::default(),
+        };
+        popup.refresh();
+        popup
+    }
+
+    fn refresh(&mut self) {
+        self.entries = self.outbox.pending();
+        if self.entries.is_empty() {
+            self.state.select(None);
+        } else {
+            let selected = self.state.selected().unwrap_or(0);
+            self.state
+                .select(Some(selected.min(self.entries.len() - 1)));
+        }
+    }
+
+    fn selected_id(&self) -> Option<u64> {
+        self.state
+            .selected()
+            .and_then(|idx| self.entries.get(idx))
+            .map(|entry| entry.id)
+    }
+
+    /// Handles a key event. Returns `false` once the popup should close.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            ct_event!(keycode press Esc) | ct_event!(key press CONTROL-'o') => return false,
+            ct_event!(keycode press Up) | ct_event!(key press 'k') => self.state.select_previous(),
+            ct_event!(keycode press Down) | ct_event!(key press 'j') => self.state.select_next(),
+            ct_event!(key press 'd') | ct_event!(keycode press Delete) => {
+                if let Some(id) = self.selected_id() {
+                    self.outbox.remove(id);
+                }
+            }
+            ct_event!(key press 'r') => {
+                if let Some(id) = self.selected_id() {
+                    self.outbox.retry_now(id);
+                }
+            }
+            _ => {}
+        }
+        self.refresh();
+        true
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.refresh();
+        let popup_area = area.centered(Constraint::Percentage(70), Constraint::Percentage(60));
+        Clear.render(popup_area, buf);
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title(format!("Pending operations ({})", self.entries.len()))
+            .title_bottom("r: retry now | d: drop | Esc: close");
+
+        if self.entries.is_empty() {
+            let inner = block.inner(popup_area);
+            block.render(popup_area, buf);
+            line![span!("Nothing queued.").dim()].render(inner, buf);
+            return;
+        }
+
+        let items = self.entries.iter().map(|entry| {
+            let status = if entry.gave_up {
+                span!(" failed ").black().on_red()
+            } else if entry.is_sending() {
+                span!(" sending ").black().on_cyan()
+            } else {
+                span!(" retry {} ", entry.attempts).black().on_yellow()
+            };
+            let error = entry.last_error.as_deref().unwrap_or("no error recorded");
+            ListItem::new(vec![
+                line![status, " ", span!(entry.op.describe())],
+                line![span!("  {error}").dim()],
+            ])
+        });
+        let list = TuiList::new(items)
+            .highlight_style(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .block(block)
+            .highlight_symbol("> ");
+        StatefulWidget::render(list, popup_area, buf, &mut self.state);
+    }
+}
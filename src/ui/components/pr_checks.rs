@@ -0,0 +1,717 @@
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hyperrat::Link;
+use rat_widget::{
+    event::ct_event,
+    focus::{FocusBuilder, FocusFlag, HasFocus, Navigation},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Block, List as TuiList, ListItem, ListState as TuiListState, Paragraph, StatefulWidget,
+        Widget, Wrap,
+    },
+};
+use ratatui_macros::{horizontal, line, span, vertical};
+use ratatui_toaster::ToastType;
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::{
+    backend::{Backend, CheckAnnotation, CheckEntry, CheckOutcome, PullRequestChecks, RerunTarget},
+    errors::AppError,
+    ui::{
+        Action, AppState,
+        components::{Component, help::HelpElementKind, issue_list::MainScreen},
+        layout::Layout,
+        toast_action,
+    },
+};
+
+pub const HELP: &[HelpElementKind] = &[
+    crate::help_text!("Pull Request Checks Help"),
+    crate::help_keybind!("Up/Down, j/k", "select check"),
+    crate::help_keybind!("Enter / Space", "expand or collapse annotations"),
+    crate::help_keybind!("r", "re-run the selected check"),
+    crate::help_keybind!("R", "re-run every failed check"),
+    crate::help_keybind!("l", "copy link to the selected check"),
+    crate::help_keybind!("u", "refresh"),
+    crate::help_keybind!("Esc", "leave the checks panel"),
+];
+
+/// Full-screen list of the checks and statuses on a pull request's head commit.
+pub struct ChecksPanel {
+    action_tx: Option<tokio::sync::mpsc::Sender<Action>>,
+    backend: Backend,
+    number: Option<u64>,
+    title: Option<Arc<str>>,
+    checks: Option<PullRequestChecks>,
+    /// Checks whose annotations are shown.
+    expanded: HashSet<CheckKey>,
+    list_state: TuiListState,
+    loading: bool,
+    rerunning: bool,
+    error: Option<String>,
+    throbber_state: ThrobberState,
+    screen: MainScreen,
+    return_screen: MainScreen,
+    focus: FocusFlag,
+    area: Rect,
+    index: usize,
+}
+
+impl ChecksPanel {
+    pub fn new(AppState { backend, .. }: AppState) -> Self {
+        Self {
+            action_tx: None,
+            backend,
+            number: None,
+            title: None,
+            checks: None,
+            expanded: HashSet::new(),
+            list_state: TuiListState::default(),
+            loading: false,
+            rerunning: false,
+            error: None,
+            throbber_state: ThrobberState::default(),
+            screen: MainScreen::default(),
+            return_screen: MainScreen::default(),
+            focus: FocusFlag::new().with_name("pr_checks"),
+            area: Rect::default(),
+            index: 0,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.screen == MainScreen::PullRequestChecks
+    }
+
+    fn load(&mut self, number: u64) {
+        self.number = Some(number);
+        self.error = None;
+        self.loading = true;
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let action = match backend.pull_request_checks(number).await {
+                Ok(checks) => Action::PullRequestChecksLoaded { number, checks },
+                Err(err) => Action::PullRequestChecksError {
+                    number,
+                    message: err.to_string().replace('\n', " "),
+                },
+            };
+            let _ = action_tx.send(action).await;
+        });
+    }
+
+    fn set_checks(&mut self, mut checks: PullRequestChecks) {
+        checks.checks.sort_by(|a, b| {
+            (outcome_rank(a.outcome), &a.name).cmp(&(outcome_rank(b.outcome), &b.name))
+        });
+        let selected = self.list_state.selected().unwrap_or(0);
+        let selected_key = self.selected().map(check_key);
+        let same_commit = self
+            .checks
+            .as_ref()
+            .is_some_and(|old| old.head_sha == checks.head_sha);
+        if !same_commit {
+            self.expanded.clear();
+        }
+        self.list_state
+            .select(reselect(&checks.checks, selected_key.as_ref(), selected));
+        self.checks = Some(checks);
+    }
+
+    fn entries(&self) -> &[CheckEntry] {
+        self.checks.as_ref().map_or(&[], |checks| &checks.checks)
+    }
+
+    fn selected(&self) -> Option<&CheckEntry> {
+        self.entries().get(self.list_state.selected()?)
+    }
+
+    fn select(&mut self, delta: isize) {
+        let len = self.entries().len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        self.list_state.select(Some(next));
+    }
+
+    /// Starts the given checks again. Several failed jobs of one workflow run
+    /// share a single re-run.
+    fn rerun(&mut self, targets: HashSet<RerunTarget>) {
+        let Some(number) = self.number else {
+            return;
+        };
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        if self.rerunning {
+            return;
+        }
+        if targets.is_empty() {
+            let _ = action_tx.try_send(toast_action(
+                "Nothing to re-run: only failed or cancelled Actions checks can be restarted",
+                ToastType::Info,
+            ));
+            return;
+        }
+        self.rerunning = true;
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let requested = targets.len();
+            let mut errors = Vec::new();
+            for target in targets {
+                if let Err(err) = backend.rerun_check(target).await {
+                    errors.push(err.to_string().replace('\n', " "));
+                }
+            }
+            let _ = action_tx
+                .send(Action::CheckRerunFinished {
+                    number,
+                    requested,
+                    errors,
+                })
+                .await;
+        });
+    }
+
+    async fn handle_key(&mut self, event: &crossterm::event::Event) -> Result<(), AppError> {
+        match event {
+            ct_event!(keycode press Esc) => {
+                if let Some(tx) = self.action_tx.clone() {
+                    tx.send(Action::ChangeIssueScreen(self.return_screen))
+                        .await?;
+                }
+            }
+            ct_event!(keycode press Up) | ct_event!(key press 'k') => self.select(-1),
+            ct_event!(keycode press Down) | ct_event!(key press 'j') => self.select(1),
+            ct_event!(keycode press Home) | ct_event!(key press 'g') => {
+                self.select(isize::MIN);
+            }
+            ct_event!(keycode press End) | ct_event!(key press SHIFT-'G') => {
+                self.select(isize::MAX);
+            }
+            ct_event!(keycode press Enter) | ct_event!(key press ' ') => {
+                if let Some(key) = self.selected().map(check_key)
+                    && !self.expanded.remove(&key)
+                {
+                    self.expanded.insert(key);
+                }
+            }
+            ct_event!(key press 'r') => {
+                let targets = rerun_targets(self.selected());
+                self.rerun(targets);
+            }
+            ct_event!(key press SHIFT-'R') => {
+                let targets = rerun_targets(self.entries());
+                self.rerun(targets);
+            }
+            ct_event!(key press 'u') => {
+                if let Some(number) = self.number {
+                    self.load(number);
+                }
+            }
+            ct_event!(key press 'l') => {
+                if let Some(url) = self.selected().and_then(|check| check.url.clone()) {
+                    cli_clipboard::set_contents(url)
+                        .map_err(|_| anyhow::anyhow!("Error copying to clipboard"))?;
+                    if let Some(tx) = self.action_tx.as_ref() {
+                        tx.send(toast_action("Copied Link", ToastType::Success))
+                            .await?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn render_w(&mut self, area: Layout, buf: &mut Buffer) {
+        let area = area.main_content;
+        self.area = area;
+        let number = self.number.unwrap_or_default();
+        let title = self.title.as_deref().unwrap_or_default();
+        let mut heading = format!("[{}] #{number} {title} | Checks", self.index);
+        if let Some(checks) = &self.checks {
+            let sha = checks.head_sha.get(..7).unwrap_or(&checks.head_sha);
+            heading.push_str(&format!(" on {sha}"));
+        }
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title(heading)
+            .title(tally_line(self.entries()).right_aligned())
+            .title_bottom(
+                "Enter: annotations  r: re-run  R: re-run failed  l: copy link  u: refresh  Esc: back",
+            );
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.loading || self.rerunning {
+            let throbber_area = Rect {
+                x: area.x + 1,
+                y: area.y + area.height.saturating_sub(1),
+                width: 20,
+                height: 1,
+            };
+            let throbber = Throbber::default()
+                .label(if self.rerunning {
+                    "Re-running"
+                } else {
+                    "Loading checks"
+                })
+                .style(Style::new().fg(Color::Cyan))
+                .throbber_set(BRAILLE_SIX_DOUBLE)
+                .use_type(WhichUse::Spin);
+            StatefulWidget::render(throbber, throbber_area, buf, &mut self.throbber_state);
+        }
+        if let Some(err) = &self.error {
+            line![span!("Failed to load checks: {err}").light_red()].render(inner, buf);
+            return;
+        }
+        let Some(checks) = &self.checks else {
+            return;
+        };
+        if checks.checks.is_empty() {
+            line![span!("No checks or statuses reported for this commit.").dim()]
+                .render(inner, buf);
+            return;
+        }
+
+        let [list_area, details_area] = horizontal![==60%, *=1].areas(inner);
+        let width = list_area.width.saturating_sub(4) as usize;
+        let now = Utc::now();
+        let items = checks
+            .checks
+            .iter()
+            .map(|check| check_item(check, self.expanded.contains(&check_key(check)), width, now))
+            .collect::<Vec<_>>();
+        let list = TuiList::new(items)
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .border_style(Style::new().cyan()),
+            )
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        StatefulWidget::render(list, list_area, buf, &mut self.list_state);
+        self.render_details(details_area, buf, now);
+    }
+
+    fn render_details(&self, area: Rect, buf: &mut Buffer, now: DateTime<Utc>) {
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::new().dim())
+            .title("Details");
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let Some(check) = self.selected() else {
+            return;
+        };
+        let [text_area, link_area] = vertical![*=1, ==1].areas(inner);
+        let label = Style::new().dim();
+        let time = |at: Option<DateTime<Utc>>| {
+            at.map_or_else(
+                || "-".to_string(),
+                |at| at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            )
+        };
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Name: ", label),
+                Span::styled(check.name.clone(), Style::new().bold()),
+            ]),
+            Line::from(vec![
+                Span::styled("Source: ", label),
+                Span::raw(
+                    check
+                        .source
+                        .clone()
+                        .unwrap_or_else(|| "commit status".to_string()),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Result: ", label),
+                outcome_span(check.outcome),
+            ]),
+            Line::from(vec![
+                Span::styled("Started: ", label),
+                Span::raw(time(check.started_at)),
+            ]),
+            Line::from(vec![
+                Span::styled("Finished: ", label),
+                Span::raw(time(check.completed_at)),
+            ]),
+            Line::from(vec![
+                Span::styled("Duration: ", label),
+                Span::raw(check_duration(check, now).unwrap_or_else(|| "-".to_string())),
+            ]),
+        ];
+        if let Some(description) = &check.description {
+            lines.push(Line::default());
+            lines.push(Line::raw(description.clone()));
+        }
+        if check.rerun.is_none() && check.outcome == CheckOutcome::Failure {
+            lines.push(Line::default());
+            lines.push(line![
+                span!("Commit statuses cannot be re-run from here.").dim()
+            ]);
+        }
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(text_area, buf);
+        if let Some(url) = &check.url {
+            Link::new("Open on GitHub", url.as_str())
+                .fallback_suffix(" (link)")
+                .render(link_area, buf);
+        }
+    }
+}
+
+/// Failures first, then running checks, then everything else.
+fn outcome_rank(outcome: CheckOutcome) -> u8 {
+    match outcome {
+        CheckOutcome::Failure => 0,
+        CheckOutcome::Pending => 1,
+        CheckOutcome::Cancelled => 2,
+        CheckOutcome::Success | CheckOutcome::Neutral | CheckOutcome::Skipped => 3,
+    }
+}
+
+fn is_rerunnable(check: &CheckEntry) -> bool {
+    check.rerun.is_some()
+        && matches!(
+            check.outcome,
+            CheckOutcome::Failure | CheckOutcome::Cancelled
+        )
+}
+
+fn rerun_targets<'a>(checks: impl IntoIterator<Item = &'a CheckEntry>) -> HashSet<RerunTarget> {
+    checks
+        .into_iter()
+        .filter(|check| is_rerunnable(check))
+        .filter_map(|check| check.rerun)
+        .collect()
+}
+
+fn outcome_span(outcome: CheckOutcome) -> Span<'static> {
+    match outcome {
+        CheckOutcome::Success => span!("✓ passed").green(),
+        CheckOutcome::Failure => span!("✗ failed").red(),
+        CheckOutcome::Pending => span!("● running").yellow(),
+        CheckOutcome::Cancelled => span!("⊘ cancelled").dim(),
+        CheckOutcome::Skipped => span!("- skipped").dim(),
+        CheckOutcome::Neutral => span!("○ neutral").dim(),
+    }
+}
+
+fn tally_line(checks: &[CheckEntry]) -> Line<'static> {
+    let count = |outcome| checks.iter().filter(|c| c.outcome == outcome).count();
+    let mut spans = Vec::new();
+    for (n, text, style) in [
+        (count(CheckOutcome::Failure), "failing", Style::new().red()),
+        (
+            count(CheckOutcome::Pending),
+            "pending",
+            Style::new().yellow(),
+        ),
+        (count(CheckOutcome::Success), "passed", Style::new().green()),
+    ] {
+        if n > 0 {
+            if !spans.is_empty() {
+                spans.push(span!(" · ").dim());
+            }
+            spans.push(Span::styled(format!("{n} {text}"), style));
+        }
+    }
+    Line::from(spans)
+}
+
+fn check_item(
+    check: &CheckEntry,
+    expanded: bool,
+    width: usize,
+    now: DateTime<Utc>,
+) -> ListItem<'static> {
+    let mut header = vec![outcome_span(check.outcome), span!(" {}", check.name).bold()];
+    if let Some(source) = &check.source {
+        header.push(span!(" ({source})").dim());
+    }
+    if let Some(duration) = check_duration(check, now) {
+        header.push(span!("  {duration}").dim());
+    }
+    if !check.annotations.is_empty() {
+        let marker = if expanded { "▾" } else { "▸" };
+        header.push(span!("  {marker} {} annotations", check.annotations.len()).cyan());
+    }
+    let mut lines = vec![Line::from(header)];
+    if expanded {
+        lines.extend(
+            check
+                .annotations
+                .iter()
+                .flat_map(|annotation| annotation_lines(annotation, width)),
+        );
+    }
+    ListItem::new(lines)
+}
+
+fn annotation_lines(annotation: &CheckAnnotation, width: usize) -> Vec<Line<'static>> {
+    let style = match annotation.level.as_str() {
+        "FAILURE" => Style::new().red(),
+        "WARNING" => Style::new().yellow(),
+        _ => Style::new().blue(),
+    };
+    let location = match annotation.start_line {
+        Some(line) => format!("{}:{line}", annotation.path),
+        None => annotation.path.clone(),
+    };
+    let mut header = vec![
+        span!("    "),
+        Span::styled(annotation.level.to_lowercase(), style.bold()),
+        span!(" {location}").dim(),
+    ];
+    if let Some(title) = &annotation.title {
+        header.push(span!(" {title}"));
+    }
+    let mut lines = vec![Line::from(header)];
+    let text_width = width.saturating_sub(6).max(10);
+    for raw in annotation.message.lines() {
+        for wrapped in textwrap::wrap(raw, text_width) {
+            lines.push(Line::from(vec![
+                span!("      "),
+                Span::styled(wrapped.into_owned(), style),
+            ]));
+        }
+    }
+    lines
+}
+
+fn check_duration(check: &CheckEntry, now: DateTime<Utc>) -> Option<String> {
+    let started = check.started_at?;
+    let finished = match (check.completed_at, check.outcome) {
+        (Some(finished), _) => finished,
+        (None, CheckOutcome::Pending) => now,
+        (None, _) => return None,
+    };
+    Some(format_duration(
+        (finished - started).num_seconds().max(0) as u64
+    ))
+}
+
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+impl HasFocus for ChecksPanel {
+    fn build(&self, builder: &mut FocusBuilder) {
+        builder.leaf_widget(self);
+    }
+
+    fn focus(&self) -> FocusFlag {
+        self.focus.clone()
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn navigable(&self) -> Navigation {
+        if self.is_active() {
+            Navigation::Regular
+        } else {
+            Navigation::None
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Component for ChecksPanel {
+    fn render(&mut self, area: Layout, buf: &mut Buffer) {
+        self.render_w(area, buf);
+    }
+
+    fn register_action_tx(&mut self, action_tx: tokio::sync::mpsc::Sender<Action>) {
+        self.action_tx = Some(action_tx);
+    }
+
+    async fn handle_event(&mut self, event: Action) -> Result<(), AppError> {
+        match event {
+            Action::EnterPullRequestChecks { number, title } => {
+                if self.number != Some(number) {
+                    self.checks = None;
+                    self.expanded.clear();
+                    self.list_state.select(None);
+                }
+                self.title = Some(title);
+                self.load(number);
+            }
+            Action::PullRequestChecksLoaded { number, checks } => {
+                if self.number == Some(number) {
+                    self.loading = false;
+                    self.set_checks(checks);
+                }
+            }
+            Action::PullRequestChecksError { number, message } => {
+                if self.number == Some(number) {
+                    self.loading = false;
+                    self.error = Some(message);
+                }
+            }
+            Action::CheckRerunFinished {
+                number,
+                requested,
+                errors,
+            } => {
+                self.rerunning = false;
+                if let Some(tx) = self.action_tx.as_ref() {
+                    let toast = if errors.is_empty() {
+                        toast_action(
+                            format!("Re-running {requested} check(s)"),
+                            ToastType::Success,
+                        )
+                    } else {
+                        toast_action(
+                            format!(
+                                "{} of {requested} re-runs failed: {}",
+                                errors.len(),
+                                errors.join("; ")
+                            ),
+                            ToastType::Error,
+                        )
+                    };
+                    tx.send(toast).await?;
+                }
+                if self.number == Some(number) {
+                    self.load(number);
+                }
+            }
+            Action::ChangeIssueScreen(screen) => {
+                self.screen = screen;
+                if screen == MainScreen::PullRequestChecks {
+                    self.focus.set(true);
+                } else {
+                    self.return_screen = screen;
+                    self.focus.set(false);
+                }
+            }
+            Action::AppEvent(ref event) if self.is_active() => self.handle_key(event).await?,
+            Action::Tick => {
+                if self.loading || self.rerunning {
+                    self.throbber_state.calc_next();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn should_render(&self) -> bool {
+        self.is_active()
+    }
+
+    fn is_animating(&self) -> bool {
+        self.is_active() && (self.loading || self.rerunning)
+    }
+
+    fn capture_focus_event(&self, _event: &crossterm::event::Event) -> bool {
+        self.is_active()
+    }
+
+    fn set_index(&mut self, index: usize) {
+        self.index = index;
+    }
+
+    fn set_global_help(&self) {
+        if let Some(action_tx) = &self.action_tx {
+            let _ = action_tx.try_send(Action::SetHelp(HELP));
+        }
+    }
+}
+
+/// Identifies a check across reloads, which re-sort the list and give
+/// re-run checks new ids: its source and name.
+type CheckKey = (Option<String>, String);
+
+fn check_key(check: &CheckEntry) -> CheckKey {
+    (check.source.clone(), check.name.clone())
+}
+
+/// The row to select after a reload: the check that was selected, or the row
+/// at the same position if that check is gone.
+fn reselect(checks: &[CheckEntry], key: Option<&CheckKey>, selected: usize) -> Option<usize> {
+    if checks.is_empty() {
+        return None;
+    }
+    key.and_then(|key| checks.iter().position(|check| check_key(check) == *key))
+        .or(Some(selected.min(checks.len() - 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(outcome: CheckOutcome, rerun: Option<RerunTarget>) -> CheckEntry {
+        CheckEntry {
+            name: "build".to_string(),
+            source: None,
+            outcome,
+            started_at: None,
+            completed_at: None,
+            url: None,
+            description: None,
+            annotations: Vec::new(),
+            rerun,
+        }
+    }
+
+    #[test]
+    fn failed_jobs_of_one_run_rerun_once() {
+        let checks = [
+            check(CheckOutcome::Failure, Some(RerunTarget::WorkflowRun(1))),
+            check(CheckOutcome::Failure, Some(RerunTarget::WorkflowRun(1))),
+            check(CheckOutcome::Success, Some(RerunTarget::WorkflowRun(2))),
+            check(CheckOutcome::Cancelled, Some(RerunTarget::CheckRun(3))),
+            check(CheckOutcome::Failure, None),
+        ];
+        let targets = rerun_targets(&checks);
+        assert_eq!(
+            targets,
+            HashSet::from([RerunTarget::WorkflowRun(1), RerunTarget::CheckRun(3)])
+        );
+    }
+
+    #[test]
+    fn selection_follows_the_check_across_reloads() {
+        let named = |name: &str| CheckEntry {
+            name: name.to_string(),
+            ..check(CheckOutcome::Success, None)
+        };
+        let checks = [named("lint"), named("build"), named("test")];
+        let key = check_key(&named("test"));
+        assert_eq!(reselect(&checks, Some(&key), 0), Some(2));
+        let gone = check_key(&named("docs"));
+        assert_eq!(reselect(&checks, Some(&gone), 7), Some(2));
+        assert_eq!(reselect(&[], Some(&key), 0), None);
+    }
+
+    #[test]
+    fn durations_are_compact() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(90), "1m 30s");
+        assert_eq!(format_duration(3_720), "1h 02m");
+    }
+}
@@ -0,0 +1,1103 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use async_trait::async_trait;
+use octocrab::models::repos::{DiffEntry, DiffEntryStatus};
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    focus::{FocusBuilder, FocusFlag, HasFocus, Navigation},
+    textarea::{TextArea, TextAreaState, TextWrap},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, Paragraph,
+        StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{horizontal, line, span, vertical};
+use ratatui_toaster::ToastType;
+use syntect::{easy::HighlightLines, parsing::SyntaxReference};
+use throbber_widgets_tui::{BRAILLE_SIX_DOUBLE, Throbber, ThrobberState, WhichUse};
+
+use crate::{
+    backend::{Backend, DiffSide, DraftReviewComment, NewReview, ReviewEvent},
+    errors::AppError,
+    ui::{
+        Action, AppState,
+        components::{
+            Component,
+            help::HelpElementKind,
+            issue_conversation::{render_markdown_lines, syntect_assets, syntect_style_to_ratatui},
+            issue_list::MainScreen,
+        },
+        diff::{DiffLine, Hunk, LineKind, TreeRow, parse_patch, split_rows, tree_rows},
+        layout::Layout,
+        toast_action,
+    },
+};
+
+pub const HELP: &[HelpElementKind] = &[
+    crate::help_text!("Pull Request Diff Help"),
+    crate::help_keybind!("Up/Down, j/k", "select file / move the line cursor"),
+    crate::help_keybind!("Tab / Enter", "switch between file tree and diff"),
+    crate::help_keybind!("n / p", "jump to next / previous hunk"),
+    crate::help_keybind!("] / [", "jump to next / previous file"),
+    crate::help_keybind!("s", "toggle unified / split view"),
+    crate::help_keybind!("o", "move the line cursor to the other side in split view"),
+    crate::help_keybind!("Left/Right, h/l", "scroll diff sideways"),
+    crate::help_keybind!("PageUp/PageDown/Home/End", "scroll diff"),
+    crate::help_keybind!("c", "comment on the line under the cursor"),
+    crate::help_keybind!("R", "submit the pending review"),
+    crate::help_keybind!("Ctrl+P (editor)", "toggle input/preview"),
+    crate::help_keybind!(
+        "Tab (review editor)",
+        "cycle Comment / Approve / Request changes"
+    ),
+    crate::help_keybind!("Ctrl+Enter / Alt+Enter", "save comment / submit review"),
+    crate::help_keybind!("Esc", "close the editor / leave the diff"),
+];
+
+const ADDED_BG: Color = Color::Rgb(24, 58, 34);
+const REMOVED_BG: Color = Color::Rgb(72, 28, 32);
+const TAB: &str = "    ";
+const REVIEW_EVENTS: [ReviewEvent; 3] = [
+    ReviewEvent::Comment,
+    ReviewEvent::Approve,
+    ReviewEvent::RequestChanges,
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Pane {
+    #[default]
+    Files,
+    Diff,
+}
+
+struct FileDiff {
+    entry: DiffEntry,
+    hunks: Vec<Hunk>,
+}
+
+/// Highlighted lines of the selected file, rebuilt when the file or the view
+/// mode changes.
+struct Rendered {
+    file: usize,
+    split: bool,
+    left: Vec<Line<'static>>,
+    /// Only filled in split view.
+    right: Vec<Line<'static>>,
+    /// The lines a review comment on each row would attach to. Hunk headers
+    /// have none.
+    anchors: Vec<RowAnchors>,
+    hunk_starts: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LineAnchor {
+    line: u32,
+    side: DiffSide,
+}
+
+impl LineAnchor {
+    fn of(line: &DiffLine) -> Option<Self> {
+        match line.kind {
+            LineKind::Removed => Some(Self {
+                line: line.old?,
+                side: DiffSide::Left,
+            }),
+            LineKind::Added | LineKind::Context => Some(Self {
+                line: line.new?,
+                side: DiffSide::Right,
+            }),
+        }
+    }
+
+    /// The line as shown in the `side` half of a split row.
+    fn on_side(line: &DiffLine, side: DiffSide) -> Option<Self> {
+        let number = match side {
+            DiffSide::Left => line.old,
+            DiffSide::Right => line.new,
+        };
+        Some(Self {
+            line: number?,
+            side,
+        })
+    }
+
+    fn matches(&self, comment: &DraftReviewComment) -> bool {
+        comment.line == self.line && comment.side == self.side
+    }
+}
+
+/// The lines shown in the left and right half of a split row. In unified
+/// view, the line of a row is in the half of its side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct RowAnchors {
+    left: Option<LineAnchor>,
+    right: Option<LineAnchor>,
+}
+
+impl RowAnchors {
+    fn unified(anchor: Option<LineAnchor>) -> Self {
+        match anchor {
+            Some(LineAnchor {
+                side: DiffSide::Left,
+                ..
+            }) => Self {
+                left: anchor,
+                right: None,
+            },
+            _ => Self {
+                left: None,
+                right: anchor,
+            },
+        }
+    }
+
+    /// The line on `side`, or the other one if that half of the row is empty.
+    fn on(self, side: DiffSide) -> Option<LineAnchor> {
+        match side {
+            DiffSide::Left => self.left.or(self.right),
+            DiffSide::Right => self.right.or(self.left),
+        }
+    }
+}
+
+enum EditorTarget {
+    Line { path: String, anchor: LineAnchor },
+    Review { event: ReviewEvent },
+}
+
+/// Popup for writing a line comment or the summary of a review.
+struct ReviewEditor {
+    number: u64,
+    target: EditorTarget,
+    input: TextAreaState,
+    preview: bool,
+    submitting: bool,
+    error: Option<String>,
+}
+
+impl ReviewEditor {
+    fn new(number: u64, target: EditorTarget, text: &str) -> Self {
+        let mut input = TextAreaState::new();
+        input.set_text(text);
+        input.move_to_end(false);
+        input.focus.set(true);
+        Self {
+            number,
+            target,
+            input,
+            preview: false,
+            submitting: false,
+            error: None,
+        }
+    }
+}
+
+/// Full-screen view of the files changed by a pull request.
+pub struct PullRequestDiff {
+    action_tx: Option<tokio::sync::mpsc::Sender<Action>>,
+    backend: Backend,
+    number: Option<u64>,
+    title: Option<Arc<str>>,
+    files: Vec<FileDiff>,
+    tree: Vec<TreeRow>,
+    tree_state: TuiListState,
+    selected_file: usize,
+    rendered: Option<Rendered>,
+    scroll: usize,
+    /// Row of the diff the line cursor is on.
+    cursor: usize,
+    /// Half of a split row the line cursor is on.
+    cursor_side: DiffSide,
+    x_scroll: u16,
+    diff_height: usize,
+    split: bool,
+    pane: Pane,
+    /// Line comments per pull request, waiting to be submitted as a review.
+    pending: HashMap<u64, Vec<DraftReviewComment>>,
+    editor: Option<ReviewEditor>,
+    loading: bool,
+    error: Option<String>,
+    throbber_state: ThrobberState,
+    screen: MainScreen,
+    return_screen: MainScreen,
+    focus: FocusFlag,
+    area: Rect,
+    index: usize,
+}
+
+impl PullRequestDiff {
+    pub fn new(AppState { backend, .. }: AppState) -> Self {
+        Self {
+            action_tx: None,
+            backend,
+            number: None,
+            title: None,
+            files: Vec::new(),
+            tree: Vec::new(),
+            tree_state: TuiListState::default(),
+            selected_file: 0,
+            rendered: None,
+            scroll: 0,
+            cursor: 0,
+            cursor_side: DiffSide::Right,
+            x_scroll: 0,
+            diff_height: 0,
+            split: false,
+            pane: Pane::default(),
+            pending: HashMap::new(),
+            editor: None,
+            loading: false,
+            error: None,
+            throbber_state: ThrobberState::default(),
+            screen: MainScreen::default(),
+            return_screen: MainScreen::default(),
+            focus: FocusFlag::new().with_name("pr_diff"),
+            area: Rect::default(),
+            index: 0,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.screen == MainScreen::PullRequestDiff
+    }
+
+    fn load(&mut self, number: u64, title: Arc<str>) {
+        self.number = Some(number);
+        self.title = Some(title);
+        self.files.clear();
+        self.tree.clear();
+        self.rendered = None;
+        self.editor = None;
+        self.error = None;
+        self.pane = Pane::Files;
+        self.loading = true;
+        let Some(action_tx) = self.action_tx.clone() else {
+            return;
+        };
+        let backend = self.backend.clone();
+        tokio::spawn(async move {
+            let action = match backend.list_pull_request_files(number).await {
+                Ok(files) => Action::PullRequestFilesLoaded { number, files },
+                Err(err) => Action::PullRequestFilesError {
+                    number,
+                    message: err.to_string().replace('\n', " "),
+                },
+            };
+            let _ = action_tx.send(action).await;
+        });
+    }
+
+    fn set_files(&mut self, mut files: Vec<DiffEntry>) {
+        files.sort_by(|a, b| a.filename.cmp(&b.filename));
+        self.tree = tree_rows(files.iter().map(|file| file.filename.as_str()));
+        self.files = files
+            .into_iter()
+            .map(|entry| FileDiff {
+                hunks: entry.patch.as_deref().map(parse_patch).unwrap_or_default(),
+                entry,
+            })
+            .collect();
+        self.select_file(0);
+    }
+
+    fn select_file(&mut self, file: usize) {
+        if self.files.is_empty() {
+            return;
+        }
+        let file = file.min(self.files.len() - 1);
+        self.selected_file = file;
+        let row = self
+            .tree
+            .iter()
+            .position(|row| matches!(row, TreeRow::File { index, .. } if *index == file));
+        self.tree_state.select(row);
+        self.scroll = 0;
+        self.cursor = 0;
+        self.x_scroll = 0;
+    }
+
+    fn ensure_rendered(&mut self) -> Option<&Rendered> {
+        let stale = self
+            .rendered
+            .as_ref()
+            .is_none_or(|r| r.file != self.selected_file || r.split != self.split);
+        if stale {
+            let file = self.files.get(self.selected_file)?;
+            self.rendered = Some(render_file(file, self.selected_file, self.split));
+        }
+        self.rendered.as_ref()
+    }
+
+    fn row_count(&mut self) -> usize {
+        self.ensure_rendered().map_or(0, |r| r.left.len())
+    }
+
+    fn max_scroll(&mut self) -> usize {
+        let height = self.diff_height;
+        self.row_count().saturating_sub(height.max(1))
+    }
+
+    /// Moves the line cursor to `row` and scrolls it into view.
+    fn set_cursor(&mut self, row: usize) {
+        self.cursor = row.min(self.row_count().saturating_sub(1));
+        let height = self.diff_height.max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        self.set_cursor(self.cursor.saturating_add_signed(delta));
+    }
+
+    /// Puts a hunk header at the top of the view, with the cursor on it.
+    fn jump_to(&mut self, row: usize) {
+        self.cursor = row;
+        self.scroll = row.min(self.max_scroll());
+    }
+
+    fn next_hunk(&mut self) {
+        let cursor = self.cursor;
+        let next = self
+            .ensure_rendered()
+            .and_then(|r| r.hunk_starts.iter().copied().find(|start| *start > cursor));
+        match next {
+            Some(start) => self.jump_to(start),
+            None if self.selected_file + 1 < self.files.len() => {
+                self.select_file(self.selected_file + 1)
+            }
+            None => {}
+        }
+    }
+
+    fn prev_hunk(&mut self) {
+        let cursor = self.cursor;
+        let prev = self.ensure_rendered().and_then(|r| {
+            r.hunk_starts
+                .iter()
+                .copied()
+                .rev()
+                .find(|start| *start < cursor)
+        });
+        match prev {
+            Some(start) => self.jump_to(start),
+            None if self.selected_file > 0 => {
+                self.select_file(self.selected_file - 1);
+                let last = self
+                    .ensure_rendered()
+                    .and_then(|r| r.hunk_starts.last().copied())
+                    .unwrap_or(0);
+                self.jump_to(last);
+            }
+            None => {}
+        }
+    }
+
+    fn cursor_anchor(&mut self) -> Option<LineAnchor> {
+        let (cursor, side) = (self.cursor, self.cursor_side);
+        self.ensure_rendered()?.anchors.get(cursor)?.on(side)
+    }
+
+    fn open_line_editor(&mut self) {
+        let Some(number) = self.number else {
+            return;
+        };
+        let Some(anchor) = self.cursor_anchor() else {
+            return;
+        };
+        let Some(file) = self.files.get(self.selected_file) else {
+            return;
+        };
+        let path = file.entry.filename.clone();
+        let existing = self
+            .pending
+            .get(&number)
+            .and_then(|comments| {
+                comments
+                    .iter()
+                    .find(|c| c.path == path && anchor.matches(c))
+            })
+            .map(|c| c.body.clone())
+            .unwrap_or_default();
+        self.editor = Some(ReviewEditor::new(
+            number,
+            EditorTarget::Line { path, anchor },
+            &existing,
+        ));
+    }
+
+    fn open_review_editor(&mut self) {
+        let Some(number) = self.number else {
+            return;
+        };
+        self.editor = Some(ReviewEditor::new(
+            number,
+            EditorTarget::Review {
+                event: ReviewEvent::default(),
+            },
+            "",
+        ));
+    }
+
+    /// Stores the line comment being edited, or drops it if it was emptied.
+    fn save_line_comment(&mut self) {
+        let Some(editor) = self.editor.take() else {
+            return;
+        };
+        let EditorTarget::Line { path, anchor } = editor.target else {
+            self.editor = Some(editor);
+            return;
+        };
+        let body = editor.input.text().trim().to_string();
+        let comments = self.pending.entry(editor.number).or_default();
+        comments.retain(|c| !(c.path == path && anchor.matches(c)));
+        if !body.is_empty() {
+            comments.push(DraftReviewComment {
+                path,
+                line: anchor.line,
+                side: anchor.side,
+                body,
+            });
+        }
+    }
+
+    fn submit_review(&mut self) {
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        let EditorTarget::Review { event } = editor.target else {
+            return;
+        };
+        if editor.submitting {
+            return;
+        }
+        let number = editor.number;
+        let body = editor.input.text().trim().to_string();
+        let comments = self.pending.get(&number).cloned().unwrap_or_default();
+        // GitHub only accepts an approval without a summary.
+        if event != ReviewEvent::Approve && body.is_empty() {
+            editor.error = Some("Write a summary first.".to_string());
+            return;
+        }
+        let Some(action_tx) = self.action_tx.clone() else {
+            editor.error = Some("Action channel unavailable.".to_string());
+            return;
+        };
+        editor.submitting = true;
+        editor.error = None;
+        let backend = self.backend.clone();
+        let review = NewReview {
+            event,
+            body,
+            comments,
+        };
+        tokio::spawn(async move {
+            let action = match backend.submit_review(number, review).await {
+                Ok(()) => Action::ReviewSubmitted { number },
+                Err(err) => Action::ReviewSubmitError {
+                    number,
+                    message: err.to_string().replace('\n', " "),
+                },
+            };
+            let _ = action_tx.send(action).await;
+        });
+    }
+
+    /// Handles a key while the editor popup is open.
+    async fn handle_editor_event(&mut self, event: &crossterm::event::Event) {
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        if editor.submitting {
+            return;
+        }
+        match event {
+            ct_event!(keycode press Esc) => self.editor = None,
+            ct_event!(key press CONTROL-'p') => {
+                editor.preview = !editor.preview;
+                editor.input.focus.set(!editor.preview);
+            }
+            ct_event!(keycode press Tab) | ct_event!(keycode press BackTab) => {
+                if let EditorTarget::Review { event: selected } = &mut editor.target {
+                    let idx = REVIEW_EVENTS
+                        .iter()
+                        .position(|e| e == selected)
+                        .unwrap_or(0);
+                    let step = if matches!(event, ct_event!(keycode press BackTab)) {
+                        REVIEW_EVENTS.len() - 1
+                    } else {
+                        1
+                    };
+                    *selected = REVIEW_EVENTS[(idx + step) % REVIEW_EVENTS.len()];
+                }
+            }
+            ct_event!(keycode press CONTROL-Enter) | ct_event!(keycode press ALT-Enter) => {
+                match editor.target {
+                    EditorTarget::Line { .. } => self.save_line_comment(),
+                    EditorTarget::Review { .. } => self.submit_review(),
+                }
+            }
+            crossterm::event::Event::Paste(text) if !editor.preview => {
+                editor.input.insert_str(text);
+            }
+            _ if !editor.preview => {
+                editor.input.handle(event, rat_widget::event::Regular);
+            }
+            _ => {}
+        }
+    }
+
+    async fn leave(&mut self) {
+        if let Some(tx) = self.action_tx.clone() {
+            let _ = tx.send(Action::ChangeIssueScreen(self.return_screen)).await;
+        }
+    }
+
+    async fn handle_key(&mut self, event: &crossterm::event::Event) {
+        if self.editor.is_some() {
+            self.handle_editor_event(event).await;
+            return;
+        }
+        match event {
+            ct_event!(keycode press Esc) => self.leave().await,
+            ct_event!(keycode press Tab) | ct_event!(keycode press Enter) => {
+                self.pane = match self.pane {
+                    Pane::Files => Pane::Diff,
+                    Pane::Diff => Pane::Files,
+                };
+            }
+            ct_event!(key press 's') => self.split = !self.split,
+            ct_event!(key press 'o') if self.split => {
+                self.cursor_side = match self.cursor_side {
+                    DiffSide::Left => DiffSide::Right,
+                    DiffSide::Right => DiffSide::Left,
+                };
+            }
+            ct_event!(key press ']') => self.select_file(self.selected_file + 1),
+            ct_event!(key press '[') => self.select_file(self.selected_file.saturating_sub(1)),
+            ct_event!(key press 'n') => self.next_hunk(),
+            ct_event!(key press 'p') => self.prev_hunk(),
+            ct_event!(key press 'c') if self.pane == Pane::Diff => self.open_line_editor(),
+            ct_event!(key press SHIFT-'R') => self.open_review_editor(),
+            ct_event!(keycode press Up) | ct_event!(key press 'k') => match self.pane {
+                Pane::Files => self.select_file(self.selected_file.saturating_sub(1)),
+                Pane::Diff => self.move_cursor(-1),
+            },
+            ct_event!(keycode press Down) | ct_event!(key press 'j') => match self.pane {
+                Pane::Files => self.select_file(self.selected_file + 1),
+                Pane::Diff => self.move_cursor(1),
+            },
+            ct_event!(keycode press PageUp) => {
+                self.move_cursor(-(self.diff_height.max(1) as isize));
+            }
+            ct_event!(keycode press PageDown) => {
+                self.move_cursor(self.diff_height.max(1) as isize);
+            }
+            ct_event!(keycode press Home) | ct_event!(key press 'g') => self.set_cursor(0),
+            ct_event!(keycode press End) | ct_event!(key press SHIFT-'G') => {
+                self.set_cursor(usize::MAX);
+            }
+            ct_event!(keycode press Left) | ct_event!(key press 'h') => {
+                self.x_scroll = self.x_scroll.saturating_sub(4);
+            }
+            ct_event!(keycode press Right) | ct_event!(key press 'l') => {
+                self.x_scroll = self.x_scroll.saturating_add(4);
+            }
+            _ => {}
+        }
+    }
+
+    fn render_w(&mut self, area: Layout, buf: &mut Buffer) {
+        let area = area.main_content;
+        self.area = area;
+        let number = self.number.unwrap_or_default();
+        let title = self.title.as_deref().unwrap_or_default();
+        let mode = if self.split { "split" } else { "unified" };
+        let mut heading = format!("[{}] #{number} {title} | {mode}", self.index);
+        let pending = self.pending.get(&number).map_or(0, Vec::len);
+        if pending > 0 {
+            heading.push_str(&format!(" | {pending} pending comments"));
+        }
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title(heading)
+            .title_bottom(
+                "n/p: hunk  ]/[: file  s: split  c: comment  R: review  Tab: switch pane  Esc: back",
+            );
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.loading {
+            let throbber = Throbber::default()
+                .label("Loading changed files")
+                .style(Style::new().fg(Color::Cyan))
+                .throbber_set(BRAILLE_SIX_DOUBLE)
+                .use_type(WhichUse::Spin);
+            StatefulWidget::render(throbber, inner, buf, &mut self.throbber_state);
+            return;
+        }
+        if let Some(err) = &self.error {
+            line![span!("Failed to load changed files: {err}").light_red()].render(inner, buf);
+            return;
+        }
+        if self.files.is_empty() {
+            line![span!("This pull request changes no files.").dim()].render(inner, buf);
+            return;
+        }
+
+        let [tree_area, diff_area] = horizontal![==30%, *=1].areas(inner);
+        self.render_tree(tree_area, buf);
+        self.render_diff(diff_area, buf);
+        self.render_editor(inner, buf);
+    }
+
+    fn render_tree(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(pane_style(self.pane == Pane::Files))
+            .title(format!("Files ({})", self.files.len()));
+        let items = self.tree.iter().map(|row| match row {
+            TreeRow::Dir { name, depth } => {
+                ListItem::new(line![span!("{}{name}/", "  ".repeat(*depth)).dim()])
+            }
+            TreeRow::File { index, name, depth } => {
+                let entry = &self.files[*index].entry;
+                ListItem::new(line![
+                    span!("{}", "  ".repeat(*depth)),
+                    status_span(&entry.status),
+                    " ",
+                    span!(name.clone()),
+                    " ",
+                    span!("+{}", entry.additions).green(),
+                    span!(" -{}", entry.deletions).red(),
+                ])
+            }
+        });
+        let list = TuiList::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        StatefulWidget::render(list, area, buf, &mut self.tree_state);
+    }
+
+    fn render_diff(&mut self, area: Rect, buf: &mut Buffer) {
+        let focused = self.pane == Pane::Diff;
+        let Some(file) = self.files.get(self.selected_file) else {
+            return;
+        };
+        let mut title = file.entry.filename.clone();
+        if let Some(previous) = &file.entry.previous_filename {
+            title = format!("{previous} -> {title}");
+        }
+        let has_patch = file.entry.patch.is_some();
+        let path = file.entry.filename.clone();
+        let block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(pane_style(focused))
+            .title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        self.diff_height = inner.height as usize;
+        if !has_patch {
+            line![span!("No textual diff (binary file or too large to show).").dim()]
+                .render(inner, buf);
+            return;
+        }
+        self.scroll = self.scroll.min(self.max_scroll());
+        let (scroll, x_scroll, height) = (self.scroll, self.x_scroll, self.diff_height);
+        let (cursor, cursor_side) = (self.cursor, self.cursor_side);
+        let pending = self
+            .number
+            .and_then(|number| self.pending.get(&number))
+            .map(|comments| {
+                comments
+                    .iter()
+                    .filter(|c| c.path == path)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let Some(rendered) = self.ensure_rendered() else {
+            return;
+        };
+        let window = |lines: &[Line<'static>]| {
+            let end = (scroll + height).min(lines.len());
+            let start = scroll.min(end);
+            Paragraph::new(lines[start..end].to_vec()).scroll((0, x_scroll))
+        };
+        let halves = if rendered.split {
+            let [left, right] = horizontal![*=1, *=1].areas(inner);
+            window(&rendered.left).render(left, buf);
+            window(&rendered.right).render(right, buf);
+            [left, right]
+        } else {
+            window(&rendered.left).render(inner, buf);
+            [inner, inner]
+        };
+        let visible = scroll..(scroll + height).min(rendered.left.len());
+        for row in visible {
+            let y = inner.y + (row - scroll) as u16;
+            let anchors = rendered.anchors[row];
+            let commented = |anchor: Option<LineAnchor>| {
+                anchor.is_some_and(|anchor| pending.iter().any(|c| anchor.matches(c)))
+            };
+            for (half, anchor) in halves.iter().zip([anchors.left, anchors.right]) {
+                if commented(anchor) {
+                    buf.set_span(half.x, y, &span!("●").yellow(), 1);
+                }
+            }
+            if row == cursor && focused {
+                // Matches the half `RowAnchors::on` picks.
+                let half = match (cursor_side, anchors.left, anchors.right) {
+                    _ if !rendered.split => inner,
+                    (_, None, None) => inner,
+                    (DiffSide::Left, None, Some(_)) | (DiffSide::Right, _, Some(_)) => halves[1],
+                    _ => halves[0],
+                };
+                let line = Rect::new(half.x, y, half.width, 1);
+                buf.set_style(line, Style::new().add_modifier(Modifier::REVERSED));
+            }
+        }
+    }
+
+    fn render_editor(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(50));
+        Clear.render(popup_area, buf);
+        let heading = match &editor.target {
+            EditorTarget::Line { path, anchor } => format!("Comment on {path}:{}", anchor.line),
+            EditorTarget::Review { .. } => {
+                let comments = self.pending.get(&editor.number).map_or(0, Vec::len);
+                format!("Review #{} ({comments} line comments)", editor.number)
+            }
+        };
+        let mut block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::new().cyan())
+            .title_bottom(match editor.target {
+                EditorTarget::Line { .. } => {
+                    "Ctrl+Enter: add to review  Ctrl+P: preview  Esc: cancel"
+                }
+                EditorTarget::Review { .. } => {
+                    "Ctrl+Enter: submit  Tab: verdict  Ctrl+P: preview  Esc: cancel"
+                }
+            });
+        block = match &editor.error {
+            Some(err) => block.title(format!("{heading} | {err}")),
+            None if editor.preview => block.title(format!("{heading} | Preview")),
+            None => block.title(heading),
+        };
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let text_area = if let EditorTarget::Review { event } = editor.target {
+            let [verdict_area, text_area] = vertical![==1, *=1].areas(inner);
+            let mut verdicts = vec![span!("Verdict: ").dim()];
+            for option in REVIEW_EVENTS {
+                let label = span!(" {} ", review_event_label(option));
+                verdicts.push(if option == event {
+                    label.reversed().bold()
+                } else {
+                    label
+                });
+                verdicts.push(span!(" "));
+            }
+            Line::from(verdicts).render(verdict_area, buf);
+            text_area
+        } else {
+            inner
+        };
+        if editor.preview {
+            let width = text_area.width.saturating_sub(2).max(10) as usize;
+            Paragraph::new(render_markdown_lines(&editor.input.text(), width, 0))
+                .render(text_area, buf);
+        } else {
+            TextArea::new()
+                .text_wrap(TextWrap::Word(4))
+                .render(text_area, buf, &mut editor.input);
+        }
+
+        if editor.submitting {
+            let title_area = Rect {
+                x: popup_area.x + 1,
+                y: popup_area.y,
+                width: 14,
+                height: 1,
+            };
+            let throbber = Throbber::default()
+                .label("Submitting")
+                .style(Style::new().fg(Color::Cyan))
+                .throbber_set(BRAILLE_SIX_DOUBLE)
+                .use_type(WhichUse::Spin);
+            StatefulWidget::render(throbber, title_area, buf, &mut self.throbber_state);
+        }
+    }
+}
+
+fn review_event_label(event: ReviewEvent) -> &'static str {
+    match event {
+        ReviewEvent::Comment => "Comment",
+        ReviewEvent::Approve => "Approve",
+        ReviewEvent::RequestChanges => "Request changes",
+    }
+}
+
+fn pane_style(focused: bool) -> Style {
+    if focused {
+        Style::new().cyan()
+    } else {
+        Style::new().dim()
+    }
+}
+
+fn status_span(status: &DiffEntryStatus) -> Span<'static> {
+    match status {
+        DiffEntryStatus::Added => span!("A").green(),
+        DiffEntryStatus::Removed => span!("D").red(),
+        DiffEntryStatus::Modified => span!("M").yellow(),
+        DiffEntryStatus::Renamed => span!("R").cyan(),
+        DiffEntryStatus::Copied => span!("C").cyan(),
+        _ => span!("?").dim(),
+    }
+}
+
+fn resolve_file_syntax(filename: &str) -> &'static SyntaxReference {
+    let syntaxes = &syntect_assets().syntaxes;
+    let path = Path::new(filename);
+    path.extension()
+        .and_then(|ext| syntaxes.find_syntax_by_extension(&ext.to_string_lossy()))
+        .or_else(|| {
+            path.file_name()
+                .and_then(|name| syntaxes.find_syntax_by_extension(&name.to_string_lossy()))
+        })
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text())
+}
+
+fn render_file(file: &FileDiff, index: usize, split: bool) -> Rendered {
+    let assets = syntect_assets();
+    let syntax = resolve_file_syntax(&file.entry.filename);
+    let mut rendered = Rendered {
+        file: index,
+        split,
+        left: Vec::new(),
+        right: Vec::new(),
+        anchors: Vec::new(),
+        hunk_starts: Vec::new(),
+    };
+    for hunk in &file.hunks {
+        rendered.hunk_starts.push(rendered.left.len());
+        let header = line![span!(hunk.header.clone()).cyan().dim()];
+        // Hunks are not contiguous, so highlighting restarts at each one.
+        let mut highlighter = HighlightLines::new(syntax, &assets.theme);
+        let code = hunk
+            .lines
+            .iter()
+            .map(|line| highlight(&mut highlighter, &line.text))
+            .collect::<Vec<_>>();
+        rendered.anchors.push(RowAnchors::default());
+        if split {
+            rendered.left.push(header);
+            rendered.right.push(Line::default());
+            for (left, right) in split_rows(hunk) {
+                let anchor = |idx: Option<usize>, side| {
+                    idx.and_then(|idx| LineAnchor::on_side(&hunk.lines[idx], side))
+                };
+                rendered.anchors.push(RowAnchors {
+                    left: anchor(left, DiffSide::Left),
+                    right: anchor(right, DiffSide::Right),
+                });
+                let side = |idx: Option<usize>, number: fn(&DiffLine) -> Option<u32>| {
+                    idx.map_or_else(Line::default, |idx| {
+                        let line = &hunk.lines[idx];
+                        side_line(line, number(line), code[idx].clone())
+                    })
+                };
+                rendered.left.push(side(left, |line| line.old));
+                rendered.right.push(side(right, |line| line.new));
+            }
+        } else {
+            rendered.left.push(header);
+            for (line, code) in hunk.lines.iter().zip(code) {
+                rendered
+                    .anchors
+                    .push(RowAnchors::unified(LineAnchor::of(line)));
+                let mut spans = vec![
+                    span!("{} {} ", line_number(line.old), line_number(line.new)).dim(),
+                    span!("{} ", line.kind.sign()),
+                ];
+                spans.extend(code);
+                rendered.left.push(tint(Line::from(spans), line.kind));
+            }
+        }
+    }
+    rendered
+}
+
+fn side_line(line: &DiffLine, number: Option<u32>, code: Vec<Span<'static>>) -> Line<'static> {
+    let mut spans = vec![
+        span!("{} ", line_number(number)).dim(),
+        span!("{} ", line.kind.sign()),
+    ];
+    spans.extend(code);
+    tint(Line::from(spans), line.kind)
+}
+
+fn line_number(number: Option<u32>) -> String {
+    number.map_or_else(|| " ".repeat(5), |n| format!("{n:>5}"))
+}
+
+fn tint(line: Line<'static>, kind: LineKind) -> Line<'static> {
+    match kind {
+        LineKind::Added => line.bg(ADDED_BG),
+        LineKind::Removed => line.bg(REMOVED_BG),
+        LineKind::Context => line,
+    }
+}
+
+fn highlight(highlighter: &mut HighlightLines<'_>, text: &str) -> Vec<Span<'static>> {
+    let text = text.replace('\t', TAB);
+    let assets = syntect_assets();
+    match highlighter.highlight_line(&text, &assets.syntaxes) {
+        Ok(regions) => regions
+            .into_iter()
+            .filter(|(_, fragment)| !fragment.is_empty())
+            .map(|(style, fragment)| {
+                Span::styled(fragment.to_string(), syntect_style_to_ratatui(style))
+            })
+            .collect(),
+        Err(_) => vec![Span::raw(text)],
+    }
+}
+
+impl HasFocus for PullRequestDiff {
+    fn build(&self, builder: &mut FocusBuilder) {
+        builder.leaf_widget(self);
+    }
+
+    fn focus(&self) -> FocusFlag {
+        self.focus.clone()
+    }
+
+    fn area(&self) -> Rect {
+        self.area
+    }
+
+    fn navigable(&self) -> Navigation {
+        if self.is_active() {
+            Navigation::Regular
+        } else {
+            Navigation::None
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Component for PullRequestDiff {
+    fn render(&mut self, area: Layout, buf: &mut Buffer) {
+        self.render_w(area, buf);
+    }
+
+    fn register_action_tx(&mut self, action_tx: tokio::sync::mpsc::Sender<Action>) {
+        self.action_tx = Some(action_tx);
+    }
+
+    async fn handle_event(&mut self, event: Action) -> Result<(), AppError> {
+        match event {
+            Action::EnterPullRequestDiff { number, title } => self.load(number, title),
+            Action::PullRequestFilesLoaded { number, files } => {
+                if self.number == Some(number) {
+                    self.loading = false;
+                    self.set_files(files);
+                }
+            }
+            Action::PullRequestFilesError { number, message } => {
+                if self.number == Some(number) {
+                    self.loading = false;
+                    self.error = Some(message);
+                }
+            }
+            Action::ChangeIssueScreen(screen) => {
+                self.screen = screen;
+                if screen == MainScreen::PullRequestDiff {
+                    self.focus.set(true);
+                } else {
+                    self.return_screen = screen;
+                    self.focus.set(false);
+                }
+            }
+            Action::AppEvent(ref event) if self.is_active() => self.handle_key(event).await,
+            Action::ReviewSubmitted { number } => {
+                self.pending.remove(&number);
+                if self
+                    .editor
+                    .as_ref()
+                    .is_some_and(|editor| editor.number == number)
+                {
+                    self.editor = None;
+                }
+                if let Some(action_tx) = self.action_tx.as_ref() {
+                    let _ = action_tx
+                        .send(toast_action(
+                            format!("Review submitted on #{number}"),
+                            ToastType::Success,
+                        ))
+                        .await;
+                }
+            }
+            Action::ReviewSubmitError { number, message } => {
+                if let Some(editor) = self.editor.as_mut().filter(|e| e.number == number) {
+                    editor.submitting = false;
+                    editor.error = Some(message);
+                }
+            }
+            Action::Tick => {
+                let submitting = self.editor.as_ref().is_some_and(|e| e.submitting);
+                if self.loading || submitting {
+                    self.throbber_state.calc_next();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn cursor(&self) -> Option<(u16, u16)> {
+        self.editor
+            .as_ref()
+            .filter(|editor| self.is_active() && !editor.preview)
+            .and_then(|editor| editor.input.screen_cursor())
+    }
+
+    fn should_render(&self) -> bool {
+        self.is_active()
+    }
+
+    fn is_animating(&self) -> bool {
+        self.is_active() && (self.loading || self.editor.as_ref().is_some_and(|e| e.submitting))
+    }
+
+    fn capture_focus_event(&self, _event: &crossterm::event::Event) -> bool {
+        self.is_active()
+    }
+
+    fn set_index(&mut self, index: usize) {
+        self.index = index;
+    }
+
+    fn set_global_help(&self) {
+        if let Some(action_tx) = &self.action_tx {
+            let _ = action_tx.try_send(Action::SetHelp(HELP));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! The popup listing the repository's saved searches, opened from the search
+//! bar to run, save, delete or bind them to number keys.
+
+use rat_cursor::HasScreenCursor;
+use rat_widget::{
+    event::{HandleEvent, ct_event},
+    text_input::{TextInput, TextInputState},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style, Stylize},
+    widgets::{
+        Block, Clear, List as TuiList, ListItem, ListState as TuiListState, Paragraph,
+        StatefulWidget, Widget,
+    },
+};
+use ratatui_macros::{line, span, vertical};
+
+use crate::searches::{SavedSearch, SavedSearches, StateFilter};
+
+pub(crate) enum SavedSearchOutcome {
+    Continue,
+    Close,
+    /// Set the search bar to the search and run it.
+    Run(SavedSearch),
+}
+
+/// State of the saved searches popup.
+pub(crate) struct SavedSearchesState {
+    entries: Vec<SavedSearch>,
+    startup: Option<String>,
+    list_state: TuiListState,
+    /// What the search bar is set to, saved under a name with `s`.
+    current: SavedSearch,
+    /// Name input, while the current search is being saved.
+    naming: Option<TextInputState>,
+    error: Option<String>,
+}
+
+impl SavedSearchesState {
+    /// Lists the saved searches. `current` is the search bar's search, with
+    /// an empty name.
+    pub(crate) fn open(searches: &SavedSearches, current: SavedSearch) -> Self {
+        let mut state = Self {
+            entries: Vec::new(),
+            startup: None,
+            list_state: TuiListState::default(),
+            current,
+            naming: None,
+            error: None,
+        };
+        state.reload(searches, None);
+        state
+    }
+
+    /// Reads the searches again, selecting the one named `select` or keeping
+    /// the selection.
+    fn reload(&mut self, searches: &SavedSearches, select: Option<&str>) {
+        self.entries = searches.list();
+        self.startup = searches.startup().map(|search| search.name);
+        let selected = select
+            .and_then(|name| {
+                self.entries
+                    .iter()
+                    .position(|search| search.name.eq_ignore_ascii_case(name))
+            })
+            .or(self.list_state.selected())
+            .or(Some(0));
+        self.list_state.select(
+            selected
+                .filter(|_| !self.entries.is_empty())
+                .map(|idx| idx.min(self.entries.len() - 1)),
+        );
+    }
+
+    fn selected(&self) -> Option<&SavedSearch> {
+        self.list_state
+            .selected()
+            .and_then(|idx| self.entries.get(idx))
+    }
+
+    /// Handles a key press.
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &crossterm::event::Event,
+        searches: &SavedSearches,
+    ) -> SavedSearchOutcome {
+        if let Some(input) = self.naming.as_mut() {
+            match event {
+                ct_event!(keycode press Esc) => self.naming = None,
+                ct_event!(keycode press Enter) => {
+                    let name = input.text().trim().to_string();
+                    if name.is_empty() {
+                        self.error = Some("Name the search first".to_string());
+                        return SavedSearchOutcome::Continue;
+                    }
+                    searches.save(SavedSearch {
+                        name: name.clone(),
+                        ..self.current.clone()
+                    });
+                    self.naming = None;
+                    self.error = None;
+                    self.reload(searches, Some(&name));
+                }
+                _ => {
+                    input.handle(event, rat_widget::event::Regular);
+                }
+            }
+            return SavedSearchOutcome::Continue;
+        }
+        match event {
+            ct_event!(keycode press Esc) => return SavedSearchOutcome::Close,
+            ct_event!(keycode press Up) => self.list_state.select_previous(),
+            ct_event!(keycode press Down) => self.list_state.select_next(),
+            ct_event!(keycode press Enter) => {
+                if let Some(search) = self.selected() {
+                    return SavedSearchOutcome::Run(search.clone());
+                }
+            }
+            ct_event!(key press 's') => {
+                if self.current.query.trim().is_empty() && self.current.labels.trim().is_empty() {
+                    self.error = Some("Type a search to save first".to_string());
+                    return SavedSearchOutcome::Continue;
+                }
+                let input = TextInputState::default();
+                input.focus.set(true);
+                self.naming = Some(input);
+                self.error = None;
+            }
+            ct_event!(key press 'd') | ct_event!(keycode press Delete) => {
+                if let Some(name) = self.selected().map(|search| search.name.clone()) {
+                    searches.remove(&name);
+                    self.reload(searches, None);
+                }
+            }
+            ct_event!(key press '*') | ct_event!(key press SHIFT-'*') => {
+                if let Some(name) = self.selected().map(|search| search.name.clone()) {
+                    let is_startup = self
+                        .startup
+                        .as_deref()
+                        .is_some_and(|startup| startup.eq_ignore_ascii_case(&name));
+                    searches.set_startup((!is_startup).then_some(name.as_str()));
+                    self.reload(searches, None);
+                }
+            }
+            crossterm::event::Event::Key(key)
+                if key.kind == crossterm::event::KeyEventKind::Press
+                    && key.modifiers.is_empty() =>
+            {
+                let crossterm::event::KeyCode::Char(digit @ '1'..='9') = key.code else {
+                    return SavedSearchOutcome::Continue;
+                };
+                if let Some(name) = self.selected().map(|search| search.name.clone()) {
+                    searches.toggle_key(&name, digit as u8 - b'0');
+                    self.reload(searches, None);
+                }
+            }
+            _ => {}
+        }
+        SavedSearchOutcome::Continue
+    }
+
+    pub(crate) fn cursor(&self) -> Option<(u16, u16)> {
+        self.naming.as_ref().and_then(|input| input.screen_cursor())
+    }
+}
+
+/// A one-line description of what a saved search looks for.
+fn summary(search: &SavedSearch) -> String {
+    let mut parts = Vec::new();
+    if !search.query.trim().is_empty() {
+        parts.push(search.query.trim().to_string());
+    }
+    if !search.labels.trim().is_empty() {
+        parts.push(format!("labels: {}", search.labels.trim()));
+    }
+    parts.push(
+        match search.state {
+            StateFilter::Open => "open",
+            StateFilter::Closed => "closed",
+            StateFilter::All => "open and closed",
+        }
+        .to_string(),
+    );
+    parts.push(search.kind.title().to_lowercase());
+    parts.join("  ·  ")
+}
+
+fn search_item(search: &SavedSearch, startup: bool) -> ListItem<'static> {
+    let mut heading = line![span!(search.name.clone()).bold()];
+    if let Some(key) = search.key {
+        heading.push_span(span!("  Alt+{key}").cyan());
+    }
+    if startup {
+        heading.push_span(span!("  ★ on launch").yellow());
+    }
+    ListItem::new(vec![heading, line![span!("  {}", summary(search)).dim()]])
+}
+
+pub(crate) fn render_saved_searches(popup: &mut SavedSearchesState, area: Rect, buf: &mut Buffer) {
+    let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(50));
+    Clear.render(popup_area, buf);
+    let mut title = "Saved searches".to_string();
+    if let Some(err) = &popup.error {
+        title.push_str(" | ");
+        title.push_str(err);
+    }
+    let hints = if popup.naming.is_some() {
+        "Enter: save  Esc: cancel"
+    } else {
+        "Enter: run  s: save current  d: delete  1-9: bind Alt+key  *: run on launch  Esc: close"
+    };
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::new().cyan())
+        .title(title)
+        .title_bottom(hints);
+    let mut inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    if let Some(input) = popup.naming.as_mut() {
+        let [list_area, input_area] = vertical![*=1, ==3].areas(inner);
+        inner = list_area;
+        TextInput::new()
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .title("Name (an existing name replaces that search)"),
+            )
+            .render(input_area, buf, input);
+    }
+
+    if popup.entries.is_empty() {
+        Paragraph::new(span!("No saved searches yet. Press s to save the current search.").dim())
+            .render(inner, buf);
+    } else {
+        let items = popup
+            .entries
+            .iter()
+            .map(|search| {
+                let startup = popup
+                    .startup
+                    .as_deref()
+                    .is_some_and(|startup| startup.eq_ignore_ascii_case(&search.name));
+                search_item(search, startup)
+            })
+            .collect::<Vec<_>>();
+        let list = TuiList::new(items)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        StatefulWidget::render(list, inner, buf, &mut popup.list_state);
+    }
+}
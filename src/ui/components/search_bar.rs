@@ -9,7 +9,8 @@ use rat_widget::{
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::Style,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
     widgets::{Block, BorderType, StatefulWidget, Widget},
 };
 use std::sync::Arc;
@@ -18,41 +19,82 @@ use tracing::instrument;
 use tracing::trace;
 
 use crate::{
-    app::GITHUB_CLIENT,
+    backend::{
+        Backend, SearchParams,
+        query::{self, ItemKind, SearchQuery},
+        query_tokens,
+    },
     errors::AppError,
+    searches::{SavedSearch, SavedSearches, StateFilter},
     ui::{
         Action, AppState, MergeStrategy,
-        components::{Component, help::HelpElementKind, issue_list::MainScreen},
+        components::{
+            Component,
+            completion::{Completer, CompletionKey, input_before_cursor, input_replace},
+            help::HelpElementKind,
+            issue_list::MainScreen,
+            saved_searches::{SavedSearchOutcome, SavedSearchesState, render_saved_searches},
+        },
         layout::Layout,
         utils::{get_border_style, get_loader_area},
     },
 };
 
 const OPTIONS: [&str; 3] = ["Open", "Closed", "All"];
+
+/// Indices of the styles used to highlight the search query.
+const STYLE_KEY: usize = 0;
+const STYLE_VALUE: usize = 1;
+const STYLE_NEGATION: usize = 2;
+const STYLE_PHRASE: usize = 3;
+const STYLE_INVALID: usize = 4;
+const STYLE_WARNING: usize = 5;
+
 pub const HELP: &[HelpElementKind] = &[
     crate::help_text!("Search Bar Help"),
-    crate::help_keybind!("Type", "issue text in Search"),
+    crate::help_keybind!("Type", "issue text and qualifiers in Search"),
+    crate::help_keybind!(
+        "Qualifiers",
+        "author: assignee: mentions: milestone: created: updated: no: sort:, -key: to negate"
+    ),
+    crate::help_keybind!("Up / Down, Enter", "pick a qualifier value completion"),
+    crate::help_keybind!("Up / Down", "recall recent queries in Search"),
     crate::help_keybind!(
         "Type",
         "labels in Search Labels (separate multiple with ';')"
     ),
-    crate::help_keybind!("Tab / Shift+Tab", "move between inputs and status selector"),
+    crate::help_keybind!(
+        "Tab / Shift+Tab",
+        "move between inputs, kind and status selectors"
+    ),
+    crate::help_keybind!("Kind selector", "search issues, pull requests or both"),
     crate::help_keybind!("Enter", "run search"),
+    crate::help_keybind!("Alt+S", "open saved searches"),
+    crate::help_keybind!("Alt+1..9", "run the saved search bound to the number"),
 ];
 
 pub struct TextSearch {
     search_state: rat_widget::text_input::TextInputState,
     label_state: rat_widget::text_input::TextInputState,
     cstate: ChoiceState,
+    kstate: ChoiceState<ItemKind>,
     state: State,
     action_tx: Option<tokio::sync::mpsc::Sender<Action>>,
     loader_state: ThrobberState,
     repo: String,
     owner: String,
+    backend: Backend,
     screen: MainScreen,
     focus: FocusFlag,
     area: Rect,
     index: usize,
+    completer: Completer,
+    searches: SavedSearches,
+    saved_popup: Option<SavedSearchesState>,
+    /// Position in the query history while recalling it with Up/Down.
+    history_pos: Option<usize>,
+    /// What was typed before recalling the history, restored past its end.
+    history_draft: String,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -63,35 +105,163 @@ enum State {
 }
 
 impl TextSearch {
-    pub fn new(AppState { repo, owner, .. }: AppState) -> Self {
-        Self {
+    pub fn new(
+        AppState {
             repo,
             owner,
+            backend,
+            searches,
+            ..
+        }: AppState,
+    ) -> Self {
+        let mut cstate = ChoiceState::default();
+        cstate.set_value(1);
+        let mut this = Self {
+            repo,
+            owner,
+            backend,
             search_state: Default::default(),
             label_state: Default::default(),
             loader_state: Default::default(),
             state: Default::default(),
-            cstate: Default::default(),
+            cstate,
+            kstate: Default::default(),
             action_tx: None,
             screen: MainScreen::default(),
             focus: FocusFlag::new().with_name("search_bar"),
             area: Rect::default(),
             index: 0,
+            completer: Completer::default(),
+            searches,
+            saved_popup: None,
+            history_pos: None,
+            history_draft: String::new(),
+        };
+        if let Some(search) = this.searches.startup() {
+            this.apply_search(&search);
+        }
+        this
+    }
+
+    /// What the search bar is set to, as an unnamed search.
+    fn current_search(&self) -> SavedSearch {
+        SavedSearch {
+            name: String::new(),
+            query: self.search_state.text().to_string(),
+            labels: self.label_state.text().to_string(),
+            state: match self.cstate.value() {
+                2 => StateFilter::Closed,
+                3 => StateFilter::All,
+                _ => StateFilter::Open,
+            },
+            kind: self.kstate.value(),
+            key: None,
+        }
+    }
+
+    /// Sets the search bar to a saved search.
+    fn apply_search(&mut self, search: &SavedSearch) {
+        self.search_state.set_text(search.query.clone());
+        self.label_state.set_text(search.labels.clone());
+        self.cstate.set_value(match search.state {
+            StateFilter::Open => 1,
+            StateFilter::Closed => 2,
+            StateFilter::All => 3,
+        });
+        self.kstate.set_value(search.kind);
+        self.history_pos = None;
+    }
+
+    async fn run_saved_search(&mut self, search: &SavedSearch) {
+        self.apply_search(search);
+        self.completer.close();
+        if let Some(action_tx) = self.action_tx.clone() {
+            self.execute_search(action_tx).await;
         }
     }
 
+    /// Handles a key press for the saved searches popup.
+    async fn handle_saved_popup_event(&mut self, event: &crossterm::event::Event) -> bool {
+        let Some(popup) = self.saved_popup.as_mut() else {
+            return false;
+        };
+        match popup.handle_event(event, &self.searches) {
+            SavedSearchOutcome::Continue => {}
+            SavedSearchOutcome::Close => self.saved_popup = None,
+            SavedSearchOutcome::Run(search) => {
+                self.saved_popup = None;
+                self.run_saved_search(&search).await;
+            }
+        }
+        true
+    }
+
+    /// Replaces the query with an older (`Up`) or newer (`Down`) one from the
+    /// history. Returns false if there is nothing to move to.
+    fn recall_history(&mut self, older: bool) -> bool {
+        let history = self.searches.history();
+        let pos = match (self.history_pos, older) {
+            (None, true) if !history.is_empty() => {
+                self.history_draft = self.search_state.text().to_string();
+                Some(0)
+            }
+            (Some(pos), true) if pos + 1 < history.len() => Some(pos + 1),
+            (Some(0), false) => None,
+            (Some(pos), false) => Some(pos - 1),
+            _ => return false,
+        };
+        self.history_pos = pos;
+        let text = match pos {
+            Some(pos) => history[pos].clone(),
+            None => std::mem::take(&mut self.history_draft),
+        };
+        self.search_state.set_text(text);
+        self.search_state.move_to_line_end(false);
+        true
+    }
+
     fn render_w(&mut self, layout: Layout, buf: &mut Buffer) {
         let total_area = layout
             .text_search
-            .union(layout.label_search.union(layout.status_dropdown));
+            .union(layout.label_search)
+            .union(layout.kind_dropdown)
+            .union(layout.status_dropdown);
         self.area = total_area;
         let contents = (1..).zip(OPTIONS).collect::<Vec<_>>();
-        let text_input = rat_widget::text_input::TextInput::new().block(
-            Block::bordered()
-                .border_type(ratatui::widgets::BorderType::Rounded)
-                .border_style(get_border_style(&self.search_state))
-                .title(format!("[{}] Search", self.index)),
-        );
+        let parsed = SearchQuery::parse(self.search_state.text());
+        self.search_state
+            .set_styles(highlights(self.search_state.text(), &parsed));
+        let mut title = Line::from(format!("[{}] Search", self.index));
+        if let Some(error) = parsed.first_error() {
+            title.push_span(Span::raw(" | "));
+            title.push_span(Span::raw(error.to_string()).red());
+        } else if let Some(warning) = parsed.first_warning() {
+            title.push_span(Span::raw(" | "));
+            title.push_span(Span::raw(warning.to_string()).yellow());
+        }
+        let text_input = rat_widget::text_input::TextInput::new()
+            .block(
+                Block::bordered()
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .border_style(get_border_style(&self.search_state))
+                    .title(title),
+            )
+            .text_style_idx(STYLE_KEY, Style::new().fg(Color::Cyan))
+            .text_style_idx(STYLE_VALUE, Style::new().fg(Color::Yellow))
+            .text_style_idx(STYLE_NEGATION, Style::new().fg(Color::Magenta).bold())
+            .text_style_idx(STYLE_PHRASE, Style::new().fg(Color::Green))
+            .text_style_idx(
+                STYLE_INVALID,
+                Style::new()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::UNDERLINED),
+            )
+            .text_style_idx(
+                STYLE_WARNING,
+                Style::new()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::UNDERLINED),
+            );
         let label = rat_widget::text_input::TextInput::new().block(
             Block::bordered()
                 .border_type(ratatui::widgets::BorderType::Rounded)
@@ -107,14 +277,30 @@ impl TextSearch {
             .style(Style::default())
             .select_marker('>')
             .into_widgets();
+        let (kind_widget, kind_popup) = Choice::new()
+            .items(ItemKind::ALL.map(|kind| (kind, kind.label())))
+            .popup_placement(Placement::Below)
+            .focus_style(Style::default())
+            .select_style(Style::default())
+            .button_style(Style::default())
+            .style(Style::default())
+            .select_marker('>')
+            .into_widgets();
         let block = Block::bordered()
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(get_border_style(&self.cstate));
         let binner = block.inner(layout.status_dropdown);
+        let kind_block = Block::bordered()
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(get_border_style(&self.kstate));
+        let kind_inner = kind_block.inner(layout.kind_dropdown);
 
         block.render(layout.status_dropdown, buf);
         popup.render(layout.status_dropdown, buf, &mut self.cstate);
         widget.render(binner, buf, &mut self.cstate);
+        kind_block.render(layout.kind_dropdown, buf);
+        kind_popup.render(layout.kind_dropdown, buf, &mut self.kstate);
+        kind_widget.render(kind_inner, buf, &mut self.kstate);
         text_input.render(layout.text_search, buf, &mut self.search_state);
         label.render(layout.label_search, buf, &mut self.label_state);
         if self.state == State::Loading {
@@ -130,43 +316,74 @@ impl TextSearch {
                 .use_type(throbber_widgets_tui::WhichUse::Spin);
             StatefulWidget::render(full, area, buf, &mut self.loader_state);
         }
+        if self.search_state.is_focused() {
+            self.completer
+                .render(self.search_state.screen_cursor(), buf);
+        }
+        if let Some(popup) = self.saved_popup.as_mut() {
+            render_saved_searches(popup, layout.main_content, buf);
+        }
     }
 
     #[instrument(skip(self, action_tx))]
     async fn execute_search(&mut self, action_tx: tokio::sync::mpsc::Sender<Action>) {
-        let mut search = self.search_state.text().to_string();
-        let label = self.label_state.text();
-        if !label.is_empty() {
-            let label_q = label.split(';').map(|s| format!("label:{s}"));
-            search.push(' ');
-            search.push_str(&label_q.collect::<Vec<_>>().join(" "));
+        let parsed = SearchQuery::parse(self.search_state.text());
+        if let Some(error) = parsed.first_error() {
+            // The error is shown in the search bar until the query is fixed.
+            trace!(error, "Not searching with an invalid query");
+            return;
         }
-        let status = self.cstate.selected();
+        let has_value = |key: &str, values: &[&str]| {
+            parsed.values(key).any(|value| {
+                values.is_empty() || values.iter().any(|v| v.eq_ignore_ascii_case(value))
+            })
+        };
+        self.searches.record(self.search_state.text());
+        self.history_pos = None;
+        let mut terms = vec![parsed.without_sort()];
+        terms.extend(
+            self.label_state
+                .text()
+                .split(';')
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .map(|label| format!("label:{}", query::quote(label))),
+        );
+        // Qualifiers typed in the query win over the selectors.
+        let status = match self.cstate.value() {
+            1 => Some("open"),
+            2 => Some("closed"),
+            _ => None,
+        };
         trace!(status, "Searching with status");
         if let Some(status) = status
-            && status != 2
+            && !has_value("is", &["open", "closed"])
+            && !has_value("state", &[])
         {
-            search.push_str(&format!(" is:{}", OPTIONS[status].to_lowercase()));
+            terms.push(format!("is:{status}"));
         }
-        let repo_q = format!("repo:{}/{}", self.owner, self.repo);
-        search.push(' ');
-        search.push_str(&repo_q);
-        search.push_str(" is:issue");
-        trace!(search, "Searching with query");
+        terms.push(format!("repo:{}/{}", self.owner, self.repo));
+        let kind = self.kstate.value();
+        if let Some(qualifier) = kind.qualifier()
+            && !has_value("is", &["issue", "pr"])
+            && !has_value("type", &[])
+        {
+            terms.push(qualifier.to_string());
+        }
+        let search = terms
+            .into_iter()
+            .filter(|term| !term.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (sort, order) = parsed.sort().unwrap_or(("created", "desc"));
+        let sort = sort.to_string();
+        trace!(search, sort, order, "Searching with query");
         self.state = State::Loading;
+        let backend = self.backend.clone();
         tokio::spawn(async move {
-            let client = GITHUB_CLIENT.get().ok_or_else(|| {
-                AppError::Other(anyhow::anyhow!("github client is not initialized"))
-            })?;
-            let page = client
-                .search()
-                .issues_and_pull_requests(&search)
-                .page(1_u32)
-                .per_page(10)
-                .sort("created")
-                .order("desc")
-                .send()
-                .await?;
+            action_tx.send(Action::ItemKindChanged(kind)).await?;
+            let params = SearchParams::new(search).per_page(10).sort(sort, order);
+            let page = backend.search_issues(params).await?;
             action_tx
                 .send(Action::NewPage(Arc::new(page), MergeStrategy::Replace))
                 .await?;
@@ -178,8 +395,75 @@ impl TextSearch {
     ///NOTE: Its named this way to not conflict with the `has_focus`
     /// fn from the impl_has_focus! macro
     fn self_is_focused(&self) -> bool {
-        self.search_state.is_focused() || self.label_state.is_focused() || self.cstate.is_focused()
+        self.search_state.is_focused()
+            || self.label_state.is_focused()
+            || self.cstate.is_focused()
+            || self.kstate.is_focused()
     }
+
+    /// Handles a key press for the completion popup of the search input.
+    fn handle_completion_event(&mut self, event: &crossterm::event::Event) -> bool {
+        if !self.search_state.is_focused() {
+            return false;
+        }
+        match self.completer.handle_event(event) {
+            CompletionKey::Ignored => false,
+            CompletionKey::Handled => true,
+            CompletionKey::Accept { token_len, text } => {
+                input_replace(&mut self.search_state, token_len, &text);
+                self.refresh_completion();
+                true
+            }
+        }
+    }
+
+    /// Offers values for the qualifier being typed in the search input.
+    fn refresh_completion(&mut self) {
+        let Some(action_tx) = self.action_tx.as_ref() else {
+            return;
+        };
+        if !self.search_state.is_focused() {
+            self.completer.close();
+            return;
+        }
+        let before = input_before_cursor(&self.search_state);
+        let repo = format!("{}/{}", self.owner, self.repo);
+        self.completer
+            .complete_search(&before, &self.backend, &repo, action_tx);
+    }
+}
+
+/// Style ranges highlighting the qualifiers, negations, quoted phrases,
+/// likely mistakes and errors in a query.
+fn highlights(query: &str, parsed: &SearchQuery) -> Vec<(std::ops::Range<usize>, usize)> {
+    let mut styles = Vec::new();
+    for term in &parsed.terms {
+        let range = term.range.clone();
+        if term.error.is_some() {
+            styles.push((range, STYLE_INVALID));
+            continue;
+        }
+        if term.warning.is_some() {
+            styles.push((range, STYLE_WARNING));
+            continue;
+        }
+        match &term.qualifier {
+            Some(qualifier) => {
+                let mut key_start = range.start;
+                if qualifier.negated {
+                    styles.push((key_start..key_start + 1, STYLE_NEGATION));
+                    key_start += 1;
+                }
+                styles.push((key_start..qualifier.value_start, STYLE_KEY));
+                styles.push((qualifier.value_start..range.end, STYLE_VALUE));
+            }
+            None if query[range.clone()].starts_with('"') => {
+                styles.push((range, STYLE_PHRASE));
+            }
+            None => {}
+        }
+    }
+    styles
 }
 
 impl HasFocus for TextSearch {
@@ -187,6 +471,7 @@ impl HasFocus for TextSearch {
         let tag = builder.start(self);
         builder.widget(&self.search_state);
         builder.widget(&self.label_state);
+        builder.widget(&self.kstate);
         builder.widget(&self.cstate);
         builder.end(tag);
     }
@@ -205,6 +490,10 @@ impl Component for TextSearch {
     }
 
     fn register_action_tx(&mut self, action_tx: tokio::sync::mpsc::Sender<Action>) {
+        if self.searches.startup().is_some() {
+            // Runs the startup search that `IssueList` left out.
+            let _ = action_tx.try_send(Action::RefreshIssueList);
+        }
         self.action_tx = Some(action_tx);
     }
     async fn handle_event(&mut self, event: Action) -> Result<(), AppError> {
@@ -214,7 +503,7 @@ impl Component for TextSearch {
             }
             Action::RefreshIssueList => {
                 if self.screen != MainScreen::CreateIssue
-                    && self.screen != MainScreen::DetailsFullscreen
+                    && !self.screen.is_fullscreen()
                     && self.state != State::Loading
                     && let Some(action_tx) = self.action_tx.clone()
                 {
@@ -222,25 +511,68 @@ impl Component for TextSearch {
                 }
             }
             Action::AppEvent(ref event) => {
-                if self.screen == MainScreen::CreateIssue
-                    || self.screen == MainScreen::DetailsFullscreen
-                {
+                if self.screen == MainScreen::CreateIssue || self.screen.is_fullscreen() {
+                    return Ok(());
+                }
+                if self.handle_saved_popup_event(event).await {
+                    return Ok(());
+                }
+                if let Some(key) = saved_search_key(event) {
+                    if let Some(search) = self.searches.by_key(key) {
+                        self.run_saved_search(&search).await;
+                    }
+                    return Ok(());
+                }
+                if self.handle_completion_event(event) {
                     return Ok(());
                 }
                 if self.self_is_focused() {
                     match event {
                         ct_event!(keycode press Enter) => {
                             if let Some(action_tx) = self.action_tx.clone() {
+                                self.completer.close();
                                 self.execute_search(action_tx).await;
                                 return Ok(());
                             }
                         }
+                        ct_event!(key press ALT-'s') => {
+                            self.completer.close();
+                            self.saved_popup = Some(SavedSearchesState::open(
+                                &self.searches,
+                                self.current_search(),
+                            ));
+                            return Ok(());
+                        }
+                        ct_event!(keycode press Up) if self.search_state.is_focused() => {
+                            if self.recall_history(true) {
+                                return Ok(());
+                            }
+                        }
+                        ct_event!(keycode press Down) if self.search_state.is_focused() => {
+                            if self.recall_history(false) {
+                                return Ok(());
+                            }
+                        }
                         _ => {}
                     }
                 }
                 self.label_state.handle(event, Regular);
+                let query = self.search_state.text().to_string();
                 self.search_state.handle(event, Regular);
+                if self.search_state.text() != query {
+                    self.history_pos = None;
+                }
+                self.kstate.handle(event, Popup);
                 self.cstate.handle(event, Popup);
+                self.refresh_completion();
+            }
+            Action::CompletionsLoaded(ref data) => self.completer.update(data),
+            Action::FilterByMilestone { title } => {
+                let query = with_milestone(self.search_state.text(), &title);
+                self.search_state.set_text(query);
+                if let Some(action_tx) = self.action_tx.clone() {
+                    self.execute_search(action_tx).await;
+                }
             }
             Action::FinishedLoading => {
                 self.state = State::Loaded;
@@ -255,26 +587,33 @@ impl Component for TextSearch {
         Ok(())
     }
     fn cursor(&self) -> Option<(u16, u16)> {
+        if let Some(popup) = &self.saved_popup {
+            return popup.cursor();
+        }
         self.search_state
             .screen_cursor()
             .or(self.label_state.screen_cursor())
+            .or(self.kstate.screen_cursor())
             .or(self.cstate.screen_cursor())
     }
 
     fn is_animating(&self) -> bool {
         self.screen != MainScreen::CreateIssue
-            && self.screen != MainScreen::DetailsFullscreen
+            && !self.screen.is_fullscreen()
             && self.state == State::Loading
     }
 
     fn should_render(&self) -> bool {
-        self.screen != MainScreen::CreateIssue && self.screen != MainScreen::DetailsFullscreen
+        self.screen != MainScreen::CreateIssue && !self.screen.is_fullscreen()
     }
     fn set_index(&mut self, index: usize) {
         self.index = index;
     }
 
     fn capture_focus_event(&self, event: &crossterm::event::Event) -> bool {
+        if self.saved_popup.is_some() {
+            return matches!(event, crossterm::event::Event::Key(_));
+        }
         self.self_is_focused()
             && !matches!(
                 event,
@@ -288,3 +627,75 @@ impl Component for TextSearch {
         }
     }
 }
+
+/// The number of the saved search an Alt+digit key press runs.
+fn saved_search_key(event: &crossterm::event::Event) -> Option<u8> {
+    let crossterm::event::Event::Key(key) = event else {
+        return None;
+    };
+    if key.kind != crossterm::event::KeyEventKind::Press
+        || key.modifiers != crossterm::event::KeyModifiers::ALT
+    {
+        return None;
+    }
+    match key.code {
+        crossterm::event::KeyCode::Char(digit @ '1'..='9') => Some(digit as u8 - b'0'),
+        _ => None,
+    }
+}
+
+/// Replaces every milestone filter in `query`, negated ones and
+/// `no:milestone` included, with one for `title`.
+fn with_milestone(query: &str, title: &str) -> String {
+    let mut tokens = query_tokens(query)
+        .into_iter()
+        .filter(|token| {
+            let token = token
+                .strip_prefix('-')
+                .unwrap_or(token)
+                .to_ascii_lowercase();
+            !token.starts_with("milestone:") && token != "no:milestone"
+        })
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    tokens.push(format!("milestone:{}", query::quote(title)));
+    tokens.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milestone_filter_replaces_the_previous_one() {
+        assert_eq!(
+            with_milestone(r#"crash milestone:"v1.0" author:me"#, "v1.1 beta"),
+            r#"crash author:me milestone:"v1.1 beta""#
+        );
+        assert_eq!(with_milestone("", "v2"), "milestone:v2");
+        assert_eq!(
+            with_milestone("-milestone:v1 no:milestone crash -NO:MILESTONE", "v2"),
+            "crash milestone:v2"
+        );
+        assert_eq!(
+            with_milestone("crash", r#"the "big" one"#),
+            r#"crash milestone:"the \"big\" one""#
+        );
+    }
+
+    #[test]
+    fn queries_are_highlighted_by_part() {
+        let query = r#"crash -label:bug "two words" asignee:me "unclosed"#;
+        assert_eq!(
+            highlights(query, &SearchQuery::parse(query)),
+            vec![
+                (6..7, STYLE_NEGATION),
+                (7..13, STYLE_KEY),
+                (13..16, STYLE_VALUE),
+                (17..28, STYLE_PHRASE),
+                (29..39, STYLE_WARNING),
+                (40..49, STYLE_INVALID),
+            ]
+        );
+    }
+}
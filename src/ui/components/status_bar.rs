@@ -5,6 +5,8 @@ use ratatui::widgets::Widget;
 use ratatui_macros::{line, span};
 use std::sync::atomic::Ordering;
 
+use crate::github::rate_limit::lowest_quota;
+use crate::outbox::PENDING_OPS_COUNT;
 use crate::ui::components::DumbComponent;
 use crate::ui::components::issue_list::LOADED_ISSUE_COUNT;
 use crate::ui::{AppState, layout::Layout};
@@ -12,6 +14,7 @@ use crate::ui::{AppState, layout::Layout};
 pub struct StatusBar {
     repo_label: String,
     user_label: String,
+    offline: bool,
 }
 
 impl StatusBar {
@@ -19,6 +22,7 @@ impl StatusBar {
         Self {
             repo_label: format!(" {}/{} ", app_state.owner, app_state.repo),
             user_label: app_state.current_user,
+            offline: app_state.backend.is_offline(),
         }
     }
 
@@ -37,7 +41,26 @@ impl StatusBar {
                 ],
                 " ",
             )
-            .start(span!(self.repo_label.as_str()).style(Style::new()), " ")
+            .start(span!(self.repo_label.as_str()).style(Style::new()), " ");
+        let pending = PENDING_OPS_COUNT.load(Ordering::Relaxed);
+        if pending > 0 {
+            ss = ss.end(span!(" Pending: {} ", pending).black().on_yellow(), " ");
+        }
+        if let Some(quota) = lowest_quota() {
+            let text = format!(" {} {}/{} ", quota.resource, quota.remaining, quota.limit);
+            let style = if quota.remaining == 0 {
+                Style::new().black().on_red()
+            } else if quota.remaining * 10 < quota.limit {
+                Style::new().black().on_yellow()
+            } else {
+                Style::new().dim()
+            };
+            ss = ss.end(span!(text).style(style), " ");
+        }
+        if self.offline {
+            ss = ss.start(span!(" OFFLINE ").black().on_yellow().bold(), " ");
+        }
+        ss = ss
             .end(span!(count_text).style(Style::new().black().on_blue()), "")
             .end(
                 line![
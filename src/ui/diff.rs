@@ -0,0 +1,233 @@
+//! Parsing of the unified diff patches GitHub returns for pull request files.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+impl LineKind {
+    pub fn sign(self) -> char {
+        match self {
+            LineKind::Context => ' ',
+            LineKind::Added => '+',
+            LineKind::Removed => '-',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: LineKind,
+    /// Line number in the base version, unset for added lines.
+    pub old: Option<u32>,
+    /// Line number in the head version, unset for removed lines.
+    pub new: Option<u32>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// The `@@ -a,b +c,d @@ context` line.
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Splits a patch into hunks. Lines before the first hunk header and
+/// `\ No newline at end of file` markers are dropped.
+pub fn parse_patch(patch: &str) -> Vec<Hunk> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let (mut old, mut new) = (0, 0);
+    for raw in patch.lines() {
+        if raw.starts_with("@@") {
+            (old, new) = hunk_start(raw).unwrap_or((1, 1));
+            hunks.push(Hunk {
+                header: raw.to_string(),
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        let (kind, text) = match raw.split_at_checked(1) {
+            Some(("+", text)) => (LineKind::Added, text),
+            Some(("-", text)) => (LineKind::Removed, text),
+            Some((" ", text)) => (LineKind::Context, text),
+            Some(("\\", _)) => continue,
+            _ => (LineKind::Context, ""),
+        };
+        let line = DiffLine {
+            kind,
+            old: (kind != LineKind::Added).then_some(old),
+            new: (kind != LineKind::Removed).then_some(new),
+            text: text.to_string(),
+        };
+        if kind != LineKind::Added {
+            old += 1;
+        }
+        if kind != LineKind::Removed {
+            new += 1;
+        }
+        hunk.lines.push(line);
+    }
+    hunks
+}
+
+/// Reads the starting line numbers out of `@@ -12,7 +12,9 @@`.
+fn hunk_start(header: &str) -> Option<(u32, u32)> {
+    let mut ranges = header.trim_start_matches('@').split_whitespace();
+    let start = |range: &str| range.split(',').next()?.parse::<u32>().ok();
+    let old = start(ranges.next()?.strip_prefix('-')?)?;
+    let new = start(ranges.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+/// Lines of a hunk laid out side by side, as indices into [`Hunk::lines`]: a
+/// run of removals is paired with the additions that follow it, context
+/// appears on both sides.
+pub fn split_rows(hunk: &Hunk) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut rows = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let flush = |rows: &mut Vec<_>, removed: &mut Vec<usize>, added: &mut Vec<usize>| {
+        let len = removed.len().max(added.len());
+        for idx in 0..len {
+            rows.push((removed.get(idx).copied(), added.get(idx).copied()));
+        }
+        removed.clear();
+        added.clear();
+    };
+    for (idx, line) in hunk.lines.iter().enumerate() {
+        match line.kind {
+            LineKind::Removed => {
+                if !added.is_empty() {
+                    flush(&mut rows, &mut removed, &mut added);
+                }
+                removed.push(idx);
+            }
+            LineKind::Added => added.push(idx),
+            LineKind::Context => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push((Some(idx), Some(idx)));
+            }
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+/// An entry of the changed-files tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeRow {
+    Dir {
+        name: String,
+        depth: usize,
+    },
+    /// `index` points into the list of paths the tree was built from.
+    File {
+        index: usize,
+        name: String,
+        depth: usize,
+    },
+}
+
+/// Lays out `paths` as a directory tree. Paths must be sorted so files in the
+/// same directory are adjacent.
+pub fn tree_rows<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<TreeRow> {
+    let mut rows = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    for (index, path) in paths.into_iter().enumerate() {
+        let mut parts: Vec<&str> = path.split('/').collect();
+        let name = parts.pop().unwrap_or_default();
+        let shared = open.iter().zip(&parts).take_while(|(a, b)| a == b).count();
+        open.truncate(shared);
+        for dir in &parts[shared..] {
+            rows.push(TreeRow::Dir {
+                name: (*dir).to_string(),
+                depth: open.len(),
+            });
+            open.push(dir);
+        }
+        rows.push(TreeRow::File {
+            index,
+            name: name.to_string(),
+            depth: open.len(),
+        });
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "@@ -1,4 +1,4 @@ fn main() {\n a\n-b\n-c\n+B\n d\n\\ No newline at end of file\n@@ -10,2 +10,3 @@\n x\n+y\n z";
+
+    #[test]
+    fn patch_lines_are_numbered() {
+        let hunks = parse_patch(PATCH);
+        assert_eq!(hunks.len(), 2);
+        let numbers = hunks[0]
+            .lines
+            .iter()
+            .map(|line| (line.kind.sign(), line.old, line.new))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            numbers,
+            vec![
+                (' ', Some(1), Some(1)),
+                ('-', Some(2), None),
+                ('-', Some(3), None),
+                ('+', None, Some(2)),
+                (' ', Some(4), Some(3)),
+            ]
+        );
+        assert_eq!(hunks[1].lines[1].new, Some(11));
+    }
+
+    #[test]
+    fn split_rows_pair_removals_with_additions() {
+        let hunks = parse_patch(PATCH);
+        let text = |idx: Option<usize>| idx.map(|idx| hunks[0].lines[idx].text.as_str());
+        let rows = split_rows(&hunks[0])
+            .into_iter()
+            .map(|(left, right)| (text(left), text(right)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("a"), Some("a")),
+                (Some("b"), Some("B")),
+                (Some("c"), None),
+                (Some("d"), Some("d")),
+            ]
+        );
+    }
+
+    #[test]
+    fn tree_rows_share_directories() {
+        let rows = tree_rows(["Cargo.toml", "src/main.rs", "src/ui/a.rs", "src/ui/b.rs"]);
+        let dir = |name: &str, depth| TreeRow::Dir {
+            name: name.to_string(),
+            depth,
+        };
+        let file = |index, name: &str, depth| TreeRow::File {
+            index,
+            name: name.to_string(),
+            depth,
+        };
+        assert_eq!(
+            rows,
+            vec![
+                file(0, "Cargo.toml", 0),
+                dir("src", 0),
+                file(1, "main.rs", 1),
+                dir("ui", 1),
+                file(2, "a.rs", 2),
+                file(3, "b.rs", 2),
+            ]
+        );
+    }
+}
@@ -8,6 +8,7 @@ pub struct Layout {
     pub label_list: Rect,
     pub text_search: Rect,
     pub status_dropdown: Rect,
+    pub kind_dropdown: Rect,
     pub issue_preview: Rect,
     pub label_search: Rect,
     pub title_bar: Rect,
@@ -19,9 +20,11 @@ impl Layout {
         let [left, right] = horizontal![==70%, *=1].areas(main);
         let [label_list, issue_preview] = vertical![*=1, *=1].areas(right);
         let [text_search, bottom_search, main_content] = vertical![==3, ==3, *=1].areas(left);
-        let [label_search, status_dropdown] = horizontal![*=1, ==30%].areas(bottom_search);
+        let [label_search, kind_dropdown, status_dropdown] =
+            horizontal![*=1, ==20%, ==20%].areas(bottom_search);
         Self {
             status_dropdown,
+            kind_dropdown,
             title_bar,
             status_bar,
             main_content,
@@ -39,6 +42,7 @@ impl Layout {
             label_list: area,
             text_search: area,
             status_dropdown: area,
+            kind_dropdown: area,
             issue_preview: area,
             label_search: area,
             title_bar: area,
@@ -1,4 +1,5 @@
 pub mod components;
+pub mod diff;
 pub mod layout;
 pub mod macros;
 pub mod theme;
@@ -6,18 +7,30 @@ pub mod utils;
 pub mod widgets;
 
 use crate::{
-    app::GITHUB_CLIENT,
+    backend::{
+        Backend, MergeStatus, PullRequestChecks, PullRequestSummary, ReviewThread, query::ItemKind,
+        templates::IssueTemplate,
+    },
     bookmarks::{Bookmarks, read_bookmarks},
     define_cid_map,
+    drafts::Drafts,
     errors::{AppError, Result},
+    github::GithubHost,
+    outbox::{Outbox, OutboxEvent, Replayed},
+    searches::SavedSearches,
     ui::components::{
         Component, DumbComponent,
+        completion::CompletionData,
         help::HelpElementKind,
-        issue_conversation::IssueConversation,
+        issue_conversation::{IssueConversation, to_reaction_snapshot},
         issue_create::IssueCreate,
         issue_detail::IssuePreview,
         issue_list::{IssueList, MainScreen},
         label_list::LabelList,
+        milestone_list::MilestoneList,
+        outbox_popup::OutboxPopup,
+        pr_checks::ChecksPanel,
+        pr_diff::PullRequestDiff,
         search_bar::TextSearch,
         status_bar::StatusBar,
         title_bar::TitleBar,
@@ -35,7 +48,7 @@ use crossterm::{
 use futures::{StreamExt, future::FutureExt};
 use octocrab::{
     Page,
-    models::{Label, issues::Issue, reactions::ReactionContent},
+    models::{Label, Milestone, issues::Issue, reactions::ReactionContent, repos::DiffEntry},
 };
 use rat_widget::{
     event::{HandleEvent, Outcome, Regular},
@@ -77,6 +90,7 @@ const HELP_TEXT: &[HelpElementKind] = &[
     crate::help_keybind!("3", "focus Issue Conversation"),
     crate::help_keybind!("4", "focus Label List"),
     crate::help_keybind!("5", "focus Issue Create"),
+    crate::help_keybind!("Ctrl+O", "show queued operations waiting to be sent"),
     crate::help_keybind!("q / Ctrl+C", "quit the application"),
     crate::help_keybind!("? / Ctrl+H", "toggle help menu"),
     crate::help_text!(""),
@@ -85,13 +99,7 @@ const HELP_TEXT: &[HelpElementKind] = &[
     ),
 ];
 
-pub async fn run(
-    AppState {
-        repo,
-        owner,
-        current_user,
-    }: AppState,
-) -> Result<(), AppError> {
+pub async fn run(state: AppState) -> Result<(), AppError> {
     if COLOR_PROFILE.get().is_none() {
         COLOR_PROFILE
             .set(TermProfile::detect(&stdout(), DetectorSettings::default()))
@@ -100,12 +108,7 @@ pub async fn run(
     let mut terminal = ratatui::init();
     setup_more_panic_hooks();
     let (action_tx, action_rx) = tokio::sync::mpsc::channel(100);
-    let mut app = App::new(
-        action_tx,
-        action_rx,
-        AppState::new(repo, owner, current_user),
-    )
-    .await?;
+    let mut app = App::new(action_tx, action_rx, state).await?;
     let run_result = app.run(&mut terminal).await;
     ratatui::restore();
     finish_teardown()?;
@@ -129,21 +132,45 @@ struct App {
     last_event_error: Option<String>,
     effects_manager: EffectManager<()>,
     bookmarks: Arc<RwLock<Bookmarks>>,
+    backend: Backend,
+    outbox: Outbox,
+    outbox_popup: Option<OutboxPopup>,
+    drafts: Drafts,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct AppState {
+    host: GithubHost,
     repo: String,
     owner: String,
     current_user: String,
+    backend: Backend,
+    outbox: Outbox,
+    drafts: Drafts,
+    searches: SavedSearches,
 }
 
 impl AppState {
-    pub fn new(repo: String, owner: String, current_user: String) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        host: GithubHost,
+        repo: String,
+        owner: String,
+        current_user: String,
+        backend: Backend,
+        outbox: Outbox,
+        drafts: Drafts,
+        searches: SavedSearches,
+    ) -> Self {
         Self {
+            host,
             repo,
             owner,
             current_user,
+            backend,
+            outbox,
+            drafts,
+            searches,
         }
     }
 }
@@ -184,26 +211,24 @@ impl App {
         let issue_preview = IssuePreview::new(state.clone());
         let mut issue_conversation = IssueConversation::new(state.clone());
         let mut issue_create = IssueCreate::new(state.clone());
+        let mut pr_diff = PullRequestDiff::new(state.clone());
+        let mut pr_checks = ChecksPanel::new(state.clone());
+        let mut milestone_list = MilestoneList::new(state.clone());
         let bookmarks = Arc::new(RwLock::new(read_bookmarks()));
-        let issue_handler = GITHUB_CLIENT
-            .get()
-            .ok_or_else(|| AppError::Other(anyhow!("github client is not initialized")))?
-            .inner()
-            .issues(state.owner.clone(), state.repo.clone());
-        let mut issue_list = IssueList::new(
-            issue_handler,
-            state.owner.clone(),
-            state.repo.clone(),
-            action_tx.clone(),
-            bookmarks.clone(),
-        )
-        .await;
+        let backend = state.backend.clone();
+        let outbox = state.outbox.clone();
+        let drafts = state.drafts.clone();
+        let mut issue_list =
+            IssueList::new(state.clone(), action_tx.clone(), bookmarks.clone()).await;
 
         let comps = define_cid_map!(
              2 -> issue_list,
              3 -> issue_conversation,
              5 -> issue_create,
              4 -> label_list,
+             6 -> pr_diff,
+             7 -> pr_checks,
+             8 -> milestone_list,
              1 -> text_search, // this needs to be the last one
         )?;
         let effects_manager = EffectManager::default();
@@ -220,6 +245,10 @@ impl App {
             effects_manager,
             action_rx,
             bookmarks,
+            backend,
+            outbox,
+            outbox_popup: None,
+            drafts,
             last_focused: None,
             last_event_error: None,
             cancel_action: Default::default(),
@@ -240,6 +269,21 @@ impl App {
         for component in self.components.iter_mut() {
             component.register_action_tx(action_tx.clone());
         }
+        if !self.backend.is_offline() {
+            let (events_tx, mut events) = tokio::sync::mpsc::channel(16);
+            self.outbox
+                .spawn_replay(self.backend.clone(), events_tx, self.cancel_action.clone());
+            let action_tx = action_tx.clone();
+            tokio::spawn(async move {
+                while let Some(event) = events.recv().await {
+                    for action in outbox_actions(event) {
+                        if action_tx.send(action).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
 
         if let Err(err) = setup_terminal() {
             self.capture_error(err);
@@ -321,7 +365,7 @@ impl App {
                 _ => true,
             };
             match action {
-                Some(Action::Tick) => {}
+                Some(Action::Tick) => self.drafts.flush_in_background(),
                 Some(Action::ToastAction(ref toast_action)) => match toast_action {
                     ToastMessage::Show {
                         message,
@@ -421,6 +465,7 @@ impl App {
                 }
             }
             if self.cancel_action.is_cancelled() {
+                self.drafts.flush();
                 if let Ok(bm) = self.bookmarks.try_write() {
                     if let Err(err) = bm.write_to_file() {
                         error!(error = %err, "failed to write bookmarks to file on shutdown");
@@ -458,6 +503,16 @@ impl App {
             }
             return Ok(());
         }
+        if let Some(popup) = self.outbox_popup.as_mut() {
+            if !popup.handle_event(event) {
+                self.outbox_popup = None;
+            }
+            return Ok(());
+        }
+        if matches!(event, ct_event!(key press CONTROL-'o')) {
+            self.outbox_popup = Some(OutboxPopup::new(self.outbox.clone()));
+            return Ok(());
+        }
         if matches!(event, ct_event!(key press CONTROL-'h')) {
             self.in_help = !self.in_help;
             self.help = Some(HELP_TEXT);
@@ -485,6 +540,7 @@ impl App {
             match key.code {
                 Char(char)
                     if ('1'..='6').contains(&char)
+                        && !key.modifiers.contains(crossterm::event::KeyModifiers::ALT)
                         && !self
                             .components
                             .iter()
@@ -555,7 +611,7 @@ impl App {
             let elapsed = self.last_frame.elapsed();
             self.last_frame = time::Instant::now();
             let area = f.area();
-            let fullscreen = self.current_screen == MainScreen::DetailsFullscreen;
+            let fullscreen = self.current_screen.is_fullscreen();
             let layout = if fullscreen {
                 layout::Layout::fullscreen(area)
             } else {
@@ -580,6 +636,9 @@ impl App {
                     component.render(layout, buf);
                 }
             }
+            if let Some(popup) = self.outbox_popup.as_mut() {
+                popup.render(area, buf);
+            }
             if self.in_help {
                 let help_text = self.help.unwrap_or(HELP_TEXT);
                 let help_component = components::help::HelpComponent::new(help_text)
@@ -625,6 +684,12 @@ pub enum Action {
     AppEvent(crossterm::event::Event),
     RefreshIssueList,
     NewPage(Arc<Page<Issue>>, MergeStrategy),
+    ItemKindChanged(ItemKind),
+    PullRequestSummariesLoaded {
+        /// Every number that was asked for, including ones without a summary.
+        numbers: Vec<u64>,
+        summaries: Vec<PullRequestSummary>,
+    },
     ForceRender,
     SelectedIssue {
         number: u64,
@@ -656,16 +721,105 @@ pub enum Action {
         number: u64,
         message: Arc<str>,
     },
+    DraftIssueLoaded {
+        issue: Box<Issue>,
+    },
+    DraftIssueLoadError {
+        number: u64,
+        message: String,
+    },
     EnterIssueDetails {
         seed: IssueConversationSeed,
     },
+    EnterPullRequestDiff {
+        number: u64,
+        title: Arc<str>,
+    },
+    PullRequestFilesLoaded {
+        number: u64,
+        files: Vec<DiffEntry>,
+    },
+    PullRequestFilesError {
+        number: u64,
+        message: String,
+    },
+    ReviewSubmitted {
+        number: u64,
+    },
+    ReviewSubmitError {
+        number: u64,
+        message: String,
+    },
+    ReviewThreadsLoaded {
+        number: u64,
+        threads: Vec<ReviewThread>,
+    },
+    ReviewThreadsError {
+        number: u64,
+        message: String,
+    },
+    ReviewThreadResolved {
+        number: u64,
+        thread_id: String,
+        resolved: bool,
+    },
+    EnterPullRequestChecks {
+        number: u64,
+        title: Arc<str>,
+    },
+    PullRequestChecksLoaded {
+        number: u64,
+        checks: PullRequestChecks,
+    },
+    PullRequestChecksError {
+        number: u64,
+        message: String,
+    },
+    CheckRerunFinished {
+        number: u64,
+        requested: usize,
+        errors: Vec<String>,
+    },
+    MergeStatusLoaded {
+        number: u64,
+        status: Box<MergeStatus>,
+    },
+    MergeStatusError {
+        number: u64,
+        message: String,
+    },
+    MergeSubmitted {
+        number: u64,
+    },
+    MergeSubmitError {
+        number: u64,
+        message: String,
+    },
+    IssueBodyLoaded {
+        number: u64,
+        body: Arc<str>,
+    },
     IssueCommentsLoaded {
         number: u64,
         comments: Vec<CommentView>,
+        /// Whether further pages are still being fetched.
+        more: bool,
+    },
+    IssueCommentsAppended {
+        number: u64,
+        comments: Vec<CommentView>,
+        more: bool,
     },
     IssueTimelineLoaded {
         number: u64,
         events: Vec<TimelineEventView>,
+        /// Whether further pages are still being fetched.
+        more: bool,
+    },
+    IssueTimelineAppended {
+        number: u64,
+        events: Vec<TimelineEventView>,
+        more: bool,
     },
     IssueTimelineError {
         number: u64,
@@ -707,6 +861,37 @@ pub enum Action {
     IssueCreateError {
         message: String,
     },
+    IssueTemplatesLoaded {
+        templates: Vec<IssueTemplate>,
+    },
+    DuplicateSuggestionsLoaded {
+        query: String,
+        result: std::result::Result<Vec<Issue>, String>,
+    },
+    CompletionsLoaded(CompletionData),
+    IssueUpdated {
+        issue: Box<Issue>,
+    },
+    IssueUpdateError {
+        number: u64,
+        message: String,
+    },
+    MilestonesLoaded {
+        milestones: Vec<Milestone>,
+    },
+    MilestonesError {
+        message: String,
+    },
+    MilestoneSaved {
+        milestone: Box<Milestone>,
+    },
+    MilestoneSaveError {
+        message: String,
+    },
+    /// Searches the issue list for items in the milestone with this title.
+    FilterByMilestone {
+        title: String,
+    },
     IssueCloseSuccess {
         issue: Box<Issue>,
     },
@@ -714,16 +899,57 @@ pub enum Action {
         number: u64,
         message: String,
     },
+    DuplicateOriginalLoaded {
+        number: u64,
+        result: std::result::Result<String, String>,
+    },
+    IssueCloseQueued {
+        number: u64,
+    },
     IssueLabelsUpdated {
         number: u64,
         labels: Vec<Label>,
     },
+    /// One issue of a bulk change was updated, or failed with `error`.
+    BulkItemFinished {
+        number: u64,
+        error: Option<String>,
+    },
+    BulkFinished,
+    /// Every page of the current search has loaded and should be selected.
+    SelectAllIssues,
     LabelMissing {
         name: String,
     },
     LabelEditError {
         message: String,
     },
+    LabelEditQueued {
+        name: String,
+    },
+    /// A label of the repository was renamed, recolored or re-described.
+    LabelUpdated {
+        old_name: String,
+        label: Label,
+    },
+    LabelDeleted {
+        name: String,
+    },
+    /// Number of open issues per label name.
+    LabelCountsLoaded {
+        counts: HashMap<String, u64>,
+    },
+    /// Logins of the users issues can be assigned to.
+    AssignableUsersLoaded {
+        users: Vec<String>,
+    },
+    AssignableUsersError {
+        message: String,
+    },
+    /// Number of open issues assigned to each user.
+    AssigneeCountsLoaded {
+        counts: HashMap<String, u64>,
+    },
     LabelSearchPageAppend {
         request_id: u64,
         items: Vec<Label>,
@@ -828,7 +1054,43 @@ fn setup_more_panic_hooks() {
     }));
 }
 
-fn toast_action(message: impl Into<String>, toast_type: ratatui_toaster::ToastType) -> Action {
+/// The actions showing the result of a replayed outbox operation.
+fn outbox_actions(event: OutboxEvent) -> Vec<Action> {
+    let OutboxEvent::Sent { op, result } = event else {
+        return vec![Action::ForceRender];
+    };
+    let update = match result {
+        Replayed::Comment { number, comment } => Action::IssueCommentPosted {
+            number,
+            comment: CommentView::from_api(*comment),
+        },
+        Replayed::Reactions {
+            comment_id,
+            reactions,
+            login,
+        } => {
+            let (counts, mine) = to_reaction_snapshot(reactions, &login);
+            Action::IssueReactionsLoaded {
+                reactions: HashMap::from([(comment_id, counts)]),
+                own_reactions: HashMap::from([(comment_id, mine)]),
+            }
+        }
+        Replayed::Labels { number, labels } => Action::IssueLabelsUpdated { number, labels },
+        Replayed::Closed(issue) => Action::IssueCloseSuccess { issue },
+    };
+    vec![
+        update,
+        toast_action(
+            format!("Sent queued: {}", op.describe()),
+            ratatui_toaster::ToastType::Success,
+        ),
+    ]
+}
+
+pub(crate) fn toast_action(
+    message: impl Into<String>,
+    toast_type: ratatui_toaster::ToastType,
+) -> Action {
     use ratatui_toaster::ToastPosition::TopRight;
     Action::ToastAction(ratatui_toaster::ToastMessage::Show {
         message: message.into(),